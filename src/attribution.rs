@@ -0,0 +1,402 @@
+//! Attribution and credits generation.
+//!
+//! Builds TASL (Title, Author, Source, License) credit lines from asset
+//! metadata and renders them as plain text, Markdown, HTML or JSON.
+//!
+//! ```
+//! use dx_media::attribution::{Credit, CreditFormat, render};
+//! use dx_media::{License, MediaAsset, MediaType};
+//!
+//! let asset = MediaAsset::builder()
+//!     .id("1")
+//!     .provider("wikimedia")
+//!     .media_type(MediaType::Image)
+//!     .title("Red fox")
+//!     .author("Jane Doe")
+//!     .source_url("https://commons.wikimedia.org/wiki/File:Red_fox.jpg")
+//!     .download_url("https://upload.wikimedia.org/red_fox.jpg")
+//!     .license(License::CcBySa)
+//!     .build();
+//!
+//! let credit = Credit::from_asset(&asset);
+//! assert!(credit.warnings.is_empty());
+//! println!("{}", render(&[credit], CreditFormat::Markdown));
+//! ```
+
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use strum::Display;
+
+use crate::error::{DxError, Result};
+use crate::types::{License, MediaAsset};
+
+/// File name used for credits written alongside downloads.
+pub const CREDITS_FILE: &str = "CREDITS.md";
+
+/// Output format for rendered credits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CreditFormat {
+    /// One plain-text line per asset.
+    #[default]
+    Text,
+    /// A Markdown document with a bullet per asset.
+    Markdown,
+    /// An HTML `<ul>` fragment.
+    Html,
+    /// A JSON array of [`Credit`]s.
+    Json,
+}
+
+/// A problem that prevents an asset from being credited or used freely.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Display)]
+#[serde(rename_all = "snake_case")]
+pub enum CreditWarning {
+    /// The license requires attribution but the author is unknown.
+    #[strum(serialize = "license requires attribution but the author is unknown")]
+    MissingAuthor,
+    /// The asset has no source page to link to.
+    #[strum(serialize = "no source URL to link to")]
+    MissingSource,
+    /// The license couldn't be identified; check the source before reuse.
+    #[strum(serialize = "license could not be identified")]
+    UnknownLicense,
+    /// The license forbids commercial use.
+    #[strum(serialize = "non-commercial use only")]
+    NonCommercial,
+}
+
+/// Attribution for a single asset.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Credit {
+    /// Provider the asset came from.
+    pub provider: String,
+    /// Provider-specific asset ID.
+    pub id: String,
+    /// Title of the work.
+    pub title: String,
+    /// Author or creator.
+    pub author: Option<String>,
+    /// Author profile URL.
+    pub author_url: Option<String>,
+    /// Page the work was obtained from.
+    pub source_url: Option<String>,
+    /// License name.
+    pub license: String,
+    /// License deed URL.
+    pub license_url: Option<String>,
+    /// Problems found while building the credit.
+    pub warnings: Vec<CreditWarning>,
+}
+
+impl Credit {
+    /// Build the credit for an asset.
+    #[must_use]
+    pub fn from_asset(asset: &MediaAsset) -> Self {
+        let author = non_empty(asset.author.as_deref());
+        let source_url = non_empty(Some(&asset.source_url));
+
+        let mut warnings = Vec::new();
+        if author.is_none() && asset.license.requires_attribution() {
+            warnings.push(CreditWarning::MissingAuthor);
+        }
+        if source_url.is_none() {
+            warnings.push(CreditWarning::MissingSource);
+        }
+        if !asset.license.is_known() {
+            warnings.push(CreditWarning::UnknownLicense);
+        }
+        if asset.license.is_non_commercial() {
+            warnings.push(CreditWarning::NonCommercial);
+        }
+
+        let title = asset.title.trim();
+        Self {
+            provider: asset.provider.clone(),
+            id: asset.id.clone(),
+            title: if title.is_empty() { "Untitled".to_string() } else { title.to_string() },
+            author,
+            author_url: non_empty(asset.author_url.as_deref()),
+            source_url,
+            license: asset.license.as_str().to_string(),
+            license_url: asset.license.url().map(str::to_string),
+            warnings,
+        }
+    }
+
+    /// Whether the credit satisfies TASL: a known author where one is required,
+    /// and a source to link to.
+    #[must_use]
+    pub fn is_attributable(&self) -> bool {
+        !self
+            .warnings
+            .iter()
+            .any(|w| matches!(w, CreditWarning::MissingAuthor | CreditWarning::MissingSource))
+    }
+
+    /// Plain-text credit line.
+    #[must_use]
+    pub fn to_text(&self) -> String {
+        let (verb, label) = self.license_clause();
+        let mut line = format!("\"{}\"", self.title);
+        if let Some(ref author) = self.author {
+            let _ = write!(line, " by {}", author);
+        }
+        let _ = write!(line, " {} {}", verb, label);
+        if let Some(ref url) = self.license_url {
+            let _ = write!(line, " ({})", url);
+        }
+        line.push('.');
+        if let Some(ref source) = self.source_url {
+            let _ = write!(line, " Source: {}", source);
+        }
+        line
+    }
+
+    /// Markdown credit line (without the list bullet).
+    #[must_use]
+    pub fn to_markdown(&self) -> String {
+        let (verb, label) = self.license_clause();
+        let title = format!("\"{}\"", escape_markdown(&self.title));
+        let mut line = link_markdown(&title, self.source_url.as_deref());
+        if let Some(ref author) = self.author {
+            let _ = write!(
+                line,
+                " by {}",
+                link_markdown(&escape_markdown(author), self.author_url.as_deref())
+            );
+        }
+        let license = link_markdown(&escape_markdown(label), self.license_url.as_deref());
+        let _ = write!(line, " {} {}.", verb, license);
+        line
+    }
+
+    /// HTML credit fragment (without the surrounding `<li>`).
+    #[must_use]
+    pub fn to_html(&self) -> String {
+        let (verb, label) = self.license_clause();
+        let title = format!("&quot;{}&quot;", escape_html(&self.title));
+        let mut line = link_html(&title, self.source_url.as_deref(), None);
+        if let Some(ref author) = self.author {
+            let _ = write!(
+                line,
+                " by {}",
+                link_html(&escape_html(author), self.author_url.as_deref(), None)
+            );
+        }
+        let license = link_html(&escape_html(label), self.license_url.as_deref(), Some("license"));
+        let _ = write!(line, " {} {}.", verb, license);
+        line
+    }
+
+    /// Verb phrase and license label, e.g. ("is licensed under", "CC-BY").
+    fn license_clause(&self) -> (&'static str, &str) {
+        match License::parse(&self.license) {
+            License::PublicDomain => ("is in the", "public domain"),
+            License::Cc0 => ("is dedicated to the public domain under", "CC0"),
+            _ => ("is licensed under", self.license.as_str()),
+        }
+    }
+}
+
+/// Build credits for a set of assets, in order.
+#[must_use]
+pub fn credits(assets: &[MediaAsset]) -> Vec<Credit> {
+    assets.iter().map(Credit::from_asset).collect()
+}
+
+/// Render credits in the given format.
+#[must_use]
+pub fn render(credits: &[Credit], format: CreditFormat) -> String {
+    match format {
+        CreditFormat::Text => credits
+            .iter()
+            .map(|c| c.to_text() + "\n")
+            .collect(),
+        CreditFormat::Markdown => {
+            let mut doc = String::from("# Credits\n\n");
+            for credit in credits {
+                let _ = writeln!(doc, "- {}", credit.to_markdown());
+            }
+            doc
+        }
+        CreditFormat::Html => {
+            let mut doc = String::from("<ul class=\"credits\">\n");
+            for credit in credits {
+                let _ = writeln!(doc, "  <li>{}</li>", credit.to_html());
+            }
+            doc.push_str("</ul>\n");
+            doc
+        }
+        CreditFormat::Json => serde_json::to_string_pretty(credits).unwrap_or_default() + "\n",
+    }
+}
+
+/// Write a Markdown credits file for the assets into `dir`.
+///
+/// Returns the path written.
+///
+/// # Errors
+///
+/// Returns an error if the file can't be written.
+pub fn write_credits(dir: &Path, assets: &[MediaAsset]) -> Result<PathBuf> {
+    let path = dir.join(CREDITS_FILE);
+    std::fs::write(&path, render(&credits(assets), CreditFormat::Markdown)).map_err(|e| {
+        DxError::FileIo {
+            path: path.clone(),
+            message: format!("Failed to write credits: {}", e),
+            source: Some(e),
+        }
+    })?;
+    Ok(path)
+}
+
+fn non_empty(value: Option<&str>) -> Option<String> {
+    value
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .map(str::to_string)
+}
+
+fn link_markdown(text: &str, url: Option<&str>) -> String {
+    match url {
+        Some(url) => format!("[{}]({})", text, url.replace(' ', "%20").replace(')', "%29")),
+        None => text.to_string(),
+    }
+}
+
+fn link_html(text: &str, url: Option<&str>, rel: Option<&str>) -> String {
+    match (url, rel) {
+        (Some(url), Some(rel)) => {
+            format!("<a href=\"{}\" rel=\"{}\">{}</a>", escape_html(url), rel, text)
+        }
+        (Some(url), None) => format!("<a href=\"{}\">{}</a>", escape_html(url), text),
+        (None, _) => text.to_string(),
+    }
+}
+
+fn escape_markdown(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '\\' | '[' | ']' | '*' | '_' | '`' | '<' | '>') {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::MediaType;
+
+    fn asset(license: License, author: Option<&str>) -> MediaAsset {
+        let mut builder = MediaAsset::builder()
+            .id("42")
+            .provider("openverse")
+            .media_type(MediaType::Image)
+            .title("Fox [kit]")
+            .download_url("https://example.com/fox.jpg")
+            .source_url("https://example.com/fox")
+            .license(license);
+        if let Some(author) = author {
+            builder = builder.author(author).author_url("https://example.com/jane");
+        }
+        builder.build()
+    }
+
+    #[test]
+    fn test_license_parse_round_trip() {
+        for license in [
+            License::Cc0,
+            License::CcBy,
+            License::CcBySa,
+            License::CcByNc,
+            License::PublicDomain,
+            License::Unsplash,
+            License::Pexels,
+            License::Pixabay,
+        ] {
+            assert_eq!(License::parse(license.as_str()), license);
+            assert!(license.url().is_some());
+        }
+        assert_eq!(License::parse("CC BY-SA 4.0"), License::CcBySa);
+        assert_eq!(License::parse("cc0"), License::Cc0);
+        assert_eq!(License::parse("Various"), License::Other("Various".to_string()));
+        assert_eq!(
+            License::parse("https://creativecommons.org/licenses/by-nc-sa/3.0/deed.de"),
+            License::CcByNc
+        );
+        assert_eq!(
+            License::from_url("http://creativecommons.org/publicdomain/zero/1.0/"),
+            Some(License::Cc0)
+        );
+        assert_eq!(License::from_url("https://creativecommons.org/licenses/by-nd/4.0/"), None);
+    }
+
+    #[test]
+    fn test_text_and_markdown() {
+        let credit = Credit::from_asset(&asset(License::CcBy, Some("Jane")));
+        assert!(credit.warnings.is_empty());
+        assert_eq!(
+            credit.to_text(),
+            "\"Fox [kit]\" by Jane is licensed under CC-BY \
+             (https://creativecommons.org/licenses/by/4.0/). Source: https://example.com/fox"
+        );
+        assert_eq!(
+            credit.to_markdown(),
+            "[\"Fox \\[kit\\]\"](https://example.com/fox) by [Jane](https://example.com/jane) \
+             is licensed under [CC-BY](https://creativecommons.org/licenses/by/4.0/)."
+        );
+    }
+
+    #[test]
+    fn test_html_escapes() {
+        let mut asset = asset(License::Cc0, Some("A & B"));
+        asset.title = "<script>".to_string();
+        let html = Credit::from_asset(&asset).to_html();
+        assert!(html.contains("&quot;&lt;script&gt;&quot;"));
+        assert!(html.contains("A &amp; B"));
+        assert!(html.contains("rel=\"license\""));
+        assert!(html.contains("dedicated to the public domain"));
+    }
+
+    #[test]
+    fn test_warnings() {
+        let credit = Credit::from_asset(&asset(License::CcByNc, None));
+        assert_eq!(
+            credit.warnings,
+            vec![CreditWarning::MissingAuthor, CreditWarning::NonCommercial]
+        );
+        assert!(!credit.is_attributable());
+
+        // Public domain works don't need an author
+        let credit = Credit::from_asset(&asset(License::PublicDomain, None));
+        assert!(credit.is_attributable());
+
+        let credit = Credit::from_asset(&asset(License::Other("by-nc-sa".to_string()), Some("J")));
+        assert_eq!(
+            credit.warnings,
+            vec![CreditWarning::UnknownLicense, CreditWarning::NonCommercial]
+        );
+    }
+
+    #[test]
+    fn test_write_credits() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = write_credits(dir.path(), &[asset(License::CcBySa, Some("Jane"))]).unwrap();
+        let doc = std::fs::read_to_string(path).unwrap();
+        assert!(doc.starts_with("# Credits\n\n- "));
+        assert!(doc.contains("CC-BY-SA"));
+    }
+}
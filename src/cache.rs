@@ -0,0 +1,282 @@
+//! On-disk cache for provider search results.
+//!
+//! Results are stored per provider under `{cache_dir}/search/{provider}/`,
+//! keyed on a hash of the normalized [`SearchQuery`], and expire after
+//! `cache_ttl_hours`.
+
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use crate::config::Config;
+use crate::error::{DxError, Result};
+use crate::types::{SearchQuery, SearchResult};
+
+/// Search result cache backed by JSON files.
+#[derive(Debug, Clone)]
+pub struct SearchCache {
+    dir: PathBuf,
+    ttl: Duration,
+}
+
+/// A cached provider response.
+#[derive(Debug, Serialize, Deserialize)]
+struct CacheEntry {
+    cached_at: DateTime<Utc>,
+    provider: String,
+    key: String,
+    result: SearchResult,
+}
+
+/// Summary of the cache contents.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CacheStats {
+    /// Cache directory.
+    pub dir: PathBuf,
+    /// Number of cached responses.
+    pub entries: usize,
+    /// Number of cached responses past their TTL.
+    pub expired: usize,
+    /// Total size on disk in bytes.
+    pub size_bytes: u64,
+    /// Number of cached responses per provider.
+    pub providers: Vec<(String, usize)>,
+}
+
+impl SearchCache {
+    /// Create a cache rooted at `dir` with the given time-to-live.
+    #[must_use]
+    pub fn new(dir: impl Into<PathBuf>, ttl: Duration) -> Self {
+        Self {
+            dir: dir.into(),
+            ttl,
+        }
+    }
+
+    /// Create the cache described by the configuration, if caching is enabled.
+    #[must_use]
+    pub fn from_config(config: &Config) -> Option<Self> {
+        config.cache_enabled.then(|| {
+            Self::new(
+                &config.cache_dir,
+                Duration::from_secs(config.cache_ttl_hours * 3600),
+            )
+        })
+    }
+
+    /// Directory holding cached search results.
+    #[must_use]
+    pub fn dir(&self) -> PathBuf {
+        self.dir.join("search")
+    }
+
+    /// Look up a fresh cached result for a provider and query.
+    pub async fn get(&self, provider: &str, query: &SearchQuery) -> Option<SearchResult> {
+        let path = self.entry_path(provider, query);
+        let bytes = tokio::fs::read(&path).await.ok()?;
+        let entry: CacheEntry = serde_json::from_slice(&bytes).ok()?;
+
+        if self.is_expired(&entry) {
+            debug!("Cache entry for {} expired: {}", provider, path.display());
+            return None;
+        }
+
+        Some(entry.result)
+    }
+
+    /// Store a provider's result for a query.
+    pub async fn put(&self, provider: &str, query: &SearchQuery, result: &SearchResult) -> Result<()> {
+        let path = self.entry_path(provider, query);
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent)
+                .await
+                .map_err(|e| DxError::FileIo {
+                    path: parent.to_path_buf(),
+                    message: format!("Failed to create cache directory: {}", e),
+                    source: Some(e),
+                })?;
+        }
+
+        let entry = CacheEntry {
+            cached_at: Utc::now(),
+            provider: provider.to_string(),
+            key: Self::normalized_key(query),
+            result: result.clone(),
+        };
+        let json = serde_json::to_vec(&entry)?;
+
+        tokio::fs::write(&path, json)
+            .await
+            .map_err(|e| DxError::FileIo {
+                path: path.clone(),
+                message: format!("Failed to write cache entry: {}", e),
+                source: Some(e),
+            })
+    }
+
+    /// Collect statistics about the cached entries.
+    pub fn stats(&self) -> CacheStats {
+        let mut stats = CacheStats {
+            dir: self.dir(),
+            ..CacheStats::default()
+        };
+        let mut providers = std::collections::BTreeMap::new();
+
+        for (path, size) in self.entry_files() {
+            stats.entries += 1;
+            stats.size_bytes += size;
+
+            let entry = std::fs::read(&path)
+                .ok()
+                .and_then(|bytes| serde_json::from_slice::<CacheEntry>(&bytes).ok());
+            match entry {
+                Some(entry) => {
+                    if self.is_expired(&entry) {
+                        stats.expired += 1;
+                    }
+                    *providers.entry(entry.provider).or_insert(0) += 1;
+                }
+                None => stats.expired += 1,
+            }
+        }
+
+        stats.providers = providers.into_iter().collect();
+        stats
+    }
+
+    /// Remove cached entries. With `expired_only`, keep entries that are still fresh.
+    ///
+    /// Returns the number of entries removed.
+    pub fn clear(&self, expired_only: bool) -> Result<usize> {
+        let mut removed = 0;
+
+        for (path, _) in self.entry_files() {
+            if expired_only {
+                let fresh = std::fs::read(&path)
+                    .ok()
+                    .and_then(|bytes| serde_json::from_slice::<CacheEntry>(&bytes).ok())
+                    .is_some_and(|entry| !self.is_expired(&entry));
+                if fresh {
+                    continue;
+                }
+            }
+
+            std::fs::remove_file(&path).map_err(|e| DxError::FileIo {
+                path: path.clone(),
+                message: format!("Failed to remove cache entry: {}", e),
+                source: Some(e),
+            })?;
+            removed += 1;
+        }
+
+        Ok(removed)
+    }
+
+    /// All cache entry files with their sizes.
+    fn entry_files(&self) -> Vec<(PathBuf, u64)> {
+        walkdir::WalkDir::new(self.dir())
+            .into_iter()
+            .filter_map(std::result::Result::ok)
+            .filter(|e| e.file_type().is_file())
+            .filter(|e| e.path().extension().is_some_and(|ext| ext == "json"))
+            .map(|e| {
+                let size = e.metadata().map(|m| m.len()).unwrap_or(0);
+                (e.into_path(), size)
+            })
+            .collect()
+    }
+
+    fn is_expired(&self, entry: &CacheEntry) -> bool {
+        let age = Utc::now().signed_duration_since(entry.cached_at);
+        age.to_std().is_ok_and(|age| age > self.ttl)
+    }
+
+    fn entry_path(&self, provider: &str, query: &SearchQuery) -> PathBuf {
+        let provider = sanitize_filename::sanitize(provider);
+        let hash = fnv1a(Self::normalized_key(query).as_bytes());
+        Path::new(&self.dir())
+            .join(provider)
+            .join(format!("{:016x}.json", hash))
+    }
+
+    /// Canonical form of the parts of a query that affect provider results.
+    ///
+    /// Provider selection, search mode and cache policy are left out since
+    /// they don't change what a single provider returns.
+    fn normalized_key(query: &SearchQuery) -> String {
+        let terms = query
+            .query
+            .split_whitespace()
+            .map(str::to_lowercase)
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        serde_json::json!({
+            "q": terms,
+            "type": query.media_type.map(|t| t.as_str()),
+            "count": query.count,
+            "page": query.page,
+            "min_width": query.min_width,
+            "min_height": query.min_height,
+            "orientation": query.orientation.map(|o| o.to_string()),
+            "color": query.color.as_deref().map(str::to_lowercase),
+        })
+        .to_string()
+    }
+}
+
+/// 64-bit FNV-1a hash, stable across Rust versions and platforms.
+pub(crate) fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &b| {
+        (hash ^ u64::from(b)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::MediaType;
+
+    #[test]
+    fn test_normalized_key_ignores_case_and_spacing() {
+        let a = SearchQuery::new("Sunset  Mountains").media_type(MediaType::Image);
+        let b = SearchQuery::new(" sunset mountains ")
+            .media_type(MediaType::Image)
+            .providers(vec!["openverse".to_string()]);
+        let c = SearchQuery::new("sunset mountains").page(2);
+
+        assert_eq!(SearchCache::normalized_key(&a), SearchCache::normalized_key(&b));
+        assert_ne!(SearchCache::normalized_key(&a), SearchCache::normalized_key(&c));
+    }
+
+    #[tokio::test]
+    async fn test_put_get_and_expiry() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let query = SearchQuery::new("cats");
+        let mut result = SearchResult::new("cats");
+        result.total_count = 7;
+
+        let cache = SearchCache::new(dir.path(), Duration::from_secs(3600));
+        assert!(cache.get("openverse", &query).await.is_none());
+
+        cache.put("openverse", &query, &result).await.unwrap();
+        let cached = cache.get("openverse", &query).await.unwrap();
+        assert_eq!(cached.total_count, 7);
+        assert!(cache.get("nasa", &query).await.is_none());
+
+        let stats = cache.stats();
+        assert_eq!(stats.entries, 1);
+        assert_eq!(stats.expired, 0);
+        assert_eq!(stats.providers, vec![("openverse".to_string(), 1)]);
+
+        let expired = SearchCache::new(dir.path(), Duration::ZERO);
+        std::thread::sleep(Duration::from_millis(5));
+        assert!(expired.get("openverse", &query).await.is_none());
+        assert_eq!(cache.clear(true).unwrap(), 0);
+        assert_eq!(expired.clear(true).unwrap(), 1);
+        assert_eq!(cache.stats().entries, 0);
+    }
+}
@@ -2,6 +2,12 @@
 
 use clap::{Parser, Subcommand, ValueEnum};
 
+use crate::cli::output::Template;
+
+mod tool;
+
+pub use tool::*;
+
 /// DX Media - Universal digital asset acquisition CLI.
 #[derive(Debug, Parser)]
 #[command(
@@ -50,8 +56,18 @@ pub enum Command {
     #[command(alias = "p")]
     Providers(ProvidersArgs),
 
-    /// Show configuration information.
-    Config,
+    /// Show or change configuration (dx.toml).
+    Config(ConfigArgs),
+
+    /// Inspect or clear the search result cache.
+    Cache(CacheArgs),
+
+    /// Generate attribution credits from saved search results.
+    Credits(CreditsArgs),
+
+    /// Run an image, video, audio, document, archive or utility tool.
+    #[command(alias = "t")]
+    Tool(ToolArgs),
 
     /// Check external dependencies (ffmpeg, etc).
     #[command(alias = "deps")]
@@ -60,10 +76,14 @@ pub enum Command {
     /// Interactive mode (TUI).
     #[command(alias = "i")]
     Interactive,
+
+    /// Show an image, video frame or PDF page inline in the terminal.
+    Preview(PreviewArgs),
 }
 
 /// Arguments for the search command.
 #[derive(Debug, Parser)]
+#[allow(clippy::struct_excessive_bools)]
 pub struct SearchArgs {
     /// Search query terms.
     #[arg(required = true)]
@@ -93,10 +113,22 @@ pub struct SearchArgs {
     #[arg(long)]
     pub color: Option<String>,
 
+    /// Minimum width in pixels.
+    #[arg(long)]
+    pub min_width: Option<u32>,
+
+    /// Minimum height in pixels.
+    #[arg(long)]
+    pub min_height: Option<u32>,
+
     /// Automatically download the first result.
     #[arg(long)]
     pub download: bool,
 
+    /// Download every result concurrently (see DX_CONCURRENT_DOWNLOADS).
+    #[arg(long, conflicts_with = "download")]
+    pub download_all: bool,
+
     /// Output directory for downloads.
     #[arg(short, long)]
     pub output: Option<String>,
@@ -105,9 +137,46 @@ pub struct SearchArgs {
     #[arg(long)]
     pub all: bool,
 
+    /// Scraping target IDs or categories to search with --all (comma-separated,
+    /// e.g. "stocksnap,textures"). Defaults to a few image sites; name a
+    /// category such as "images" to search all of its sites.
+    #[arg(long, value_delimiter = ',', requires = "all")]
+    pub targets: Vec<String>,
+
     /// Search mode: quantity (fast, early-exit) or quality (wait for all providers).
-    #[arg(short = 'm', long, value_enum, default_value = "quantity")]
-    pub mode: SearchModeArg,
+    /// Defaults to `search.mode` from the configuration.
+    #[arg(short = 'm', long, value_enum)]
+    pub mode: Option<SearchModeArg>,
+
+    /// Don't read or write the search result cache.
+    #[arg(long, conflicts_with = "refresh")]
+    pub no_cache: bool,
+
+    /// Ignore cached results and re-query providers, updating the cache.
+    #[arg(long)]
+    pub refresh: bool,
+
+    /// Write CREDITS.md for downloaded assets (with --download-all).
+    #[arg(long, requires = "download_all")]
+    pub credits: bool,
+
+    /// How results from several providers are ordered.
+    #[arg(long, value_enum, default_value = "interleave")]
+    pub rank: RankingArg,
+
+    /// Show a thumbnail of each result inline (text output only).
+    #[arg(long)]
+    pub preview: bool,
+
+    /// Columns for tsv and csv output and keys for ndjson, in order
+    /// (comma-separated, e.g. "id,provider,width,license").
+    #[arg(long, value_enum, value_delimiter = ',')]
+    pub fields: Vec<AssetField>,
+
+    /// Print one line per result from a template instead of --format,
+    /// e.g. '{provider}:{id} {title}'. Use `{{` and `}}` for literal braces.
+    #[arg(long, value_parser = Template::parse)]
+    pub template: Option<Template>,
 }
 
 impl SearchArgs {
@@ -116,30 +185,92 @@ impl SearchArgs {
     pub fn query_string(&self) -> String {
         self.query.join(" ")
     }
+
+    /// Get the cache policy selected by `--no-cache` / `--refresh`.
+    #[must_use]
+    pub fn cache_policy(&self) -> crate::types::CachePolicy {
+        if self.no_cache {
+            crate::types::CachePolicy::Bypass
+        } else if self.refresh {
+            crate::types::CachePolicy::Refresh
+        } else {
+            crate::types::CachePolicy::Use
+        }
+    }
 }
 
 /// Arguments for the download command.
 #[derive(Debug, Parser)]
 pub struct DownloadArgs {
     /// Asset ID to download (format: provider:id).
-    #[arg(required = true)]
-    pub asset_id: String,
+    #[arg(
+        required_unless_present = "from",
+        conflicts_with_all = ["from", "license", "media_type", "max"]
+    )]
+    pub asset_id: Option<String>,
 
     /// Output directory.
     #[arg(short = 'd', long)]
     pub output: Option<String>,
 
     /// Custom filename (without -f since it conflicts with global --format).
-    #[arg(long)]
+    #[arg(long, conflicts_with = "from")]
     pub filename: Option<String>,
+
+    /// Download every asset listed in a file (`-` for stdin): `dx search
+    /// --format json` output, NDJSON, or `provider:id` lines.
+    #[arg(long, value_name = "FILE")]
+    pub from: Option<String>,
+
+    /// Only download assets with one of these licenses (comma-separated,
+    /// e.g. "cc0,cc-by").
+    #[arg(long, value_delimiter = ',')]
+    pub license: Vec<String>,
+
+    /// Only download assets of this media type.
+    #[arg(short = 't', long = "type", value_enum)]
+    pub media_type: Option<MediaTypeArg>,
+
+    /// Download at most this many assets.
+    #[arg(long)]
+    pub max: Option<usize>,
+}
+
+/// Arguments for the preview command.
+#[derive(Debug, Parser)]
+pub struct PreviewArgs {
+    /// Image, video or PDF to preview (a local path or an http(s) URL).
+    pub source: String,
+
+    /// Width in terminal columns (default: terminal width, at most 80).
+    #[arg(long)]
+    pub width: Option<u16>,
+
+    /// Height in terminal rows (default: terminal height).
+    #[arg(long)]
+    pub height: Option<u16>,
+
+    /// Graphics protocol to draw with.
+    #[arg(long, value_enum, default_value = "auto")]
+    pub protocol: PreviewProtocolArg,
 }
 
 /// Arguments for the scrape command.
 #[derive(Debug, Parser)]
+#[allow(clippy::struct_excessive_bools)]
 pub struct ScrapeArgs {
-    /// URL to scrape media from.
-    #[arg(required = true)]
-    pub url: String,
+    /// URL to scrape media from (a web page, or an RSS/Atom feed).
+    #[arg(required_unless_present = "target")]
+    pub url: Option<String>,
+
+    /// Search pre-configured sites instead of a URL, by target ID or category
+    /// (comma-separated, e.g. "stocksnap,textures").
+    #[arg(long, value_delimiter = ',', conflicts_with = "url")]
+    pub target: Vec<String>,
+
+    /// Search query for --target (the site is browsed when omitted).
+    #[arg(long, requires = "target")]
+    pub query: Option<String>,
 
     /// Output directory for downloaded files.
     #[arg(short, long, default_value = ".")]
@@ -164,6 +295,31 @@ pub struct ScrapeArgs {
     /// Only show found media, don't download.
     #[arg(long)]
     pub dry_run: bool,
+
+    /// Harvest media listed in the site's sitemaps instead of scraping pages.
+    /// Sitemap-based --target sites are harvested when no --query is given.
+    #[arg(long)]
+    pub sitemap: bool,
+
+    /// Only harvest sitemap entries modified on or after this date (YYYY-MM-DD).
+    #[arg(long)]
+    pub since: Option<chrono::NaiveDate>,
+
+    /// Harvest every sitemap entry again, forgetting what earlier runs saw.
+    #[arg(long)]
+    pub full: bool,
+
+    /// Which links --depth follows.
+    #[arg(long, value_enum, default_value = "host")]
+    pub scope: CrawlScopeArg,
+
+    /// Most pages to fetch from any one host.
+    #[arg(long)]
+    pub max_pages_per_host: Option<usize>,
+
+    /// Crawl pages that robots.txt disallows, without its Crawl-delay.
+    #[arg(long)]
+    pub ignore_robots: bool,
 }
 
 /// Arguments for the providers command.
@@ -176,6 +332,83 @@ pub struct ProvidersArgs {
     /// Show detailed information.
     #[arg(short, long)]
     pub detailed: bool,
+
+    /// Show latency, error rates and circuit breaker state.
+    #[arg(long)]
+    pub health: bool,
+
+    /// Provider operation.
+    #[command(subcommand)]
+    pub action: Option<ProvidersAction>,
+}
+
+/// Provider operations.
+#[derive(Debug, Subcommand)]
+pub enum ProvidersAction {
+    /// Probe providers with a canned query and report their health.
+    Test {
+        /// Providers to probe (default: all available).
+        providers: Vec<String>,
+    },
+}
+
+/// Arguments for the cache command.
+#[derive(Debug, Parser)]
+pub struct CacheArgs {
+    /// Cache operation.
+    #[command(subcommand)]
+    pub action: CacheAction,
+}
+
+/// Cache operations.
+#[derive(Debug, Subcommand)]
+pub enum CacheAction {
+    /// Show cache location, size and entry counts.
+    Stats,
+    /// Remove cached search results.
+    Clear {
+        /// Only remove entries older than the cache TTL.
+        #[arg(long)]
+        expired: bool,
+    },
+}
+
+/// Arguments for the config command.
+#[derive(Debug, Parser)]
+pub struct ConfigArgs {
+    /// Config operation (defaults to `show`).
+    #[command(subcommand)]
+    pub action: Option<ConfigAction>,
+}
+
+/// Config operations.
+#[derive(Debug, Subcommand)]
+pub enum ConfigAction {
+    /// Print the effective configuration and where each value came from.
+    Show,
+    /// Print a single value, e.g. `search.mode` or `providers.openverse.count`.
+    Get {
+        /// Dotted setting key.
+        key: String,
+        /// Print API keys in full instead of masked.
+        #[arg(long)]
+        reveal: bool,
+    },
+    /// Write a value to the user config file.
+    Set {
+        /// Dotted setting key.
+        key: String,
+        /// New value.
+        value: String,
+        /// Write to ./dx.toml instead of the user config file.
+        #[arg(long)]
+        project: bool,
+        /// Write into the named profile's table.
+        #[arg(long)]
+        profile: Option<String>,
+    },
+    /// Print the config file locations.
+    Path,
 }
 
 /// Media type argument.
@@ -248,6 +481,58 @@ pub enum OutputFormat {
     JsonCompact,
     /// Tab-separated values.
     Tsv,
+    /// Comma-separated values (RFC 4180 quoting).
+    Csv,
+    /// Newline-delimited JSON, one record per line. Search results are
+    /// printed as each provider or scraper returns them.
+    Ndjson,
+}
+
+impl OutputFormat {
+    /// Whether this format prints JSON on a single line.
+    #[must_use]
+    pub fn is_compact_json(self) -> bool {
+        matches!(self, Self::JsonCompact | Self::Ndjson)
+    }
+}
+
+/// Asset field selectable with `--fields` and `--template`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+#[value(rename_all = "snake_case")]
+pub enum AssetField {
+    /// Provider-specific ID.
+    Id,
+    /// Provider name.
+    Provider,
+    /// Media type.
+    #[value(alias = "type")]
+    MediaType,
+    /// Title.
+    Title,
+    /// Author name.
+    Author,
+    /// Author profile URL.
+    AuthorUrl,
+    /// Source page URL.
+    SourceUrl,
+    /// Direct download URL.
+    DownloadUrl,
+    /// Preview/thumbnail URL.
+    PreviewUrl,
+    /// Width in pixels.
+    Width,
+    /// Height in pixels.
+    Height,
+    /// Duration in seconds.
+    DurationSecs,
+    /// File size in bytes.
+    FileSize,
+    /// MIME type.
+    MimeType,
+    /// License name.
+    License,
+    /// Tags (comma-separated in tabular output).
+    Tags,
 }
 
 /// Search mode argument.
@@ -271,6 +556,118 @@ impl From<SearchModeArg> for crate::types::SearchMode {
     }
 }
 
+/// Arguments for the credits command.
+#[derive(Debug, Parser)]
+pub struct CreditsArgs {
+    /// Results file from `dx search --format json` (`-` for stdin).
+    #[arg(required = true)]
+    pub input: String,
+
+    /// Credit style. Defaults to JSON when `--format json` is given, text otherwise.
+    #[arg(short, long, value_enum)]
+    pub style: Option<CreditStyleArg>,
+
+    /// Write credits to a file instead of stdout.
+    #[arg(short, long)]
+    pub output: Option<std::path::PathBuf>,
+}
+
+/// Credit style argument.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum CreditStyleArg {
+    /// One plain-text line per asset.
+    Text,
+    /// Markdown document.
+    Markdown,
+    /// HTML list.
+    Html,
+    /// JSON array.
+    Json,
+}
+
+impl From<CreditStyleArg> for crate::attribution::CreditFormat {
+    fn from(arg: CreditStyleArg) -> Self {
+        match arg {
+            CreditStyleArg::Text => Self::Text,
+            CreditStyleArg::Markdown => Self::Markdown,
+            CreditStyleArg::Html => Self::Html,
+            CreditStyleArg::Json => Self::Json,
+        }
+    }
+}
+
+/// Crawl scope argument.
+#[derive(Debug, Clone, Copy, Default, ValueEnum)]
+pub enum CrawlScopeArg {
+    /// Only the starting host. DEFAULT.
+    #[default]
+    Host,
+    /// Any host under the starting registrable domain.
+    Domain,
+    /// Any site.
+    Any,
+}
+
+impl From<CrawlScopeArg> for crate::engine::CrawlScope {
+    fn from(arg: CrawlScopeArg) -> Self {
+        match arg {
+            CrawlScopeArg::Host => crate::engine::CrawlScope::Host,
+            CrawlScopeArg::Domain => crate::engine::CrawlScope::Domain,
+            CrawlScopeArg::Any => crate::engine::CrawlScope::Any,
+        }
+    }
+}
+
+/// Result ranking argument.
+#[derive(Debug, Clone, Copy, Default, ValueEnum)]
+pub enum RankingArg {
+    /// Round-robin across providers, best matches first. DEFAULT.
+    #[default]
+    Interleave,
+    /// Sort purely by title and tag relevance.
+    Relevance,
+    /// Keep the order providers responded in, without deduplication.
+    Arrival,
+}
+
+impl From<RankingArg> for crate::types::RankingStrategy {
+    fn from(arg: RankingArg) -> Self {
+        match arg {
+            RankingArg::Interleave => crate::types::RankingStrategy::Interleave,
+            RankingArg::Relevance => crate::types::RankingStrategy::Relevance,
+            RankingArg::Arrival => crate::types::RankingStrategy::Arrival,
+        }
+    }
+}
+
+/// Inline preview protocol argument.
+#[derive(Debug, Clone, Copy, Default, ValueEnum)]
+pub enum PreviewProtocolArg {
+    /// Detect from the terminal. DEFAULT.
+    #[default]
+    Auto,
+    /// Kitty graphics protocol.
+    Kitty,
+    /// iTerm2 inline images.
+    Iterm,
+    /// Sixel graphics.
+    Sixel,
+    /// Unicode half blocks.
+    Blocks,
+}
+
+impl From<PreviewProtocolArg> for crate::cli::preview::Protocol {
+    fn from(arg: PreviewProtocolArg) -> Self {
+        match arg {
+            PreviewProtocolArg::Auto => Self::detect(),
+            PreviewProtocolArg::Kitty => Self::Kitty,
+            PreviewProtocolArg::Iterm => Self::Iterm,
+            PreviewProtocolArg::Sixel => Self::Sixel,
+            PreviewProtocolArg::Blocks => Self::Blocks,
+        }
+    }
+}
+
 impl Args {
     /// Parse command-line arguments.
     #[must_use]
@@ -293,12 +690,64 @@ mod tests {
             providers: vec![],
             orientation: None,
             color: None,
+            min_width: None,
+            min_height: None,
             download: false,
+            download_all: false,
             output: None,
             all: false,
-            mode: SearchModeArg::Quantity,
+            targets: vec![],
+            mode: None,
+            no_cache: false,
+            refresh: false,
+            credits: false,
+            preview: false,
+            fields: vec![],
+            template: None,
+            rank: RankingArg::Interleave,
         };
 
         assert_eq!(args.query_string(), "sunset mountains");
     }
+
+    #[test]
+    fn test_tool_args() {
+        use clap::CommandFactory;
+        Args::command().debug_assert();
+
+        let parse = |line: &str| Args::try_parse_from(line.split_whitespace());
+
+        let args = parse("dx tool image batch-watermark photos/*.jpg -o out --text (c)").unwrap();
+        let Command::Tool(ToolArgs {
+            category: ToolCommand::Image(ImageTool::BatchWatermark { batch, text, .. }),
+        }) = args.command
+        else {
+            panic!("unexpected command: {:?}", args.command);
+        };
+        assert_eq!(batch.inputs, ["photos/*.jpg"]);
+        assert_eq!(batch.output_dir, "out");
+        assert_eq!(text, "(c)");
+
+        assert!(parse("dx tool audio remove-silence a.mp3 b.mp3 --threshold-db -40").is_ok());
+        assert!(parse("dx tool audio split a.mp3 out --every 30 --parts 4").is_err());
+    }
+
+    #[test]
+    fn test_download_from_args() {
+        let parse = |line: &str| Args::try_parse_from(line.split_whitespace());
+
+        let args = parse("dx download --from - --license cc0,cc-by --type image --max 50").unwrap();
+        let Command::Download(download) = args.command else {
+            panic!("unexpected command: {:?}", args.command);
+        };
+        assert_eq!(download.from.as_deref(), Some("-"));
+        assert_eq!(download.license, ["cc0", "cc-by"]);
+        assert_eq!(download.max, Some(50));
+
+        assert!(parse("dx download openverse:1").is_ok());
+        assert!(parse("dx download").is_err());
+        assert!(parse("dx download openverse:1 --from -").is_err());
+        assert!(parse("dx download openverse:1 --max 5").is_err());
+        assert!(parse("dx download --max 5").is_err());
+    }
 }
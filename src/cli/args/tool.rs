@@ -0,0 +1,1486 @@
+//! Arguments for `dx tool`, one subcommand per media tool.
+//!
+//! Operations are named after the `ImageTools`, `VideoTools`, `AudioTools`,
+//! `DocumentTools`, `ArchiveTools` and `UtilityTools` methods, plus the
+//! module-level `batch_*` functions. Batch operations take glob patterns.
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+
+use crate::tools::{archive, audio, document, image, utility, video};
+
+/// Arguments for the tool command.
+#[derive(Debug, Parser)]
+pub struct ToolArgs {
+    /// Tool category.
+    #[command(subcommand)]
+    pub category: ToolCommand,
+}
+
+/// Tool categories.
+#[derive(Debug, Subcommand)]
+pub enum ToolCommand {
+    /// Image tools (ImageMagick).
+    #[command(subcommand)]
+    Image(ImageTool),
+    /// Video tools (FFmpeg).
+    #[command(subcommand)]
+    Video(VideoTool),
+    /// Audio tools (FFmpeg).
+    #[command(subcommand)]
+    Audio(AudioTool),
+    /// PDF and document tools.
+    #[command(subcommand)]
+    Document(DocumentTool),
+    /// Archive and compression tools.
+    #[command(subcommand)]
+    Archive(ArchiveTool),
+    /// Hashing, encoding and format conversion tools.
+    #[command(subcommand)]
+    Utility(UtilityTool),
+}
+
+/// Input files for batch operations.
+#[derive(Debug, Args)]
+pub struct BatchInputs {
+    /// Input files or glob patterns (e.g. "photos/*.jpg").
+    #[arg(required = true)]
+    pub inputs: Vec<String>,
+
+    /// Directory to write results to.
+    #[arg(short, long)]
+    pub output_dir: String,
+}
+
+/// Image operations.
+#[derive(Debug, Subcommand)]
+pub enum ImageTool {
+    /// Convert an image to the format of the output extension.
+    Convert {
+        /// Input image.
+        input: String,
+        /// Output image.
+        output: String,
+    },
+    /// Resize an image to fit within a width and height.
+    Resize {
+        /// Input image.
+        input: String,
+        /// Output image.
+        output: String,
+        /// Width in pixels.
+        #[arg(long)]
+        width: u32,
+        /// Height in pixels.
+        #[arg(long)]
+        height: u32,
+    },
+    /// Reduce file size at a given quality.
+    Compress {
+        /// Input image.
+        input: String,
+        /// Output image.
+        output: String,
+        /// Quality from 1 to 100.
+        #[arg(long, default_value_t = 80, value_parser = clap::value_parser!(u8).range(1..=100))]
+        quality: u8,
+    },
+    /// Overlay a text watermark.
+    AddWatermark {
+        /// Input image.
+        input: String,
+        /// Output image.
+        output: String,
+        /// Watermark text.
+        #[arg(long)]
+        text: String,
+        /// Where to place the text.
+        #[arg(long, value_enum, default_value = "bottom-center")]
+        position: WatermarkPositionArg,
+    },
+    /// Remove EXIF and other metadata.
+    StripMetadata {
+        /// Input image.
+        input: String,
+        /// Output image.
+        output: String,
+    },
+    /// Generate a QR code image.
+    GenerateQr {
+        /// Text or URL to encode.
+        data: String,
+        /// Output image.
+        output: String,
+        /// Size in pixels.
+        #[arg(long, default_value_t = 256)]
+        size: u32,
+    },
+    /// Decode a QR code from an image.
+    DecodeQr {
+        /// Input image.
+        input: String,
+    },
+    /// Extract the dominant colors.
+    ExtractPalette {
+        /// Input image.
+        input: String,
+        /// Number of colors.
+        #[arg(long, default_value_t = 5)]
+        colors: u32,
+    },
+    /// Apply a visual filter.
+    ApplyFilter {
+        /// Input image.
+        input: String,
+        /// Output image.
+        output: String,
+        /// Filter to apply.
+        #[arg(long, value_enum)]
+        filter: FilterArg,
+    },
+    /// Extract text with OCR (Tesseract).
+    ExtractText {
+        /// Input image.
+        input: String,
+    },
+    /// Generate favicons and app icons.
+    GenerateIcons {
+        /// Source image.
+        input: String,
+        /// Directory to write icons to.
+        output_dir: String,
+    },
+    /// Remove metadata from many images.
+    BatchStripMetadata {
+        /// Inputs and output directory.
+        #[command(flatten)]
+        batch: BatchInputs,
+    },
+    /// Watermark many images.
+    BatchWatermark {
+        /// Inputs and output directory.
+        #[command(flatten)]
+        batch: BatchInputs,
+        /// Watermark text.
+        #[arg(long)]
+        text: String,
+        /// Where to place the text.
+        #[arg(long, value_enum, default_value = "bottom-center")]
+        position: WatermarkPositionArg,
+    },
+    /// Extract text from many images into text files.
+    BatchExtract {
+        /// Inputs and output directory.
+        #[command(flatten)]
+        batch: BatchInputs,
+        /// Tesseract language code.
+        #[arg(long, default_value = "eng")]
+        language: String,
+    },
+}
+
+/// Transcoding flags shared by `transcode` and `batch-transcode`.
+#[derive(Debug, Args)]
+pub struct TranscodeFlags {
+    /// Output container.
+    #[arg(long, value_enum, default_value = "mp4")]
+    pub to: VideoFormatArg,
+    /// Quality preset.
+    #[arg(long, value_enum, default_value = "medium")]
+    pub quality: VideoQualityArg,
+    /// Video bitrate (e.g. "2M").
+    #[arg(long)]
+    pub bitrate: Option<String>,
+    /// Audio bitrate (e.g. "128k").
+    #[arg(long, default_value = "128k")]
+    pub audio_bitrate: String,
+    /// Frame rate.
+    #[arg(long)]
+    pub fps: Option<f32>,
+    /// Drop the audio track.
+    #[arg(long)]
+    pub no_audio: bool,
+}
+
+impl From<&TranscodeFlags> for video::TranscodeOptions {
+    fn from(flags: &TranscodeFlags) -> Self {
+        Self {
+            format: flags.to.into(),
+            quality: flags.quality.into(),
+            bitrate: flags.bitrate.clone(),
+            audio_bitrate: Some(flags.audio_bitrate.clone()),
+            fps: flags.fps,
+            no_audio: flags.no_audio,
+            ..Self::default()
+        }
+    }
+}
+
+/// Video operations.
+#[derive(Debug, Subcommand)]
+pub enum VideoTool {
+    /// Transcode to another container and quality.
+    Transcode {
+        /// Input video.
+        input: String,
+        /// Output video.
+        output: String,
+        /// Transcoding options.
+        #[command(flatten)]
+        options: TranscodeFlags,
+    },
+    /// Extract the audio track.
+    ExtractAudio {
+        /// Input video.
+        input: String,
+        /// Output audio file.
+        output: String,
+        /// Audio format.
+        #[arg(long, value_enum, default_value = "mp3")]
+        to: AudioFormatArg,
+    },
+    /// Cut a section between two timestamps.
+    Trim {
+        /// Input video.
+        input: String,
+        /// Output video.
+        output: String,
+        /// Start time in seconds.
+        #[arg(long)]
+        start: f64,
+        /// End time in seconds.
+        #[arg(long)]
+        end: f64,
+    },
+    /// Convert to an animated GIF.
+    ToGif {
+        /// Input video.
+        input: String,
+        /// Output GIF.
+        output: String,
+        /// Width in pixels.
+        #[arg(long, default_value_t = 480)]
+        width: u32,
+        /// Frames per second.
+        #[arg(long, default_value_t = 15)]
+        fps: u32,
+        /// Start time in seconds.
+        #[arg(long)]
+        start: Option<f64>,
+        /// Length in seconds.
+        #[arg(long)]
+        duration: Option<f64>,
+        /// Palette size.
+        #[arg(long, default_value_t = 256)]
+        colors: u32,
+    },
+    /// Save a single frame as an image.
+    Thumbnail {
+        /// Input video.
+        input: String,
+        /// Output image.
+        output: String,
+        /// Time of the frame in seconds.
+        #[arg(long, default_value_t = 1.0)]
+        at: f64,
+    },
+    /// Change the resolution.
+    Scale {
+        /// Input video.
+        input: String,
+        /// Output video.
+        output: String,
+        /// Width in pixels.
+        #[arg(long)]
+        width: u32,
+        /// Height in pixels.
+        #[arg(long)]
+        height: u32,
+    },
+    /// Join videos end to end.
+    Concatenate {
+        /// Input videos or glob patterns, in order.
+        #[arg(required = true)]
+        inputs: Vec<String>,
+        /// Output video.
+        #[arg(short, long)]
+        output: String,
+    },
+    /// Remove the audio track.
+    Mute {
+        /// Input video.
+        input: String,
+        /// Output video.
+        output: String,
+    },
+    /// Overlay a text watermark.
+    AddWatermark {
+        /// Input video.
+        input: String,
+        /// Output video.
+        output: String,
+        /// Watermark text.
+        #[arg(long)]
+        text: String,
+    },
+    /// Speed up or slow down playback.
+    ChangeSpeed {
+        /// Input video.
+        input: String,
+        /// Output video.
+        output: String,
+        /// Speed factor (2.0 is twice as fast).
+        #[arg(long)]
+        factor: f32,
+    },
+    /// Burn a subtitle file into the picture.
+    BurnSubtitles {
+        /// Input video.
+        input: String,
+        /// Subtitle file (SRT, ASS or VTT).
+        subtitles: String,
+        /// Output video.
+        output: String,
+    },
+    /// Transcode many videos.
+    BatchTranscode {
+        /// Inputs and output directory.
+        #[command(flatten)]
+        batch: BatchInputs,
+        /// Transcoding options.
+        #[command(flatten)]
+        options: TranscodeFlags,
+    },
+    /// Extract audio from many videos.
+    BatchExtractAudio {
+        /// Inputs and output directory.
+        #[command(flatten)]
+        batch: BatchInputs,
+        /// Audio format.
+        #[arg(long, value_enum, default_value = "mp3")]
+        to: AudioFormatArg,
+    },
+    /// Scale many videos.
+    BatchScale {
+        /// Inputs and output directory.
+        #[command(flatten)]
+        batch: BatchInputs,
+        /// Width in pixels.
+        #[arg(long)]
+        width: u32,
+        /// Height in pixels.
+        #[arg(long)]
+        height: u32,
+    },
+    /// Mute many videos.
+    BatchMute {
+        /// Inputs and output directory.
+        #[command(flatten)]
+        batch: BatchInputs,
+    },
+    /// Watermark many videos.
+    BatchWatermark {
+        /// Inputs and output directory.
+        #[command(flatten)]
+        batch: BatchInputs,
+        /// Watermark text.
+        #[arg(long)]
+        text: String,
+    },
+    /// Change the speed of many videos.
+    BatchSpeed {
+        /// Inputs and output directory.
+        #[command(flatten)]
+        batch: BatchInputs,
+        /// Speed factor (2.0 is twice as fast).
+        #[arg(long)]
+        factor: f32,
+    },
+    /// Burn subtitles into many videos, using the subtitle file next to each.
+    BatchBurnSubtitles {
+        /// Inputs and output directory.
+        #[command(flatten)]
+        batch: BatchInputs,
+        /// Extension of the subtitle file sharing each video's name.
+        #[arg(long, default_value = "srt")]
+        subtitle_ext: String,
+    },
+}
+
+/// Audio conversion flags shared by `convert` and `batch-convert`.
+#[derive(Debug, Args)]
+pub struct ConvertFlags {
+    /// Output format.
+    #[arg(long, value_enum, default_value = "mp3")]
+    pub to: AudioOutputFormatArg,
+    /// Bitrate in kbps.
+    #[arg(long, default_value_t = 192)]
+    pub bitrate: u32,
+    /// Sample rate in Hz.
+    #[arg(long)]
+    pub sample_rate: Option<u32>,
+    /// Number of channels.
+    #[arg(long)]
+    pub channels: Option<u8>,
+}
+
+impl From<&ConvertFlags> for audio::ConvertOptions {
+    fn from(flags: &ConvertFlags) -> Self {
+        Self {
+            format: flags.to.into(),
+            bitrate: Some(flags.bitrate),
+            sample_rate: flags.sample_rate,
+            channels: flags.channels,
+        }
+    }
+}
+
+/// Normalization flags shared by `normalize` and `batch-normalize`.
+#[derive(Debug, Args)]
+pub struct NormalizeFlags {
+    /// Normalization method.
+    #[arg(long, value_enum, default_value = "peak")]
+    pub method: NormalizeMethodArg,
+    /// Target level in dB (LUFS for loudness).
+    #[arg(long, default_value_t = -1.0, allow_negative_numbers = true)]
+    pub target: f32,
+    /// Don't apply a limiter.
+    #[arg(long)]
+    pub no_limiter: bool,
+}
+
+impl From<&NormalizeFlags> for audio::NormalizeOptions {
+    fn from(flags: &NormalizeFlags) -> Self {
+        Self {
+            method: flags.method.into(),
+            target_level: flags.target,
+            limiter: !flags.no_limiter,
+            ..Self::default()
+        }
+    }
+}
+
+/// Silence removal flags shared by `remove-silence` and `batch-remove-silence`.
+#[derive(Debug, Args)]
+pub struct SilenceFlags {
+    /// Level below which audio counts as silence, in dB.
+    #[arg(long, default_value_t = -50.0, allow_negative_numbers = true)]
+    pub threshold_db: f32,
+    /// Shortest silence to remove, in seconds.
+    #[arg(long, default_value_t = 0.5)]
+    pub min_duration: f64,
+    /// Silence to keep around sounds, in seconds.
+    #[arg(long, default_value_t = 0.1)]
+    pub padding: f64,
+}
+
+impl From<&SilenceFlags> for audio::SilenceOptions {
+    fn from(flags: &SilenceFlags) -> Self {
+        Self {
+            threshold_db: flags.threshold_db,
+            min_duration: flags.min_duration,
+            padding: flags.padding,
+        }
+    }
+}
+
+/// Audio operations.
+#[derive(Debug, Subcommand)]
+pub enum AudioTool {
+    /// Convert to another format.
+    Convert {
+        /// Input audio file.
+        input: String,
+        /// Output audio file.
+        output: String,
+        /// Conversion options.
+        #[command(flatten)]
+        options: ConvertFlags,
+    },
+    /// Normalize levels.
+    Normalize {
+        /// Input audio file.
+        input: String,
+        /// Output audio file.
+        output: String,
+        /// Normalization options.
+        #[command(flatten)]
+        options: NormalizeFlags,
+    },
+    /// Cut a section between two timestamps.
+    Trim {
+        /// Input audio file.
+        input: String,
+        /// Output audio file.
+        output: String,
+        /// Start time in seconds.
+        #[arg(long)]
+        start: f64,
+        /// End time in seconds.
+        #[arg(long)]
+        end: f64,
+    },
+    /// Join audio files end to end.
+    Merge {
+        /// Input files or glob patterns, in order.
+        #[arg(required = true)]
+        inputs: Vec<String>,
+        /// Output audio file.
+        #[arg(short, long)]
+        output: String,
+    },
+    /// Render a waveform or spectrogram image.
+    Spectrum {
+        /// Input audio file.
+        input: String,
+        /// Output image.
+        output: String,
+        /// Visualization type.
+        #[arg(long, value_enum, default_value = "waveform")]
+        kind: SpectrumTypeArg,
+        /// Width in pixels.
+        #[arg(long, default_value_t = 1920)]
+        width: u32,
+        /// Height in pixels.
+        #[arg(long, default_value_t = 1080)]
+        height: u32,
+        /// Draw axes.
+        #[arg(long)]
+        show_axis: bool,
+    },
+    /// Show tags and stream information.
+    Metadata {
+        /// Input audio file.
+        input: String,
+    },
+    /// Cut out silent passages.
+    RemoveSilence {
+        /// Input audio file.
+        input: String,
+        /// Output audio file.
+        output: String,
+        /// Silence detection options.
+        #[command(flatten)]
+        options: SilenceFlags,
+    },
+    /// Split into segments (every 60 seconds by default).
+    Split {
+        /// Input audio file.
+        input: String,
+        /// Directory to write segments to.
+        output_dir: String,
+        /// Segment length in seconds.
+        #[arg(long, group = "method")]
+        every: Option<f64>,
+        /// Number of equal parts.
+        #[arg(long, group = "method")]
+        parts: Option<u32>,
+        /// Split points in seconds (comma-separated).
+        #[arg(long, value_delimiter = ',', group = "method")]
+        at: Vec<f64>,
+        /// Split at silences quieter than this level in dB.
+        #[arg(long, group = "method", allow_negative_numbers = true)]
+        at_silence: Option<f32>,
+    },
+    /// Apply an effect, e.g. `reverse`, `speed=1.5` or `echo=0.5:0.3`.
+    ApplyEffect {
+        /// Input audio file.
+        input: String,
+        /// Output audio file.
+        output: String,
+        /// Effect, e.g. `reverse`, `speed=1.5`, `echo=0.5:0.3` or `band-pass=200:3000`.
+        #[arg(long, value_parser = parse_effect)]
+        effect: audio::AudioEffect,
+    },
+    /// Transcribe speech to text.
+    Transcribe {
+        /// Input audio file.
+        input: String,
+    },
+    /// Convert many audio files.
+    BatchConvert {
+        /// Inputs and output directory.
+        #[command(flatten)]
+        batch: BatchInputs,
+        /// Conversion options.
+        #[command(flatten)]
+        options: ConvertFlags,
+    },
+    /// Normalize many audio files.
+    BatchNormalize {
+        /// Inputs and output directory.
+        #[command(flatten)]
+        batch: BatchInputs,
+        /// Normalization options.
+        #[command(flatten)]
+        options: NormalizeFlags,
+    },
+    /// Trim many audio files.
+    BatchTrim {
+        /// Inputs and output directory.
+        #[command(flatten)]
+        batch: BatchInputs,
+        /// Start time in seconds.
+        #[arg(long)]
+        start: f64,
+        /// End time in seconds.
+        #[arg(long)]
+        end: f64,
+    },
+    /// Render waveforms for many audio files.
+    BatchWaveform {
+        /// Inputs and output directory.
+        #[command(flatten)]
+        batch: BatchInputs,
+        /// Width in pixels.
+        #[arg(long, default_value_t = 1920)]
+        width: u32,
+        /// Height in pixels.
+        #[arg(long, default_value_t = 1080)]
+        height: u32,
+    },
+    /// Remove silence from many audio files.
+    BatchRemoveSilence {
+        /// Inputs and output directory.
+        #[command(flatten)]
+        batch: BatchInputs,
+        /// Silence detection options.
+        #[command(flatten)]
+        options: SilenceFlags,
+    },
+    /// Apply an effect to many audio files.
+    BatchEffect {
+        /// Inputs and output directory.
+        #[command(flatten)]
+        batch: BatchInputs,
+        /// Effect specification, as for `apply-effect`.
+        #[arg(long, value_parser = parse_effect)]
+        effect: audio::AudioEffect,
+    },
+}
+
+/// Document operations.
+#[derive(Debug, Subcommand)]
+pub enum DocumentTool {
+    /// Combine PDFs into one.
+    MergePdf {
+        /// Input PDFs or glob patterns, in order.
+        #[arg(required = true)]
+        inputs: Vec<String>,
+        /// Output PDF.
+        #[arg(short, long)]
+        output: String,
+    },
+    /// Split a PDF into one file per page.
+    SplitPdf {
+        /// Input PDF.
+        input: String,
+        /// Directory to write pages to.
+        output_dir: String,
+    },
+    /// Reduce PDF size (Ghostscript).
+    CompressPdf {
+        /// Input PDF.
+        input: String,
+        /// Output PDF.
+        output: String,
+        /// Quality preset.
+        #[arg(long, value_enum, default_value = "ebook")]
+        quality: CompressionQualityArg,
+    },
+    /// Render PDF pages as images.
+    PdfToImages {
+        /// Input PDF.
+        input: String,
+        /// Directory to write images to.
+        output_dir: String,
+    },
+    /// Render Markdown as HTML.
+    MarkdownToHtml {
+        /// Input Markdown file.
+        input: String,
+        /// Output HTML file.
+        output: String,
+    },
+    /// Render HTML as PDF.
+    HtmlToPdf {
+        /// Input HTML file.
+        input: String,
+        /// Output PDF.
+        output: String,
+    },
+    /// Convert between document formats (Pandoc/LibreOffice).
+    ConvertDocument {
+        /// Input document.
+        input: String,
+        /// Output document.
+        output: String,
+        /// Output format.
+        #[arg(long, value_enum)]
+        to: DocFormatArg,
+    },
+    /// Extract plain text.
+    ExtractText {
+        /// Input document.
+        input: String,
+    },
+    /// Stamp text on every page.
+    WatermarkPdf {
+        /// Input PDF.
+        input: String,
+        /// Output PDF.
+        output: String,
+        /// Watermark text.
+        #[arg(long)]
+        text: String,
+    },
+    /// Protect a PDF with a password.
+    EncryptPdf {
+        /// Input PDF.
+        input: String,
+        /// Output PDF.
+        output: String,
+        /// Password.
+        #[arg(long)]
+        password: String,
+    },
+    /// Remove a PDF's password.
+    DecryptPdf {
+        /// Input PDF.
+        input: String,
+        /// Output PDF.
+        output: String,
+        /// Password.
+        #[arg(long)]
+        password: String,
+    },
+    /// Compress many PDFs.
+    BatchCompress {
+        /// Inputs and output directory.
+        #[command(flatten)]
+        batch: BatchInputs,
+        /// Quality preset.
+        #[arg(long, value_enum, default_value = "ebook")]
+        quality: CompressionQualityArg,
+    },
+    /// Render the pages of many PDFs as images.
+    BatchPdfToImages {
+        /// Inputs and output directory.
+        #[command(flatten)]
+        batch: BatchInputs,
+    },
+    /// Render many Markdown files as HTML.
+    BatchMarkdownToHtml {
+        /// Inputs and output directory.
+        #[command(flatten)]
+        batch: BatchInputs,
+    },
+    /// Render many HTML files as PDF.
+    BatchHtmlToPdf {
+        /// Inputs and output directory.
+        #[command(flatten)]
+        batch: BatchInputs,
+    },
+    /// Convert many documents.
+    BatchConvert {
+        /// Inputs and output directory.
+        #[command(flatten)]
+        batch: BatchInputs,
+        /// Output format.
+        #[arg(long, value_enum)]
+        to: DocFormatArg,
+    },
+    /// Extract text from many documents into text files.
+    BatchExtract {
+        /// Inputs and output directory.
+        #[command(flatten)]
+        batch: BatchInputs,
+    },
+    /// Watermark many PDFs.
+    BatchWatermark {
+        /// Inputs and output directory.
+        #[command(flatten)]
+        batch: BatchInputs,
+        /// Watermark text.
+        #[arg(long)]
+        text: String,
+    },
+    /// Encrypt many PDFs with the same password.
+    BatchEncrypt {
+        /// Inputs and output directory.
+        #[command(flatten)]
+        batch: BatchInputs,
+        /// Password.
+        #[arg(long)]
+        password: String,
+    },
+}
+
+/// Archive operations.
+#[derive(Debug, Subcommand)]
+pub enum ArchiveTool {
+    /// Create a ZIP archive.
+    CreateZip {
+        /// Files, directories or glob patterns to add.
+        #[arg(required = true)]
+        inputs: Vec<String>,
+        /// Output archive.
+        #[arg(short, long)]
+        output: String,
+    },
+    /// Extract a ZIP archive.
+    ExtractZip {
+        /// Input archive.
+        input: String,
+        /// Directory to extract into.
+        output_dir: String,
+    },
+    /// Create a TAR archive.
+    CreateTar {
+        /// Files, directories or glob patterns to add.
+        #[arg(required = true)]
+        inputs: Vec<String>,
+        /// Output archive.
+        #[arg(short, long)]
+        output: String,
+    },
+    /// Extract a TAR archive.
+    ExtractTar {
+        /// Input archive.
+        input: String,
+        /// Directory to extract into.
+        output_dir: String,
+    },
+    /// Compress a file with gzip.
+    Gzip {
+        /// Input file.
+        input: String,
+        /// Output file.
+        output: String,
+    },
+    /// Decompress a gzip file.
+    Gunzip {
+        /// Input file.
+        input: String,
+        /// Output file.
+        output: String,
+    },
+    /// List an archive's contents.
+    List {
+        /// Input archive.
+        input: String,
+    },
+    /// Create a password-protected ZIP archive.
+    EncryptArchive {
+        /// Files, directories or glob patterns to add.
+        #[arg(required = true)]
+        inputs: Vec<String>,
+        /// Output archive.
+        #[arg(short, long)]
+        output: String,
+        /// Password.
+        #[arg(long)]
+        password: String,
+    },
+    /// Split an archive into fixed-size parts.
+    SplitArchive {
+        /// Input archive.
+        input: String,
+        /// Directory to write parts to.
+        output_dir: String,
+        /// Part size in megabytes.
+        #[arg(long)]
+        part_size_mb: u64,
+    },
+    /// Join split archive parts.
+    MergeArchives {
+        /// Parts or glob patterns, in order.
+        #[arg(required = true)]
+        parts: Vec<String>,
+        /// Output archive.
+        #[arg(short, long)]
+        output: String,
+    },
+    /// Compress many files individually.
+    BatchCompress {
+        /// Inputs and output directory.
+        #[command(flatten)]
+        batch: BatchInputs,
+        /// Compression algorithm.
+        #[arg(long, value_enum, default_value = "gzip")]
+        algorithm: CompressionAlgorithmArg,
+    },
+    /// Decompress many files.
+    BatchDecompress {
+        /// Inputs and output directory.
+        #[command(flatten)]
+        batch: BatchInputs,
+    },
+}
+
+/// Utility operations.
+#[derive(Debug, Subcommand)]
+pub enum UtilityTool {
+    /// Hash a file.
+    HashFile {
+        /// Input file.
+        input: String,
+        /// Hash algorithm.
+        #[arg(long, value_enum, default_value = "sha256")]
+        algorithm: HashAlgorithmArg,
+    },
+    /// Encode a file as base64.
+    Base64Encode {
+        /// Input file.
+        input: String,
+    },
+    /// Decode a base64 file.
+    Base64Decode {
+        /// Input file.
+        input: String,
+        /// Output file.
+        output: String,
+    },
+    /// Percent-encode text.
+    UrlEncode {
+        /// Text to encode.
+        text: String,
+    },
+    /// Decode percent-encoded text.
+    UrlDecode {
+        /// Text to decode.
+        text: String,
+    },
+    /// Pretty-print a JSON file.
+    FormatJson {
+        /// Input file.
+        input: String,
+        /// Output file.
+        output: String,
+    },
+    /// Convert JSON to YAML.
+    JsonToYaml {
+        /// Input file.
+        input: String,
+        /// Output file.
+        output: String,
+    },
+    /// Convert YAML to JSON.
+    YamlToJson {
+        /// Input file.
+        input: String,
+        /// Output file.
+        output: String,
+    },
+    /// Compare two files line by line.
+    DiffFiles {
+        /// Original file.
+        file1: String,
+        /// Changed file.
+        file2: String,
+    },
+    /// Generate a random UUID (v4).
+    GenerateUuid,
+    /// Print the current time.
+    Timestamp,
+    /// Generate a random alphanumeric string.
+    RandomString {
+        /// Number of characters.
+        #[arg(long, default_value_t = 16)]
+        length: usize,
+    },
+    /// Convert CSV to JSON.
+    CsvToJson {
+        /// Input file.
+        input: String,
+        /// Output file.
+        output: String,
+    },
+    /// Hash many files.
+    BatchHash {
+        /// Input files or glob patterns.
+        #[arg(required = true)]
+        inputs: Vec<String>,
+        /// Hash algorithm.
+        #[arg(long, value_enum, default_value = "sha256")]
+        algorithm: HashAlgorithmArg,
+    },
+}
+
+/// Parse an audio effect given as `name` or `name=value[:value...]`.
+///
+/// Effects taking values: `speed`, `pitch`, `bass-boost`, `treble-boost`,
+/// `distortion`, `denoise`, `stereo-widen`, `low-pass`, `high-pass`,
+/// `band-pass=LOW:HIGH`, `echo=DELAY:DECAY`, `reverb=ROOM:DAMPING`,
+/// `compressor=THRESHOLD:RATIO`, `equalizer=GAIN:GAIN:...` and `custom=FILTER`.
+/// Effects without values: `flanger`, `phaser`, `chorus`, `reverse`.
+pub fn parse_effect(spec: &str) -> Result<audio::AudioEffect, String> {
+    use audio::AudioEffect;
+
+    fn number<T: std::str::FromStr>(value: &str) -> Result<T, String> {
+        value
+            .trim()
+            .parse()
+            .map_err(|_| format!("invalid number '{value}'"))
+    }
+    fn pair<A: std::str::FromStr, B: std::str::FromStr>(value: &str) -> Result<(A, B), String> {
+        let (a, b) = value
+            .split_once(':')
+            .ok_or_else(|| format!("expected two values separated by ':', got '{value}'"))?;
+        Ok((number(a)?, number(b)?))
+    }
+
+    let (name, value) = match spec.split_once('=') {
+        Some((name, value)) => (name, Some(value)),
+        None => (spec, None),
+    };
+    let name = name.trim().to_lowercase().replace('_', "-");
+    let Some(value) = value else {
+        return match name.as_str() {
+            "flanger" => Ok(AudioEffect::Flanger),
+            "phaser" => Ok(AudioEffect::Phaser),
+            "chorus" => Ok(AudioEffect::Chorus),
+            "reverse" => Ok(AudioEffect::Reverse),
+            _ => Err(format!(
+                "unknown effect '{name}', or it needs a value ({name}=...)"
+            )),
+        };
+    };
+
+    match name.as_str() {
+        "speed" => Ok(AudioEffect::Speed(number(value)?)),
+        "pitch" => Ok(AudioEffect::Pitch(number(value)?)),
+        "bass-boost" => Ok(AudioEffect::BassBoost(number(value)?)),
+        "treble-boost" => Ok(AudioEffect::TrebleBoost(number(value)?)),
+        "distortion" => Ok(AudioEffect::Distortion(number(value)?)),
+        "denoise" => Ok(AudioEffect::DeNoise(number(value)?)),
+        "stereo-widen" => Ok(AudioEffect::StereoWiden(number(value)?)),
+        "low-pass" => Ok(AudioEffect::LowPass(number(value)?)),
+        "high-pass" => Ok(AudioEffect::HighPass(number(value)?)),
+        "band-pass" => pair(value).map(|(low, high)| AudioEffect::BandPass { low, high }),
+        "echo" => pair(value).map(|(delay, decay)| AudioEffect::Echo { delay, decay }),
+        "reverb" => {
+            pair(value).map(|(room_size, damping)| AudioEffect::Reverb { room_size, damping })
+        }
+        "compressor" => {
+            pair(value).map(|(threshold, ratio)| AudioEffect::Compressor { threshold, ratio })
+        }
+        "equalizer" => value
+            .split(':')
+            .map(number)
+            .collect::<Result<_, _>>()
+            .map(AudioEffect::Equalizer),
+        "custom" => Ok(AudioEffect::Custom(value.to_string())),
+        _ => Err(format!("unknown effect '{name}'")),
+    }
+}
+
+/// Image watermark position argument.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum WatermarkPositionArg {
+    /// Top left corner.
+    TopLeft,
+    /// Top edge, centered.
+    TopCenter,
+    /// Top right corner.
+    TopRight,
+    /// Left edge, centered.
+    CenterLeft,
+    /// Center.
+    Center,
+    /// Right edge, centered.
+    CenterRight,
+    /// Bottom left corner.
+    BottomLeft,
+    /// Bottom edge, centered.
+    BottomCenter,
+    /// Bottom right corner.
+    BottomRight,
+}
+
+impl From<WatermarkPositionArg> for image::WatermarkPosition {
+    fn from(arg: WatermarkPositionArg) -> Self {
+        match arg {
+            WatermarkPositionArg::TopLeft => Self::TopLeft,
+            WatermarkPositionArg::TopCenter => Self::TopCenter,
+            WatermarkPositionArg::TopRight => Self::TopRight,
+            WatermarkPositionArg::CenterLeft => Self::CenterLeft,
+            WatermarkPositionArg::Center => Self::Center,
+            WatermarkPositionArg::CenterRight => Self::CenterRight,
+            WatermarkPositionArg::BottomLeft => Self::BottomLeft,
+            WatermarkPositionArg::BottomCenter => Self::BottomCenter,
+            WatermarkPositionArg::BottomRight => Self::BottomRight,
+        }
+    }
+}
+
+/// Image filter argument.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum FilterArg {
+    /// Grayscale.
+    Grayscale,
+    /// Sepia tone.
+    Sepia,
+    /// Inverted colors.
+    Invert,
+    /// Gaussian blur.
+    Blur,
+    /// Sharpen.
+    Sharpen,
+    /// Emboss.
+    Emboss,
+    /// Edge detection.
+    Edge,
+    /// Oil painting.
+    OilPaint,
+    /// Charcoal drawing.
+    Charcoal,
+    /// Pencil sketch.
+    Sketch,
+    /// Darkened corners.
+    Vignette,
+    /// Reduced color levels.
+    Posterize,
+    /// Solarization.
+    Solarize,
+}
+
+impl From<FilterArg> for image::Filter {
+    fn from(arg: FilterArg) -> Self {
+        match arg {
+            FilterArg::Grayscale => Self::Grayscale,
+            FilterArg::Sepia => Self::Sepia,
+            FilterArg::Invert => Self::Invert,
+            FilterArg::Blur => Self::Blur,
+            FilterArg::Sharpen => Self::Sharpen,
+            FilterArg::Emboss => Self::Emboss,
+            FilterArg::Edge => Self::Edge,
+            FilterArg::OilPaint => Self::OilPaint,
+            FilterArg::Charcoal => Self::Charcoal,
+            FilterArg::Sketch => Self::Sketch,
+            FilterArg::Vignette => Self::Vignette,
+            FilterArg::Posterize => Self::Posterize,
+            FilterArg::Solarize => Self::Solarize,
+        }
+    }
+}
+
+/// Video container argument.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum VideoFormatArg {
+    /// MP4 (H.264).
+    Mp4,
+    /// WebM (VP9).
+    Webm,
+    /// Matroska.
+    Mkv,
+    /// AVI.
+    Avi,
+    /// QuickTime.
+    Mov,
+    /// Animated GIF.
+    Gif,
+}
+
+impl From<VideoFormatArg> for video::VideoFormat {
+    fn from(arg: VideoFormatArg) -> Self {
+        match arg {
+            VideoFormatArg::Mp4 => Self::Mp4,
+            VideoFormatArg::Webm => Self::WebM,
+            VideoFormatArg::Mkv => Self::Mkv,
+            VideoFormatArg::Avi => Self::Avi,
+            VideoFormatArg::Mov => Self::Mov,
+            VideoFormatArg::Gif => Self::Gif,
+        }
+    }
+}
+
+/// Video quality argument.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum VideoQualityArg {
+    /// Smallest files.
+    Low,
+    /// Balanced.
+    Medium,
+    /// High quality.
+    High,
+    /// Very high quality.
+    VeryHigh,
+    /// Lossless.
+    Lossless,
+}
+
+impl From<VideoQualityArg> for video::VideoQuality {
+    fn from(arg: VideoQualityArg) -> Self {
+        match arg {
+            VideoQualityArg::Low => Self::Low,
+            VideoQualityArg::Medium => Self::Medium,
+            VideoQualityArg::High => Self::High,
+            VideoQualityArg::VeryHigh => Self::VeryHigh,
+            VideoQualityArg::Lossless => Self::Lossless,
+        }
+    }
+}
+
+/// Extracted audio format argument.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum AudioFormatArg {
+    /// MP3.
+    Mp3,
+    /// AAC.
+    Aac,
+    /// WAV.
+    Wav,
+    /// FLAC.
+    Flac,
+    /// Ogg Vorbis.
+    Ogg,
+    /// M4A.
+    M4a,
+    /// Opus.
+    Opus,
+}
+
+impl From<AudioFormatArg> for video::AudioFormat {
+    fn from(arg: AudioFormatArg) -> Self {
+        match arg {
+            AudioFormatArg::Mp3 => Self::Mp3,
+            AudioFormatArg::Aac => Self::Aac,
+            AudioFormatArg::Wav => Self::Wav,
+            AudioFormatArg::Flac => Self::Flac,
+            AudioFormatArg::Ogg => Self::Ogg,
+            AudioFormatArg::M4a => Self::M4a,
+            AudioFormatArg::Opus => Self::Opus,
+        }
+    }
+}
+
+/// Audio conversion format argument.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum AudioOutputFormatArg {
+    /// MP3.
+    Mp3,
+    /// WAV.
+    Wav,
+    /// FLAC.
+    Flac,
+    /// Ogg Vorbis.
+    Ogg,
+    /// AAC.
+    Aac,
+    /// M4A.
+    M4a,
+    /// Windows Media Audio.
+    Wma,
+    /// Opus.
+    Opus,
+}
+
+impl From<AudioOutputFormatArg> for audio::AudioOutputFormat {
+    fn from(arg: AudioOutputFormatArg) -> Self {
+        match arg {
+            AudioOutputFormatArg::Mp3 => Self::Mp3,
+            AudioOutputFormatArg::Wav => Self::Wav,
+            AudioOutputFormatArg::Flac => Self::Flac,
+            AudioOutputFormatArg::Ogg => Self::Ogg,
+            AudioOutputFormatArg::Aac => Self::Aac,
+            AudioOutputFormatArg::M4a => Self::M4a,
+            AudioOutputFormatArg::Wma => Self::Wma,
+            AudioOutputFormatArg::Opus => Self::Opus,
+        }
+    }
+}
+
+/// Normalization method argument.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum NormalizeMethodArg {
+    /// Peak level.
+    Peak,
+    /// RMS level.
+    Rms,
+    /// EBU R128 loudness.
+    Loudness,
+    /// Dynamic range compression.
+    DynamicRange,
+}
+
+impl From<NormalizeMethodArg> for audio::NormalizeMethod {
+    fn from(arg: NormalizeMethodArg) -> Self {
+        match arg {
+            NormalizeMethodArg::Peak => Self::Peak,
+            NormalizeMethodArg::Rms => Self::Rms,
+            NormalizeMethodArg::Loudness => Self::Loudness,
+            NormalizeMethodArg::DynamicRange => Self::DynamicRange,
+        }
+    }
+}
+
+/// Audio visualization argument.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum SpectrumTypeArg {
+    /// Waveform.
+    Waveform,
+    /// Spectrogram.
+    Spectrogram,
+    /// Frequency bars.
+    FrequencyBars,
+    /// Level histogram.
+    Histogram,
+}
+
+impl From<SpectrumTypeArg> for audio::SpectrumType {
+    fn from(arg: SpectrumTypeArg) -> Self {
+        match arg {
+            SpectrumTypeArg::Waveform => Self::Waveform,
+            SpectrumTypeArg::Spectrogram => Self::Spectrogram,
+            SpectrumTypeArg::FrequencyBars => Self::FrequencyBars,
+            SpectrumTypeArg::Histogram => Self::Histogram,
+        }
+    }
+}
+
+/// PDF compression quality argument.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum CompressionQualityArg {
+    /// 72 dpi, smallest files.
+    Screen,
+    /// 150 dpi.
+    Ebook,
+    /// 300 dpi.
+    Printer,
+    /// 300 dpi, color preserving.
+    Prepress,
+    /// Ghostscript's default.
+    Default,
+}
+
+impl From<CompressionQualityArg> for document::CompressionQuality {
+    fn from(arg: CompressionQualityArg) -> Self {
+        match arg {
+            CompressionQualityArg::Screen => Self::Screen,
+            CompressionQualityArg::Ebook => Self::Ebook,
+            CompressionQualityArg::Printer => Self::Printer,
+            CompressionQualityArg::Prepress => Self::Prepress,
+            CompressionQualityArg::Default => Self::Default,
+        }
+    }
+}
+
+/// Document format argument.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum DocFormatArg {
+    /// Word 97-2003.
+    Doc,
+    /// Word.
+    Docx,
+    /// OpenDocument text.
+    Odt,
+    /// Rich text.
+    Rtf,
+    /// PDF.
+    Pdf,
+    /// Plain text.
+    Txt,
+    /// HTML.
+    Html,
+    /// EPUB.
+    Epub,
+}
+
+impl From<DocFormatArg> for document::doc_convert::DocFormat {
+    fn from(arg: DocFormatArg) -> Self {
+        match arg {
+            DocFormatArg::Doc => Self::Doc,
+            DocFormatArg::Docx => Self::Docx,
+            DocFormatArg::Odt => Self::Odt,
+            DocFormatArg::Rtf => Self::Rtf,
+            DocFormatArg::Pdf => Self::Pdf,
+            DocFormatArg::Txt => Self::Txt,
+            DocFormatArg::Html => Self::Html,
+            DocFormatArg::Epub => Self::Epub,
+        }
+    }
+}
+
+/// Compression algorithm argument.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum CompressionAlgorithmArg {
+    /// gzip.
+    Gzip,
+    /// bzip2.
+    Bzip2,
+    /// xz.
+    Xz,
+    /// Zstandard.
+    Zstd,
+    /// LZ4.
+    Lz4,
+}
+
+impl From<CompressionAlgorithmArg> for archive::compress::CompressionAlgorithm {
+    fn from(arg: CompressionAlgorithmArg) -> Self {
+        match arg {
+            CompressionAlgorithmArg::Gzip => Self::Gzip,
+            CompressionAlgorithmArg::Bzip2 => Self::Bzip2,
+            CompressionAlgorithmArg::Xz => Self::Xz,
+            CompressionAlgorithmArg::Zstd => Self::Zstd,
+            CompressionAlgorithmArg::Lz4 => Self::Lz4,
+        }
+    }
+}
+
+/// Hash algorithm argument.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum HashAlgorithmArg {
+    /// MD5.
+    Md5,
+    /// SHA-1.
+    Sha1,
+    /// SHA-256.
+    Sha256,
+    /// SHA-384.
+    Sha384,
+    /// SHA-512.
+    Sha512,
+    /// CRC-32.
+    Crc32,
+}
+
+impl From<HashAlgorithmArg> for utility::hash::HashAlgorithm {
+    fn from(arg: HashAlgorithmArg) -> Self {
+        match arg {
+            HashAlgorithmArg::Md5 => Self::Md5,
+            HashAlgorithmArg::Sha1 => Self::Sha1,
+            HashAlgorithmArg::Sha256 => Self::Sha256,
+            HashAlgorithmArg::Sha384 => Self::Sha384,
+            HashAlgorithmArg::Sha512 => Self::Sha512,
+            HashAlgorithmArg::Crc32 => Self::Crc32,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use audio::AudioEffect;
+
+    #[test]
+    fn test_parse_effect() {
+        assert!(matches!(parse_effect("reverse"), Ok(AudioEffect::Reverse)));
+        assert!(
+            matches!(parse_effect("speed=1.5"), Ok(AudioEffect::Speed(s)) if (s - 1.5).abs() < f32::EPSILON)
+        );
+        assert!(matches!(
+            parse_effect("band_pass=200:3000"),
+            Ok(AudioEffect::BandPass {
+                low: 200,
+                high: 3000
+            })
+        ));
+        assert!(
+            matches!(parse_effect("equalizer=1:-2:3"), Ok(AudioEffect::Equalizer(g)) if g.len() == 3)
+        );
+        assert!(parse_effect("speed").is_err());
+        assert!(parse_effect("echo=0.5").is_err());
+        assert!(parse_effect("warble=2").is_err());
+    }
+}
@@ -0,0 +1,96 @@
+//! Cache command implementation.
+
+use std::time::Duration;
+
+use colored::Colorize;
+use indicatif::HumanBytes;
+
+use crate::cache::SearchCache;
+use crate::cli::args::{CacheAction, CacheArgs, OutputFormat};
+use crate::cli::output::print_row;
+use crate::config::Config;
+use crate::error::Result;
+
+/// Execute the cache command.
+pub fn execute(args: &CacheArgs, format: OutputFormat) -> Result<()> {
+    let config = Config::load()?;
+    // Managed even when DX_CACHE_ENABLED=false so stale entries can still be cleared
+    let cache = SearchCache::new(
+        &config.cache_dir,
+        Duration::from_secs(config.cache_ttl_hours * 3600),
+    );
+
+    match args.action {
+        CacheAction::Stats => {
+            let stats = cache.stats();
+            match format {
+                OutputFormat::Json | OutputFormat::JsonCompact | OutputFormat::Ndjson => {
+                    let json = serde_json::json!({
+                        "dir": stats.dir,
+                        "enabled": config.cache_enabled,
+                        "ttl_hours": config.cache_ttl_hours,
+                        "entries": stats.entries,
+                        "expired": stats.expired,
+                        "size_bytes": stats.size_bytes,
+                        "providers": stats
+                            .providers
+                            .iter()
+                            .map(|(provider, count)| (provider.clone(), serde_json::json!(count)))
+                            .collect::<serde_json::Map<_, _>>(),
+                    });
+                    if format.is_compact_json() {
+                        println!("{}", serde_json::to_string(&json)?);
+                    } else {
+                        println!("{}", serde_json::to_string_pretty(&json)?);
+                    }
+                }
+                OutputFormat::Tsv | OutputFormat::Csv => {
+                    print_row(format, &[&"provider", &"entries"]);
+                    for (provider, count) in &stats.providers {
+                        print_row(format, &[provider, count]);
+                    }
+                }
+                OutputFormat::Text => {
+                    println!("{}", "Search Cache".bold().cyan());
+                    println!();
+                    println!("  {} {}", "Directory:".dimmed(), stats.dir.display());
+                    println!(
+                        "  {} {}",
+                        "Enabled:".dimmed(),
+                        if config.cache_enabled { "yes".green() } else { "no".yellow() }
+                    );
+                    println!("  {} {} hours", "TTL:".dimmed(), config.cache_ttl_hours);
+                    println!(
+                        "  {} {} ({} expired)",
+                        "Entries:".dimmed(),
+                        stats.entries,
+                        stats.expired
+                    );
+                    println!("  {} {}", "Size:".dimmed(), HumanBytes(stats.size_bytes));
+
+                    if !stats.providers.is_empty() {
+                        println!();
+                        println!("{}", "Providers:".bold());
+                        for (provider, count) in &stats.providers {
+                            println!("  {} {}", format!("{:<16}", provider).cyan(), count);
+                        }
+                    }
+                }
+            }
+        }
+        CacheAction::Clear { expired } => {
+            let removed = cache.clear(expired)?;
+            match format {
+                OutputFormat::Json | OutputFormat::JsonCompact | OutputFormat::Ndjson => {
+                    println!("{}", serde_json::json!({ "removed": removed }));
+                }
+                _ => {
+                    let what = if expired { "expired cache entries" } else { "cache entries" };
+                    println!("{} Removed {} {}", "✓".green(), removed, what);
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
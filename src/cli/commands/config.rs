@@ -0,0 +1,182 @@
+//! Config command implementation.
+
+use colored::Colorize;
+
+use crate::cli::args::{ConfigAction, ConfigArgs, OutputFormat};
+use crate::cli::output::{print_json, print_json_list, print_row};
+use crate::config::layers::{self, ConfigEntry, ConfigLayers};
+use crate::error::{DxError, Result};
+
+/// Execute the config command.
+pub fn execute(args: &ConfigArgs, format: OutputFormat, quiet: bool) -> Result<()> {
+    match args.action {
+        None | Some(ConfigAction::Show) => show(format),
+        Some(ConfigAction::Get { ref key, reveal }) => get(key, reveal, format, quiet),
+        Some(ConfigAction::Set {
+            ref key,
+            ref value,
+            project,
+            ref profile,
+        }) => set(key, value, project, profile.as_deref(), quiet),
+        Some(ConfigAction::Path) => path(format),
+    }
+}
+
+/// Print every setting with its effective value and origin.
+fn show(format: OutputFormat) -> Result<()> {
+    let layers = ConfigLayers::discover()?;
+    let entries = layers.entries()?;
+
+    match format {
+        // One line per setting; the files and profile are settings' origins
+        OutputFormat::Ndjson => {
+            let json = entries.iter().map(entry_json).collect::<Result<Vec<_>>>()?;
+            print_json_list(&json, format)?;
+        }
+        OutputFormat::Json | OutputFormat::JsonCompact => {
+            let json = serde_json::json!({
+                "files": layers.files().collect::<Vec<_>>(),
+                "profile": layers.profile(),
+                "settings": entries.iter().map(entry_json).collect::<Result<Vec<_>>>()?,
+            });
+            print_json(&json, format)?;
+        }
+        OutputFormat::Tsv | OutputFormat::Csv => {
+            print_row(format, &[&"key", &"value", &"origin"]);
+            for entry in &entries {
+                print_row(format, &[&entry.key, &entry.display_value(), &entry.origin]);
+            }
+        }
+        OutputFormat::Text => {
+            println!("{}", "DX Media Configuration".bold().cyan());
+            println!();
+            let files: Vec<_> = layers.files().map(|p| p.display().to_string()).collect();
+            if files.is_empty() {
+                println!("  {} {}", "Files:".dimmed(), "none (run `dx config path`)".dimmed());
+            } else {
+                println!("  {} {}", "Files:".dimmed(), files.join(", "));
+            }
+            println!();
+
+            let values: Vec<String> = entries
+                .iter()
+                .map(|e| if e.value.is_some() { e.display_value() } else { "(unset)".to_string() })
+                .collect();
+            let key_width = entries.iter().map(|e| e.key.len()).max().unwrap_or(0);
+            let value_width = values.iter().map(|v| v.chars().count()).max().unwrap_or(0).min(40);
+            for (entry, value) in entries.iter().zip(values) {
+                let value = format!("{:value_width$}", value);
+                let value = if entry.value.is_some() { value.normal() } else { value.dimmed() };
+                println!(
+                    "  {:key_width$}  {}  {}",
+                    entry.key,
+                    value,
+                    format!("# {}", entry.origin).dimmed(),
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Print a single value, with API keys masked unless `reveal` is set. The
+/// origin goes to stderr so the value can be captured.
+fn get(key: &str, reveal: bool, format: OutputFormat, quiet: bool) -> Result<()> {
+    let mut entry = ConfigLayers::discover()?.get(key)?;
+    entry.secret &= !reveal;
+
+    match format {
+        OutputFormat::Json | OutputFormat::JsonCompact | OutputFormat::Ndjson => {
+            print_json(&entry_json(&entry)?, format)?;
+        }
+        _ => {
+            println!("{}", entry.display_value());
+            if !quiet {
+                eprintln!("{}", format!("# {}", entry.origin).dimmed());
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Write a value to the user or project file.
+fn set(key: &str, value: &str, project: bool, profile: Option<&str>, quiet: bool) -> Result<()> {
+    let path = if project {
+        layers::project_path()
+    } else {
+        layers::user_config_path()
+            .ok_or_else(|| DxError::config("Could not determine the user config directory"))?
+    };
+
+    if key == "profile" && !ConfigLayers::discover()?.profiles().contains(value) {
+        return Err(DxError::config(format!("Unknown profile '{}'", value)));
+    }
+
+    layers::set_value(&path, key, value, profile)?;
+
+    if !quiet {
+        let target = match profile {
+            Some(name) => format!("profile '{}' in {}", name, path.display()),
+            None => path.display().to_string(),
+        };
+        println!("{} Set {} = {} in {}", "✓".green(), key, value, target);
+    }
+    Ok(())
+}
+
+/// Print where the config files are looked for.
+fn path(format: OutputFormat) -> Result<()> {
+    let user = layers::user_config_path();
+    let project = layers::project_path();
+
+    match format {
+        OutputFormat::Json | OutputFormat::JsonCompact | OutputFormat::Ndjson => {
+            let json = serde_json::json!({
+                "user": user,
+                "user_exists": user.as_ref().is_some_and(|p| p.is_file()),
+                "project": project,
+                "project_exists": project.is_file(),
+            });
+            print_json(&json, format)?;
+        }
+        OutputFormat::Tsv | OutputFormat::Csv => {
+            print_row(format, &[&"layer", &"path", &"exists"]);
+            if let Some(ref user) = user {
+                print_row(format, &[&"user", &user.display(), &user.is_file()]);
+            }
+            print_row(
+                format,
+                &[&"project", &project.display(), &project.is_file()],
+            );
+        }
+        OutputFormat::Text => {
+            let status = |exists: bool| if exists { "✓".green() } else { "○".dimmed() };
+            if let Some(ref user) = user {
+                println!("  {} {} {}", status(user.is_file()), "User:   ".dimmed(), user.display());
+            }
+            println!(
+                "  {} {} {}",
+                status(project.is_file()),
+                "Project:".dimmed(),
+                project.display()
+            );
+        }
+    }
+
+    Ok(())
+}
+
+fn entry_json(entry: &ConfigEntry) -> Result<serde_json::Value> {
+    let value = match entry.value {
+        Some(_) if entry.secret => serde_json::Value::String(entry.display_value()),
+        Some(ref value) => serde_json::to_value(value)?,
+        None => serde_json::Value::Null,
+    };
+    Ok(serde_json::json!({
+        "key": entry.key,
+        "value": value,
+        "origin": entry.origin.to_string(),
+    }))
+}
@@ -0,0 +1,60 @@
+//! Credits command implementation.
+
+use colored::Colorize;
+
+use crate::attribution::{self, Credit, CreditFormat};
+use crate::cli::args::{CreditsArgs, OutputFormat};
+use crate::cli::input::read_assets;
+use crate::error::{DxError, Result};
+
+/// Execute the credits command.
+pub fn execute(args: &CreditsArgs, format: OutputFormat, quiet: bool) -> Result<()> {
+    let assets = read_assets(&args.input)?;
+    let credits = attribution::credits(&assets);
+
+    let style = match (args.style, format) {
+        (Some(style), _) => style.into(),
+        (None, OutputFormat::Json | OutputFormat::JsonCompact) => CreditFormat::Json,
+        (None, _) => CreditFormat::Text,
+    };
+    let rendered = attribution::render(&credits, style);
+
+    match args.output {
+        Some(ref path) => {
+            std::fs::write(path, &rendered).map_err(|e| DxError::FileIo {
+                path: path.clone(),
+                message: format!("Failed to write credits: {}", e),
+                source: Some(e),
+            })?;
+            if !quiet {
+                println!(
+                    "{} Wrote {} credits to {}",
+                    "✓".green(),
+                    credits.len(),
+                    path.display()
+                );
+            }
+        }
+        None => print!("{}", rendered),
+    }
+
+    if !quiet {
+        print_warnings(&credits);
+    }
+
+    Ok(())
+}
+
+/// Print credit warnings to stderr so they don't end up in redirected output.
+pub(super) fn print_warnings(credits: &[Credit]) {
+    for credit in credits.iter().filter(|c| !c.warnings.is_empty()) {
+        let warnings: Vec<String> = credit.warnings.iter().map(ToString::to_string).collect();
+        eprintln!(
+            "  {} {}:{} {}",
+            "⚠".yellow(),
+            credit.provider,
+            credit.id,
+            warnings.join("; ").dimmed()
+        );
+    }
+}
@@ -1,66 +1,75 @@
 //! Download command implementation.
 
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
 use colored::Colorize;
-use indicatif::{ProgressBar, ProgressStyle};
+use futures::StreamExt;
+use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
 
 use crate::DxMedia;
-use crate::cli::args::DownloadArgs;
+use crate::cli::args::{DownloadArgs, OutputFormat};
+use crate::cli::input::{AssetEntry, read_asset_list};
+use crate::cli::output::print_row;
+use crate::engine::{DownloadReport, DownloadStatus, ProgressCallback, QueueEvent};
+use crate::types::{License, MediaAsset};
 use crate::error::{DxError, Result};
 
+/// `provider:id` references looked up at once.
+const LOOKUP_CONCURRENCY: usize = 8;
+
 /// Execute the download command.
-pub async fn execute(args: DownloadArgs, quiet: bool) -> Result<()> {
+pub async fn execute(args: DownloadArgs, format: OutputFormat, quiet: bool) -> Result<()> {
     let dx = DxMedia::new()?;
 
-    // Parse asset ID (format: provider:id)
-    let (provider_name, asset_id) = parse_asset_id(&args.asset_id)?;
+    if let Some(ref from) = args.from {
+        return download_list(&dx, from, &args, format, quiet).await;
+    }
+    let asset_id = args.asset_id.as_deref().unwrap_or_default();
 
-    // First, search for the asset to get full details
     if !quiet {
-        println!("{} {}:{}", "Looking up".cyan(), provider_name, asset_id);
+        println!("{} {}", "Looking up".cyan(), asset_id);
     }
 
-    // We need to search for the asset to get the download URL
-    // This is a limitation - ideally we'd have a get_by_id endpoint
-    let query = crate::types::SearchQuery::new(asset_id);
-    let search_result = dx.search_query(&query).await?;
+    let asset = &dx.get_asset(asset_id).await?;
 
-    // Find the matching asset
-    let asset = search_result
-        .assets
-        .iter()
-        .find(|a| a.provider == provider_name && a.id == asset_id)
-        .or_else(|| {
-            // If exact match not found, try finding by ID only
-            search_result.assets.iter().find(|a| a.id == asset_id)
-        })
-        .ok_or_else(|| DxError::NoResults {
-            query: args.asset_id.clone(),
-        })?;
-
-    // Show progress
-    let spinner = if !quiet {
-        let pb = ProgressBar::new_spinner();
+    // Show byte progress
+    let progress = if !quiet {
+        let pb = ProgressBar::new(0);
         pb.set_style(
-            ProgressStyle::default_spinner()
-                .tick_chars("⠋⠙⠹⠸⠼⠴⠦⠧⠇⠏")
-                .template("{spinner:.cyan} {msg}")
-                .unwrap(),
+            ProgressStyle::default_bar()
+                .template(
+                    "{spinner:.cyan} {msg} [{bar:30.cyan/blue}] {bytes}/{total_bytes} ({bytes_per_sec}, {eta})",
+                )
+                .unwrap()
+                .progress_chars("█▓░"),
         );
-        pb.set_message(format!("Downloading '{}'...", asset.title));
+        pb.set_message(format!("Downloading '{}'", asset.title));
         pb.enable_steady_tick(std::time::Duration::from_millis(80));
         Some(pb)
     } else {
         None
     };
 
-    // Download
-    let path = if let Some(ref output_dir) = args.output {
-        dx.download_to(asset, std::path::Path::new(output_dir))
-            .await?
-    } else {
-        dx.download(asset).await?
+    let on_progress: ProgressCallback = match progress {
+        Some(ref pb) => {
+            let pb = pb.clone();
+            Arc::new(move |received, total| {
+                if total > 0 && pb.length() != Some(total) {
+                    pb.set_length(total);
+                }
+                pb.set_position(received);
+            })
+        }
+        None => Arc::new(|_, _| {}),
     };
 
+    // Download
+    let output_dir = args.output.as_deref().map(std::path::Path::new);
+    let path = dx
+        .download_with_progress(asset, output_dir, on_progress)
+        .await?;
+
     // Rename if custom filename provided
     if let Some(ref filename) = args.filename {
         let new_path = path
@@ -75,7 +84,7 @@ pub async fn execute(args: DownloadArgs, quiet: bool) -> Result<()> {
                 source: Some(e),
             })?;
 
-        if let Some(pb) = spinner {
+        if let Some(pb) = progress {
             pb.finish_and_clear();
         }
 
@@ -83,7 +92,7 @@ pub async fn execute(args: DownloadArgs, quiet: bool) -> Result<()> {
             println!("{} {}", "Downloaded:".green().bold(), new_path.display());
         }
     } else {
-        if let Some(pb) = spinner {
+        if let Some(pb) = progress {
             pb.finish_and_clear();
         }
 
@@ -95,12 +104,269 @@ pub async fn execute(args: DownloadArgs, quiet: bool) -> Result<()> {
     Ok(())
 }
 
-/// Parse asset ID in format "provider:id" or just "id".
-fn parse_asset_id(asset_id: &str) -> Result<(&str, &str)> {
-    if let Some((provider, id)) = asset_id.split_once(':') {
-        Ok((provider, id))
+/// Download the assets listed in `from`, looking up `provider:id`
+/// references and applying the license, type and count filters.
+///
+/// Fails if any asset couldn't be looked up or downloaded, after reporting
+/// every outcome.
+async fn download_list(
+    dx: &DxMedia,
+    from: &str,
+    args: &DownloadArgs,
+    format: OutputFormat,
+    quiet: bool,
+) -> Result<()> {
+    let text = matches!(format, OutputFormat::Text);
+    let licenses: Vec<License> = args.license.iter().map(|l| License::parse(l)).collect();
+    let media_type = args.media_type.and_then(Into::into);
+    let max = args.max.unwrap_or(usize::MAX);
+
+    let mut entries = read_asset_list(from)?;
+    if licenses.is_empty() && media_type.is_none() {
+        // Nothing to filter on, so skip looking up what won't be downloaded
+        entries.truncate(max);
+    }
+
+    let references = entries
+        .iter()
+        .filter(|e| matches!(e, AssetEntry::Ref(_)))
+        .count();
+    if references > 0 && text && !quiet {
+        println!("{} {} assets", "Looking up".cyan(), references);
+    }
+
+    let mut lookup_failures = Vec::new();
+    let mut assets = Vec::new();
+    let mut resolved = futures::stream::iter(entries)
+        .map(|entry| async move {
+            match entry {
+                AssetEntry::Asset(asset) => Ok(*asset),
+                AssetEntry::Ref(reference) => dx
+                    .get_asset(&reference)
+                    .await
+                    .map_err(|e| (reference, e.to_string())),
+            }
+        })
+        .buffered(LOOKUP_CONCURRENCY);
+    while let Some(result) = resolved.next().await {
+        match result {
+            Ok(asset) => assets.push(asset),
+            Err(failure) => lookup_failures.push(failure),
+        }
+    }
+
+    let assets: Vec<MediaAsset> = assets
+        .into_iter()
+        .filter(|a| licenses.is_empty() || licenses.contains(&a.license))
+        .filter(|a| media_type.is_none_or(|t| a.media_type == t))
+        .take(max)
+        .collect();
+
+    let report = if assets.is_empty() {
+        if text && !quiet {
+            println!("{}", "No assets to download".yellow());
+        }
+        DownloadReport::default()
     } else {
-        // Default to openverse if no provider specified
-        Ok(("openverse", asset_id))
+        let output_dir = args.output.as_deref().map(std::path::Path::new);
+        download_batch(dx, &assets, output_dir, quiet || !text).await
+    };
+
+    match format {
+        OutputFormat::Text => {
+            if !quiet {
+                for (reference, error) in &lookup_failures {
+                    println!("  {} {} {}", "✗".red(), reference, error.red());
+                }
+            }
+            if !report.outcomes.is_empty() {
+                print_report(&report, quiet);
+            }
+        }
+        OutputFormat::Ndjson => {
+            for outcome in outcomes_json(&report, &lookup_failures) {
+                println!("{}", serde_json::to_string(&outcome)?);
+            }
+        }
+        OutputFormat::Json | OutputFormat::JsonCompact => {
+            let json = report_json(&report, &lookup_failures);
+            let rendered = if matches!(format, OutputFormat::Json) {
+                serde_json::to_string_pretty(&json)?
+            } else {
+                serde_json::to_string(&json)?
+            };
+            println!("{}", rendered);
+        }
+        OutputFormat::Tsv | OutputFormat::Csv => {
+            for (reference, error) in &lookup_failures {
+                print_row(format, &[&"failed", reference, error]);
+            }
+            for outcome in &report.outcomes {
+                let (status, detail) = match &outcome.status {
+                    DownloadStatus::Downloaded(path) => ("downloaded", path.display().to_string()),
+                    DownloadStatus::Skipped(reason) => ("skipped", reason.clone()),
+                    DownloadStatus::Failed(error) => ("failed", error.clone()),
+                };
+                let reference = format!("{}:{}", outcome.asset.provider, outcome.asset.id);
+                print_row(format, &[&status, &reference, &detail]);
+            }
+        }
+    }
+
+    let failed = report.failed().len() + lookup_failures.len();
+    if failed > 0 {
+        return Err(DxError::Download {
+            url: from.to_string(),
+            message: format!(
+                "{} of {} assets failed",
+                failed,
+                report.outcomes.len() + lookup_failures.len()
+            ),
+        });
+    }
+    Ok(())
+}
+
+/// Per-asset outcomes and totals as JSON.
+fn report_json(report: &DownloadReport, lookup_failures: &[(String, String)]) -> serde_json::Value {
+    serde_json::json!({
+        "downloaded": report.downloaded().len(),
+        "skipped": report.skipped().len(),
+        "failed": report.failed().len() + lookup_failures.len(),
+        "outcomes": outcomes_json(report, lookup_failures),
+    })
+}
+
+/// One JSON object per asset, lookup failures first.
+fn outcomes_json(
+    report: &DownloadReport,
+    lookup_failures: &[(String, String)],
+) -> Vec<serde_json::Value> {
+    let mut outcomes: Vec<serde_json::Value> = lookup_failures
+        .iter()
+        .map(|(reference, error)| {
+            serde_json::json!({"asset": reference, "status": "failed", "error": error})
+        })
+        .collect();
+    outcomes.extend(report.outcomes.iter().map(|outcome| {
+        let asset = format!("{}:{}", outcome.asset.provider, outcome.asset.id);
+        match &outcome.status {
+            DownloadStatus::Downloaded(path) => {
+                serde_json::json!({"asset": asset, "status": "downloaded", "path": path})
+            }
+            DownloadStatus::Skipped(reason) => {
+                serde_json::json!({"asset": asset, "status": "skipped", "reason": reason})
+            }
+            DownloadStatus::Failed(error) => {
+                serde_json::json!({"asset": asset, "status": "failed", "error": error})
+            }
+        }
+    }));
+    outcomes
+}
+
+/// Download a batch of assets through the download queue.
+///
+/// Shows one progress bar per active download plus an overall bar, unless
+/// `quiet` is set.
+pub(super) async fn download_batch(
+    dx: &DxMedia,
+    assets: &[MediaAsset],
+    output_dir: Option<&std::path::Path>,
+    quiet: bool,
+) -> DownloadReport {
+    let mut queue = dx.download_queue();
+
+    if !quiet {
+        let multi = MultiProgress::new();
+        let overall = multi.add(ProgressBar::new(assets.len() as u64));
+        overall.set_style(
+            ProgressStyle::default_bar()
+                .template("{prefix:.bold} [{bar:30.green/white}] {pos}/{len} assets")
+                .unwrap()
+                .progress_chars("█▓░"),
+        );
+        overall.set_prefix("Total");
+
+        let item_style = ProgressStyle::default_bar()
+            .template("  {msg:30!} [{bar:25.cyan/blue}] {bytes}/{total_bytes} ({bytes_per_sec})")
+            .unwrap()
+            .progress_chars("█▓░");
+
+        let titles: Vec<String> = assets.iter().map(|a| a.title.clone()).collect();
+        let bars: Mutex<HashMap<usize, ProgressBar>> = Mutex::new(HashMap::new());
+
+        queue = queue.on_event(Arc::new(move |event| {
+            let mut bars = bars.lock().unwrap_or_else(std::sync::PoisonError::into_inner);
+            match event {
+                QueueEvent::Started { index } => {
+                    let pb = multi.insert_before(&overall, ProgressBar::new(0));
+                    pb.set_style(item_style.clone());
+                    pb.set_message(titles[index].clone());
+                    bars.insert(index, pb);
+                }
+                QueueEvent::Progress {
+                    index,
+                    received,
+                    total,
+                } => {
+                    if let Some(pb) = bars.get(&index) {
+                        if total > 0 && pb.length() != Some(total) {
+                            pb.set_length(total);
+                        }
+                        pb.set_position(received);
+                    }
+                }
+                QueueEvent::Retrying { index, attempt, .. } => {
+                    if let Some(pb) = bars.get(&index) {
+                        pb.set_message(format!("{} (retry {})", titles[index], attempt - 1));
+                        pb.set_position(0);
+                    }
+                }
+                QueueEvent::Finished { index, .. } => {
+                    if let Some(pb) = bars.remove(&index) {
+                        pb.finish_and_clear();
+                    }
+                    overall.inc(1);
+                    if overall.position() == overall.length().unwrap_or(0) {
+                        overall.finish_and_clear();
+                    }
+                }
+            }
+        }));
+    }
+
+    let dir = output_dir.unwrap_or_else(|| dx.download_dir());
+    queue.run(assets, dir).await
+}
+
+/// Print the per-asset outcome of a batch download.
+pub(super) fn print_report(report: &DownloadReport, quiet: bool) {
+    if quiet {
+        return;
     }
+
+    for outcome in &report.outcomes {
+        let id = format!("{}:{}", outcome.asset.provider, outcome.asset.id);
+        match &outcome.status {
+            DownloadStatus::Downloaded(path) => {
+                println!("  {} {} → {}", "✓".green(), id, path.display());
+            }
+            DownloadStatus::Skipped(reason) => {
+                println!("  {} {} {}", "○".yellow(), id, format!("({})", reason).dimmed());
+            }
+            DownloadStatus::Failed(error) => {
+                println!("  {} {} {}", "✗".red(), id, error.red());
+            }
+        }
+    }
+
+    println!();
+    println!(
+        "{} {} downloaded, {} skipped, {} failed",
+        "Summary:".bold(),
+        report.downloaded().len().to_string().green(),
+        report.skipped().len().to_string().yellow(),
+        report.failed().len().to_string().red()
+    );
 }
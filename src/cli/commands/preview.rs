@@ -0,0 +1,39 @@
+//! Preview command implementation.
+
+use std::path::Path;
+
+use crate::cli::args::PreviewArgs;
+use crate::cli::preview::Previewer;
+use crate::config::Config;
+use crate::error::{DxError, Result};
+
+/// Widest default preview, in columns.
+const MAX_DEFAULT_WIDTH: u16 = 80;
+
+/// Execute the preview command.
+pub async fn execute(args: PreviewArgs) -> Result<()> {
+    let (columns, rows) = ratatui::crossterm::terminal::size().unwrap_or((MAX_DEFAULT_WIDTH, 24));
+    let previewer = Previewer::new(
+        args.protocol.into(),
+        args.width.unwrap_or(columns.min(MAX_DEFAULT_WIDTH)),
+        args.height.unwrap_or(rows.saturating_sub(2)),
+        &Config::load()?,
+    )?;
+
+    let rendered = if args.source.starts_with("http://") || args.source.starts_with("https://") {
+        previewer.render_url(&args.source).await?
+    } else {
+        let path = Path::new(&args.source);
+        if !path.exists() {
+            return Err(DxError::FileIo {
+                path: path.to_path_buf(),
+                message: "File not found".to_string(),
+                source: None,
+            });
+        }
+        previewer.render_file(path).await?
+    };
+
+    println!("{rendered}");
+    Ok(())
+}
@@ -1,16 +1,28 @@
 //! Providers command implementation.
 
+use std::time::Duration;
+
 use colored::Colorize;
 
 use crate::DxMedia;
-use crate::cli::args::{OutputFormat, ProvidersArgs};
+use crate::cli::args::{OutputFormat, ProvidersAction, ProvidersArgs};
+use crate::cli::output::{print_json_list, print_row};
 use crate::error::Result;
+use crate::providers::{CircuitState, PROBE_QUERY, ProviderHealth, ProviderRegistry};
 
 /// Execute the providers command.
 pub async fn execute(args: ProvidersArgs, format: OutputFormat) -> Result<()> {
     let dx = DxMedia::new()?;
     let registry = dx.registry();
 
+    if let Some(ProvidersAction::Test { providers }) = args.action {
+        return test(registry, providers, format).await;
+    }
+    if args.health {
+        let base = dx.config().search_mode.provider_timeout();
+        return health(registry, args.available, base, format);
+    }
+
     let providers = if args.available {
         registry.available()
     } else {
@@ -18,7 +30,7 @@ pub async fn execute(args: ProvidersArgs, format: OutputFormat) -> Result<()> {
     };
 
     match format {
-        OutputFormat::Json | OutputFormat::JsonCompact => {
+        OutputFormat::Json | OutputFormat::JsonCompact | OutputFormat::Ndjson => {
             let json: Vec<serde_json::Value> = providers
                 .iter()
                 .map(|p| {
@@ -26,6 +38,7 @@ pub async fn execute(args: ProvidersArgs, format: OutputFormat) -> Result<()> {
                         "name": p.name(),
                         "display_name": p.display_name(),
                         "available": p.is_available(),
+                        "enabled": registry.is_enabled(p.name()),
                         "requires_api_key": p.requires_api_key(),
                         "supported_types": p.supported_media_types()
                             .iter()
@@ -47,21 +60,22 @@ pub async fn execute(args: ProvidersArgs, format: OutputFormat) -> Result<()> {
                 })
                 .collect();
 
-            if matches!(format, OutputFormat::JsonCompact) {
-                println!("{}", serde_json::to_string(&json)?);
-            } else {
-                println!("{}", serde_json::to_string_pretty(&json)?);
-            }
+            print_json_list(&json, format)?;
         }
-        OutputFormat::Tsv => {
-            println!("name\tdisplay_name\tavailable\trequires_api_key");
+        OutputFormat::Tsv | OutputFormat::Csv => {
+            print_row(
+                format,
+                &[&"name", &"display_name", &"available", &"requires_api_key"],
+            );
             for p in &providers {
-                println!(
-                    "{}\t{}\t{}\t{}",
-                    p.name(),
-                    p.display_name(),
-                    p.is_available(),
-                    p.requires_api_key()
+                print_row(
+                    format,
+                    &[
+                        &p.name(),
+                        &p.display_name(),
+                        &p.is_available(),
+                        &p.requires_api_key(),
+                    ],
                 );
             }
         }
@@ -69,20 +83,26 @@ pub async fn execute(args: ProvidersArgs, format: OutputFormat) -> Result<()> {
             let stats = registry.stats();
 
             println!("{}", "Available Providers".bold().cyan());
-            println!(
+            print!(
                 "{} {} total, {} available, {} need API keys",
                 "Stats:".dimmed(),
                 stats.total,
                 stats.available.to_string().green(),
                 stats.unavailable.to_string().yellow()
             );
+            if stats.disabled > 0 {
+                print!(", {} disabled", stats.disabled);
+            }
+            println!();
             println!();
 
             for p in &providers {
-                let status = if p.is_available() {
+                let status = if !p.is_available() {
+                    "✗".red()
+                } else if registry.is_enabled(p.name()) {
                     "✓".green()
                 } else {
-                    "✗".red()
+                    "○".dimmed()
                 };
 
                 let types: Vec<&str> = p
@@ -123,3 +143,218 @@ pub async fn execute(args: ProvidersArgs, format: OutputFormat) -> Result<()> {
 
     Ok(())
 }
+
+/// Show each provider's latency, error rate and circuit state.
+///
+/// `base` is the default timeout for the configured search mode, from which
+/// the adaptive timeouts are derived.
+fn health(
+    registry: &ProviderRegistry,
+    available_only: bool,
+    base: Duration,
+    format: OutputFormat,
+) -> Result<()> {
+    let stats = registry.stats();
+    let health: Vec<&ProviderHealth> = stats
+        .health
+        .iter()
+        .filter(|h| {
+            !available_only
+                || registry
+                    .get(&h.name)
+                    .is_some_and(|p| p.is_available() && registry.is_enabled(&h.name))
+        })
+        .collect();
+
+    match format {
+        OutputFormat::Json | OutputFormat::JsonCompact | OutputFormat::Ndjson => {
+            let json: Vec<serde_json::Value> = health
+                .iter()
+                .map(|h| {
+                    serde_json::json!({
+                        "name": h.name,
+                        "circuit": h.circuit.as_str(),
+                        "retry_at": match h.circuit {
+                            CircuitState::Open { retry_at } => Some(retry_at),
+                            _ => None,
+                        },
+                        "requests": h.requests,
+                        "failures": h.failures,
+                        "consecutive_failures": h.consecutive_failures,
+                        "error_rate": h.error_rate(),
+                        "p50_ms": h.p50.map(|d| d.as_millis()),
+                        "p95_ms": h.p95.map(|d| d.as_millis()),
+                        "timeout_ms": h.timeout(base).as_millis(),
+                        "last_error": h.last_error,
+                    })
+                })
+                .collect();
+            print_json_list(&json, format)?;
+        }
+        OutputFormat::Tsv | OutputFormat::Csv => {
+            print_row(
+                format,
+                &[
+                    &"name",
+                    &"circuit",
+                    &"requests",
+                    &"error_rate",
+                    &"p95_ms",
+                    &"timeout_ms",
+                ],
+            );
+            for h in &health {
+                print_row(
+                    format,
+                    &[
+                        &h.name,
+                        &h.circuit.as_str(),
+                        &h.requests,
+                        &format!("{:.2}", h.error_rate()),
+                        &h.p95.map(|d| d.as_millis().to_string()).unwrap_or_default(),
+                        &h.timeout(base).as_millis(),
+                    ],
+                );
+            }
+        }
+        OutputFormat::Text => {
+            println!("{}", "Provider Health".bold().cyan());
+            if stats.open_circuits > 0 {
+                println!(
+                    "{} {} open circuit(s)",
+                    "Stats:".dimmed(),
+                    stats.open_circuits.to_string().red()
+                );
+            }
+            println!();
+
+            for h in &health {
+                let circuit = match h.circuit {
+                    CircuitState::Closed => "✓".green(),
+                    CircuitState::HalfOpen => "◐".yellow(),
+                    CircuitState::Open { .. } => "✗".red(),
+                };
+                if h.requests == 0 {
+                    println!("  {} {:<16} {}", circuit, h.name, "no requests yet".dimmed());
+                    continue;
+                }
+
+                println!(
+                    "  {} {:<16} {:<9} {:>3} req  {:>3.0}% errors  p95 {:>7}  timeout {}",
+                    circuit,
+                    h.name,
+                    h.circuit.as_str(),
+                    h.requests,
+                    h.error_rate() * 100.0,
+                    h.p95.map_or_else(|| "-".to_string(), format_latency),
+                    format_latency(h.timeout(base))
+                );
+                if let CircuitState::Open { retry_at } = h.circuit {
+                    println!(
+                        "      {} {}",
+                        "Retry after:".dimmed(),
+                        retry_at.with_timezone(&chrono::Local).format("%H:%M:%S")
+                    );
+                }
+                if let Some(ref error) = h.last_error {
+                    println!("      {} {}", "Last error:".dimmed(), error.yellow());
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Probe providers with a canned query and report the results.
+async fn test(
+    registry: &ProviderRegistry,
+    mut names: Vec<String>,
+    format: OutputFormat,
+) -> Result<()> {
+    if names.is_empty() {
+        names = registry.available_provider_names();
+    }
+    names.sort();
+
+    if matches!(format, OutputFormat::Text) {
+        println!(
+            "{} {} providers with \"{}\"...",
+            "Probing".bold().cyan(),
+            names.len(),
+            PROBE_QUERY
+        );
+        println!();
+    }
+    let results = registry.probe(&names).await;
+
+    match format {
+        OutputFormat::Json | OutputFormat::JsonCompact | OutputFormat::Ndjson => {
+            let json: Vec<serde_json::Value> = results
+                .iter()
+                .map(|r| {
+                    serde_json::json!({
+                        "provider": r.provider,
+                        "ok": r.is_ok(),
+                        "latency_ms": r.latency.as_millis(),
+                        "assets": r.assets,
+                        "error": r.error,
+                    })
+                })
+                .collect();
+            print_json_list(&json, format)?;
+        }
+        OutputFormat::Tsv | OutputFormat::Csv => {
+            print_row(
+                format,
+                &[&"provider", &"ok", &"latency_ms", &"assets", &"error"],
+            );
+            for r in &results {
+                print_row(
+                    format,
+                    &[
+                        &r.provider,
+                        &r.is_ok(),
+                        &r.latency.as_millis(),
+                        &r.assets,
+                        &r.error.as_deref().unwrap_or_default(),
+                    ],
+                );
+            }
+        }
+        OutputFormat::Text => {
+            for r in &results {
+                match r.error {
+                    None => println!(
+                        "  {} {:<16} {:>7}  {} assets",
+                        "✓".green(),
+                        r.provider,
+                        format_latency(r.latency),
+                        r.assets
+                    ),
+                    Some(ref error) => println!(
+                        "  {} {:<16} {:>7}  {}",
+                        "✗".red(),
+                        r.provider,
+                        format_latency(r.latency),
+                        error.yellow()
+                    ),
+                }
+            }
+            let ok = results.iter().filter(|r| r.is_ok()).count();
+            println!();
+            println!("{} of {} providers answered", ok, results.len());
+        }
+    }
+
+    Ok(())
+}
+
+/// Format a latency as milliseconds below a second, seconds above.
+fn format_latency(latency: Duration) -> String {
+    if latency < Duration::from_secs(1) {
+        format!("{}ms", latency.as_millis())
+    } else {
+        format!("{:.1}s", latency.as_secs_f64())
+    }
+}
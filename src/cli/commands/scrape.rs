@@ -4,14 +4,17 @@ use colored::Colorize;
 use indicatif::{ProgressBar, ProgressStyle};
 
 use crate::cli::args::{MediaTypeArg, OutputFormat, ScrapeArgs};
+use crate::cli::output::print_row;
 use crate::config::Config;
-use crate::engine::{ScrapeOptions, Scraper};
-use crate::error::Result;
+use crate::engine::{ScrapeOptions, ScrapeResult, Scraper, SitemapOptions, SitemapState};
+use crate::error::{DxError, Result};
+use crate::scraping::{ScrapingMethod, ScrapingRegistry};
 use crate::types::MediaType;
 
 /// Execute the scrape command.
 pub async fn execute(args: ScrapeArgs, format: OutputFormat, quiet: bool) -> Result<()> {
-    let scraper = Scraper::new()?.with_depth(args.depth);
+    let config = Config::load()?;
+    let scraper = Scraper::with_config(&config)?.with_depth(args.depth);
 
     // Build scrape options
     let media_types = match args.media_type {
@@ -38,6 +41,9 @@ pub async fn execute(args: ScrapeArgs, format: OutputFormat, quiet: bool) -> Res
         pattern: args.pattern.clone(),
         media_types,
         max_assets: args.count,
+        respect_robots: !args.ignore_robots,
+        scope: args.scope.into(),
+        max_pages_per_host: args.max_pages_per_host,
     };
 
     // Show progress
@@ -48,7 +54,6 @@ pub async fn execute(args: ScrapeArgs, format: OutputFormat, quiet: bool) -> Res
                 .template("{spinner:.cyan} {msg}")
                 .unwrap(),
         );
-        spinner.set_message(format!("Scraping {}...", args.url));
         spinner.enable_steady_tick(std::time::Duration::from_millis(100));
         Some(spinner)
     } else {
@@ -56,7 +61,26 @@ pub async fn execute(args: ScrapeArgs, format: OutputFormat, quiet: bool) -> Res
     };
 
     // Perform scrape
-    let result = scraper.scrape(&args.url, &options).await?;
+    let result = if args.target.is_empty() {
+        let url = args
+            .url
+            .as_deref()
+            .ok_or_else(|| DxError::InvalidQuery { message: "No URL given".to_string() })?;
+        if args.sitemap {
+            if let Some(ref spinner) = spinner {
+                spinner.set_message(format!("Harvesting sitemaps of {}...", url));
+            }
+            let sitemap = sitemap_options(&args, &config, url)?;
+            scraper.harvest_sitemaps(url, &options, &sitemap).await?
+        } else {
+            if let Some(ref spinner) = spinner {
+                spinner.set_message(format!("Scraping {}...", url));
+            }
+            scraper.scrape(url, &options).await?
+        }
+    } else {
+        search_targets(&scraper, &args, &config, &options, spinner.as_ref()).await?
+    };
 
     if let Some(spinner) = spinner {
         spinner.finish_and_clear();
@@ -64,7 +88,7 @@ pub async fn execute(args: ScrapeArgs, format: OutputFormat, quiet: bool) -> Res
 
     // Output results based on format
     match format {
-        OutputFormat::Json | OutputFormat::JsonCompact => {
+        OutputFormat::Json | OutputFormat::JsonCompact | OutputFormat::Ndjson => {
             let output = serde_json::json!({
                 "source_url": result.source_url,
                 "pages_scraped": result.pages_scraped,
@@ -77,21 +101,29 @@ pub async fn execute(args: ScrapeArgs, format: OutputFormat, quiet: bool) -> Res
                     "source_url": a.source_url,
                     "width": a.width,
                     "height": a.height,
+                    "duration_secs": a.duration_secs,
+                    "author": a.author,
+                    "license": a.license.as_str(),
                 })).collect::<Vec<_>>(),
                 "errors": result.errors,
             });
-            if matches!(format, OutputFormat::JsonCompact) {
+            if format.is_compact_json() {
                 println!("{}", serde_json::to_string(&output)?);
             } else {
                 println!("{}", serde_json::to_string_pretty(&output)?);
             }
         }
-        OutputFormat::Tsv => {
-            println!("id\ttitle\ttype\tdownload_url");
+        OutputFormat::Tsv | OutputFormat::Csv => {
+            print_row(format, &[&"id", &"title", &"type", &"download_url"]);
             for asset in &result.assets {
-                println!(
-                    "{}\t{}\t{:?}\t{}",
-                    asset.id, asset.title, asset.media_type, asset.download_url
+                print_row(
+                    format,
+                    &[
+                        &asset.id,
+                        &asset.title,
+                        &format!("{:?}", asset.media_type),
+                        &asset.download_url,
+                    ],
                 );
             }
         }
@@ -164,8 +196,7 @@ pub async fn execute(args: ScrapeArgs, format: OutputFormat, quiet: bool) -> Res
         let output_dir = PathBuf::from(&args.output);
         std::fs::create_dir_all(&output_dir)?;
 
-        let config = Config::load()?;
-        let downloader = Downloader::new(&config).with_download_dir(&output_dir);
+        let downloader = Downloader::new(&config)?.with_download_dir(&output_dir);
 
         if !quiet {
             println!(
@@ -237,3 +268,79 @@ pub async fn execute(args: ScrapeArgs, format: OutputFormat, quiet: bool) -> Res
 
     Ok(())
 }
+
+/// Search each selected target, combining the results.
+///
+/// Targets are harvested through their sitemaps with `--sitemap`, or when
+/// they are sitemap-based and no query was given. Feed-based targets are
+/// read as feeds.
+async fn search_targets(
+    scraper: &Scraper,
+    args: &ScrapeArgs,
+    config: &Config,
+    options: &ScrapeOptions,
+    spinner: Option<&ProgressBar>,
+) -> Result<ScrapeResult> {
+    let targets = ScrapingRegistry::select(&args.target)?;
+    let query = args.query.as_deref().unwrap_or_default();
+
+    let mut combined = ScrapeResult {
+        source_url: targets.iter().map(|t| t.name).collect::<Vec<_>>().join(", "),
+        assets: Vec::new(),
+        pages_scraped: 0,
+        errors: Vec::new(),
+    };
+    for target in targets {
+        let harvest = args.sitemap || (target.method == ScrapingMethod::Sitemap && query.is_empty());
+        let outcome = if harvest {
+            if let Some(spinner) = spinner {
+                spinner.set_message(format!("Harvesting sitemaps of {}...", target.name));
+            }
+            match sitemap_options(args, config, target.base_url) {
+                Ok(sitemap) => scraper.harvest_target(target, options, &sitemap).await,
+                Err(e) => Err(e),
+            }
+        } else if target.method == ScrapingMethod::Feed {
+            if let Some(spinner) = spinner {
+                spinner.set_message(format!("Reading feed of {}...", target.name));
+            }
+            scraper.read_target_feed(target, query, options).await
+        } else {
+            if let Some(spinner) = spinner {
+                spinner.set_message(format!("Searching {}...", target.name));
+            }
+            scraper.search_target(target, query, options).await
+        };
+        match outcome {
+            Ok(result) => {
+                combined.pages_scraped += result.pages_scraped;
+                combined.assets.extend(result.assets);
+                combined
+                    .errors
+                    .extend(result.errors.into_iter().map(|e| format!("{}: {}", target.id, e)));
+            }
+            Err(e) => combined.errors.push(format!("{}: {}", target.id, e)),
+        }
+    }
+    Ok(combined)
+}
+
+/// Harvest options for a site, keeping state under the cache directory.
+fn sitemap_options(args: &ScrapeArgs, config: &Config, site: &str) -> Result<SitemapOptions> {
+    let url = url::Url::parse(site).map_err(|e| DxError::InvalidQuery {
+        message: format!("Invalid URL: {e}"),
+    })?;
+    let state_path = SitemapState::default_path(&config.cache_dir, &url);
+    if args.full && state_path.exists() {
+        std::fs::remove_file(&state_path)
+            .map_err(|e| DxError::file_io(&state_path, e.to_string()))?;
+    }
+
+    Ok(SitemapOptions {
+        since: args
+            .since
+            .and_then(|date| date.and_hms_opt(0, 0, 0))
+            .map(|time| time.and_utc()),
+        state_path: Some(state_path),
+    })
+}
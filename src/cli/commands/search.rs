@@ -1,17 +1,31 @@
 //! Search command implementation.
 
+use std::collections::HashSet;
+
 use colored::Colorize;
+use futures::StreamExt;
 use indicatif::{ProgressBar, ProgressStyle};
 
+use super::{credits, download};
 use crate::DxMedia;
 use crate::cli::OutputFormatter;
 use crate::cli::args::{OutputFormat, SearchArgs};
+use crate::cli::preview::{Previewer, Protocol};
+use crate::config::Config;
 use crate::error::Result;
-use crate::types::SearchQuery;
+use crate::ranking::dedupe_key;
+use crate::types::{MediaAsset, SearchQuery, SearchResult};
+
+/// Size of `--preview` thumbnails, in terminal columns and rows.
+const PREVIEW_SIZE: (u16, u16) = (32, 8);
+
+/// Previews fetched at once.
+const PREVIEW_CONCURRENCY: usize = 8;
 
 /// Execute the search command.
 pub async fn execute(args: SearchArgs, format: OutputFormat, quiet: bool) -> Result<()> {
     let dx = DxMedia::new()?;
+    let search_mode = args.mode.map_or(dx.config().search_mode, Into::into);
 
     // Show progress indicator
     let spinner = if !quiet && matches!(format, OutputFormat::Text) {
@@ -23,9 +37,9 @@ pub async fn execute(args: SearchArgs, format: OutputFormat, quiet: bool) -> Res
                 .unwrap(),
         );
         let search_type = if args.all { "all providers & scrapers" } else { "providers" };
-        let mode_str = match args.mode {
-            crate::cli::args::SearchModeArg::Quantity => "⚡ quantity",
-            crate::cli::args::SearchModeArg::Quality => "🎯 quality",
+        let mode_str = match search_mode {
+            crate::types::SearchMode::Quantity => "⚡ quantity",
+            crate::types::SearchMode::Quality => "🎯 quality",
         };
         pb.set_message(format!("Searching {} for '{}' ({} mode)...", search_type, args.query_string(), mode_str));
         pb.enable_steady_tick(std::time::Duration::from_millis(80));
@@ -34,38 +48,97 @@ pub async fn execute(args: SearchArgs, format: OutputFormat, quiet: bool) -> Res
         None
     };
 
-    // Convert CLI mode to types::SearchMode
-    let search_mode: crate::types::SearchMode = args.mode.into();
+    let mut formatter = OutputFormatter::new(format, quiet)
+        .with_fields(args.fields.clone())
+        .with_template(args.template.clone());
+    // NDJSON is printed as each provider answers instead of after ranking
+    let streaming = matches!(format, OutputFormat::Ndjson) && args.template.is_none();
+
+    let mut query = SearchQuery::new(args.query_string());
+    query.count = args.count;
+    query.page = args.page;
+    query.media_type = args.media_type.and_then(Into::into);
+    query.providers = args.providers.clone();
+    query.orientation = args.orientation.map(Into::into);
+    query.color = args.color.clone();
+    query.min_width = args.min_width;
+    query.min_height = args.min_height;
+    query.mode = search_mode;
+    query.cache_policy = args.cache_policy();
+    query.ranking = args.rank.into();
 
     // Execute search - use unified search if --all is specified
     let result = if args.all {
-        dx.search_all_with_mode(&args.query_string(), args.count, search_mode).await?
+        query.scrape_targets = args.targets.clone();
+        if streaming {
+            stream_ndjson(&dx, &query, true, &formatter).await?
+        } else {
+            dx.search_all_query(&query).await?
+        }
+    } else if streaming {
+        stream_ndjson(&dx, &query, false, &formatter).await?
     } else {
-        // Build the search query for regular search
-        let mut query = SearchQuery::new(args.query_string());
-        query.count = args.count;
-        query.page = args.page;
-        query.media_type = args.media_type.and_then(Into::into);
-        query.providers = args.providers.clone();
-        query.orientation = args.orientation.map(Into::into);
-        query.color = args.color.clone();
-        query.mode = search_mode;
-        
         dx.search_query(&query).await?
     };
 
+    if args.preview && matches!(format, OutputFormat::Text) && !quiet {
+        if let Some(ref pb) = spinner {
+            pb.set_message("Fetching previews...");
+        }
+        formatter = formatter.with_previews(render_previews(&result.assets, dx.config()).await?);
+    }
+
     // Clear spinner
     if let Some(pb) = spinner {
         pb.finish_and_clear();
     }
 
     // Format and display results
-    let formatter = OutputFormatter::new(format, quiet);
-    formatter.format_search_results(&result)?;
+    if streaming {
+        if !quiet {
+            for (provider, error) in &result.provider_errors {
+                eprintln!("{} {}: {}", "warning:".yellow(), provider, error);
+            }
+        }
+    } else {
+        formatter.format_search_results(&result)?;
+    }
+
+    // Download progress goes to stdout only when it can't corrupt the results
+    let chatty = !quiet && matches!(format, OutputFormat::Text);
+
+    // Batch download if requested
+    if args.download_all && !result.assets.is_empty() {
+        if chatty {
+            println!();
+            println!(
+                "{}",
+                format!("Downloading {} results...", result.assets.len()).cyan()
+            );
+        }
+
+        let output_dir = args.output.as_deref().map(std::path::Path::new);
+        let report = download::download_batch(&dx, &result.assets, output_dir, !chatty).await;
+        download::print_report(&report, !chatty);
+
+        if args.credits {
+            // Files that were already there are credited too, as the file is rewritten
+            let saved: Vec<_> = report.saved().into_iter().cloned().collect();
+            let dir = output_dir.unwrap_or_else(|| dx.download_dir());
+            let path = crate::attribution::write_credits(dir, &saved)?;
+            if chatty {
+                println!("{} {}", "Credits:".green(), path.display());
+            }
+            if !quiet {
+                credits::print_warnings(&crate::attribution::credits(&saved));
+            }
+        }
+        return Ok(());
+    }
 
     // Auto-download if requested
     if args.download && !result.assets.is_empty() {
-        if !quiet {
+        if chatty {
             println!();
             println!("{}", "Downloading first result...".cyan());
         }
@@ -78,10 +151,61 @@ pub async fn execute(args: SearchArgs, format: OutputFormat, quiet: bool) -> Res
             dx.download(asset).await?
         };
 
-        if !quiet {
+        if chatty {
             println!("{} {}", "Downloaded:".green(), path.display());
         }
     }
 
     Ok(())
 }
+
+/// Search, printing each source's assets as NDJSON as soon as they arrive.
+///
+/// With `all`, scrapers are searched too, as by `DxMedia::search_all_query`.
+/// Assets already printed for another source are skipped, using the same key
+/// as the deduplication of ranked results.
+async fn stream_ndjson(
+    dx: &DxMedia,
+    query: &SearchQuery,
+    all: bool,
+    formatter: &OutputFormatter,
+) -> Result<SearchResult> {
+    let mut seen = HashSet::new();
+    let mut printed = Ok(());
+    let on_result = |_: &str, source_result: &SearchResult| {
+        for asset in &source_result.assets {
+            if printed.is_ok() && seen.insert(dedupe_key(asset)) {
+                printed = formatter.format_asset_ndjson(asset);
+            }
+        }
+    };
+    let result = if all {
+        dx.search_all_query_with(query, on_result).await?
+    } else {
+        dx.search_query_with(query, on_result).await?
+    };
+    printed?;
+    Ok(result)
+}
+
+/// Render a thumbnail of each asset from its preview URL. Assets without
+/// one, or whose preview can't be rendered, get `None`; originals are never
+/// downloaded just for a thumbnail.
+async fn render_previews(assets: &[MediaAsset], config: &Config) -> Result<Vec<Option<String>>> {
+    let previewer = Previewer::new(Protocol::detect(), PREVIEW_SIZE.0, PREVIEW_SIZE.1, config)?;
+    let previewer = &previewer;
+    Ok(futures::stream::iter(assets)
+        .map(|asset| async move {
+            let url = asset.preview_url.as_deref()?;
+            previewer
+                .render_url(url)
+                .await
+                .map_err(|e| {
+                    tracing::debug!("No preview for {}:{}: {}", asset.provider, asset.id, e);
+                })
+                .ok()
+        })
+        .buffered(PREVIEW_CONCURRENCY)
+        .collect()
+        .await)
+}
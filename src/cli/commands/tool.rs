@@ -0,0 +1,632 @@
+//! Tool command implementation.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use colored::Colorize;
+
+use crate::cli::args::{
+    ArchiveTool, AudioTool, BatchInputs, DocumentTool, ImageTool, OutputFormat, ToolArgs,
+    ToolCommand, UtilityTool, VideoTool,
+};
+use crate::cli::output::print_row;
+use crate::error::{DxError, Result};
+use crate::tools::{
+    ArchiveTools, AudioTools, DocumentTools, ImageTools, ToolOutput, UtilityTools, VideoTools,
+    archive, audio, document, image, utility, video,
+};
+
+/// Execute the tool command.
+pub fn execute(args: ToolArgs, format: OutputFormat, quiet: bool) -> Result<()> {
+    let output = match args.category {
+        ToolCommand::Image(op) => run_image(op)?,
+        ToolCommand::Video(op) => run_video(op)?,
+        ToolCommand::Audio(op) => run_audio(op)?,
+        ToolCommand::Document(op) => run_document(op)?,
+        ToolCommand::Archive(op) => run_archive(op)?,
+        ToolCommand::Utility(op) => run_utility(op)?,
+    };
+
+    print_output(&output, format, quiet)?;
+    if output.success {
+        Ok(())
+    } else {
+        Err(DxError::Internal {
+            message: output.message,
+        })
+    }
+}
+
+/// Run an image operation.
+fn run_image(op: ImageTool) -> Result<ToolOutput> {
+    let tools = ImageTools::new();
+    match op {
+        ImageTool::Convert { input, output } => tools.convert(&input, &output),
+        ImageTool::Resize {
+            input,
+            output,
+            width,
+            height,
+        } => tools.resize(&input, &output, width, height),
+        ImageTool::Compress {
+            input,
+            output,
+            quality,
+        } => tools.compress(&input, &output, quality),
+        ImageTool::AddWatermark {
+            input,
+            output,
+            text,
+            position,
+        } => tools.add_watermark(&input, &output, &text, position.into()),
+        ImageTool::StripMetadata { input, output } => tools.strip_metadata(&input, &output),
+        ImageTool::GenerateQr { data, output, size } => tools.generate_qr(&data, &output, size),
+        ImageTool::DecodeQr { input } => tools.decode_qr(&input),
+        ImageTool::ExtractPalette { input, colors } => tools.extract_palette(&input, colors),
+        ImageTool::ApplyFilter {
+            input,
+            output,
+            filter,
+        } => tools.apply_filter(&input, &output, filter.into()),
+        ImageTool::ExtractText { input } => tools.extract_text(&input),
+        ImageTool::GenerateIcons { input, output_dir } => tools.generate_icons(&input, &output_dir),
+        ImageTool::BatchStripMetadata { batch } => {
+            let (inputs, dir) = batch_paths(&batch)?;
+            image::exif::batch_strip_metadata(&inputs, dir)
+        }
+        ImageTool::BatchWatermark {
+            batch,
+            text,
+            position,
+        } => {
+            let (inputs, dir) = batch_paths(&batch)?;
+            image::watermark::batch_watermark(&inputs, dir, &text, position.into())
+        }
+        ImageTool::BatchExtract { batch, language } => {
+            let (inputs, dir) = batch_paths(&batch)?;
+            let options = image::OcrOptions {
+                language,
+                ..image::OcrOptions::default()
+            };
+            image::ocr::batch_extract(&inputs, dir, options)
+        }
+    }
+}
+
+/// Run a video operation.
+fn run_video(op: VideoTool) -> Result<ToolOutput> {
+    let tools = VideoTools::new();
+    match op {
+        VideoTool::Transcode {
+            input,
+            output,
+            options,
+        } => tools.transcode(&input, &output, (&options).into()),
+        VideoTool::ExtractAudio { input, output, to } => {
+            tools.extract_audio(&input, &output, to.into())
+        }
+        VideoTool::Trim {
+            input,
+            output,
+            start,
+            end,
+        } => tools.trim(&input, &output, start, end),
+        VideoTool::ToGif {
+            input,
+            output,
+            width,
+            fps,
+            start,
+            duration,
+            colors,
+        } => {
+            let options = video::GifOptions {
+                width,
+                fps,
+                start,
+                duration,
+                colors,
+                ..video::GifOptions::default()
+            };
+            tools.to_gif(&input, &output, options)
+        }
+        VideoTool::Thumbnail { input, output, at } => tools.thumbnail(&input, &output, at),
+        VideoTool::Scale {
+            input,
+            output,
+            width,
+            height,
+        } => tools.scale(&input, &output, width, height),
+        VideoTool::Concatenate { inputs, output } => {
+            tools.concatenate(&expand_inputs(&inputs)?, PathBuf::from(output))
+        }
+        VideoTool::Mute { input, output } => tools.mute(&input, &output),
+        VideoTool::AddWatermark {
+            input,
+            output,
+            text,
+        } => tools.add_watermark(&input, &output, &text),
+        VideoTool::ChangeSpeed {
+            input,
+            output,
+            factor,
+        } => tools.change_speed(&input, &output, factor),
+        VideoTool::BurnSubtitles {
+            input,
+            subtitles,
+            output,
+        } => tools.burn_subtitles(&input, &subtitles, &output),
+        VideoTool::BatchTranscode { batch, options } => {
+            let (inputs, dir) = batch_paths(&batch)?;
+            video::batch_transcode(&inputs, dir, (&options).into())
+        }
+        VideoTool::BatchExtractAudio { batch, to } => {
+            let (inputs, dir) = batch_paths(&batch)?;
+            video::batch_extract_audio(&inputs, dir, to.into())
+        }
+        VideoTool::BatchScale {
+            batch,
+            width,
+            height,
+        } => {
+            let (inputs, dir) = batch_paths(&batch)?;
+            video::batch_scale(&inputs, dir, video::Resolution::Custom(width, height))
+        }
+        VideoTool::BatchMute { batch } => {
+            let (inputs, dir) = batch_paths(&batch)?;
+            video::batch_mute(&inputs, dir)
+        }
+        VideoTool::BatchWatermark { batch, text } => {
+            let (inputs, dir) = batch_paths(&batch)?;
+            video::batch_watermark(&inputs, dir, &text)
+        }
+        VideoTool::BatchSpeed { batch, factor } => {
+            let (inputs, dir) = batch_paths(&batch)?;
+            video::batch_speed(&inputs, dir, factor)
+        }
+        VideoTool::BatchBurnSubtitles {
+            batch,
+            subtitle_ext,
+        } => {
+            let (inputs, dir) = batch_paths(&batch)?;
+            let pairs: Vec<(PathBuf, PathBuf)> = inputs
+                .into_iter()
+                .map(|video| {
+                    let subtitles = video.with_extension(&subtitle_ext);
+                    (video, subtitles)
+                })
+                .filter(|(_, subtitles)| subtitles.is_file())
+                .collect();
+            if pairs.is_empty() {
+                return Err(DxError::FileIo {
+                    path: PathBuf::from(&batch.inputs[0]),
+                    message: format!("No videos have a matching .{subtitle_ext} file"),
+                    source: None,
+                });
+            }
+            video::batch_burn_subtitles(&pairs, dir)
+        }
+    }
+}
+
+/// Run an audio operation.
+fn run_audio(op: AudioTool) -> Result<ToolOutput> {
+    let tools = AudioTools::new();
+    match op {
+        AudioTool::Convert {
+            input,
+            output,
+            options,
+        } => tools.convert(&input, &output, (&options).into()),
+        AudioTool::Normalize {
+            input,
+            output,
+            options,
+        } => tools.normalize(&input, &output, (&options).into()),
+        AudioTool::Trim {
+            input,
+            output,
+            start,
+            end,
+        } => tools.trim(&input, &output, start, end),
+        AudioTool::Merge { inputs, output } => {
+            tools.merge(&expand_inputs(&inputs)?, PathBuf::from(output))
+        }
+        AudioTool::Spectrum {
+            input,
+            output,
+            kind,
+            width,
+            height,
+            show_axis,
+        } => {
+            let options = audio::SpectrumOptions {
+                spectrum_type: kind.into(),
+                width,
+                height,
+                show_axis,
+                ..audio::SpectrumOptions::default()
+            };
+            tools.spectrum(&input, &output, options)
+        }
+        AudioTool::Metadata { input } => tools.metadata(&input).map(|m| metadata_output(&m)),
+        AudioTool::RemoveSilence {
+            input,
+            output,
+            options,
+        } => tools.remove_silence(&input, &output, (&options).into()),
+        AudioTool::Split {
+            input,
+            output_dir,
+            every,
+            parts,
+            at,
+            at_silence,
+        } => {
+            let options = if let Some(count) = parts {
+                audio::SplitOptions::into_parts(count)
+            } else if !at.is_empty() {
+                audio::SplitOptions {
+                    method: audio::SplitMethod::Timestamps(at),
+                    ..audio::SplitOptions::default()
+                }
+            } else if let Some(threshold_db) = at_silence {
+                let min_duration = audio::SilenceOptions::default().min_duration;
+                audio::SplitOptions::at_silence(threshold_db, min_duration)
+            } else {
+                every.map_or_else(
+                    audio::SplitOptions::default,
+                    audio::SplitOptions::every_seconds,
+                )
+            };
+            tools.split(&input, &output_dir, options)
+        }
+        AudioTool::ApplyEffect {
+            input,
+            output,
+            effect,
+        } => tools.apply_effect(&input, &output, effect),
+        AudioTool::Transcribe { input } => tools.transcribe(&input),
+        AudioTool::BatchConvert { batch, options } => {
+            let (inputs, dir) = batch_paths(&batch)?;
+            audio::batch_convert(&inputs, dir, (&options).into())
+        }
+        AudioTool::BatchNormalize { batch, options } => {
+            let (inputs, dir) = batch_paths(&batch)?;
+            audio::batch_normalize(&inputs, dir, (&options).into())
+        }
+        AudioTool::BatchTrim { batch, start, end } => {
+            let (inputs, dir) = batch_paths(&batch)?;
+            audio::batch_trim(&inputs, dir, start, end)
+        }
+        AudioTool::BatchWaveform {
+            batch,
+            width,
+            height,
+        } => {
+            let (inputs, dir) = batch_paths(&batch)?;
+            audio::batch_waveform(&inputs, dir, width, height)
+        }
+        AudioTool::BatchRemoveSilence { batch, options } => {
+            let (inputs, dir) = batch_paths(&batch)?;
+            audio::batch_remove_silence(&inputs, dir, (&options).into())
+        }
+        AudioTool::BatchEffect { batch, effect } => {
+            let (inputs, dir) = batch_paths(&batch)?;
+            audio::batch_effect(&inputs, dir, effect)
+        }
+    }
+}
+
+/// Run a document operation.
+fn run_document(op: DocumentTool) -> Result<ToolOutput> {
+    let tools = DocumentTools::new();
+    match op {
+        DocumentTool::MergePdf { inputs, output } => {
+            tools.merge_pdf(&expand_inputs(&inputs)?, PathBuf::from(output))
+        }
+        DocumentTool::SplitPdf { input, output_dir } => tools.split_pdf(&input, &output_dir),
+        DocumentTool::CompressPdf {
+            input,
+            output,
+            quality,
+        } => tools.compress_pdf(&input, &output, quality.into()),
+        DocumentTool::PdfToImages { input, output_dir } => tools.pdf_to_images(&input, &output_dir),
+        DocumentTool::MarkdownToHtml { input, output } => tools.markdown_to_html(&input, &output),
+        DocumentTool::HtmlToPdf { input, output } => tools.html_to_pdf(&input, &output),
+        DocumentTool::ConvertDocument { input, output, to } => {
+            tools.convert_document(&input, &output, to.into())
+        }
+        DocumentTool::ExtractText { input } => tools.extract_text(&input),
+        DocumentTool::WatermarkPdf {
+            input,
+            output,
+            text,
+        } => tools.watermark_pdf(&input, &output, &text),
+        DocumentTool::EncryptPdf {
+            input,
+            output,
+            password,
+        } => tools.encrypt_pdf(&input, &output, &password),
+        DocumentTool::DecryptPdf {
+            input,
+            output,
+            password,
+        } => tools.decrypt_pdf(&input, &output, &password),
+        DocumentTool::BatchCompress { batch, quality } => {
+            let (inputs, dir) = batch_paths(&batch)?;
+            document::pdf_compress::batch_compress(&inputs, dir, quality.into())
+        }
+        DocumentTool::BatchPdfToImages { batch } => {
+            let (inputs, dir) = batch_paths(&batch)?;
+            document::pdf_to_image::batch_pdf_to_images(
+                &inputs,
+                dir,
+                document::PdfToImageOptions::default(),
+            )
+        }
+        DocumentTool::BatchMarkdownToHtml { batch } => {
+            let (inputs, dir) = batch_paths(&batch)?;
+            document::markdown::batch_markdown_to_html(
+                &inputs,
+                dir,
+                document::MarkdownOptions::default(),
+            )
+        }
+        DocumentTool::BatchHtmlToPdf { batch } => {
+            let (inputs, dir) = batch_paths(&batch)?;
+            document::html_to_pdf::batch_html_to_pdf(
+                &inputs,
+                dir,
+                document::HtmlToPdfOptions::default(),
+            )
+        }
+        DocumentTool::BatchConvert { batch, to } => {
+            let (inputs, dir) = batch_paths(&batch)?;
+            document::doc_convert::batch_convert(&inputs, dir, to.into())
+        }
+        DocumentTool::BatchExtract { batch } => {
+            let (inputs, dir) = batch_paths(&batch)?;
+            document::text_extract::batch_extract(&inputs, dir)
+        }
+        DocumentTool::BatchWatermark { batch, text } => {
+            let (inputs, dir) = batch_paths(&batch)?;
+            document::pdf_watermark::batch_watermark(
+                &inputs,
+                dir,
+                &text,
+                document::WatermarkOptions::default(),
+            )
+        }
+        DocumentTool::BatchEncrypt { batch, password } => {
+            let (inputs, dir) = batch_paths(&batch)?;
+            document::pdf_encrypt::batch_encrypt(&inputs, dir, &password)
+        }
+    }
+}
+
+/// Run an archive operation.
+fn run_archive(op: ArchiveTool) -> Result<ToolOutput> {
+    let tools = ArchiveTools::new();
+    match op {
+        ArchiveTool::CreateZip { inputs, output } => {
+            tools.create_zip(&expand_inputs(&inputs)?, PathBuf::from(output))
+        }
+        ArchiveTool::ExtractZip { input, output_dir } => tools.extract_zip(&input, &output_dir),
+        ArchiveTool::CreateTar { inputs, output } => {
+            tools.create_tar(&expand_inputs(&inputs)?, PathBuf::from(output))
+        }
+        ArchiveTool::ExtractTar { input, output_dir } => tools.extract_tar(&input, &output_dir),
+        ArchiveTool::Gzip { input, output } => tools.gzip(&input, &output),
+        ArchiveTool::Gunzip { input, output } => tools.gunzip(&input, &output),
+        ArchiveTool::List { input } => tools.list(&input),
+        ArchiveTool::EncryptArchive {
+            inputs,
+            output,
+            password,
+        } => tools.encrypt_archive(&expand_inputs(&inputs)?, PathBuf::from(output), &password),
+        ArchiveTool::SplitArchive {
+            input,
+            output_dir,
+            part_size_mb,
+        } => tools.split_archive(&input, &output_dir, part_size_mb),
+        ArchiveTool::MergeArchives { parts, output } => {
+            tools.merge_archives(&expand_inputs(&parts)?, PathBuf::from(output))
+        }
+        ArchiveTool::BatchCompress { batch, algorithm } => {
+            let (inputs, dir) = batch_paths(&batch)?;
+            archive::compress::batch_compress(&inputs, dir, algorithm.into())
+        }
+        ArchiveTool::BatchDecompress { batch } => {
+            let (inputs, dir) = batch_paths(&batch)?;
+            archive::decompress::batch_decompress(&inputs, dir)
+        }
+    }
+}
+
+/// Run a utility operation.
+fn run_utility(op: UtilityTool) -> Result<ToolOutput> {
+    let tools = UtilityTools::new();
+    match op {
+        UtilityTool::HashFile { input, algorithm } => tools.hash_file(&input, algorithm.into()),
+        UtilityTool::Base64Encode { input } => tools.base64_encode(&input),
+        UtilityTool::Base64Decode { input, output } => tools.base64_decode(&input, &output),
+        UtilityTool::UrlEncode { text } => tools.url_encode(&text),
+        UtilityTool::UrlDecode { text } => tools.url_decode(&text),
+        UtilityTool::FormatJson { input, output } => tools.format_json(&input, &output),
+        UtilityTool::JsonToYaml { input, output } => tools.json_to_yaml(&input, &output),
+        UtilityTool::YamlToJson { input, output } => tools.yaml_to_json(&input, &output),
+        UtilityTool::DiffFiles { file1, file2 } => tools.diff_files(&file1, &file2),
+        UtilityTool::GenerateUuid => tools.generate_uuid(),
+        UtilityTool::Timestamp => tools.timestamp(),
+        UtilityTool::RandomString { length } => tools.random_string(length),
+        UtilityTool::CsvToJson { input, output } => tools.csv_to_json(&input, &output),
+        UtilityTool::BatchHash { inputs, algorithm } => {
+            utility::hash::batch_hash(&expand_inputs(&inputs)?, algorithm.into())
+        }
+    }
+}
+
+/// Expanded inputs and output directory of a batch operation.
+fn batch_paths(batch: &BatchInputs) -> Result<(Vec<PathBuf>, PathBuf)> {
+    Ok((
+        expand_inputs(&batch.inputs)?,
+        PathBuf::from(&batch.output_dir),
+    ))
+}
+
+/// Expand glob patterns into the files they match, in order.
+///
+/// Arguments without glob characters are passed through as given, so the
+/// tool can report missing files itself. A pattern matching nothing is an
+/// error rather than an empty batch.
+fn expand_inputs(patterns: &[String]) -> Result<Vec<PathBuf>> {
+    let mut paths = Vec::new();
+    for pattern in patterns {
+        if !pattern.contains(['*', '?', '[']) {
+            paths.push(PathBuf::from(pattern));
+            continue;
+        }
+
+        let invalid = |message: String| DxError::FileIo {
+            path: PathBuf::from(pattern),
+            message,
+            source: None,
+        };
+        let matches = glob::glob(pattern)
+            .map_err(|e| invalid(format!("Invalid glob pattern: {}", e)))?
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|e| invalid(format!("Failed to read {}: {}", e.path().display(), e)))?;
+        if matches.is_empty() {
+            return Err(invalid("No files match this pattern".to_string()));
+        }
+        paths.extend(matches);
+    }
+    Ok(paths)
+}
+
+/// Tool output for audio metadata.
+fn metadata_output(metadata: &audio::AudioMetadata) -> ToolOutput {
+    let fields = [
+        ("title", metadata.title.clone()),
+        ("artist", metadata.artist.clone()),
+        ("album", metadata.album.clone()),
+        ("album_artist", metadata.album_artist.clone()),
+        ("track", metadata.track.map(|n| n.to_string())),
+        ("total_tracks", metadata.total_tracks.map(|n| n.to_string())),
+        ("disc", metadata.disc.map(|n| n.to_string())),
+        ("year", metadata.year.map(|n| n.to_string())),
+        ("genre", metadata.genre.clone()),
+        ("composer", metadata.composer.clone()),
+        ("duration", metadata.duration.map(|n| n.to_string())),
+        ("bitrate", metadata.bitrate.map(|n| n.to_string())),
+        ("sample_rate", metadata.sample_rate.map(|n| n.to_string())),
+        ("channels", metadata.channels.map(|n| n.to_string())),
+        ("codec", metadata.codec.clone()),
+    ];
+
+    let mut output = ToolOutput::success(audio::format_metadata(metadata));
+    for (key, value) in fields {
+        if let Some(value) = value {
+            output = output.with_metadata(key, value);
+        }
+    }
+    for (key, value) in &metadata.extra {
+        output = output.with_metadata(key.clone(), value.clone());
+    }
+    output
+}
+
+/// JSON representation of a tool output, with metadata in key order.
+fn output_json(output: &ToolOutput) -> serde_json::Value {
+    serde_json::json!({
+        "success": output.success,
+        "message": output.message,
+        "output_paths": output.output_paths,
+        "metadata": output.metadata.iter().collect::<BTreeMap<_, _>>(),
+    })
+}
+
+/// Print a tool output in the requested format.
+fn print_output(output: &ToolOutput, format: OutputFormat, quiet: bool) -> Result<()> {
+    let metadata: BTreeMap<_, _> = output.metadata.iter().collect();
+
+    match format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&output_json(output))?),
+        OutputFormat::JsonCompact | OutputFormat::Ndjson => {
+            println!("{}", serde_json::to_string(&output_json(output))?);
+        }
+        OutputFormat::Tsv | OutputFormat::Csv => {
+            print_row(format, &[&"success", &output.success]);
+            print_row(format, &[&"message", &output.message]);
+            for path in &output.output_paths {
+                print_row(format, &[&"output", &path.display()]);
+            }
+            for (key, value) in metadata {
+                print_row(format, &[key, value]);
+            }
+        }
+        // Failures are reported through the returned error
+        OutputFormat::Text if !output.success => {}
+        OutputFormat::Text => {
+            // The message is often the result itself (a hash, an encoded string)
+            println!("{}", output.message);
+            if quiet {
+                return Ok(());
+            }
+            for path in &output.output_paths {
+                println!("  {} {}", "→".cyan(), path.display());
+            }
+            // Skip entries repeating the message
+            for (key, value) in metadata.iter().filter(|(_, v)| **v != &output.message) {
+                println!("  {} {}", format!("{}:", key).dimmed(), value);
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expand_inputs() {
+        let dir = tempfile::tempdir().unwrap();
+        for name in ["b.jpg", "a.jpg", "c.png"] {
+            std::fs::write(dir.path().join(name), b"").unwrap();
+        }
+        let pattern = dir.path().join("*.jpg").display().to_string();
+        let literal = dir.path().join("missing.gif").display().to_string();
+
+        let paths = expand_inputs(&[pattern, literal.clone()]).unwrap();
+        assert_eq!(
+            paths,
+            vec![
+                dir.path().join("a.jpg"),
+                dir.path().join("b.jpg"),
+                PathBuf::from(literal),
+            ]
+        );
+
+        let none = dir.path().join("*.webp").display().to_string();
+        assert!(expand_inputs(&[none]).is_err());
+    }
+
+    #[test]
+    fn test_output_json() {
+        let output = ToolOutput::success_with_path("Done", "out.png")
+            .with_metadata("width", "10")
+            .with_metadata("format", "png");
+
+        assert_eq!(
+            output_json(&output),
+            serde_json::json!({
+                "success": true,
+                "message": "Done",
+                "output_paths": ["out.png"],
+                "metadata": {"format": "png", "width": "10"},
+            })
+        );
+    }
+}
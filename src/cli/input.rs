@@ -0,0 +1,232 @@
+//! Reading assets back from saved CLI output.
+
+use std::io::Read;
+
+use serde_json::Value;
+
+use crate::error::{DxError, Result};
+use crate::types::{License, MediaAsset};
+
+/// Read assets from a JSON file, or from stdin when `path` is `-`.
+///
+/// Accepts `dx search --format json` output, a bare array of assets, or a
+/// single asset object. Licenses may be given by name (e.g. `"CC-BY"`).
+pub fn read_assets(path: &str) -> Result<Vec<MediaAsset>> {
+    parse_assets(&read_text(path)?)
+}
+
+/// Read an asset list from a file, or from stdin when `path` is `-`.
+///
+/// Accepts everything [`read_assets`] does, plus NDJSON (one asset per line)
+/// and plain `provider:id` lines. See [`parse_asset_list`].
+pub fn read_asset_list(path: &str) -> Result<Vec<AssetEntry>> {
+    parse_asset_list(&read_text(path)?)
+}
+
+fn read_text(path: &str) -> Result<String> {
+    if path == "-" {
+        let mut text = String::new();
+        std::io::stdin()
+            .read_to_string(&mut text)
+            .map_err(|e| DxError::FileIo {
+                path: "<stdin>".into(),
+                message: format!("Failed to read stdin: {}", e),
+                source: Some(e),
+            })?;
+        Ok(text)
+    } else {
+        std::fs::read_to_string(path).map_err(|e| DxError::FileIo {
+            path: path.into(),
+            message: format!("Failed to read {}: {}", path, e),
+            source: Some(e),
+        })
+    }
+}
+
+/// An entry of an asset list.
+#[derive(Debug, Clone)]
+pub enum AssetEntry {
+    /// A complete asset, e.g. from search output.
+    Asset(Box<MediaAsset>),
+    /// A `provider:id` reference to look up.
+    Ref(String),
+}
+
+/// Parse an asset list.
+///
+/// Input made only of JSON values is read as a sequence of documents, each
+/// as by [`parse_assets`] except that strings are `provider:id` references.
+/// This covers a single search output as well as NDJSON and the
+/// pretty-printed objects `jq` emits. Otherwise each non-empty line is an
+/// asset object, a JSON string, or a bare `provider:id`; lines starting with
+/// `#` are ignored.
+pub fn parse_asset_list(text: &str) -> Result<Vec<AssetEntry>> {
+    let values: std::result::Result<Vec<Value>, _> =
+        serde_json::Deserializer::from_str(text).into_iter::<Value>().collect();
+    if let Ok(values) = values {
+        let mut entries = Vec::new();
+        for value in values {
+            for item in asset_items(value)? {
+                entries.push(entry_from_value(item)?);
+            }
+        }
+        return Ok(entries);
+    }
+
+    text.lines()
+        .enumerate()
+        .map(|(n, line)| (n + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+        .map(|(line_number, line)| {
+            if line.starts_with('{') || line.starts_with('"') {
+                let value = serde_json::from_str(line).map_err(|e| DxError::InvalidQuery {
+                    message: format!("Line {}: {}", line_number, e),
+                })?;
+                entry_from_value(value)
+            } else {
+                Ok(AssetEntry::Ref(line.to_string()))
+            }
+        })
+        .collect()
+}
+
+fn entry_from_value(value: Value) -> Result<AssetEntry> {
+    match value {
+        Value::String(reference) => Ok(AssetEntry::Ref(reference)),
+        value => Ok(AssetEntry::Asset(Box::new(asset_from_value(value)?))),
+    }
+}
+
+/// Parse assets from JSON text. See [`read_assets`] for the accepted shapes.
+pub fn parse_assets(text: &str) -> Result<Vec<MediaAsset>> {
+    let value: Value = serde_json::from_str(text)?;
+    asset_items(value)?
+        .into_iter()
+        .map(asset_from_value)
+        .collect()
+}
+
+/// The asset values of search output, an array, or a single object.
+fn asset_items(value: Value) -> Result<Vec<Value>> {
+    match value {
+        Value::Object(mut map) if map.contains_key("assets") => match map.remove("assets") {
+            Some(Value::Array(items)) => Ok(items),
+            _ => Err(DxError::InvalidQuery {
+                message: "\"assets\" must be an array".to_string(),
+            }),
+        },
+        Value::Array(items) => Ok(items),
+        value @ (Value::Object(_) | Value::String(_)) => Ok(vec![value]),
+        _ => Err(DxError::InvalidQuery {
+            message: "Expected search results, an array of assets, or an asset".to_string(),
+        }),
+    }
+}
+
+/// Deserialize an asset, filling in fields the CLI's JSON output leaves out.
+fn asset_from_value(mut value: Value) -> Result<MediaAsset> {
+    if let Value::Object(ref mut map) = value {
+        if let Some(Value::String(name)) = map.get("license") {
+            let license = License::parse(name);
+            map.insert("license".to_string(), serde_json::to_value(license)?);
+        }
+        map.entry("tags").or_insert_with(|| Value::Array(Vec::new()));
+        map.entry("indexed_at")
+            .or_insert_with(|| Value::String(chrono::Utc::now().to_rfc3339()));
+    }
+
+    Ok(serde_json::from_value(value)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_search_output() {
+        let json = r#"{
+            "query": "fox",
+            "assets": [{
+                "id": "1",
+                "provider": "openverse",
+                "media_type": "image",
+                "title": "Fox",
+                "author": null,
+                "author_url": null,
+                "source_url": "https://example.com/fox",
+                "download_url": "https://example.com/fox.jpg",
+                "preview_url": null,
+                "width": 640,
+                "height": null,
+                "license": "CC-BY-SA"
+            }]
+        }"#;
+
+        let assets = parse_assets(json).unwrap();
+        assert_eq!(assets.len(), 1);
+        assert_eq!(assets[0].license, License::CcBySa);
+        assert_eq!(assets[0].width, Some(640));
+        assert!(parse_assets("42").is_err());
+    }
+
+    #[test]
+    fn test_parse_asset_list() {
+        let refs = |entries: Vec<AssetEntry>| {
+            entries
+                .into_iter()
+                .map(|entry| match entry {
+                    AssetEntry::Asset(asset) => format!("{}:{}", asset.provider, asset.id),
+                    AssetEntry::Ref(reference) => format!("ref {reference}"),
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let ndjson = r#"
+            # picked with jq
+            {"id": "1", "provider": "openverse", "media_type": "image", "title": "Fox", "source_url": "https://example.com/fox", "download_url": "https://example.com/fox.jpg", "license": "CC0"}
+            "nasa:PIA123"
+            wikimedia:File:Fox.jpg
+        "#;
+        assert_eq!(
+            refs(parse_asset_list(ndjson).unwrap()),
+            [
+                "openverse:1",
+                "ref nasa:PIA123",
+                "ref wikimedia:File:Fox.jpg"
+            ]
+        );
+        assert_eq!(
+            refs(parse_asset_list(r#"["met:42"]"#).unwrap()),
+            ["ref met:42"]
+        );
+        let err = parse_asset_list("met:1\n\n{broken").unwrap_err();
+        assert!(err.to_string().contains("Line 3"), "{err}");
+    }
+
+    #[test]
+    fn test_parse_pretty_printed_objects() {
+        // What `dx search --format json | jq '.assets[]'` prints
+        let jq = r#"{
+  "id": "1",
+  "provider": "openverse",
+  "media_type": "image",
+  "title": "Fox",
+  "source_url": "https://example.com/fox",
+  "download_url": "https://example.com/fox.jpg",
+  "license": "CC0"
+}
+{
+  "id": "2",
+  "provider": "wikimedia",
+  "media_type": "image",
+  "title": "Fox den",
+  "source_url": "https://example.com/fox",
+  "download_url": "https://example.com/den.jpg",
+  "license": "CC BY 4.0"
+}
+"#;
+        let entries = parse_asset_list(jq).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert!(matches!(&entries[1], AssetEntry::Asset(asset) if asset.id == "2"));
+    }
+}
@@ -5,7 +5,10 @@
 
 mod args;
 mod commands;
+mod input;
 mod output;
+mod preview;
+mod tui;
 
 pub use args::{Args, Command};
 pub use output::OutputFormatter;
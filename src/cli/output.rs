@@ -1,27 +1,83 @@
 //! Output formatting for CLI commands.
 
+use std::fmt::Display;
+
+use clap::ValueEnum;
 use colored::Colorize;
+use serde_json::Value;
 
-use crate::cli::args::OutputFormat;
+use crate::cli::args::{AssetField, OutputFormat};
 use crate::error::Result;
-use crate::types::{MediaAsset, SearchResult};
+use crate::types::{FilterStatus, MediaAsset, SearchFilter, SearchResult};
+
+/// Columns of tsv and csv search results when no fields are selected.
+const DEFAULT_FIELDS: &[AssetField] = &[
+    AssetField::Provider,
+    AssetField::Id,
+    AssetField::MediaType,
+    AssetField::Title,
+    AssetField::Author,
+    AssetField::Width,
+    AssetField::Height,
+    AssetField::DownloadUrl,
+];
 
 /// Output formatter for CLI results.
 #[derive(Debug, Clone)]
 pub struct OutputFormatter {
     format: OutputFormat,
     quiet: bool,
+    /// Rendered inline previews, by result index.
+    previews: Vec<Option<String>>,
+    /// Selected fields for tabular and ndjson output (empty = defaults).
+    fields: Vec<AssetField>,
+    /// One-line template that replaces the output format.
+    template: Option<Template>,
 }
 
 impl OutputFormatter {
     /// Create a new output formatter.
     #[must_use]
     pub fn new(format: OutputFormat, quiet: bool) -> Self {
-        Self { format, quiet }
+        Self {
+            format,
+            quiet,
+            previews: Vec::new(),
+            fields: Vec::new(),
+            template: None,
+        }
+    }
+
+    /// Select the fields of tsv, csv and ndjson results (empty = defaults).
+    #[must_use]
+    pub fn with_fields(mut self, fields: Vec<AssetField>) -> Self {
+        self.fields = fields;
+        self
+    }
+
+    /// Print results through a one-line template instead of the format.
+    #[must_use]
+    pub fn with_template(mut self, template: Option<Template>) -> Self {
+        self.template = template;
+        self
+    }
+
+    /// Show rendered previews under text results, by result index.
+    #[must_use]
+    pub fn with_previews(mut self, previews: Vec<Option<String>>) -> Self {
+        self.previews = previews;
+        self
     }
 
     /// Format and print search results.
     pub fn format_search_results(&self, result: &SearchResult) -> Result<()> {
+        if let Some(ref template) = self.template {
+            for asset in &result.assets {
+                println!("{}", template.render(asset));
+            }
+            return Ok(());
+        }
+
         if self.quiet && matches!(self.format, OutputFormat::Text) {
             return Ok(());
         }
@@ -30,7 +86,75 @@ impl OutputFormatter {
             OutputFormat::Text => self.format_search_results_text(result),
             OutputFormat::Json => self.format_search_results_json(result, true),
             OutputFormat::JsonCompact => self.format_search_results_json(result, false),
-            OutputFormat::Tsv => self.format_search_results_tsv(result),
+            OutputFormat::Tsv | OutputFormat::Csv => {
+                self.format_search_results_table(result);
+                Ok(())
+            }
+            OutputFormat::Ndjson => {
+                for asset in &result.assets {
+                    self.format_asset_ndjson(asset)?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Print an asset as one NDJSON line, limited to the selected fields.
+    pub fn format_asset_ndjson(&self, asset: &MediaAsset) -> Result<()> {
+        let record = if self.fields.is_empty() {
+            self.asset_to_json(asset)
+        } else {
+            self.fields
+                .iter()
+                .map(|&field| (field_name(field), field_value(asset, field)))
+                .collect::<serde_json::Map<_, _>>()
+                .into()
+        };
+        println!("{}", serde_json::to_string(&record)?);
+        Ok(())
+    }
+
+    /// Summarize how each query filter was applied, one line per filter.
+    fn format_filter_reports_text(&self, result: &SearchResult) {
+        let filters = [
+            SearchFilter::MinWidth,
+            SearchFilter::MinHeight,
+            SearchFilter::Orientation,
+            SearchFilter::Color,
+        ];
+
+        for filter in filters {
+            if !result.filters.iter().any(|r| r.filter == filter) {
+                continue;
+            }
+
+            let providers = |status: FilterStatus| {
+                result
+                    .filters
+                    .iter()
+                    .filter(|r| r.filter == filter && r.status == status)
+                    .map(|r| match status {
+                        FilterStatus::ClientSide if r.removed > 0 => {
+                            format!("{} -{}", r.provider, r.removed)
+                        }
+                        _ => r.provider.clone(),
+                    })
+                    .collect::<Vec<_>>()
+            };
+
+            let mut parts = Vec::new();
+            for (status, label) in [
+                (FilterStatus::Native, "native"),
+                (FilterStatus::ClientSide, "client-side"),
+                (FilterStatus::Unevaluated, "not evaluated"),
+            ] {
+                let names = providers(status);
+                if !names.is_empty() {
+                    parts.push(format!("{} ({})", label, names.join(", ")));
+                }
+            }
+
+            println!("  {} {}: {}", "⊘".cyan(), filter, parts.join(", ").dimmed());
         }
     }
 
@@ -45,6 +169,17 @@ impl OutputFormatter {
             result.duration_ms
         );
 
+        if !result.cache_hits.is_empty() {
+            println!(
+                "  {} {} from cache, {} fetched",
+                "↺".cyan(),
+                result.cache_hits.len(),
+                result.cache_misses.len()
+            );
+        }
+
+        self.format_filter_reports_text(result);
+
         if !result.provider_errors.is_empty() {
             for (provider, error) in &result.provider_errors {
                 println!("  {} {}: {}", "⚠".yellow(), provider, error.dimmed());
@@ -116,6 +251,12 @@ impl OutputFormatter {
             asset.license.as_str().green()
         );
 
+        if let Some(Some(preview)) = self.previews.get(index - 1) {
+            for line in preview.lines() {
+                println!("     {}", line);
+            }
+        }
+
         // Download command hint
         println!(
             "     {} dx download {}:{}",
@@ -139,6 +280,9 @@ impl OutputFormatter {
             "provider_errors": result.provider_errors.iter()
                 .map(|(p, e)| serde_json::json!({"provider": p, "error": e}))
                 .collect::<Vec<_>>(),
+            "cache_hits": result.cache_hits,
+            "cache_misses": result.cache_misses,
+            "filters": result.filters,
             "assets": result.assets.iter().map(|a| self.asset_to_json(a)).collect::<Vec<_>>(),
         });
 
@@ -170,28 +314,20 @@ impl OutputFormatter {
         })
     }
 
-    /// Format search results as TSV.
-    fn format_search_results_tsv(&self, result: &SearchResult) -> Result<()> {
-        // Header
-        println!("provider\tid\ttype\ttitle\tauthor\twidth\theight\tdownload_url");
+    /// Format search results as TSV or CSV, with a header row.
+    fn format_search_results_table(&self, result: &SearchResult) {
+        let fields = if self.fields.is_empty() {
+            DEFAULT_FIELDS
+        } else {
+            &self.fields
+        };
 
-        // Data
+        let header: Vec<String> = fields.iter().map(|&f| field_name(f)).collect();
+        println!("{}", tabular_row(self.format, &header));
         for asset in &result.assets {
-            let author = asset.author.as_deref().unwrap_or("");
-            println!(
-                "{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}",
-                asset.provider,
-                asset.id,
-                asset.media_type.as_str(),
-                asset.title.replace('\t', " "),
-                author.replace('\t', " "),
-                asset.width.map(|w| w.to_string()).unwrap_or_default(),
-                asset.height.map(|h| h.to_string()).unwrap_or_default(),
-                asset.download_url,
-            );
+            let row: Vec<String> = fields.iter().map(|&f| field_text(asset, f)).collect();
+            println!("{}", tabular_row(self.format, &row));
         }
-
-        Ok(())
     }
 
     /// Format a single asset for display.
@@ -209,13 +345,16 @@ impl OutputFormatter {
             OutputFormat::JsonCompact => {
                 println!("{}", serde_json::to_string(&self.asset_to_json(asset))?);
             }
-            OutputFormat::Tsv => {
-                println!(
-                    "{}\t{}\t{}\t{}",
-                    asset.provider,
-                    asset.id,
-                    asset.media_type.as_str(),
-                    asset.title
+            OutputFormat::Ndjson => self.format_asset_ndjson(asset)?,
+            OutputFormat::Tsv | OutputFormat::Csv => {
+                print_row(
+                    self.format,
+                    &[
+                        &asset.provider,
+                        &asset.id,
+                        &asset.media_type.as_str(),
+                        &asset.title,
+                    ],
                 );
             }
         }
@@ -223,8 +362,215 @@ impl OutputFormatter {
     }
 }
 
+/// A `--template` for one-line asset output, e.g. `{provider}:{id} {title}`.
+#[derive(Debug, Clone)]
+pub struct Template {
+    parts: Vec<TemplatePart>,
+}
+
+#[derive(Debug, Clone)]
+enum TemplatePart {
+    Text(String),
+    Field(AssetField),
+}
+
+impl Template {
+    /// Parse a template, checking its field names. `{{` and `}}` are
+    /// literal braces.
+    pub fn parse(template: &str) -> std::result::Result<Self, String> {
+        let mut parts = Vec::new();
+        let mut text = String::new();
+        let mut chars = template.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '{' | '}' if chars.peek() == Some(&c) => {
+                    chars.next();
+                    text.push(c);
+                }
+                '{' => {
+                    let mut name = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(c) => name.push(c),
+                            None => return Err(format!("unclosed '{{{name}'")),
+                        }
+                    }
+                    let field = AssetField::from_str(name.trim(), true)
+                        .map_err(|_| format!("unknown field '{name}'"))?;
+                    if !text.is_empty() {
+                        parts.push(TemplatePart::Text(std::mem::take(&mut text)));
+                    }
+                    parts.push(TemplatePart::Field(field));
+                }
+                '}' => return Err("unmatched '}' (use '}}' for a literal brace)".to_string()),
+                c => text.push(c),
+            }
+        }
+        if !text.is_empty() {
+            parts.push(TemplatePart::Text(text));
+        }
+
+        Ok(Self { parts })
+    }
+
+    /// Render the template for an asset.
+    #[must_use]
+    pub fn render(&self, asset: &MediaAsset) -> String {
+        self.parts
+            .iter()
+            .map(|part| match part {
+                TemplatePart::Text(text) => text.clone(),
+                TemplatePart::Field(field) => field_text(asset, *field),
+            })
+            .collect()
+    }
+}
+
+/// Name of a field in headers and ndjson keys.
+fn field_name(field: AssetField) -> String {
+    field
+        .to_possible_value()
+        .map(|value| value.get_name().to_string())
+        .unwrap_or_default()
+}
+
+/// A field of an asset as JSON.
+fn field_value(asset: &MediaAsset, field: AssetField) -> Value {
+    match field {
+        AssetField::Id => asset.id.clone().into(),
+        AssetField::Provider => asset.provider.clone().into(),
+        AssetField::MediaType => asset.media_type.as_str().into(),
+        AssetField::Title => asset.title.clone().into(),
+        AssetField::Author => asset.author.clone().into(),
+        AssetField::AuthorUrl => asset.author_url.clone().into(),
+        AssetField::SourceUrl => asset.source_url.clone().into(),
+        AssetField::DownloadUrl => asset.download_url.clone().into(),
+        AssetField::PreviewUrl => asset.preview_url.clone().into(),
+        AssetField::Width => asset.width.into(),
+        AssetField::Height => asset.height.into(),
+        AssetField::DurationSecs => asset.duration_secs.into(),
+        AssetField::FileSize => asset.file_size.into(),
+        AssetField::MimeType => asset.mime_type.clone().into(),
+        AssetField::License => asset.license.as_str().into(),
+        AssetField::Tags => asset.tags.clone().into(),
+    }
+}
+
+/// A field of an asset as text; missing values are empty and tags are
+/// comma-separated.
+fn field_text(asset: &MediaAsset, field: AssetField) -> String {
+    match field_value(asset, field) {
+        Value::Null => String::new(),
+        Value::String(text) => text,
+        Value::Array(items) => items
+            .iter()
+            .filter_map(Value::as_str)
+            .collect::<Vec<_>>()
+            .join(","),
+        value => value.to_string(),
+    }
+}
+
+/// Format one row of CSV for [`OutputFormat::Csv`], or TSV otherwise.
+///
+/// CSV fields are quoted when they contain a comma, quote or line break;
+/// tabs and line breaks in TSV fields become spaces.
+pub fn tabular_row<T: AsRef<str>>(format: OutputFormat, fields: &[T]) -> String {
+    if matches!(format, OutputFormat::Csv) {
+        fields
+            .iter()
+            .map(|field| {
+                let field = field.as_ref();
+                if field.contains([',', '"', '\n', '\r']) {
+                    format!("\"{}\"", field.replace('"', "\"\""))
+                } else {
+                    field.to_string()
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(",")
+    } else {
+        fields
+            .iter()
+            .map(|field| field.as_ref().replace(['\t', '\n', '\r'], " "))
+            .collect::<Vec<_>>()
+            .join("\t")
+    }
+}
+
+/// Print one row of TSV or CSV; see [`tabular_row`].
+pub fn print_row(format: OutputFormat, fields: &[&dyn Display]) {
+    let fields: Vec<String> = fields.iter().map(ToString::to_string).collect();
+    println!("{}", tabular_row(format, &fields));
+}
+
+/// Print a JSON value, on one line for the compact formats.
+pub fn print_json<T: serde::Serialize + ?Sized>(json: &T, format: OutputFormat) -> Result<()> {
+    if format.is_compact_json() {
+        println!("{}", serde_json::to_string(json)?);
+    } else {
+        println!("{}", serde_json::to_string_pretty(json)?);
+    }
+    Ok(())
+}
+
+/// Print a list of JSON records: one per line for [`OutputFormat::Ndjson`],
+/// otherwise as a single array like [`print_json`].
+pub fn print_json_list<T: serde::Serialize>(items: &[T], format: OutputFormat) -> Result<()> {
+    if matches!(format, OutputFormat::Ndjson) {
+        for item in items {
+            println!("{}", serde_json::to_string(item)?);
+        }
+        Ok(())
+    } else {
+        print_json(items, format)
+    }
+}
+
 impl Default for OutputFormatter {
     fn default() -> Self {
         Self::new(OutputFormat::Text, false)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{License, MediaType};
+
+    #[test]
+    fn test_template() {
+        let asset = MediaAsset::builder()
+            .id("42")
+            .provider("openverse")
+            .media_type(MediaType::Image)
+            .title("Red fox")
+            .download_url("https://example.com/fox.jpg")
+            .source_url("https://example.com/fox")
+            .license(License::Cc0)
+            .build();
+
+        let template = Template::parse("{provider}:{id} {{{title}}} {width}").unwrap();
+        assert_eq!(template.render(&asset), "openverse:42 {Red fox} ");
+        assert_eq!(Template::parse("{type}").unwrap().render(&asset), "image");
+
+        assert!(Template::parse("{nope}").is_err());
+        assert!(Template::parse("{id").is_err());
+        assert!(Template::parse("id}").is_err());
+    }
+
+    #[test]
+    fn test_tabular_row() {
+        let fields = ["plain", "a,b", "say \"hi\"", "two\nlines"];
+        assert_eq!(
+            tabular_row(OutputFormat::Csv, &fields),
+            "plain,\"a,b\",\"say \"\"hi\"\"\",\"two\nlines\""
+        );
+        assert_eq!(
+            tabular_row(OutputFormat::Tsv, &["a\tb", "c\nd"]),
+            "a b\tc d"
+        );
+    }
+}
@@ -0,0 +1,530 @@
+//! Inline image previews in the terminal.
+//!
+//! Images, the first frame of videos and the first page of PDFs are decoded
+//! with FFmpeg or Ghostscript, scaled to fit a box of terminal cells and
+//! drawn with the Kitty, iTerm2 or Sixel graphics protocol, falling back to
+//! Unicode half blocks on terminals without graphics support.
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::io::Write as _;
+use std::path::Path;
+
+use base64::Engine as _;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use flate2::Compression;
+use flate2::write::ZlibEncoder;
+use tokio::io::AsyncReadExt as _;
+use tokio::process::Command;
+
+use crate::config::Config;
+use crate::error::{DxError, Result};
+use crate::http::HttpClient;
+use crate::types::RateLimitConfig;
+
+/// Cell size in pixels assumed when the terminal doesn't report one.
+const DEFAULT_CELL_SIZE: (u32, u32) = (10, 20);
+
+/// Largest remote file downloaded for a preview.
+const MAX_PREVIEW_BYTES: usize = 20 * 1024 * 1024;
+
+/// Base64 bytes per Kitty graphics escape.
+const KITTY_CHUNK: usize = 4096;
+
+/// How an image is drawn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Protocol {
+    /// Kitty graphics protocol (Kitty, Ghostty).
+    Kitty,
+    /// iTerm2 inline images (iTerm2, WezTerm).
+    Iterm,
+    /// DEC Sixel graphics.
+    Sixel,
+    /// Unicode half blocks with 24-bit color; works everywhere.
+    Blocks,
+}
+
+impl Protocol {
+    /// Pick the best protocol the current terminal supports.
+    #[must_use]
+    pub fn detect() -> Self {
+        Self::detect_from(|name| std::env::var(name).ok())
+    }
+
+    /// Pick a protocol from terminal environment variables.
+    fn detect_from(env: impl Fn(&str) -> Option<String>) -> Self {
+        // Graphics escapes don't reach the outer terminal through tmux
+        if env("TMUX").is_some() {
+            return Self::Blocks;
+        }
+
+        let term = env("TERM").unwrap_or_default();
+        let program = env("TERM_PROGRAM").unwrap_or_default();
+        if env("KITTY_WINDOW_ID").is_some() || term.contains("kitty") || program == "ghostty" {
+            Self::Kitty
+        } else if matches!(program.as_str(), "iTerm.app" | "WezTerm")
+            || env("LC_TERMINAL").as_deref() == Some("iTerm2")
+        {
+            Self::Iterm
+        } else if term.contains("sixel")
+            || term.starts_with("foot")
+            || matches!(program.as_str(), "mlterm" | "contour")
+        {
+            Self::Sixel
+        } else {
+            Self::Blocks
+        }
+    }
+}
+
+/// Renders previews into a box of terminal cells.
+#[derive(Debug, Clone)]
+pub struct Previewer {
+    protocol: Protocol,
+    columns: u32,
+    rows: u32,
+    cell: (u32, u32),
+    client: HttpClient,
+}
+
+impl Previewer {
+    /// Create a previewer drawing at most `columns` x `rows` cells, fetching
+    /// remote files with the retry, timeout and rate limiting settings of
+    /// `config`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP client cannot be created.
+    pub fn new(protocol: Protocol, columns: u16, rows: u16, config: &Config) -> Result<Self> {
+        let cell = ratatui::crossterm::terminal::window_size()
+            .ok()
+            .filter(|size| size.width > 0 && size.height > 0 && size.columns > 0 && size.rows > 0)
+            .map_or(DEFAULT_CELL_SIZE, |size| {
+                (
+                    u32::from(size.width / size.columns),
+                    u32::from(size.height / size.rows),
+                )
+            });
+        Ok(Self {
+            protocol,
+            columns: u32::from(columns.max(1)),
+            rows: u32::from(rows.max(1)),
+            cell,
+            client: HttpClient::from_config(RateLimitConfig::unlimited(), config)?,
+        })
+    }
+
+    /// Render a local image, video or PDF.
+    pub async fn render_file(&self, path: &Path) -> Result<String> {
+        let (width, height) = match self.protocol {
+            Protocol::Blocks => (self.columns, self.rows * 2),
+            _ => (self.columns * self.cell.0, self.rows * self.cell.1),
+        };
+        let image = Image::load(path, width, height).await?;
+        Ok(self.encode(&image))
+    }
+
+    /// Download and render a remote image, video or PDF of at most
+    /// [`MAX_PREVIEW_BYTES`].
+    pub async fn render_url(&self, url: &str) -> Result<String> {
+        let too_large = || DxError::download(url, "Too large to preview");
+
+        let mut response = self.client.get_raw(url).await?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(DxError::Http {
+                message: format!("Failed to fetch preview '{}': HTTP {}", url, status),
+                status_code: Some(status.as_u16()),
+                source: None,
+            });
+        }
+        if response
+            .content_length()
+            .is_some_and(|len| len > MAX_PREVIEW_BYTES as u64)
+        {
+            return Err(too_large());
+        }
+
+        let mut bytes = Vec::new();
+        while let Some(chunk) = response.chunk().await? {
+            if bytes.len() + chunk.len() > MAX_PREVIEW_BYTES {
+                return Err(too_large());
+            }
+            bytes.extend_from_slice(&chunk);
+        }
+
+        let file = tempfile::NamedTempFile::new()?;
+        tokio::fs::write(file.path(), &bytes).await?;
+        self.render_file(file.path()).await
+    }
+
+    /// Encode a decoded image for the terminal.
+    fn encode(&self, image: &Image) -> String {
+        let columns = image.width.div_ceil(self.cell.0).clamp(1, self.columns);
+        match self.protocol {
+            Protocol::Kitty => image.to_kitty(columns),
+            Protocol::Iterm => image.to_iterm(columns),
+            Protocol::Sixel => image.to_sixel(),
+            Protocol::Blocks => image.to_blocks(),
+        }
+    }
+}
+
+/// A decoded RGB image.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Image {
+    width: u32,
+    height: u32,
+    /// Row-major RGB triples.
+    pixels: Vec<u8>,
+}
+
+impl Image {
+    /// Decode the first frame or page of a file, scaled to fit the box.
+    async fn load(path: &Path, max_width: u32, max_height: u32) -> Result<Self> {
+        let mut head = [0u8; 5];
+        let is_pdf = match tokio::fs::File::open(path).await {
+            Ok(mut file) => file.read_exact(&mut head).await.is_ok() && &head == b"%PDF-",
+            Err(_) => false,
+        };
+
+        let mut cmd = if is_pdf {
+            // Render at roughly the box width for a US Letter page
+            let dpi = (max_width * 72 / 612).clamp(10, 150);
+            let mut cmd = Command::new("gs");
+            cmd.args(["-q", "-dSAFER", "-dBATCH", "-dNOPAUSE", "-sDEVICE=ppmraw"])
+                .args(["-dFirstPage=1", "-dLastPage=1", "-sOutputFile=-"])
+                .arg(format!("-r{dpi}"))
+                .arg(path);
+            cmd
+        } else {
+            let mut cmd = Command::new("ffmpeg");
+            cmd.args(["-v", "error", "-i"])
+                .arg(path)
+                .args(["-frames:v", "1", "-vf"])
+                .arg(format!(
+                    "scale='min(iw,{max_width})':'min(ih,{max_height})':force_original_aspect_ratio=decrease"
+                ))
+                .args(["-f", "image2pipe", "-c:v", "ppm", "-"]);
+            cmd
+        };
+        let program = if is_pdf { "Ghostscript" } else { "FFmpeg" };
+
+        let output = cmd.output().await.map_err(|e| DxError::Config {
+            message: format!("Failed to run {program}: {e}"),
+            source: None,
+        })?;
+        if !output.status.success() || output.stdout.is_empty() {
+            return Err(DxError::FileIo {
+                path: path.to_path_buf(),
+                message: format!(
+                    "{program} couldn't decode a preview: {}",
+                    String::from_utf8_lossy(&output.stderr).trim()
+                ),
+                source: None,
+            });
+        }
+
+        Ok(Self::from_ppm(&output.stdout)?.fit(max_width, max_height))
+    }
+
+    /// Parse a binary PPM (`P6`) image.
+    fn from_ppm(data: &[u8]) -> Result<Self> {
+        let invalid = || DxError::Internal {
+            message: "Invalid PPM image".to_string(),
+        };
+
+        // Header: magic, width, height and max value, separated by
+        // whitespace and `#` comments, then a single whitespace byte
+        let mut fields = Vec::new();
+        let mut pos = 0;
+        while fields.len() < 4 {
+            match data.get(pos) {
+                Some(b'#') => {
+                    while data.get(pos).is_some_and(|&b| b != b'\n') {
+                        pos += 1;
+                    }
+                }
+                Some(b) if b.is_ascii_whitespace() => pos += 1,
+                Some(_) => {
+                    let start = pos;
+                    while data.get(pos).is_some_and(|b| !b.is_ascii_whitespace()) {
+                        pos += 1;
+                    }
+                    fields.push(std::str::from_utf8(&data[start..pos]).map_err(|_| invalid())?);
+                }
+                None => return Err(invalid()),
+            }
+        }
+        let [magic, width, height, max] = fields[..] else {
+            return Err(invalid());
+        };
+        let number = |s: &str| s.parse::<u32>().map_err(|_| invalid());
+        let (width, height) = (number(width)?, number(height)?);
+        if magic != "P6" || number(max)? != 255 || width == 0 || height == 0 {
+            return Err(invalid());
+        }
+
+        let len = width as usize * height as usize * 3;
+        let pixels = data.get(pos + 1..pos + 1 + len).ok_or_else(invalid)?;
+        Ok(Self {
+            width,
+            height,
+            pixels: pixels.to_vec(),
+        })
+    }
+
+    /// Downscale by area averaging to fit the box, keeping the aspect ratio.
+    fn fit(self, max_width: u32, max_height: u32) -> Self {
+        let (max_width, max_height) = (max_width.max(1), max_height.max(1));
+        if self.width <= max_width && self.height <= max_height {
+            return self;
+        }
+
+        let (w, h) = (u64::from(self.width), u64::from(self.height));
+        let (new_w, new_h) = if w * u64::from(max_height) > h * u64::from(max_width) {
+            (u64::from(max_width), (h * u64::from(max_width) / w).max(1))
+        } else {
+            (
+                (w * u64::from(max_height) / h).max(1),
+                u64::from(max_height),
+            )
+        };
+
+        let mut pixels = Vec::with_capacity((new_w * new_h * 3) as usize);
+        for y in 0..new_h {
+            let (y0, y1) = (y * h / new_h, ((y + 1) * h / new_h).max(y * h / new_h + 1));
+            for x in 0..new_w {
+                let (x0, x1) = (x * w / new_w, ((x + 1) * w / new_w).max(x * w / new_w + 1));
+                let mut sum = [0u64; 3];
+                for sy in y0..y1 {
+                    for sx in x0..x1 {
+                        let i = ((sy * w + sx) * 3) as usize;
+                        for (total, &value) in sum.iter_mut().zip(&self.pixels[i..i + 3]) {
+                            *total += u64::from(value);
+                        }
+                    }
+                }
+                let count = (y1 - y0) * (x1 - x0);
+                pixels.extend(sum.map(|total| (total / count) as u8));
+            }
+        }
+
+        Self {
+            width: new_w as u32,
+            height: new_h as u32,
+            pixels,
+        }
+    }
+
+    fn pixel(&self, x: u32, y: u32) -> [u8; 3] {
+        let i = (y as usize * self.width as usize + x as usize) * 3;
+        [self.pixels[i], self.pixels[i + 1], self.pixels[i + 2]]
+    }
+
+    /// Encode as PNG (8-bit RGB, unfiltered).
+    fn to_png(&self) -> Vec<u8> {
+        let row = self.width as usize * 3;
+        let mut raw = Vec::with_capacity((row + 1) * self.height as usize);
+        for line in self.pixels.chunks(row) {
+            raw.push(0);
+            raw.extend_from_slice(line);
+        }
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::fast());
+        let _ = encoder.write_all(&raw);
+        let data = encoder.finish().unwrap_or_default();
+
+        let mut header = Vec::with_capacity(13);
+        header.extend_from_slice(&self.width.to_be_bytes());
+        header.extend_from_slice(&self.height.to_be_bytes());
+        header.extend_from_slice(&[8, 2, 0, 0, 0]);
+
+        let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+        for (kind, body) in [
+            (b"IHDR", &header[..]),
+            (b"IDAT", &data[..]),
+            (b"IEND", &[][..]),
+        ] {
+            let mut crc = flate2::Crc::new();
+            crc.update(kind);
+            crc.update(body);
+            png.extend_from_slice(&(body.len() as u32).to_be_bytes());
+            png.extend_from_slice(kind);
+            png.extend_from_slice(body);
+            png.extend_from_slice(&crc.sum().to_be_bytes());
+        }
+        png
+    }
+
+    /// Kitty graphics escapes transmitting raw RGB, scaled to `columns` cells.
+    fn to_kitty(&self, columns: u32) -> String {
+        let data = BASE64.encode(&self.pixels);
+        let chunks: Vec<&[u8]> = data.as_bytes().chunks(KITTY_CHUNK).collect();
+        let mut out = String::new();
+        for (i, chunk) in chunks.iter().enumerate() {
+            let more = u8::from(i + 1 < chunks.len());
+            let chunk = std::str::from_utf8(chunk).unwrap_or_default();
+            if i == 0 {
+                let _ = write!(
+                    out,
+                    "\x1b_Ga=T,q=2,f=24,s={},v={},c={columns},m={more};{chunk}\x1b\\",
+                    self.width, self.height
+                );
+            } else {
+                let _ = write!(out, "\x1b_Gm={more};{chunk}\x1b\\");
+            }
+        }
+        out
+    }
+
+    /// iTerm2 inline image escape carrying a PNG, scaled to `columns` cells.
+    fn to_iterm(&self, columns: u32) -> String {
+        let png = self.to_png();
+        format!(
+            "\x1b]1337;File=inline=1;size={};width={columns};preserveAspectRatio=1:{}\x07",
+            png.len(),
+            BASE64.encode(&png)
+        )
+    }
+
+    /// Sixel graphics with a 6x6x6 color cube palette.
+    fn to_sixel(&self) -> String {
+        let level = |v: u8| (usize::from(v) * 5 + 127) / 255;
+        let color = |[r, g, b]: [u8; 3]| level(r) * 36 + level(g) * 6 + level(b);
+
+        let mut out = format!("\x1bP0;1;0q\"1;1;{};{}", self.width, self.height);
+        for i in 0..216 {
+            let percent = |l: usize| l * 100 / 5;
+            let _ = write!(
+                out,
+                "#{i};2;{};{};{}",
+                percent(i / 36),
+                percent(i / 6 % 6),
+                percent(i % 6)
+            );
+        }
+
+        for band in (0..self.height).step_by(6) {
+            // Six-pixel-high columns of each color in this band
+            let mut layers = BTreeMap::<usize, Vec<u8>>::new();
+            for dy in 0..6.min(self.height - band) {
+                for x in 0..self.width {
+                    let bits = layers
+                        .entry(color(self.pixel(x, band + dy)))
+                        .or_insert_with(|| vec![0; self.width as usize]);
+                    bits[x as usize] |= 1 << dy;
+                }
+            }
+
+            for (n, (color, bits)) in layers.iter().enumerate() {
+                if n > 0 {
+                    out.push('$');
+                }
+                let _ = write!(out, "#{color}");
+                for run in bits.chunk_by(|a, b| a == b) {
+                    let sixel = char::from(63 + run[0]);
+                    if run.len() > 3 {
+                        let _ = write!(out, "!{}{sixel}", run.len());
+                    } else {
+                        out.extend(std::iter::repeat_n(sixel, run.len()));
+                    }
+                }
+            }
+            out.push('-');
+        }
+        out.push_str("\x1b\\");
+        out
+    }
+
+    /// Lines of `▀` cells, each showing two pixels with 24-bit colors.
+    fn to_blocks(&self) -> String {
+        let mut out = String::new();
+        for y in (0..self.height).step_by(2) {
+            for x in 0..self.width {
+                let [r, g, b] = self.pixel(x, y);
+                let _ = write!(out, "\x1b[38;2;{r};{g};{b}");
+                if y + 1 < self.height {
+                    let [r, g, b] = self.pixel(x, y + 1);
+                    let _ = write!(out, ";48;2;{r};{g};{b}");
+                }
+                out.push_str("m▀");
+            }
+            out.push_str("\x1b[0m\n");
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn image(width: u32, height: u32) -> Image {
+        let pixels = (0..width * height)
+            .flat_map(|i| [(i * 10) as u8, 0, 255])
+            .collect();
+        Image {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    #[test]
+    fn test_detect() {
+        let detect = |vars: &[(&str, &str)]| {
+            Protocol::detect_from(|name| {
+                vars.iter()
+                    .find(|(k, _)| *k == name)
+                    .map(|(_, v)| (*v).to_string())
+            })
+        };
+        assert_eq!(detect(&[("TERM", "xterm-kitty")]), Protocol::Kitty);
+        assert_eq!(detect(&[("TERM_PROGRAM", "iTerm.app")]), Protocol::Iterm);
+        assert_eq!(detect(&[("TERM", "foot")]), Protocol::Sixel);
+        assert_eq!(detect(&[("TERM", "xterm-256color")]), Protocol::Blocks);
+        assert_eq!(
+            detect(&[("TERM", "xterm-kitty"), ("TMUX", "/tmp/tmux")]),
+            Protocol::Blocks
+        );
+    }
+
+    #[test]
+    fn test_ppm_and_fit() {
+        let mut ppm = b"P6\n# comment\n2 2\n255\n".to_vec();
+        ppm.extend_from_slice(&[0, 0, 0, 100, 100, 100, 200, 200, 200, 40, 40, 40]);
+        let decoded = Image::from_ppm(&ppm).unwrap();
+        assert_eq!((decoded.width, decoded.height), (2, 2));
+        assert_eq!(decoded.pixel(1, 1), [40, 40, 40]);
+        assert!(Image::from_ppm(b"P6\n2 2\n255\n\0\0\0").is_err());
+
+        let fitted = decoded.fit(1, 5);
+        assert_eq!((fitted.width, fitted.height), (1, 1));
+        assert_eq!(fitted.pixels, [85, 85, 85]);
+
+        let fitted = image(40, 10).fit(8, 8);
+        assert_eq!((fitted.width, fitted.height), (8, 2));
+    }
+
+    #[test]
+    fn test_encodings() {
+        let img = image(3, 3);
+
+        let blocks = img.to_blocks();
+        assert_eq!(blocks.lines().count(), 2);
+        assert_eq!(blocks.matches('▀').count(), 6);
+        assert!(blocks.starts_with("\x1b[38;2;0;0;255;48;2;30;0;255m▀"));
+
+        let png = img.to_png();
+        assert!(png.starts_with(b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR\0\0\0\x03\0\0\0\x03"));
+        assert!(png.ends_with(&[0xAE, 0x42, 0x60, 0x82]));
+
+        let kitty = image(100, 100).to_kitty(10);
+        assert!(kitty.starts_with("\x1b_Ga=T,q=2,f=24,s=100,v=100,c=10,m=1;"));
+        assert!(kitty.ends_with("\x1b\\") && kitty.contains("\x1b_Gm=0;"));
+
+        let sixel = img.to_sixel();
+        assert!(sixel.starts_with("\x1bP0;1;0q\"1;1;3;3#0;2;0;0;0"));
+        assert!(sixel.ends_with("-\x1b\\"));
+    }
+}
@@ -0,0 +1,675 @@
+//! Interactive mode state and key handling.
+//!
+//! [`App`] holds everything the screen shows. Key presses turn into
+//! [`Action`]s that the event loop carries out (searches and downloads run
+//! in the background), and their results come back as [`Message`]s.
+
+use std::collections::BTreeSet;
+use std::fmt::Write as _;
+use std::path::PathBuf;
+
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::widgets::ListState;
+
+use crate::engine::{DownloadReport, DownloadStatus, QueueEvent};
+use crate::error::Result;
+use crate::types::{
+    License, MediaAsset, MediaType, Orientation, SearchMode, SearchQuery, SearchResult,
+};
+
+/// Results requested per page.
+pub const PAGE_SIZE: usize = 20;
+
+/// Rows moved by PageUp/PageDown.
+const SCROLL_STEP: isize = 10;
+
+/// Media type filter values, in cycling order.
+const MEDIA_TYPES: &[Option<MediaType>] = &[
+    None,
+    Some(MediaType::Image),
+    Some(MediaType::Video),
+    Some(MediaType::Audio),
+    Some(MediaType::Gif),
+    Some(MediaType::Vector),
+    Some(MediaType::Document),
+    Some(MediaType::Model3D),
+];
+
+/// Orientation filter values, in cycling order.
+const ORIENTATIONS: &[Option<Orientation>] = &[
+    None,
+    Some(Orientation::Landscape),
+    Some(Orientation::Portrait),
+    Some(Orientation::Square),
+];
+
+/// License filter values, in cycling order.
+const LICENSES: &[Option<License>] = &[
+    None,
+    Some(License::Cc0),
+    Some(License::PublicDomain),
+    Some(License::CcBy),
+    Some(License::CcBySa),
+    Some(License::CcByNc),
+    Some(License::Unsplash),
+    Some(License::Pexels),
+    Some(License::Pixabay),
+];
+
+/// Which pane receives typed keys.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Focus {
+    /// The query box.
+    Query,
+    /// The result list.
+    Results,
+}
+
+/// Work for the event loop to start.
+#[derive(Debug)]
+pub enum Action {
+    /// Run a search; the result comes back tagged with `id`.
+    Search {
+        /// Search generation, to drop superseded results.
+        id: u64,
+        /// Query to run.
+        query: SearchQuery,
+    },
+    /// Download assets; their rows start at `offset` in [`App::downloads`].
+    Download {
+        /// Index of the batch's first download row.
+        offset: usize,
+        /// Assets to download.
+        assets: Vec<MediaAsset>,
+    },
+    /// Leave interactive mode.
+    Quit,
+}
+
+/// Results of background work.
+#[derive(Debug)]
+pub enum Message {
+    /// A search finished.
+    SearchDone {
+        /// Search generation.
+        id: u64,
+        /// The search outcome.
+        result: Result<SearchResult>,
+    },
+    /// A download in a batch made progress.
+    Download {
+        /// Index of the batch's first download row.
+        offset: usize,
+        /// Queue event, indexed within the batch.
+        event: QueueEvent,
+    },
+    /// A download batch finished.
+    DownloadsDone(DownloadReport),
+}
+
+/// State of one queued download.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DownloadState {
+    /// Waiting for a slot.
+    Queued,
+    /// Transferring.
+    Active,
+    /// Retrying after an error.
+    Retrying(u32),
+    /// Saved to the given path.
+    Done(PathBuf),
+    /// Not downloaded, for the given reason.
+    Skipped(String),
+    /// Failed with the given error.
+    Failed(String),
+}
+
+/// One row of the download pane.
+#[derive(Debug, Clone)]
+pub struct DownloadRow {
+    /// Asset reference, as `provider:id`.
+    pub asset: String,
+    /// Asset title.
+    pub title: String,
+    /// Bytes received.
+    pub received: u64,
+    /// Total bytes (`0` if unknown).
+    pub total: u64,
+    /// Current state.
+    pub state: DownloadState,
+}
+
+/// Search filters.
+#[derive(Debug, Clone, Default)]
+pub struct Filters {
+    /// Media type, or any.
+    pub media_type: Option<MediaType>,
+    /// Single provider, or all.
+    pub provider: Option<String>,
+    /// Orientation, or any.
+    pub orientation: Option<Orientation>,
+    /// License, or any. Applied to the results rather than the query.
+    pub license: Option<License>,
+}
+
+/// Interactive mode state.
+#[derive(Debug)]
+pub struct App {
+    /// Text in the query box.
+    pub query: String,
+    /// Focused pane.
+    pub focus: Focus,
+    /// Active filters.
+    pub filters: Filters,
+    /// Search mode.
+    pub mode: SearchMode,
+    /// Result page, from 1.
+    pub page: usize,
+    /// Latest search result.
+    pub result: Option<SearchResult>,
+    /// Indices into the result's assets that pass the license filter.
+    pub visible: Vec<usize>,
+    /// Cursor in the result list (an index into `visible`).
+    pub list: ListState,
+    /// Marked assets, as indices into the result's assets.
+    pub marked: BTreeSet<usize>,
+    /// Queued, running and finished downloads.
+    pub downloads: Vec<DownloadRow>,
+    /// Status line text.
+    pub status: String,
+    /// Whether a search is running.
+    pub searching: bool,
+    providers: Vec<String>,
+    search_id: u64,
+}
+
+impl App {
+    /// Create the state for the given providers and default search mode.
+    pub fn new(mut providers: Vec<String>, mode: SearchMode) -> Self {
+        providers.sort();
+        Self {
+            query: String::new(),
+            focus: Focus::Query,
+            filters: Filters::default(),
+            mode,
+            page: 1,
+            result: None,
+            visible: Vec::new(),
+            list: ListState::default(),
+            marked: BTreeSet::new(),
+            downloads: Vec::new(),
+            status: "Type a query and press Enter".to_string(),
+            searching: false,
+            providers,
+            search_id: 0,
+        }
+    }
+
+    /// The asset under the cursor.
+    pub fn selected(&self) -> Option<&MediaAsset> {
+        let index = *self.visible.get(self.list.selected()?)?;
+        self.result.as_ref()?.assets.get(index)
+    }
+
+    /// Whether the asset at `index` in the result is marked.
+    pub fn is_marked(&self, index: usize) -> bool {
+        self.marked.contains(&index)
+    }
+
+    /// Query for the current text, filters, mode and page.
+    pub fn search_query(&self) -> SearchQuery {
+        let mut query = SearchQuery::new(self.query.trim())
+            .count(PAGE_SIZE)
+            .page(self.page)
+            .mode(self.mode);
+        query.media_type = self.filters.media_type;
+        query.orientation = self.filters.orientation;
+        query.providers = self.filters.provider.iter().cloned().collect();
+        query
+    }
+
+    /// Handle a key press.
+    pub fn handle_key(&mut self, key: KeyEvent) -> Option<Action> {
+        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+            return Some(Action::Quit);
+        }
+        match self.focus {
+            Focus::Query => self.handle_query_key(key),
+            Focus::Results => self.handle_results_key(key),
+        }
+    }
+
+    fn handle_query_key(&mut self, key: KeyEvent) -> Option<Action> {
+        match key.code {
+            KeyCode::Char(c) => self.query.push(c),
+            KeyCode::Backspace => {
+                self.query.pop();
+            }
+            KeyCode::Enter => {
+                self.page = 1;
+                let action = self.search();
+                if action.is_some() {
+                    self.focus = Focus::Results;
+                }
+                return action;
+            }
+            KeyCode::Esc | KeyCode::Tab | KeyCode::Down => {
+                if self.result.is_some() {
+                    self.focus = Focus::Results;
+                } else if key.code == KeyCode::Esc {
+                    return Some(Action::Quit);
+                }
+            }
+            _ => {}
+        }
+        None
+    }
+
+    fn handle_results_key(&mut self, key: KeyEvent) -> Option<Action> {
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return Some(Action::Quit),
+            KeyCode::Char('/') | KeyCode::Tab => self.focus = Focus::Query,
+            KeyCode::Up | KeyCode::Char('k') => self.move_cursor(-1),
+            KeyCode::Down | KeyCode::Char('j') => self.move_cursor(1),
+            KeyCode::PageUp => self.move_cursor(-SCROLL_STEP),
+            KeyCode::PageDown => self.move_cursor(SCROLL_STEP),
+            KeyCode::Home | KeyCode::Char('g') => self.move_cursor(isize::MIN),
+            KeyCode::End | KeyCode::Char('G') => self.move_cursor(isize::MAX),
+            KeyCode::Right | KeyCode::Char('n') => {
+                if self.visible.is_empty()
+                    && self.result.as_ref().is_some_and(|r| r.assets.is_empty())
+                {
+                    self.status = "No more results".to_string();
+                    return None;
+                }
+                self.page += 1;
+                return self.search();
+            }
+            KeyCode::Left | KeyCode::Char('p') if self.page > 1 => {
+                self.page -= 1;
+                return self.search();
+            }
+            KeyCode::Char(' ') => {
+                if let Some(&index) = self.list.selected().and_then(|i| self.visible.get(i)) {
+                    if !self.marked.remove(&index) {
+                        self.marked.insert(index);
+                    }
+                    self.move_cursor(1);
+                }
+            }
+            KeyCode::Char('a') => {
+                if self.visible.iter().all(|i| self.marked.contains(i)) {
+                    self.marked.clear();
+                } else {
+                    self.marked.extend(self.visible.iter().copied());
+                }
+            }
+            KeyCode::Enter | KeyCode::Char('d') => return self.download(),
+            KeyCode::Char('r') => return self.search(),
+            KeyCode::Char('t') => {
+                self.filters.media_type = cycle(MEDIA_TYPES, &self.filters.media_type);
+                return self.refilter_search();
+            }
+            KeyCode::Char('s') => {
+                self.filters.provider = self.next_provider();
+                return self.refilter_search();
+            }
+            KeyCode::Char('o') => {
+                self.filters.orientation = cycle(ORIENTATIONS, &self.filters.orientation);
+                return self.refilter_search();
+            }
+            KeyCode::Char('l') => {
+                self.filters.license = cycle(LICENSES, &self.filters.license);
+                self.update_visible();
+            }
+            KeyCode::Char('m') => {
+                self.mode = match self.mode {
+                    SearchMode::Quantity => SearchMode::Quality,
+                    SearchMode::Quality => SearchMode::Quantity,
+                };
+                return self.refilter_search();
+            }
+            _ => {}
+        }
+        None
+    }
+
+    /// Apply the outcome of background work.
+    pub fn handle_message(&mut self, message: Message) {
+        match message {
+            Message::SearchDone { id, .. } if id != self.search_id => {}
+            Message::SearchDone { result, .. } => {
+                self.searching = false;
+                match result {
+                    Ok(result) => self.set_result(result),
+                    Err(e) => self.status = format!("Search failed: {e}"),
+                }
+            }
+            Message::Download { offset, event } => self.apply_download_event(offset, event),
+            Message::DownloadsDone(report) => {
+                self.status = format!(
+                    "Downloads finished: {} downloaded, {} skipped, {} failed",
+                    report.downloaded().len(),
+                    report.skipped().len(),
+                    report.failed().len()
+                );
+            }
+        }
+    }
+
+    fn set_result(&mut self, result: SearchResult) {
+        let mut status = format!(
+            "{} results from {} providers in {} ms",
+            result.assets.len(),
+            result.providers_searched.len(),
+            result.duration_ms
+        );
+        if !result.provider_errors.is_empty() {
+            let _ = write!(
+                status,
+                " ({} providers failed)",
+                result.provider_errors.len()
+            );
+        }
+        self.status = status;
+        self.marked.clear();
+        self.result = Some(result);
+        self.update_visible();
+        self.list.select(if self.visible.is_empty() {
+            None
+        } else {
+            Some(0)
+        });
+    }
+
+    fn apply_download_event(&mut self, offset: usize, event: QueueEvent) {
+        let index = match &event {
+            QueueEvent::Started { index }
+            | QueueEvent::Progress { index, .. }
+            | QueueEvent::Retrying { index, .. }
+            | QueueEvent::Finished { index, .. } => offset + index,
+        };
+        let Some(row) = self.downloads.get_mut(index) else {
+            return;
+        };
+        match event {
+            QueueEvent::Started { .. } => row.state = DownloadState::Active,
+            QueueEvent::Progress {
+                received, total, ..
+            } => {
+                row.received = received;
+                row.total = total;
+            }
+            QueueEvent::Retrying { attempt, .. } => {
+                row.received = 0;
+                row.state = DownloadState::Retrying(attempt);
+            }
+            QueueEvent::Finished { status, .. } => {
+                row.state = match status {
+                    DownloadStatus::Downloaded(path) => DownloadState::Done(path),
+                    DownloadStatus::Skipped(reason) => DownloadState::Skipped(reason),
+                    DownloadStatus::Failed(error) => DownloadState::Failed(error),
+                };
+            }
+        }
+    }
+
+    /// Start a search for the current query, if there is one.
+    fn search(&mut self) -> Option<Action> {
+        if self.query.trim().is_empty() {
+            self.status = "Type a query first".to_string();
+            return None;
+        }
+        self.search_id += 1;
+        self.searching = true;
+        self.status = format!(
+            "Searching for '{}' (page {})...",
+            self.query.trim(),
+            self.page
+        );
+        Some(Action::Search {
+            id: self.search_id,
+            query: self.search_query(),
+        })
+    }
+
+    /// Re-run the search from the first page after a filter change.
+    fn refilter_search(&mut self) -> Option<Action> {
+        self.result.as_ref()?;
+        self.page = 1;
+        self.search()
+    }
+
+    /// Queue the marked assets, or the selected one if none are marked.
+    ///
+    /// Assets that are already queued or downloading are left out.
+    fn download(&mut self) -> Option<Action> {
+        let result = self.result.as_ref()?;
+        let requested: Vec<MediaAsset> = if self.marked.is_empty() {
+            self.selected().into_iter().cloned().collect()
+        } else {
+            self.marked
+                .iter()
+                .filter_map(|&i| result.assets.get(i))
+                .cloned()
+                .collect()
+        };
+        if requested.is_empty() {
+            return None;
+        }
+        let assets: Vec<MediaAsset> = requested
+            .into_iter()
+            .filter(|asset| !self.is_downloading(asset))
+            .collect();
+        if assets.is_empty() {
+            self.status = "Already downloading".to_string();
+            return None;
+        }
+
+        let offset = self.downloads.len();
+        self.downloads
+            .extend(assets.iter().map(|asset| DownloadRow {
+                asset: asset_ref(asset),
+                title: asset.title.clone(),
+                received: 0,
+                total: 0,
+                state: DownloadState::Queued,
+            }));
+        self.marked.clear();
+        self.status = format!("Downloading {} assets...", assets.len());
+        Some(Action::Download { offset, assets })
+    }
+
+    /// Whether an asset has a queued or running download.
+    fn is_downloading(&self, asset: &MediaAsset) -> bool {
+        let reference = asset_ref(asset);
+        self.downloads.iter().any(|row| {
+            row.asset == reference
+                && matches!(
+                    row.state,
+                    DownloadState::Queued | DownloadState::Active | DownloadState::Retrying(_)
+                )
+        })
+    }
+
+    fn update_visible(&mut self) {
+        let assets = self.result.as_ref().map_or(&[][..], |r| &r.assets);
+        self.visible = (0..assets.len())
+            .filter(|&i| {
+                self.filters
+                    .license
+                    .as_ref()
+                    .is_none_or(|license| assets[i].license == *license)
+            })
+            .collect();
+        let cursor = self.list.selected().unwrap_or(0);
+        self.list.select(match self.visible.len() {
+            0 => None,
+            len => Some(cursor.min(len - 1)),
+        });
+    }
+
+    fn move_cursor(&mut self, delta: isize) {
+        if self.visible.is_empty() {
+            return;
+        }
+        let last = self.visible.len() - 1;
+        let current = self.list.selected().unwrap_or(0);
+        self.list
+            .select(Some(current.saturating_add_signed(delta).min(last)));
+    }
+
+    fn next_provider(&self) -> Option<String> {
+        match &self.filters.provider {
+            None => self.providers.first().cloned(),
+            Some(current) => {
+                let position = self.providers.iter().position(|p| p == current)?;
+                self.providers.get(position + 1).cloned()
+            }
+        }
+    }
+}
+
+/// The value after `current` in `values`, wrapping around.
+/// `provider:id` reference for an asset.
+fn asset_ref(asset: &MediaAsset) -> String {
+    format!("{}:{}", asset.provider, asset.id)
+}
+
+fn cycle<T: Clone + PartialEq>(values: &[T], current: &T) -> T {
+    let position = values.iter().position(|v| v == current).unwrap_or(0);
+    values[(position + 1) % values.len()].clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn asset(id: &str, license: License) -> MediaAsset {
+        MediaAsset::builder()
+            .id(id)
+            .provider("openverse")
+            .media_type(MediaType::Image)
+            .title(id)
+            .download_url(format!("https://example.com/{id}.jpg"))
+            .source_url(format!("https://example.com/{id}"))
+            .license(license)
+            .build()
+    }
+
+    fn app_with_results() -> App {
+        let mut app = App::new(
+            vec!["pexels".into(), "openverse".into()],
+            SearchMode::Quantity,
+        );
+        for c in "fox".chars() {
+            app.handle_key(key(KeyCode::Char(c)));
+        }
+        let Some(Action::Search { id, query }) = app.handle_key(key(KeyCode::Enter)) else {
+            panic!("expected a search");
+        };
+        assert_eq!(query.query, "fox");
+        assert_eq!(app.focus, Focus::Results);
+
+        let mut result = SearchResult::new("fox");
+        result.assets = vec![
+            asset("a", License::Cc0),
+            asset("b", License::CcBy),
+            asset("c", License::Cc0),
+        ];
+        app.handle_message(Message::SearchDone {
+            id,
+            result: Ok(result),
+        });
+        app
+    }
+
+    #[test]
+    fn test_license_filter_and_marking() {
+        let mut app = app_with_results();
+        assert_eq!(app.visible, [0, 1, 2]);
+        assert_eq!(app.selected().unwrap().id, "a");
+
+        app.handle_key(key(KeyCode::Char('l')));
+        assert_eq!(app.filters.license, Some(License::Cc0));
+        assert_eq!(app.visible, [0, 2]);
+
+        app.handle_key(key(KeyCode::Char(' ')));
+        app.handle_key(key(KeyCode::Char(' ')));
+        assert!(app.is_marked(0) && app.is_marked(2));
+
+        let Some(Action::Download { offset, assets }) = app.handle_key(key(KeyCode::Char('d')))
+        else {
+            panic!("expected a download");
+        };
+        assert_eq!(offset, 0);
+        assert_eq!(
+            assets.iter().map(|a| a.id.as_str()).collect::<Vec<_>>(),
+            ["a", "c"]
+        );
+        assert_eq!(app.downloads.len(), 2);
+        assert!(app.marked.is_empty());
+
+        app.handle_message(Message::Download {
+            offset,
+            event: QueueEvent::Finished {
+                index: 1,
+                status: DownloadStatus::Failed("404".into()),
+            },
+        });
+        assert_eq!(app.downloads[1].state, DownloadState::Failed("404".into()));
+    }
+
+    #[test]
+    fn test_repeated_download_is_ignored() {
+        let mut app = app_with_results();
+        assert!(app.handle_key(key(KeyCode::Char('d'))).is_some());
+        assert!(app.handle_key(key(KeyCode::Char('d'))).is_none());
+        assert_eq!(app.downloads.len(), 1);
+
+        // Once finished, the asset can be downloaded again
+        app.handle_message(Message::Download {
+            offset: 0,
+            event: QueueEvent::Finished {
+                index: 0,
+                status: DownloadStatus::Failed("timeout".into()),
+            },
+        });
+        assert!(app.handle_key(key(KeyCode::Char('d'))).is_some());
+        assert_eq!(app.downloads.len(), 2);
+    }
+
+    #[test]
+    fn test_paging_filters_and_mode() {
+        let mut app = app_with_results();
+
+        let Some(Action::Search { query, .. }) = app.handle_key(key(KeyCode::Char('n'))) else {
+            panic!("expected a search");
+        };
+        assert_eq!(query.page, 2);
+        assert!(app.handle_key(key(KeyCode::Char('p'))).is_some());
+        assert_eq!(app.page, 1);
+        assert!(app.handle_key(key(KeyCode::Char('p'))).is_none());
+
+        let Some(Action::Search { query, .. }) = app.handle_key(key(KeyCode::Char('s'))) else {
+            panic!("expected a search");
+        };
+        assert_eq!(query.providers, ["openverse"]);
+
+        let Some(Action::Search { query, .. }) = app.handle_key(key(KeyCode::Char('m'))) else {
+            panic!("expected a search");
+        };
+        assert_eq!(query.mode, SearchMode::Quality);
+
+        // Results of a superseded search are dropped
+        app.handle_message(Message::SearchDone {
+            id: 1,
+            result: Ok(SearchResult::new("fox")),
+        });
+        assert_eq!(app.visible.len(), 3);
+    }
+}
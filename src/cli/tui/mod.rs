@@ -0,0 +1,107 @@
+//! Full-screen interactive search and download.
+//!
+//! The event loop redraws the screen, feeds key presses to [`App`] and runs
+//! the searches and downloads it asks for on background tasks, which report
+//! back over a channel so the screen stays responsive. Terminal input is read
+//! on a blocking thread and forwarded over a channel of its own.
+
+mod app;
+mod ui;
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use ratatui::DefaultTerminal;
+use ratatui::crossterm::event::{self, Event, KeyEventKind};
+use tokio::sync::mpsc;
+
+use crate::engine::DxMedia;
+use crate::error::Result;
+
+use app::{Action, App, Message};
+
+/// How long the input thread waits for an event before checking whether the
+/// event loop has exited.
+const TICK: Duration = Duration::from_millis(50);
+
+/// Run interactive mode until the user quits.
+pub async fn run(dx: DxMedia) -> Result<()> {
+    let dx = Arc::new(dx);
+    let mut app = App::new(dx.available_providers(), dx.config().search_mode);
+
+    let mut terminal = ratatui::try_init()?;
+    let result = event_loop(&mut terminal, &mut app, &dx).await;
+    ratatui::restore();
+    result
+}
+
+async fn event_loop(
+    terminal: &mut DefaultTerminal,
+    app: &mut App,
+    dx: &Arc<DxMedia>,
+) -> Result<()> {
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    let mut input = spawn_input();
+
+    loop {
+        terminal.draw(|frame| ui::draw(frame, app))?;
+
+        let key = tokio::select! {
+            Some(message) = rx.recv() => {
+                app.handle_message(message);
+                while let Ok(message) = rx.try_recv() {
+                    app.handle_message(message);
+                }
+                continue;
+            }
+            event = input.recv() => match event {
+                Some(Ok(Event::Key(key))) if key.kind == KeyEventKind::Press => key,
+                Some(Ok(_)) => continue,
+                Some(Err(e)) => return Err(e.into()),
+                None => return Ok(()),
+            },
+        };
+        match app.handle_key(key) {
+            Some(Action::Quit) => return Ok(()),
+            Some(Action::Search { id, query }) => {
+                let (dx, tx) = (Arc::clone(dx), tx.clone());
+                tokio::spawn(async move {
+                    let result = dx.search_query(&query).await;
+                    let _ = tx.send(Message::SearchDone { id, result });
+                });
+            }
+            Some(Action::Download { offset, assets }) => {
+                let (dx, tx) = (Arc::clone(dx), tx.clone());
+                tokio::spawn(async move {
+                    let events = tx.clone();
+                    let queue = dx.download_queue().on_event(Arc::new(move |event| {
+                        let _ = events.send(Message::Download { offset, event });
+                    }));
+                    let report = queue.run(&assets, dx.download_dir()).await;
+                    let _ = tx.send(Message::DownloadsDone(report));
+                });
+            }
+            None => {}
+        }
+    }
+}
+
+/// Read terminal events on a blocking thread, which stops once the receiver
+/// is dropped or reading fails.
+fn spawn_input() -> mpsc::UnboundedReceiver<std::io::Result<Event>> {
+    let (tx, rx) = mpsc::unbounded_channel();
+    tokio::task::spawn_blocking(move || {
+        while !tx.is_closed() {
+            let event = match event::poll(TICK) {
+                Ok(false) => continue,
+                Ok(true) => event::read(),
+                Err(e) => Err(e),
+            };
+            let failed = event.is_err();
+            if tx.send(event).is_err() || failed {
+                break;
+            }
+        }
+    });
+    rx
+}
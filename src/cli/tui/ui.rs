@@ -0,0 +1,325 @@
+//! Interactive mode rendering.
+
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, LineGauge, List, ListItem, Paragraph, Wrap};
+
+use super::app::{App, DownloadRow, DownloadState, Focus};
+use crate::types::{MediaAsset, MediaType};
+
+/// Download rows shown at most.
+const MAX_DOWNLOAD_ROWS: usize = 6;
+
+/// Draw the whole screen.
+pub fn draw(frame: &mut Frame, app: &mut App) {
+    let download_rows = app.downloads.len().min(MAX_DOWNLOAD_ROWS);
+    let download_height = if download_rows == 0 {
+        0
+    } else {
+        download_rows as u16 + 2
+    };
+    let [query, filters, main, downloads, status] = Layout::vertical([
+        Constraint::Length(3),
+        Constraint::Length(1),
+        Constraint::Min(5),
+        Constraint::Length(download_height),
+        Constraint::Length(2),
+    ])
+    .areas(frame.area());
+    let [results, detail] =
+        Layout::horizontal([Constraint::Percentage(55), Constraint::Percentage(45)]).areas(main);
+
+    draw_query(frame, app, query);
+    draw_filters(frame, app, filters);
+    draw_results(frame, app, results);
+    draw_detail(frame, app, detail);
+    draw_downloads(frame, app, downloads);
+    draw_status(frame, app, status);
+}
+
+fn focused_block(title: &str, focused: bool) -> Block<'_> {
+    let block = Block::bordered().title(title);
+    if focused {
+        block.border_style(Style::new().fg(Color::Cyan))
+    } else {
+        block
+    }
+}
+
+fn draw_query(frame: &mut Frame, app: &App, area: Rect) {
+    let focused = app.focus == Focus::Query;
+    let query = Paragraph::new(app.query.as_str()).block(focused_block(" Search ", focused));
+    frame.render_widget(query, area);
+    if focused {
+        let cursor = u16::try_from(app.query.chars().count()).unwrap_or(u16::MAX);
+        frame.set_cursor_position((
+            area.x + 1 + cursor.min(area.width.saturating_sub(3)),
+            area.y + 1,
+        ));
+    }
+}
+
+fn draw_filters(frame: &mut Frame, app: &App, area: Rect) {
+    let filter = |key: &'static str, name: &'static str, value: String| {
+        vec![
+            Span::raw(" "),
+            Span::styled(key, Style::new().fg(Color::Yellow)),
+            Span::raw(format!(" {name}: ")),
+            Span::styled(value, Style::new().add_modifier(Modifier::BOLD)),
+        ]
+    };
+    let filters = &app.filters;
+    let any = || "any".to_string();
+    let spans: Vec<Span> = [
+        filter(
+            "t",
+            "type",
+            filters
+                .media_type
+                .map_or_else(any, |t| t.as_str().to_string()),
+        ),
+        filter(
+            "s",
+            "source",
+            filters
+                .provider
+                .clone()
+                .unwrap_or_else(|| "all".to_string()),
+        ),
+        filter(
+            "o",
+            "orientation",
+            filters.orientation.map_or_else(any, |o| o.to_string()),
+        ),
+        filter(
+            "l",
+            "license",
+            filters
+                .license
+                .as_ref()
+                .map_or_else(any, |l| l.as_str().to_string()),
+        ),
+        filter("m", "mode", app.mode.to_string()),
+        vec![Span::raw(format!("  page {}", app.page)).dim()],
+    ]
+    .concat();
+    frame.render_widget(Line::from(spans), area);
+}
+
+fn draw_results(frame: &mut Frame, app: &mut App, area: Rect) {
+    let assets = app.result.as_ref().map_or(&[][..], |r| &r.assets);
+    let items: Vec<ListItem> = app
+        .visible
+        .iter()
+        .map(|&i| {
+            let asset = &assets[i];
+            let mark = if app.is_marked(i) { "● " } else { "  " };
+            ListItem::new(Line::from(vec![
+                Span::styled(mark, Style::new().fg(Color::Green)),
+                Span::styled(
+                    format!("[{}] ", badge(asset.media_type)),
+                    Style::new().fg(Color::Cyan),
+                ),
+                Span::raw(asset.title.clone()),
+                Span::raw(format!(" {}", asset.provider)).dim(),
+            ]))
+        })
+        .collect();
+
+    let title = match &app.result {
+        _ if app.searching => " Results (searching...) ".to_string(),
+        Some(_) => format!(" Results ({}) ", app.visible.len()),
+        None => " Results ".to_string(),
+    };
+    let list = List::new(items)
+        .block(focused_block(&title, app.focus == Focus::Results))
+        .highlight_style(Style::new().add_modifier(Modifier::REVERSED))
+        .highlight_symbol("› ");
+    frame.render_stateful_widget(list, area, &mut app.list);
+}
+
+fn draw_detail(frame: &mut Frame, app: &App, area: Rect) {
+    let block = Block::bordered().title(" Details ");
+    let Some(asset) = app.selected() else {
+        frame.render_widget(block, area);
+        return;
+    };
+    let detail = Paragraph::new(detail_lines(asset))
+        .block(block)
+        .wrap(Wrap { trim: false });
+    frame.render_widget(detail, area);
+}
+
+fn detail_lines(asset: &MediaAsset) -> Vec<Line<'_>> {
+    let field = |name: &'static str, value: String| {
+        Line::from(vec![
+            Span::raw(format!("{name:<9}")).dim(),
+            Span::raw(value),
+        ])
+    };
+
+    let mut lines = vec![
+        Line::from(asset.title.as_str()).bold(),
+        Line::default(),
+        field("id", format!("{}:{}", asset.provider, asset.id)),
+        field("type", asset.media_type.as_str().to_string()),
+        field("license", asset.license.as_str().to_string()).green(),
+    ];
+    if let Some(author) = asset.author.as_deref().filter(|a| !a.is_empty()) {
+        lines.push(field("author", author.to_string()));
+    }
+    if let (Some(w), Some(h)) = (asset.width, asset.height) {
+        lines.push(field("size", format!("{w}x{h}")));
+    }
+    if let Some(secs) = asset.duration_secs {
+        lines.push(field("duration", format!("{}:{:02}", secs / 60, secs % 60)));
+    }
+    if let Some(bytes) = asset.file_size {
+        lines.push(field("file", format_bytes(bytes)));
+    }
+    if let Some(mime) = &asset.mime_type {
+        lines.push(field("mime", mime.clone()));
+    }
+    if !asset.tags.is_empty() {
+        lines.push(field("tags", asset.tags.join(", ")));
+    }
+    lines.push(field("source", asset.source_url.clone()));
+    lines
+}
+
+fn draw_downloads(frame: &mut Frame, app: &App, area: Rect) {
+    if area.height == 0 {
+        return;
+    }
+    let block = Block::bordered().title(" Downloads ");
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    // Show the most recent rows
+    let skip = app.downloads.len().saturating_sub(MAX_DOWNLOAD_ROWS);
+    let rows = Layout::vertical(vec![Constraint::Length(1); MAX_DOWNLOAD_ROWS]).split(inner);
+    for (row, area) in app.downloads.iter().skip(skip).zip(rows.iter()) {
+        frame.render_widget(download_gauge(row), *area);
+    }
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn download_gauge(row: &DownloadRow) -> LineGauge<'_> {
+    let (ratio, label, color) = match &row.state {
+        DownloadState::Queued => (0.0, "queued".to_string(), Color::DarkGray),
+        DownloadState::Active if row.total > 0 => (
+            (row.received as f64 / row.total as f64).min(1.0),
+            format!(
+                "{} / {}",
+                format_bytes(row.received),
+                format_bytes(row.total)
+            ),
+            Color::Cyan,
+        ),
+        DownloadState::Active => (0.0, format_bytes(row.received), Color::Cyan),
+        DownloadState::Retrying(attempt) => (0.0, format!("retry {attempt}"), Color::Yellow),
+        DownloadState::Done(path) => (1.0, path.display().to_string(), Color::Green),
+        DownloadState::Skipped(reason) => (1.0, format!("skipped: {reason}"), Color::DarkGray),
+        DownloadState::Failed(error) => (1.0, format!("failed: {error}"), Color::Red),
+    };
+    let title: String = row.title.chars().take(30).collect();
+    LineGauge::default()
+        .ratio(ratio)
+        .label(format!("{title:<30} {label}"))
+        .filled_style(Style::new().fg(color))
+}
+
+fn draw_status(frame: &mut Frame, app: &App, area: Rect) {
+    let help = match app.focus {
+        Focus::Query => "Enter search · Tab results · Esc back · Ctrl-C quit",
+        Focus::Results => {
+            "j/k move · n/p page · Space mark · a all · d download · t/s/o/l filters · m mode · / query · q quit"
+        }
+    };
+    let lines = vec![Line::from(app.status.as_str()), Line::from(help).dim()];
+    frame.render_widget(Paragraph::new(lines), area);
+}
+
+fn badge(media_type: MediaType) -> &'static str {
+    match media_type {
+        MediaType::Image => "IMG",
+        MediaType::Video => "VID",
+        MediaType::Audio => "AUD",
+        MediaType::Gif => "GIF",
+        MediaType::Vector => "SVG",
+        MediaType::Document => "DOC",
+        MediaType::Model3D => "3D ",
+        MediaType::Data => "DAT",
+        MediaType::Code => "COD",
+        MediaType::Text => "TXT",
+    }
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{size:.1} {}", UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{License, SearchMode, SearchResult};
+    use ratatui::Terminal;
+    use ratatui::backend::TestBackend;
+
+    #[test]
+    fn test_draw() {
+        let mut app = App::new(vec!["openverse".into()], SearchMode::Quantity);
+        app.query = "fox".into();
+        app.focus = Focus::Results;
+        let mut result = SearchResult::new("fox");
+        result.assets.push(
+            MediaAsset::builder()
+                .id("42")
+                .provider("openverse")
+                .media_type(MediaType::Image)
+                .title("Red fox")
+                .download_url("https://example.com/fox.jpg")
+                .source_url("https://example.com/fox")
+                .license(License::Cc0)
+                .build(),
+        );
+        app.visible = vec![0];
+        app.list.select(Some(0));
+        app.result = Some(result);
+
+        let mut terminal = Terminal::new(TestBackend::new(100, 20)).unwrap();
+        terminal.draw(|frame| draw(frame, &mut app)).unwrap();
+
+        let screen: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(ratatui::buffer::Cell::symbol)
+            .collect();
+        assert!(screen.contains("[IMG] Red fox"));
+        assert!(screen.contains("openverse:42"));
+        assert!(screen.contains("license: any"));
+    }
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KB");
+        assert_eq!(format_bytes(5 * 1024 * 1024), "5.0 MB");
+    }
+}
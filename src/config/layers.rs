@@ -0,0 +1,839 @@
+//! Layered configuration from `dx.toml` files and the environment.
+//!
+//! Each setting is resolved from the highest-precedence layer that sets it:
+//!
+//! 1. Environment variables (including `.env`)
+//! 2. The active profile's `[profiles.<name>]` table, project file first
+//! 3. The project file, `./dx.toml`
+//! 4. The user file, `$XDG_CONFIG_HOME/dx/dx.toml` (or `DX_CONFIG`)
+//! 5. Built-in defaults
+//!
+//! The active profile is named by `DX_PROFILE` or a top-level `profile` key.
+//!
+//! ```toml
+//! profile = "hires"
+//!
+//! [search]
+//! mode = "quality"
+//!
+//! [download]
+//! dir = "./media"
+//! template = "{provider}/{media_type}"
+//!
+//! [providers.unsplash]
+//! enabled = false
+//!
+//! [providers.openverse]
+//! timeout_secs = 10
+//! count = 40
+//!
+//! # An RSS or Atom feed searched as a provider
+//! [providers.nasa-iotd]
+//! feed = "https://www.nasa.gov/feeds/iotd-feed/"
+//! license = "public-domain"
+//!
+//! [profiles.hires.download]
+//! dir = "./hires"
+//! ```
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use toml::{Table, Value};
+
+use super::{Config, ProviderSettings};
+use crate::error::{DxError, Result};
+use crate::types::SearchMode;
+
+/// File name of the configuration file in both locations.
+pub const CONFIG_FILE: &str = "dx.toml";
+
+/// Environment variable overriding the user configuration file path.
+const CONFIG_ENV: &str = "DX_CONFIG";
+
+/// Environment variable selecting the active profile.
+const PROFILE_ENV: &str = "DX_PROFILE";
+
+/// Top-level key selecting the active profile.
+const PROFILE_KEY: &str = "profile";
+
+/// Type of value a setting holds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    String,
+    Bool,
+    /// An integer between `min` and `max`, inclusive.
+    Integer { min: u32, max: u32 },
+    SearchMode,
+}
+
+const fn range(min: u32, max: u32) -> Kind {
+    Kind::Integer { min, max }
+}
+
+/// A known top-level setting.
+struct Setting {
+    key: &'static str,
+    env: &'static str,
+    default: Option<&'static str>,
+    kind: Kind,
+    secret: bool,
+}
+
+const fn setting(key: &'static str, env: &'static str, default: &'static str, kind: Kind) -> Setting {
+    Setting {
+        key,
+        env,
+        default: Some(default),
+        kind,
+        secret: false,
+    }
+}
+
+const fn api_key(key: &'static str, env: &'static str) -> Setting {
+    Setting {
+        key,
+        env,
+        default: None,
+        kind: Kind::String,
+        secret: true,
+    }
+}
+
+const SETTINGS: &[Setting] = &[
+    api_key("keys.unsplash", "UNSPLASH_ACCESS_KEY"),
+    api_key("keys.pexels", "PEXELS_API_KEY"),
+    api_key("keys.pixabay", "PIXABAY_API_KEY"),
+    api_key("keys.freesound", "FREESOUND_API_KEY"),
+    api_key("keys.giphy", "GIPHY_API_KEY"),
+    api_key("keys.flickr", "FLICKR_API_KEY"),
+    setting("download.dir", "DX_MEDIA_DIR", "./media", Kind::String),
+    Setting {
+        key: "download.template",
+        env: "DX_DOWNLOAD_TEMPLATE",
+        default: None,
+        kind: Kind::String,
+        secret: false,
+    },
+    setting("download.concurrent", "DX_CONCURRENT_DOWNLOADS", "5", range(1, 64)),
+    setting("http.timeout_secs", "DX_TIMEOUT_SECONDS", "300", range(1, 86_400)),
+    setting("http.retry_attempts", "DX_RETRY_ATTEMPTS", "3", range(0, 10)),
+    setting("http.respect_rate_limits", "DX_RESPECT_RATE_LIMITS", "true", Kind::Bool),
+    setting("cache.dir", "DX_CACHE_DIR", "./cache", Kind::String),
+    setting("cache.enabled", "DX_CACHE_ENABLED", "true", Kind::Bool),
+    setting("cache.ttl_hours", "DX_CACHE_TTL_HOURS", "24", range(0, 8760)),
+    setting("temp.dir", "DX_TEMP_DIR", "./temp", Kind::String),
+    setting("search.mode", "DX_SEARCH_MODE", "quantity", Kind::SearchMode),
+    Setting {
+        key: "rest.dir",
+        env: "DX_REST_PROVIDERS_DIR",
+        default: None,
+        kind: Kind::String,
+        secret: false,
+    },
+];
+
+/// Settings available under `[providers.<name>]`.
+const PROVIDER_SETTINGS: &[(&str, Kind)] = &[
+    ("enabled", Kind::Bool),
+    ("timeout_secs", range(1, 3600)),
+    ("count", range(1, 1000)),
+    ("feed", Kind::String),
+    ("license", Kind::String),
+];
+
+/// Where a configuration value came from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigOrigin {
+    /// Built-in default.
+    Default,
+    /// A `dx.toml` file.
+    File(PathBuf),
+    /// A profile table in a `dx.toml` file.
+    Profile {
+        /// Profile name.
+        name: String,
+        /// File the profile was read from.
+        path: PathBuf,
+    },
+    /// An environment variable.
+    Env(String),
+}
+
+impl fmt::Display for ConfigOrigin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Default => write!(f, "default"),
+            Self::File(path) => write!(f, "{}", path.display()),
+            Self::Profile { name, path } => write!(f, "profile '{}' in {}", name, path.display()),
+            Self::Env(var) => write!(f, "env {}", var),
+        }
+    }
+}
+
+/// A resolved setting.
+#[derive(Debug, Clone)]
+pub struct ConfigEntry {
+    /// Dotted key, e.g. `search.mode` or `providers.openverse.count`.
+    pub key: String,
+    /// Effective value, or `None` if unset.
+    pub value: Option<Value>,
+    /// Layer the value came from.
+    pub origin: ConfigOrigin,
+    /// Whether the value is a credential that should be masked when shown.
+    pub secret: bool,
+}
+
+impl ConfigEntry {
+    /// The value as shown to users: strings unquoted, secrets masked.
+    #[must_use]
+    pub fn display_value(&self) -> String {
+        match &self.value {
+            None => String::new(),
+            Some(_) if self.secret => "********".to_string(),
+            Some(Value::String(s)) => s.clone(),
+            Some(value) => value.to_string(),
+        }
+    }
+}
+
+/// A `dx.toml` file that was found and parsed.
+#[derive(Debug, Clone)]
+struct Layer {
+    path: PathBuf,
+    table: Table,
+}
+
+/// Configuration files and environment, ready to resolve settings from.
+#[derive(Debug, Clone)]
+pub struct ConfigLayers {
+    /// Parsed files, lowest precedence first.
+    files: Vec<Layer>,
+    env: HashMap<String, String>,
+    profile: Option<(String, ConfigOrigin)>,
+}
+
+impl ConfigLayers {
+    /// Read the user and project files and the process environment.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a file exists but can't be read or parsed, or if
+    /// the selected profile isn't defined.
+    pub fn discover() -> Result<Self> {
+        // Load .env file if present (ignore errors)
+        let _ = dotenvy::dotenv();
+
+        let env: HashMap<String, String> = std::env::vars().collect();
+        let mut paths = Vec::new();
+        if let Some(user) = user_path(&env) {
+            paths.push(user);
+        }
+        paths.push(project_path());
+
+        Self::from_files(&paths, env)
+    }
+
+    /// Build layers from explicit files (lowest precedence first) and
+    /// environment. Files that don't exist are skipped.
+    ///
+    /// # Errors
+    ///
+    /// See [`ConfigLayers::discover`].
+    pub fn from_files(paths: &[PathBuf], env: HashMap<String, String>) -> Result<Self> {
+        let mut files = Vec::new();
+        for path in paths {
+            if !path.is_file() {
+                continue;
+            }
+            let text = std::fs::read_to_string(path).map_err(|e| DxError::FileIo {
+                path: path.clone(),
+                message: format!("Failed to read config: {}", e),
+                source: Some(e),
+            })?;
+            let table: Table = text.parse().map_err(|e: toml::de::Error| DxError::Config {
+                message: format!("Invalid TOML in {}: {}", path.display(), e.message()),
+                source: Some(Box::new(e)),
+            })?;
+            files.push(Layer {
+                path: path.clone(),
+                table,
+            });
+        }
+
+        let profile = env
+            .get(PROFILE_ENV)
+            .filter(|name| !name.is_empty())
+            .map(|name| (name.clone(), ConfigOrigin::Env(PROFILE_ENV.to_string())))
+            .or_else(|| {
+                files.iter().rev().find_map(|layer| {
+                    let name = layer.table.get(PROFILE_KEY).and_then(Value::as_str)?;
+                    Some((name.to_string(), ConfigOrigin::File(layer.path.clone())))
+                })
+            });
+
+        let layers = Self {
+            files,
+            env,
+            profile,
+        };
+        if let Some((ref name, _)) = layers.profile {
+            if !layers.profiles().contains(name) {
+                return Err(DxError::config(format!("Unknown profile '{}'", name)));
+            }
+        }
+        Ok(layers)
+    }
+
+    /// Files that were found, lowest precedence first.
+    pub fn files(&self) -> impl Iterator<Item = &Path> {
+        self.files.iter().map(|layer| layer.path.as_path())
+    }
+
+    /// The active profile, if any.
+    #[must_use]
+    pub fn profile(&self) -> Option<&str> {
+        self.profile.as_ref().map(|(name, _)| name.as_str())
+    }
+
+    /// Names of all profiles defined in any file.
+    #[must_use]
+    pub fn profiles(&self) -> BTreeSet<String> {
+        self.files
+            .iter()
+            .filter_map(|layer| layer.table.get("profiles").and_then(Value::as_table))
+            .flat_map(|profiles| profiles.keys().cloned())
+            .collect()
+    }
+
+    /// Resolve a single setting by dotted key.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error for unknown keys and for values of the wrong type.
+    pub fn get(&self, key: &str) -> Result<ConfigEntry> {
+        if key == PROFILE_KEY {
+            let (value, origin) = match self.profile {
+                Some((ref name, ref origin)) => (Some(Value::String(name.clone())), origin.clone()),
+                None => (None, ConfigOrigin::Default),
+            };
+            return Ok(entry(key, value, origin, None));
+        }
+        let (kind, setting) = lookup(key)?;
+
+        let env = setting.and_then(|s| {
+            let raw = self.env.get(s.env).filter(|v| !v.is_empty())?;
+            let value = parse_env(raw, kind);
+            if value.is_none() {
+                tracing::warn!("Ignoring invalid value for {}: {}", s.env, raw);
+            }
+            Some((value?, ConfigOrigin::Env(s.env.to_string())))
+        });
+        if let Some((value, origin)) = env {
+            return Ok(entry(key, Some(value), origin, setting));
+        }
+
+        let profiles = self.profile().into_iter().flat_map(|name| {
+            self.files.iter().rev().filter_map(move |layer| {
+                let table = lookup_path(&layer.table, &format!("profiles.{}", name))?.as_table()?;
+                Some((
+                    table,
+                    ConfigOrigin::Profile {
+                        name: name.to_string(),
+                        path: layer.path.clone(),
+                    },
+                ))
+            })
+        });
+        let files = self
+            .files
+            .iter()
+            .rev()
+            .map(|layer| (&layer.table, ConfigOrigin::File(layer.path.clone())));
+
+        for (table, origin) in profiles.chain(files) {
+            if let Some(value) = lookup_path(table, key) {
+                check_kind(key, value, kind, &origin)?;
+                return Ok(entry(key, Some(value.clone()), origin, setting));
+            }
+        }
+
+        let default = setting
+            .and_then(|s| s.default)
+            .and_then(|raw| parse_env(raw, kind));
+        Ok(entry(key, default, ConfigOrigin::Default, setting))
+    }
+
+    /// Resolve every known setting, plus each provider setting set anywhere.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error for values of the wrong type.
+    pub fn entries(&self) -> Result<Vec<ConfigEntry>> {
+        let mut entries = vec![self.get(PROFILE_KEY)?];
+        for setting in SETTINGS {
+            entries.push(self.get(setting.key)?);
+        }
+        for (name, fields) in self.provider_keys() {
+            for field in fields {
+                entries.push(self.get(&format!("providers.{}.{}", name, field))?);
+            }
+        }
+        Ok(entries)
+    }
+
+    /// Build the effective [`Config`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error for values of the wrong type.
+    pub fn config(&self) -> Result<Config> {
+        let string = |key: &str| -> Result<Option<String>> {
+            Ok(self.get(key)?.value.and_then(|v| v.as_str().map(String::from)))
+        };
+        let integer = |key: &str| -> Result<u64> {
+            let value = self.get(key)?.value.and_then(|v| v.as_integer()).unwrap_or(0);
+            Ok(u64::try_from(value).unwrap_or(0))
+        };
+        let boolean = |key: &str| -> Result<bool> {
+            Ok(self.get(key)?.value.and_then(|v| v.as_bool()).unwrap_or(false))
+        };
+
+        let mut providers = BTreeMap::new();
+        for (name, fields) in self.provider_keys() {
+            let mut settings = ProviderSettings::default();
+            for field in fields {
+                let key = format!("providers.{}.{}", name, field);
+                let Some(value) = self.get(&key)?.value else {
+                    continue;
+                };
+                match field.as_str() {
+                    "enabled" => settings.enabled = value.as_bool(),
+                    "timeout_secs" => {
+                        settings.timeout_secs = value.as_integer().and_then(|v| u64::try_from(v).ok());
+                    }
+                    "count" => settings.count = value.as_integer().and_then(|v| usize::try_from(v).ok()),
+                    "feed" => settings.feed = value.as_str().map(String::from),
+                    "license" => settings.license = value.as_str().map(String::from),
+                    _ => {}
+                }
+            }
+            providers.insert(name, settings);
+        }
+
+        let media_dir = PathBuf::from(string("download.dir")?.unwrap_or_default());
+        Ok(Config {
+            unsplash_api_key: string("keys.unsplash")?,
+            pexels_api_key: string("keys.pexels")?,
+            pixabay_api_key: string("keys.pixabay")?,
+            freesound_api_key: string("keys.freesound")?,
+            giphy_api_key: string("keys.giphy")?,
+            flickr_api_key: string("keys.flickr")?,
+
+            download_dir: media_dir.clone(),
+            media_dir,
+            cache_dir: PathBuf::from(string("cache.dir")?.unwrap_or_default()),
+            temp_dir: PathBuf::from(string("temp.dir")?.unwrap_or_default()),
+            download_template: string("download.template")?,
+
+            // Both are range-checked when resolved, so the conversions can't fail
+            concurrent_downloads: usize::try_from(integer("download.concurrent")?).unwrap_or(1),
+            retry_attempts: u32::try_from(integer("http.retry_attempts")?).unwrap_or(0),
+            timeout_secs: integer("http.timeout_secs")?,
+            respect_rate_limits: boolean("http.respect_rate_limits")?,
+
+            cache_enabled: boolean("cache.enabled")?,
+            cache_ttl_hours: integer("cache.ttl_hours")?,
+
+            rest_providers_dir: string("rest.dir")?.map(PathBuf::from).or_else(|| {
+                user_path(&self.env)
+                    .and_then(|path| path.parent().map(|dir| dir.join("providers")))
+            }),
+
+            search_mode: string("search.mode")?
+                .and_then(|mode| SearchMode::from_str(&mode).ok())
+                .unwrap_or_default(),
+            providers,
+            profile: self.profile().map(String::from),
+        })
+    }
+
+    /// Provider names and the fields set for each, across files and the
+    /// active profile.
+    fn provider_keys(&self) -> BTreeMap<String, BTreeSet<String>> {
+        let mut tables: Vec<&Table> = self.files.iter().map(|layer| &layer.table).collect();
+        if let Some(name) = self.profile() {
+            tables.extend(
+                self.files
+                    .iter()
+                    .filter_map(|layer| lookup_path(&layer.table, &format!("profiles.{}", name)))
+                    .filter_map(Value::as_table),
+            );
+        }
+
+        let mut keys: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+        for providers in tables.into_iter().filter_map(|t| t.get("providers").and_then(Value::as_table)) {
+            for (name, fields) in providers {
+                if let Some(fields) = fields.as_table() {
+                    keys.entry(name.clone())
+                        .or_default()
+                        .extend(fields.keys().filter(|f| provider_kind(f).is_some()).cloned());
+                }
+            }
+        }
+        keys
+    }
+}
+
+/// The user configuration file: `DX_CONFIG`, or `dx/dx.toml` in the
+/// platform config directory (`$XDG_CONFIG_HOME` on Linux).
+#[must_use]
+pub fn user_config_path() -> Option<PathBuf> {
+    let env: HashMap<String, String> = std::env::vars().collect();
+    user_path(&env)
+}
+
+fn user_path(env: &HashMap<String, String>) -> Option<PathBuf> {
+    if let Some(path) = env.get(CONFIG_ENV).filter(|p| !p.is_empty()) {
+        return Some(PathBuf::from(path));
+    }
+    directories::BaseDirs::new().map(|dirs| dirs.config_dir().join("dx").join(CONFIG_FILE))
+}
+
+/// The project configuration file, `dx.toml` in the current directory.
+#[must_use]
+pub fn project_path() -> PathBuf {
+    PathBuf::from(CONFIG_FILE)
+}
+
+/// Set a value in a configuration file, creating the file if needed.
+///
+/// With a `profile`, the value is written under `[profiles.<profile>]`.
+/// The `profile` key itself selects the active profile.
+/// The rest of the file, including comments, is left as it was.
+///
+/// # Errors
+///
+/// Returns an error for unknown keys, values that don't parse as the
+/// setting's type, or if the file can't be read or written.
+pub fn set_value(path: &Path, key: &str, raw: &str, profile: Option<&str>) -> Result<()> {
+    let kind = match key {
+        PROFILE_KEY if profile.is_some() => {
+            return Err(DxError::config("A profile can't select another profile"));
+        }
+        PROFILE_KEY => Kind::String,
+        _ => lookup(key)?.0,
+    };
+    let value = parse_strict(raw, kind).ok_or_else(|| {
+        DxError::config(format!("Invalid value for {}: {} (expected {})", key, raw, expected(kind)))
+    })?;
+
+    let text = match std::fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(e) => {
+            return Err(DxError::FileIo {
+                path: path.to_path_buf(),
+                message: format!("Failed to read config: {}", e),
+                source: Some(e),
+            });
+        }
+    };
+    let mut doc: toml_edit::DocumentMut = text.parse().map_err(|e: toml_edit::TomlError| {
+        DxError::Config {
+            message: format!("Invalid TOML in {}: {}", path.display(), e.message()),
+            source: Some(Box::new(e)),
+        }
+    })?;
+
+    let full_key = match profile {
+        Some(name) => format!("profiles.{}.{}", name, key),
+        None => key.to_string(),
+    };
+    let (parents, last) = full_key.rsplit_once('.').map_or((None, full_key.as_str()), |(p, l)| (Some(p), l));
+
+    let mut table = doc.as_table_mut();
+    for part in parents.into_iter().flat_map(|p| p.split('.')) {
+        let item = table
+            .entry(part)
+            .or_insert_with(|| {
+                let mut new = toml_edit::Table::new();
+                new.set_implicit(true);
+                toml_edit::Item::Table(new)
+            });
+        table = item
+            .as_table_mut()
+            .ok_or_else(|| DxError::config(format!("{} in {} is not a table", part, path.display())))?;
+    }
+    table.insert(last, toml_edit::value(to_edit_value(&value)));
+
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent).map_err(|e| DxError::FileIo {
+            path: parent.to_path_buf(),
+            message: format!("Failed to create directory: {}", e),
+            source: Some(e),
+        })?;
+    }
+    std::fs::write(path, doc.to_string()).map_err(|e| DxError::FileIo {
+        path: path.to_path_buf(),
+        message: format!("Failed to write config: {}", e),
+        source: Some(e),
+    })
+}
+
+fn to_edit_value(value: &Value) -> toml_edit::Value {
+    match value {
+        Value::Boolean(b) => (*b).into(),
+        Value::Integer(i) => (*i).into(),
+        other => other.as_str().unwrap_or_default().into(),
+    }
+}
+
+/// Find the type (and top-level setting, if any) for a dotted key.
+fn lookup(key: &str) -> Result<(Kind, Option<&'static Setting>)> {
+    if let Some(setting) = SETTINGS.iter().find(|s| s.key == key) {
+        return Ok((setting.kind, Some(setting)));
+    }
+    if let Some(rest) = key.strip_prefix("providers.") {
+        if let Some((name, field)) = rest.split_once('.') {
+            if let Some(kind) = provider_kind(field).filter(|_| !name.is_empty()) {
+                return Ok((kind, None));
+            }
+        }
+    }
+    Err(DxError::config(format!("Unknown config key '{}'", key)))
+}
+
+fn provider_kind(field: &str) -> Option<Kind> {
+    PROVIDER_SETTINGS
+        .iter()
+        .find(|(name, _)| *name == field)
+        .map(|(_, kind)| *kind)
+}
+
+fn entry(key: &str, value: Option<Value>, origin: ConfigOrigin, setting: Option<&Setting>) -> ConfigEntry {
+    ConfigEntry {
+        key: key.to_string(),
+        value,
+        origin,
+        secret: setting.is_some_and(|s| s.secret),
+    }
+}
+
+fn lookup_path<'a>(table: &'a Table, key: &str) -> Option<&'a Value> {
+    let (parents, last) = key.rsplit_once('.').map_or((None, key), |(p, l)| (Some(p), l));
+    let mut table = table;
+    for part in parents.into_iter().flat_map(|p| p.split('.')) {
+        table = table.get(part)?.as_table()?;
+    }
+    table.get(last)
+}
+
+fn check_kind(key: &str, value: &Value, kind: Kind, origin: &ConfigOrigin) -> Result<()> {
+    let ok = match kind {
+        Kind::String => value.is_str(),
+        Kind::Bool => value.is_bool(),
+        Kind::Integer { min, max } => value
+            .as_integer()
+            .is_some_and(|i| (i64::from(min)..=i64::from(max)).contains(&i)),
+        Kind::SearchMode => value.as_str().is_some_and(|s| SearchMode::from_str(s).is_ok()),
+    };
+    if ok {
+        return Ok(());
+    }
+    Err(DxError::config(format!("{} ({}) must be {}", key, origin, expected(kind))))
+}
+
+/// Description of the values a setting accepts, for error messages.
+fn expected(kind: Kind) -> String {
+    match kind {
+        Kind::String => "a string".to_string(),
+        Kind::Bool => "true or false".to_string(),
+        Kind::Integer { min, max } => format!("an integer from {} to {}", min, max),
+        Kind::SearchMode => "\"quantity\" or \"quality\"".to_string(),
+    }
+}
+
+/// Parse an environment value, as leniently as `Config::load` always has.
+fn parse_env(raw: &str, kind: Kind) -> Option<Value> {
+    match kind {
+        Kind::Bool => Some(Value::Boolean(matches!(
+            raw.to_lowercase().as_str(),
+            "true" | "1" | "yes"
+        ))),
+        _ => parse_strict(raw, kind),
+    }
+}
+
+/// Parse a value given on the command line.
+fn parse_strict(raw: &str, kind: Kind) -> Option<Value> {
+    match kind {
+        Kind::String => Some(Value::String(raw.to_string())),
+        Kind::Bool => match raw.to_lowercase().as_str() {
+            "true" | "1" | "yes" => Some(Value::Boolean(true)),
+            "false" | "0" | "no" => Some(Value::Boolean(false)),
+            _ => None,
+        },
+        Kind::Integer { min, max } => raw
+            .parse::<u32>()
+            .ok()
+            .filter(|i| (min..=max).contains(i))
+            .map(|i| Value::Integer(i64::from(i))),
+        Kind::SearchMode => SearchMode::from_str(raw)
+            .ok()
+            .map(|mode| Value::String(mode.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write(dir: &TempDir, name: &str, text: &str) -> PathBuf {
+        let path = dir.path().join(name);
+        std::fs::write(&path, text).unwrap();
+        path
+    }
+
+    fn env(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs.iter().map(|(k, v)| ((*k).to_string(), (*v).to_string())).collect()
+    }
+
+    #[test]
+    fn test_layer_precedence() {
+        let dir = TempDir::new().unwrap();
+        let user = write(
+            &dir,
+            "user.toml",
+            "[search]\nmode = \"quality\"\n[http]\ntimeout_secs = 60\nretry_attempts = 7\n",
+        );
+        let project = write(&dir, "project.toml", "[http]\ntimeout_secs = 30\n");
+
+        let layers = ConfigLayers::from_files(
+            &[user.clone(), project.clone()],
+            env(&[("DX_RETRY_ATTEMPTS", "1")]),
+        )
+        .unwrap();
+
+        let timeout = layers.get("http.timeout_secs").unwrap();
+        assert_eq!(timeout.value, Some(Value::Integer(30)));
+        assert_eq!(timeout.origin, ConfigOrigin::File(project));
+
+        let mode = layers.get("search.mode").unwrap();
+        assert_eq!(mode.origin, ConfigOrigin::File(user));
+
+        let retries = layers.get("http.retry_attempts").unwrap();
+        assert_eq!(retries.origin, ConfigOrigin::Env("DX_RETRY_ATTEMPTS".to_string()));
+
+        let ttl = layers.get("cache.ttl_hours").unwrap();
+        assert_eq!(ttl.origin, ConfigOrigin::Default);
+
+        let config = layers.config().unwrap();
+        assert_eq!(config.timeout_secs, 30);
+        assert_eq!(config.retry_attempts, 1);
+        assert_eq!(config.search_mode, SearchMode::Quality);
+        assert_eq!(config.cache_ttl_hours, 24);
+    }
+
+    #[test]
+    fn test_profiles_and_providers() {
+        let dir = TempDir::new().unwrap();
+        let path = write(
+            &dir,
+            "dx.toml",
+            r#"
+profile = "fast"
+
+[providers.openverse]
+count = 40
+timeout_secs = 10
+
+[profiles.fast.providers.openverse]
+timeout_secs = 3
+
+[profiles.fast.providers.unsplash]
+enabled = false
+
+[providers.birds]
+feed = "https://birds.example.com/rss"
+
+[profiles.slow.search]
+mode = "quality"
+"#,
+        );
+
+        let layers = ConfigLayers::from_files(std::slice::from_ref(&path), HashMap::new()).unwrap();
+        assert_eq!(layers.profile(), Some("fast"));
+        let config = layers.config().unwrap();
+        let openverse = config.provider("openverse");
+        assert_eq!(openverse.count, Some(40));
+        assert_eq!(openverse.timeout_secs, Some(3));
+        assert!(!config.is_provider_enabled("unsplash"));
+        assert!(config.is_provider_enabled("nasa"));
+        assert_eq!(config.provider("birds").feed.as_deref(), Some("https://birds.example.com/rss"));
+        assert_eq!(config.search_mode, SearchMode::Quantity);
+
+        let layers =
+            ConfigLayers::from_files(std::slice::from_ref(&path), env(&[("DX_PROFILE", "slow")])).unwrap();
+        assert_eq!(layers.config().unwrap().search_mode, SearchMode::Quality);
+        assert!(layers.config().unwrap().is_provider_enabled("unsplash"));
+
+        assert!(ConfigLayers::from_files(&[path], env(&[("DX_PROFILE", "nope")])).is_err());
+    }
+
+    #[test]
+    fn test_invalid_values() {
+        let dir = TempDir::new().unwrap();
+        let path = write(&dir, "dx.toml", "[cache]\nttl_hours = \"soon\"\n");
+        let layers = ConfigLayers::from_files(&[path], HashMap::new()).unwrap();
+        assert!(layers.get("cache.ttl_hours").is_err());
+        assert!(layers.get("cache.nonsense").is_err());
+        assert!(layers.get("providers.openverse.count").is_ok());
+    }
+
+    #[test]
+    fn test_integer_ranges() {
+        let dir = TempDir::new().unwrap();
+        let path = write(
+            &dir,
+            "dx.toml",
+            "[download]\nconcurrent = 0\n[http]\nretry_attempts = 4294967296\n",
+        );
+        let layers = ConfigLayers::from_files(&[path.clone()], HashMap::new()).unwrap();
+        let err = layers.get("download.concurrent").unwrap_err();
+        assert!(err.to_string().contains("from 1 to 64"), "{err}");
+        assert!(layers.get("http.retry_attempts").is_err());
+        assert!(layers.config().is_err());
+
+        // Out-of-range environment values are ignored like malformed ones
+        let layers =
+            ConfigLayers::from_files(&[], env(&[("DX_CONCURRENT_DOWNLOADS", "0")])).unwrap();
+        assert_eq!(layers.config().unwrap().concurrent_downloads, 5);
+
+        assert!(set_value(&path, "http.retry_attempts", "1000", None).is_err());
+    }
+
+    #[test]
+    fn test_set_value_preserves_file() {
+        let dir = TempDir::new().unwrap();
+        let path = write(&dir, "dx.toml", "# my settings\n[search]\nmode = \"quality\"\n");
+
+        set_value(&path, "http.timeout_secs", "45", None).unwrap();
+        set_value(&path, "providers.nasa.enabled", "no", None).unwrap();
+        set_value(&path, "search.mode", "quantity", Some("quick")).unwrap();
+        assert!(set_value(&path, "http.timeout_secs", "soon", None).is_err());
+        assert!(set_value(&path, "bogus", "1", None).is_err());
+
+        let text = std::fs::read_to_string(&path).unwrap();
+        assert!(text.starts_with("# my settings\n"));
+
+        let layers = ConfigLayers::from_files(&[path], env(&[("DX_PROFILE", "quick")])).unwrap();
+        let config = layers.config().unwrap();
+        assert_eq!(config.timeout_secs, 45);
+        assert_eq!(config.search_mode, SearchMode::Quantity);
+        assert!(!config.is_provider_enabled("nasa"));
+    }
+}
@@ -1,10 +1,40 @@
 //! Configuration management for DX Media.
 //!
-//! Loads configuration from environment variables and .env files.
+//! Loads configuration from `dx.toml` files, environment variables and
+//! `.env` files. See [`layers`] for the precedence rules.
+
+pub mod layers;
+
+pub use layers::{ConfigEntry, ConfigLayers, ConfigOrigin};
 
 use crate::error::Result;
-use std::env;
+use crate::types::SearchMode;
+use std::collections::BTreeMap;
 use std::path::PathBuf;
+use std::time::Duration;
+
+/// Per-provider overrides from a `[providers.<name>]` table.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProviderSettings {
+    /// Whether the provider takes part in searches (default: enabled).
+    pub enabled: Option<bool>,
+    /// How long to wait for the provider's search results.
+    pub timeout_secs: Option<u64>,
+    /// Number of results to request from the provider.
+    pub count: Option<usize>,
+    /// RSS or Atom feed URL; registers the name as a feed provider.
+    pub feed: Option<String>,
+    /// License of a feed's assets when its items don't state one.
+    pub license: Option<String>,
+}
+
+impl ProviderSettings {
+    /// The timeout override, if any.
+    #[must_use]
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout_secs.map(Duration::from_secs)
+    }
+}
 
 /// Application configuration.
 #[derive(Debug, Clone)]
@@ -36,6 +66,8 @@ pub struct Config {
     pub temp_dir: PathBuf,
     /// Alias for media_dir, for convenience.
     pub download_dir: PathBuf,
+    /// Subdirectory template for downloads, e.g. `{provider}/{media_type}`.
+    pub download_template: Option<String>,
 
     // ─────────────────────────────────────────────────────────────
     // Download Settings
@@ -56,47 +88,43 @@ pub struct Config {
     pub cache_enabled: bool,
     /// Cache time-to-live in hours.
     pub cache_ttl_hours: u64,
+
+    // ─────────────────────────────────────────────────────────────
+    // Search Settings
+    // ─────────────────────────────────────────────────────────────
+    /// Search mode used when none is given.
+    pub search_mode: SearchMode,
+    /// Per-provider overrides, keyed by provider name.
+    pub providers: BTreeMap<String, ProviderSettings>,
+    /// Name of the active profile, if any.
+    pub profile: Option<String>,
+    /// Directory of declarative REST provider definitions (`rest.dir`,
+    /// by default `providers/` next to the user config file).
+    pub rest_providers_dir: Option<PathBuf>,
 }
 
 impl Config {
-    /// Load configuration from environment variables.
+    /// Load configuration from `dx.toml` files and environment variables.
     ///
     /// This will also load variables from a `.env` file if present.
     ///
     /// # Errors
     ///
-    /// Returns an error if critical configuration is invalid.
+    /// Returns an error if a configuration file is invalid.
     pub fn load() -> Result<Self> {
-        // Load .env file if present (ignore errors)
-        let _ = dotenvy::dotenv();
-
-        let media_dir = Self::get_path("DX_MEDIA_DIR", "./media");
-
-        Ok(Self {
-            // API Keys (all optional - graceful degradation when missing)
-            unsplash_api_key: Self::get_optional_string("UNSPLASH_ACCESS_KEY"),
-            pexels_api_key: Self::get_optional_string("PEXELS_API_KEY"),
-            pixabay_api_key: Self::get_optional_string("PIXABAY_API_KEY"),
-            freesound_api_key: Self::get_optional_string("FREESOUND_API_KEY"),
-            giphy_api_key: Self::get_optional_string("GIPHY_API_KEY"),
-            flickr_api_key: Self::get_optional_string("FLICKR_API_KEY"),
+        ConfigLayers::discover()?.config()
+    }
 
-            // Directories
-            download_dir: media_dir.clone(),
-            media_dir,
-            cache_dir: Self::get_path("DX_CACHE_DIR", "./cache"),
-            temp_dir: Self::get_path("DX_TEMP_DIR", "./temp"),
-
-            // Download settings
-            concurrent_downloads: Self::get_usize("DX_CONCURRENT_DOWNLOADS", 5),
-            retry_attempts: Self::get_u32("DX_RETRY_ATTEMPTS", 3),
-            timeout_secs: Self::get_u64("DX_TIMEOUT_SECONDS", 300),
-            respect_rate_limits: Self::get_bool("DX_RESPECT_RATE_LIMITS", true),
-
-            // Cache settings
-            cache_enabled: Self::get_bool("DX_CACHE_ENABLED", true),
-            cache_ttl_hours: Self::get_u64("DX_CACHE_TTL_HOURS", 24),
-        })
+    /// The built-in defaults, without reading any file or the environment.
+    ///
+    /// # Panics
+    ///
+    /// Never: the built-in defaults always resolve.
+    #[must_use]
+    pub fn builtin() -> Self {
+        ConfigLayers::from_files(&[], std::collections::HashMap::new())
+            .and_then(|layers| layers.config())
+            .expect("built-in defaults are valid")
     }
 
     /// Create a default configuration for testing.
@@ -116,60 +144,45 @@ impl Config {
             media_dir,
             cache_dir: PathBuf::from("./test_cache"),
             temp_dir: PathBuf::from("./test_temp"),
+            download_template: None,
             concurrent_downloads: 2,
             retry_attempts: 1,
             timeout_secs: 30,
             respect_rate_limits: true,
             cache_enabled: false,
             cache_ttl_hours: 1,
+            search_mode: SearchMode::default(),
+            providers: BTreeMap::new(),
+            profile: None,
+            rest_providers_dir: None,
         }
     }
 
-    // ─────────────────────────────────────────────────────────────
-    // Helper Methods
-    // ─────────────────────────────────────────────────────────────
-
-    fn get_path(key: &str, default: &str) -> PathBuf {
-        env::var(key)
-            .map(PathBuf::from)
-            .unwrap_or_else(|_| PathBuf::from(default))
-    }
-
-    fn get_usize(key: &str, default: usize) -> usize {
-        env::var(key)
-            .ok()
-            .and_then(|v| v.parse().ok())
-            .unwrap_or(default)
-    }
-
-    fn get_u32(key: &str, default: u32) -> u32 {
-        env::var(key)
-            .ok()
-            .and_then(|v| v.parse().ok())
-            .unwrap_or(default)
-    }
-
-    fn get_u64(key: &str, default: u64) -> u64 {
-        env::var(key)
-            .ok()
-            .and_then(|v| v.parse().ok())
-            .unwrap_or(default)
-    }
-
-    fn get_bool(key: &str, default: bool) -> bool {
-        env::var(key)
-            .ok()
-            .map(|v| matches!(v.to_lowercase().as_str(), "true" | "1" | "yes"))
-            .unwrap_or(default)
+    /// Overrides for a provider (empty if none are configured).
+    #[must_use]
+    pub fn provider(&self, name: &str) -> ProviderSettings {
+        self.providers.get(name).cloned().unwrap_or_default()
     }
 
-    fn get_optional_string(key: &str) -> Option<String> {
-        env::var(key).ok().filter(|s| !s.is_empty())
+    /// Whether a provider is enabled (providers are enabled unless configured otherwise).
+    #[must_use]
+    pub fn is_provider_enabled(&self, name: &str) -> bool {
+        self.providers
+            .get(name)
+            .and_then(|p| p.enabled)
+            .unwrap_or(true)
     }
 }
 
 impl Default for Config {
+    /// Load the configuration as [`Config::load`] does.
+    ///
+    /// If that fails, a warning is logged and the built-in defaults are used,
+    /// ignoring configuration files and environment variables.
     fn default() -> Self {
-        Self::load().unwrap_or_else(|_| Self::default_for_testing())
+        Self::load().unwrap_or_else(|e| {
+            tracing::warn!("{}; using the built-in defaults", e);
+            Self::builtin()
+        })
     }
 }
@@ -0,0 +1,522 @@
+//! Content type detection for downloaded files.
+//!
+//! Works out what a download actually is from its magic bytes, the
+//! `Content-Disposition` filename, the `Content-Type` header and the URL, in
+//! that order of trust.
+
+use crate::types::MediaType;
+
+/// MIME type used when nothing more specific is known.
+pub const OCTET_STREAM: &str = "application/octet-stream";
+
+/// Whether a MIME type says nothing about the content.
+///
+/// Besides the standard `application/octet-stream` this covers
+/// `binary/octet-stream`, the default type of objects uploaded to S3.
+fn is_generic(mime: &str) -> bool {
+    mime.is_empty() || mime == OCTET_STREAM || mime == "binary/octet-stream"
+}
+
+/// Known file extensions and their MIME types.
+const EXTENSIONS: &[(&str, &str)] = &[
+    // Images
+    ("jpg", "image/jpeg"),
+    ("jpeg", "image/jpeg"),
+    ("png", "image/png"),
+    ("gif", "image/gif"),
+    ("webp", "image/webp"),
+    ("avif", "image/avif"),
+    ("heic", "image/heic"),
+    ("bmp", "image/bmp"),
+    ("tiff", "image/tiff"),
+    ("tif", "image/tiff"),
+    ("svg", "image/svg+xml"),
+    ("ico", "image/x-icon"),
+    ("exr", "image/x-exr"),
+    ("hdr", "image/vnd.radiance"),
+    // Video
+    ("mp4", "video/mp4"),
+    ("m4v", "video/x-m4v"),
+    ("webm", "video/webm"),
+    ("mov", "video/quicktime"),
+    ("avi", "video/x-msvideo"),
+    ("mkv", "video/x-matroska"),
+    ("ogv", "video/ogg"),
+    // Audio
+    ("mp3", "audio/mpeg"),
+    ("wav", "audio/wav"),
+    ("flac", "audio/flac"),
+    ("ogg", "audio/ogg"),
+    ("oga", "audio/ogg"),
+    ("opus", "audio/opus"),
+    ("aac", "audio/aac"),
+    ("m4a", "audio/mp4"),
+    // Documents
+    ("pdf", "application/pdf"),
+    ("doc", "application/msword"),
+    (
+        "docx",
+        "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+    ),
+    ("epub", "application/epub+zip"),
+    // Data & text
+    ("json", "application/json"),
+    ("csv", "text/csv"),
+    ("xml", "application/xml"),
+    ("txt", "text/plain"),
+    ("md", "text/markdown"),
+    ("html", "text/html"),
+    ("htm", "text/html"),
+    // 3D & archives
+    ("glb", "model/gltf-binary"),
+    ("gltf", "model/gltf+json"),
+    ("obj", "model/obj"),
+    ("fbx", "application/octet-stream"),
+    ("blend", "application/x-blender"),
+    ("zip", "application/zip"),
+];
+
+/// Preferred extension for MIME types with more than one common extension.
+const PREFERRED_EXTENSIONS: &[(&str, &str)] = &[
+    ("image/jpeg", "jpg"),
+    ("image/pjpeg", "jpg"),
+    ("image/tiff", "tiff"),
+    ("audio/x-wav", "wav"),
+    ("audio/wave", "wav"),
+    ("audio/mp3", "mp3"),
+    ("audio/ogg", "ogg"),
+    ("video/ogg", "ogv"),
+    ("text/html", "html"),
+    ("text/xml", "xml"),
+    ("application/x-pdf", "pdf"),
+];
+
+/// Result of content detection for a downloaded file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DetectedType {
+    /// Best guess at the MIME type.
+    pub mime_type: String,
+    /// File extension to save the file with (without the dot).
+    pub extension: String,
+}
+
+/// Detect the type of a download.
+///
+/// `head` is the first bytes of the body, `content_type` and
+/// `content_disposition` are the raw response headers.
+#[must_use]
+pub fn detect(
+    head: &[u8],
+    content_type: Option<&str>,
+    content_disposition: Option<&str>,
+    url: &str,
+) -> Option<DetectedType> {
+    let header_mime = content_type.map(essence).filter(|m| !is_generic(m));
+    let disposition_ext = content_disposition
+        .and_then(filename_from_disposition)
+        .and_then(|name| known_extension(&name));
+
+    // Magic bytes win, then the server's suggested filename, then headers, then the URL
+    if let Some(mime) = sniff(head) {
+        // Container formats (MP4, Ogg, ZIP...) hold several file types, so a
+        // more specific hint from the filename or URL is kept when it fits
+        if is_container(mime) {
+            let hint = disposition_ext
+                .or_else(|| extension_from_url(url))
+                .and_then(|ext| Some((ext, mime_for_extension(ext)?)))
+                .filter(|(_, hint_mime)| fits_container(mime, hint_mime));
+            if let Some((ext, hint_mime)) = hint {
+                return Some(DetectedType {
+                    mime_type: hint_mime.to_string(),
+                    extension: ext.to_string(),
+                });
+            }
+        }
+
+        return Some(DetectedType {
+            mime_type: mime.to_string(),
+            extension: extension_for_mime(mime)?.to_string(),
+        });
+    }
+
+    if let Some(ext) = disposition_ext {
+        return Some(DetectedType {
+            mime_type: header_mime
+                .clone()
+                .unwrap_or_else(|| mime_for_extension(ext).unwrap_or(OCTET_STREAM).to_string()),
+            extension: ext.to_string(),
+        });
+    }
+
+    if let Some(mime) = header_mime {
+        if let Some(ext) = extension_for_mime(&mime) {
+            return Some(DetectedType {
+                mime_type: mime,
+                extension: ext.to_string(),
+            });
+        }
+    }
+
+    extension_from_url(url).map(|ext| DetectedType {
+        mime_type: mime_for_extension(ext).unwrap_or(OCTET_STREAM).to_string(),
+        extension: ext.to_string(),
+    })
+}
+
+/// Identify a file format from its leading bytes.
+#[must_use]
+pub fn sniff(head: &[u8]) -> Option<&'static str> {
+    let starts = |magic: &[u8]| head.starts_with(magic);
+    let at = |offset: usize, magic: &[u8]| {
+        head.get(offset..offset + magic.len())
+            .is_some_and(|bytes| bytes == magic)
+    };
+
+    let mime = if starts(b"\xFF\xD8\xFF") {
+        "image/jpeg"
+    } else if starts(b"\x89PNG\r\n\x1a\n") {
+        "image/png"
+    } else if starts(b"GIF87a") || starts(b"GIF89a") {
+        "image/gif"
+    } else if starts(b"RIFF") && at(8, b"WEBP") {
+        "image/webp"
+    } else if starts(b"RIFF") && at(8, b"WAVE") {
+        "audio/wav"
+    } else if starts(b"RIFF") && at(8, b"AVI ") {
+        "video/x-msvideo"
+    } else if starts(b"BM") && head.len() > 14 {
+        "image/bmp"
+    } else if starts(b"II*\0") || starts(b"MM\0*") {
+        "image/tiff"
+    } else if starts(b"v/1\x01") {
+        "image/x-exr"
+    } else if starts(b"#?RADIANCE") || starts(b"#?RGBE") {
+        "image/vnd.radiance"
+    } else if at(4, b"ftyp") {
+        match head.get(8..12) {
+            Some(b"avif" | b"avis") => "image/avif",
+            Some(b"heic" | b"heix" | b"mif1") => "image/heic",
+            Some(b"qt  ") => "video/quicktime",
+            Some(b"M4A " | b"M4B ") => "audio/mp4",
+            _ => "video/mp4",
+        }
+    } else if starts(b"\x1A\x45\xDF\xA3") {
+        "video/webm"
+    } else if starts(b"OggS") {
+        "audio/ogg"
+    } else if starts(b"fLaC") {
+        "audio/flac"
+    } else if starts(b"ID3") || starts(b"\xFF\xFB") || starts(b"\xFF\xF3") || starts(b"\xFF\xF2")
+    {
+        "audio/mpeg"
+    } else if starts(b"%PDF-") {
+        "application/pdf"
+    } else if starts(b"glTF") {
+        "model/gltf-binary"
+    } else if starts(b"PK\x03\x04") {
+        "application/zip"
+    } else {
+        return sniff_text(head);
+    };
+
+    Some(mime)
+}
+
+/// Recognise markup formats, which may start with whitespace or a BOM.
+fn sniff_text(head: &[u8]) -> Option<&'static str> {
+    let text = String::from_utf8_lossy(&head[..head.len().min(512)]).to_lowercase();
+    let text = text.trim_start_matches('\u{feff}').trim_start();
+
+    if text.starts_with("<!doctype html") || text.starts_with("<html") || text.starts_with("<head")
+    {
+        Some("text/html")
+    } else if text.starts_with("<svg")
+        || ((text.starts_with("<?xml") || text.starts_with("<!doctype svg"))
+            && text.contains("<svg"))
+    {
+        Some("image/svg+xml")
+    } else {
+        None
+    }
+}
+
+/// Check whether a MIME type is plausible for the asset's media type.
+///
+/// `expected_ext` is the extension the asset's URL suggests, if any. An HTML
+/// page is only accepted as a document or data file when that extension
+/// says HTML, so login and error pages aren't saved as PDFs or CSVs.
+///
+/// Only rejects clear mismatches: unknown or generic types are accepted.
+#[must_use]
+pub fn is_compatible(mime: &str, media_type: MediaType, expected_ext: Option<&str>) -> bool {
+    let mime = essence(mime);
+    if is_generic(&mime) {
+        return true;
+    }
+    let top = mime.split('/').next().unwrap_or_default();
+
+    match media_type {
+        // SVG is often served as plain XML
+        MediaType::Image | MediaType::Gif | MediaType::Vector => {
+            top == "image" || matches!(mime.as_str(), "text/xml" | "application/xml")
+        }
+        // Audio and video share container formats (MP4, WebM, Ogg)
+        MediaType::Video | MediaType::Audio => {
+            matches!(top, "video" | "audio") || mime == "application/ogg"
+        }
+        MediaType::Model3D => mime != "text/html",
+        MediaType::Document | MediaType::Data => {
+            mime != "text/html" || matches!(expected_ext, Some("html" | "htm"))
+        }
+        MediaType::Code | MediaType::Text => true,
+    }
+}
+
+/// Extract a lowercase known extension from a URL's last path segment.
+///
+/// Handles suffixes after the extension such as NASA's `image.jpg~orig`.
+#[must_use]
+pub fn extension_from_url(url: &str) -> Option<&'static str> {
+    let path = url::Url::parse(url)
+        .map(|u| u.path().to_string())
+        .unwrap_or_else(|_| url.split(['?', '#']).next().unwrap_or_default().to_string());
+    let segment = path.rsplit('/').next()?;
+    let (_, ext) = segment.rsplit_once('.')?;
+    let ext: String = ext
+        .chars()
+        .take_while(char::is_ascii_alphanumeric)
+        .collect();
+    known_extension(&ext)
+}
+
+/// MIME type for a known extension.
+#[must_use]
+pub fn mime_for_extension(ext: &str) -> Option<&'static str> {
+    let ext = ext.to_ascii_lowercase();
+    EXTENSIONS
+        .iter()
+        .find(|(e, _)| *e == ext)
+        .map(|(_, mime)| *mime)
+}
+
+/// Preferred extension for a MIME type.
+#[must_use]
+pub fn extension_for_mime(mime: &str) -> Option<&'static str> {
+    let mime = essence(mime);
+    if is_generic(&mime) {
+        return None;
+    }
+    PREFERRED_EXTENSIONS
+        .iter()
+        .find(|(m, _)| *m == mime)
+        .map(|(_, ext)| *ext)
+        .or_else(|| {
+            EXTENSIONS
+                .iter()
+                .find(|(_, m)| *m == mime)
+                .map(|(ext, _)| *ext)
+        })
+}
+
+/// Pull the filename out of a `Content-Disposition` header.
+///
+/// Prefers the RFC 5987 `filename*=` form over plain `filename=`.
+#[must_use]
+pub fn filename_from_disposition(header: &str) -> Option<String> {
+    let mut plain = None;
+    for part in header.split(';').map(str::trim) {
+        let Some((key, value)) = part.split_once('=') else {
+            continue;
+        };
+        match key.trim().to_ascii_lowercase().as_str() {
+            "filename*" => {
+                // e.g. UTF-8''photo%20one.jpg
+                let encoded = value.rsplit("''").next().unwrap_or(value);
+                let decoded = percent_decode(encoded.trim_matches('"'));
+                if !decoded.is_empty() {
+                    return Some(decoded);
+                }
+            }
+            "filename" => plain = Some(value.trim().trim_matches('"').to_string()),
+            _ => {}
+        }
+    }
+    plain.filter(|name| !name.is_empty())
+}
+
+/// Lowercased MIME type without parameters (`text/html; charset=utf-8` → `text/html`).
+fn essence(mime: &str) -> String {
+    mime.split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase()
+}
+
+/// Extension of a filename if it's one we know about.
+fn known_extension(name: &str) -> Option<&'static str> {
+    let ext = name.rsplit_once('.').map_or(name, |(_, ext)| ext);
+    let ext = ext.to_ascii_lowercase();
+    EXTENSIONS.iter().find(|(e, _)| *e == ext).map(|(e, _)| *e)
+}
+
+/// Decode `%XX` escapes, leaving malformed sequences untouched.
+fn percent_decode(input: &str) -> String {
+    let bytes = input.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes
+            .get(i + 1..i + 3)
+            .and_then(|h| std::str::from_utf8(h).ok())
+            .and_then(|h| u8::from_str_radix(h, 16).ok());
+        match (bytes[i], hex) {
+            (b'%', Some(byte)) => {
+                out.push(byte);
+                i += 3;
+            }
+            (byte, _) => {
+                out.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+/// Formats whose magic bytes are shared by several file types.
+fn is_container(mime: &str) -> bool {
+    matches!(
+        mime,
+        "video/mp4" | "audio/mp4" | "video/quicktime" | "video/webm" | "audio/ogg" | "application/zip"
+    )
+}
+
+/// Whether a filename hint describes something that can live in `container`.
+fn fits_container(container: &str, hint: &str) -> bool {
+    let is_av = |m: &str| m.starts_with("audio/") || m.starts_with("video/");
+    if container == "application/zip" {
+        hint.ends_with("+zip") || hint.contains("officedocument") || hint == "model/gltf+json"
+    } else {
+        is_av(hint)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sniff_magic_bytes() {
+        assert_eq!(sniff(b"\xFF\xD8\xFF\xE0\0\x10JFIF"), Some("image/jpeg"));
+        assert_eq!(sniff(b"\x89PNG\r\n\x1a\n\0\0"), Some("image/png"));
+        assert_eq!(sniff(b"RIFF\0\0\0\0WEBPVP8 "), Some("image/webp"));
+        assert_eq!(sniff(b"\0\0\0\x18ftypmp42"), Some("video/mp4"));
+        assert_eq!(sniff(b"%PDF-1.7"), Some("application/pdf"));
+        assert_eq!(sniff(b"  <!DOCTYPE html><html>"), Some("text/html"));
+        assert_eq!(sniff(b"<?xml version=\"1.0\"?><svg"), Some("image/svg+xml"));
+        assert_eq!(sniff(b"plain text"), None);
+    }
+
+    #[test]
+    fn test_extension_from_url() {
+        assert_eq!(
+            extension_from_url("https://images-assets.nasa.gov/image/a/a~orig.jpg"),
+            Some("jpg")
+        );
+        assert_eq!(
+            extension_from_url("https://example.com/photo.JPG~orig"),
+            Some("jpg")
+        );
+        assert_eq!(
+            extension_from_url("https://example.com/photo.png?w=400"),
+            Some("png")
+        );
+        assert_eq!(extension_from_url("https://example.com/api/v1/asset"), None);
+        assert_eq!(
+            extension_from_url("https://example.jpg.com/download"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_filename_from_disposition() {
+        assert_eq!(
+            filename_from_disposition("attachment; filename=\"sunset.png\""),
+            Some("sunset.png".to_string())
+        );
+        assert_eq!(
+            filename_from_disposition(
+                "attachment; filename=\"fallback.jpg\"; filename*=UTF-8''photo%20one.webp"
+            ),
+            Some("photo one.webp".to_string())
+        );
+        assert_eq!(filename_from_disposition("inline"), None);
+    }
+
+    #[test]
+    fn test_detect_prefers_magic_bytes() {
+        let detected = detect(
+            b"\x89PNG\r\n\x1a\n",
+            Some("image/jpeg"),
+            None,
+            "https://example.com/a.jpg",
+        )
+        .unwrap();
+        assert_eq!(detected.mime_type, "image/png");
+        assert_eq!(detected.extension, "png");
+
+        let detected = detect(
+            b"\0\0\0\x18ftypisom",
+            None,
+            Some("attachment; filename=clip.mov"),
+            "https://example.com/download",
+        )
+        .unwrap();
+        assert_eq!(detected.mime_type, "video/quicktime");
+        assert_eq!(detected.extension, "mov");
+    }
+
+    #[test]
+    fn test_detect_falls_back_to_headers() {
+        let detected = detect(
+            b"unknown",
+            Some("audio/mpeg; charset=binary"),
+            None,
+            "https://example.com/api/asset/1",
+        )
+        .unwrap();
+        assert_eq!(detected.mime_type, "audio/mpeg");
+        assert_eq!(detected.extension, "mp3");
+
+        assert!(detect(b"", None, None, "https://example.com/api").is_none());
+
+        // S3's default type is as uninformative as application/octet-stream
+        let detected = detect(
+            b"unknown",
+            Some("binary/octet-stream"),
+            None,
+            "https://bucket.s3.amazonaws.com/photo.webp",
+        )
+        .unwrap();
+        assert_eq!(detected.mime_type, "image/webp");
+    }
+
+    #[test]
+    fn test_is_compatible() {
+        assert!(is_compatible("image/png", MediaType::Image, None));
+        assert!(is_compatible("image/gif", MediaType::Image, Some("jpg")));
+        assert!(!is_compatible("text/html; charset=utf-8", MediaType::Image, None));
+        assert!(!is_compatible("text/html", MediaType::Video, None));
+        assert!(is_compatible("video/mp4", MediaType::Audio, None));
+        assert!(is_compatible(OCTET_STREAM, MediaType::Image, None));
+        assert!(is_compatible("binary/octet-stream", MediaType::Video, None));
+        assert!(is_compatible("text/xml", MediaType::Vector, None));
+        assert!(is_compatible("application/xml; charset=utf-8", MediaType::Image, None));
+        assert!(is_compatible("application/pdf", MediaType::Document, Some("pdf")));
+        assert!(!is_compatible("text/html", MediaType::Document, Some("pdf")));
+        assert!(!is_compatible("text/html", MediaType::Data, None));
+        assert!(is_compatible("text/html", MediaType::Document, Some("html")));
+        assert!(is_compatible("text/html", MediaType::Code, None));
+    }
+}
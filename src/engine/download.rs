@@ -1,15 +1,28 @@
 //! Download functionality for fetching media assets.
 
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use std::time::Duration;
+
+use futures::StreamExt;
+use reqwest::StatusCode;
+use reqwest::header::{
+    CONTENT_DISPOSITION, CONTENT_RANGE, CONTENT_TYPE, ETAG, HeaderName, LAST_MODIFIED,
+};
+use serde::{Deserialize, Serialize};
+use tokio::io::AsyncWriteExt;
+use tracing::{debug, warn};
 
 use crate::config::Config;
+use crate::engine::content_type;
 use crate::error::{DxError, Result};
-use crate::http::HttpClient;
+use crate::http::{HttpClient, registrable_domain};
 use crate::types::{MediaAsset, RateLimitConfig};
 
 /// Progress callback type for download progress updates.
+///
+/// Invoked with `(bytes_received, total_bytes)` after every chunk is written.
+/// `total_bytes` is `0` when the server did not send a `Content-Length`.
 pub type ProgressCallback = Arc<dyn Fn(u64, u64) + Send + Sync>;
 
 /// Downloader for fetching media assets.
@@ -17,25 +30,118 @@ pub type ProgressCallback = Arc<dyn Fn(u64, u64) + Send + Sync>;
 pub struct Downloader {
     client: HttpClient,
     download_dir: PathBuf,
+    template: Option<String>,
+    max_retries: u32,
+    site_limits: Arc<HashMap<String, RateLimitConfig>>,
 }
 
-impl Downloader {
-    /// Create a new downloader with default settings.
-    #[must_use]
-    pub fn new(config: &Config) -> Self {
-        // No rate limiting for downloads - providers handle this
-        let rate_limit = RateLimitConfig::unlimited();
-        let client = HttpClient::with_config(
-            rate_limit,
-            config.retry_attempts,
-            Duration::from_secs(config.timeout_secs),
-        )
-        .unwrap_or_default();
+/// A finished download and what was actually received.
+#[derive(Debug, Clone)]
+pub struct DownloadedFile {
+    /// Where the file was saved.
+    pub path: PathBuf,
+    /// Detected MIME type, if it could be determined.
+    pub mime_type: Option<String>,
+    /// Size of the file on disk in bytes.
+    pub file_size: u64,
+}
+
+impl DownloadedFile {
+    /// Record the received MIME type and size on the asset.
+    pub fn apply_to(&self, asset: &mut MediaAsset) {
+        if let Some(ref mime) = self.mime_type {
+            asset.mime_type = Some(mime.clone());
+        }
+        asset.file_size = Some(self.file_size);
+    }
+}
+
+/// Type-related headers of the response that completed a download.
+#[derive(Debug, Default)]
+struct ResponseMeta {
+    content_type: Option<String>,
+    content_disposition: Option<String>,
+}
+
+/// Outcome of a single download attempt.
+enum Attempt {
+    /// The whole body was written to the `.part` file.
+    Complete(ResponseMeta),
+    /// The transfer broke off midway and can be resumed.
+    Interrupted(DxError),
+}
 
+/// Sidecar stored next to a `.part` file so an interrupted download can be
+/// resumed with `Range`/`If-Range` against the same version of the resource.
+#[derive(Debug, Serialize, Deserialize)]
+struct PartialDownload {
+    url: String,
+    etag: Option<String>,
+    last_modified: Option<String>,
+}
+
+impl PartialDownload {
+    fn from_response(url: &str, response: &reqwest::Response) -> Self {
         Self {
+            url: url.to_string(),
+            etag: header_string(response, ETAG),
+            last_modified: header_string(response, LAST_MODIFIED),
+        }
+    }
+
+    async fn load(path: &Path) -> Option<Self> {
+        let bytes = tokio::fs::read(path).await.ok()?;
+        serde_json::from_slice(&bytes).ok()
+    }
+
+    async fn save(&self, path: &Path) -> Result<()> {
+        let json = serde_json::to_vec(self)?;
+        tokio::fs::write(path, json)
+            .await
+            .map_err(|e| DxError::FileIo {
+                path: path.to_path_buf(),
+                message: format!("Failed to write download sidecar: {}", e),
+                source: Some(e),
+            })
+    }
+}
+
+impl Downloader {
+    /// Create a new downloader with default settings.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP client cannot be created.
+    pub fn new(config: &Config) -> Result<Self> {
+        // Downloads set no limit of their own but still honor the host limits
+        // set by providers (see `with_site_limits`) and servers
+        let client = HttpClient::from_config(RateLimitConfig::unlimited(), config)?;
+
+        Ok(Self {
             client,
             download_dir: config.download_dir.clone(),
-        }
+            template: config.download_template.clone(),
+            max_retries: config.retry_attempts,
+            site_limits: Arc::default(),
+        })
+    }
+
+    /// Limit downloads from each site, keyed by registrable domain (see
+    /// [`ProviderRegistry::site_limits`](crate::providers::ProviderRegistry::site_limits)),
+    /// so a provider's CDN downloads count against its limit.
+    #[must_use]
+    pub fn with_site_limits(mut self, limits: HashMap<String, RateLimitConfig>) -> Self {
+        self.site_limits = Arc::new(limits);
+        self
+    }
+
+    /// Retry failed requests up to `retries` times.
+    ///
+    /// This doesn't affect resuming interrupted transfers.
+    #[must_use]
+    pub fn with_request_retries(mut self, retries: u32) -> Self {
+        self.client = self.client.with_max_retries(retries);
+        self
     }
 
     /// Set the download directory.
@@ -45,6 +151,17 @@ impl Downloader {
         self
     }
 
+    /// Save assets into subdirectories named by `template`.
+    ///
+    /// `{provider}`, `{media_type}`, `{license}`, `{author}` and `{date}`
+    /// (today, as `YYYY-MM-DD`) are replaced per asset, so
+    /// `{provider}/{media_type}` saves to e.g. `openverse/image/`.
+    #[must_use]
+    pub fn with_template(mut self, template: Option<String>) -> Self {
+        self.template = template;
+        self
+    }
+
     /// Download a media asset to the default download directory.
     pub async fn download(&self, asset: &MediaAsset) -> Result<PathBuf> {
         self.download_to(&self.download_dir, asset).await
@@ -52,8 +169,48 @@ impl Downloader {
 
     /// Download a media asset to a specific directory.
     pub async fn download_to(&self, dir: &Path, asset: &MediaAsset) -> Result<PathBuf> {
-        let filename = self.generate_filename(asset);
-        let filepath = dir.join(&filename);
+        self.download_to_with_progress(dir, asset, None).await
+    }
+
+    /// Download a media asset with progress callback.
+    pub async fn download_with_progress(
+        &self,
+        asset: &MediaAsset,
+        on_progress: ProgressCallback,
+    ) -> Result<PathBuf> {
+        self.download_to_with_progress(&self.download_dir, asset, Some(on_progress))
+            .await
+    }
+
+    /// Download a media asset to a specific directory, reporting progress.
+    ///
+    /// The body is streamed chunk by chunk into a `.part` file next to the
+    /// target and renamed into place once the transfer completes, so a
+    /// partially written file never shows up under the final name. If a
+    /// previous attempt left a `.part` file behind, the download resumes from
+    /// where it stopped.
+    pub async fn download_to_with_progress(
+        &self,
+        dir: &Path,
+        asset: &MediaAsset,
+        on_progress: Option<ProgressCallback>,
+    ) -> Result<PathBuf> {
+        self.fetch(dir, asset, on_progress).await.map(|file| file.path)
+    }
+
+    /// Download a media asset and report what was actually received.
+    ///
+    /// The file type is detected from the magic bytes, `Content-Disposition`
+    /// and `Content-Type`, and the file is saved with the matching extension.
+    /// A body that clearly doesn't match the asset's media type (such as an
+    /// HTML error page for an image) is rejected with [`DxError::Download`].
+    pub async fn fetch(
+        &self,
+        dir: &Path,
+        asset: &MediaAsset,
+        on_progress: Option<ProgressCallback>,
+    ) -> Result<DownloadedFile> {
+        let filepath = self.target_path(dir, asset);
 
         // Ensure directory exists
         if let Some(parent) = filepath.parent() {
@@ -67,49 +224,386 @@ impl Downloader {
         }
 
         // Download the file
-        self.download_file(&asset.download_url, &filepath).await?;
+        self.download_file(asset, &filepath, on_progress.as_ref())
+            .await
+    }
 
-        Ok(filepath)
+    /// Download a file from URL to a path.
+    ///
+    /// Interrupted transfers are resumed from the `.part` file up to the
+    /// configured number of retries. The `.part` file and its sidecar are kept
+    /// on failure so a later call can pick up where this one stopped.
+    async fn download_file(
+        &self,
+        asset: &MediaAsset,
+        path: &Path,
+        on_progress: Option<&ProgressCallback>,
+    ) -> Result<DownloadedFile> {
+        let url = asset.download_url.as_str();
+        let part_path = Self::part_path(path);
+        let sidecar_path = Self::sidecar_path(path);
+
+        let mut attempt = 0;
+        let meta = loop {
+            match self
+                .attempt_download(asset, &part_path, &sidecar_path, on_progress)
+                .await?
+            {
+                Attempt::Complete(meta) => break meta,
+                Attempt::Interrupted(e) if attempt < self.max_retries => {
+                    attempt += 1;
+                    warn!(
+                        "Download of {} interrupted ({}), resuming (attempt {}/{})",
+                        url, e, attempt, self.max_retries
+                    );
+                }
+                Attempt::Interrupted(e) => return Err(e),
+            }
+        };
+
+        // Work out what we actually received
+        let head = Self::read_head(&part_path).await;
+        let detected = content_type::detect(
+            &head,
+            meta.content_type.as_deref(),
+            meta.content_disposition.as_deref(),
+            url,
+        );
+
+        if let Some(ref detected) = detected {
+            let expected = content_type::extension_from_url(url);
+            if !content_type::is_compatible(&detected.mime_type, asset.media_type, expected) {
+                let _ = tokio::fs::remove_file(&part_path).await;
+                let _ = tokio::fs::remove_file(&sidecar_path).await;
+                return Err(Self::mismatch_error(asset, &detected.mime_type));
+            }
+        }
+
+        let final_path = Self::saved_path(path, detected.as_ref().map(|d| d.extension.as_str()));
+
+        tokio::fs::rename(&part_path, &final_path)
+            .await
+            .map_err(|e| DxError::FileIo {
+                path: final_path.clone(),
+                message: format!("Failed to move completed download into place: {}", e),
+                source: Some(e),
+            })?;
+        let _ = tokio::fs::remove_file(&sidecar_path).await;
+
+        let file_size = tokio::fs::metadata(&final_path)
+            .await
+            .map(|m| m.len())
+            .unwrap_or(0);
+
+        Ok(DownloadedFile {
+            path: final_path,
+            mime_type: detected.map(|d| d.mime_type),
+            file_size,
+        })
     }
 
-    /// Download a media asset with progress callback.
-    pub async fn download_with_progress(
+    /// The client for `url`, limited to its site's rate limit if it has one.
+    fn client_for(&self, url: &str) -> HttpClient {
+        let limit = url::Url::parse(url)
+            .ok()
+            .and_then(|u| Some(registrable_domain(&u.host_str()?.to_lowercase()).to_string()))
+            .and_then(|domain| self.site_limits.get(&domain).copied());
+        match limit {
+            Some(limit) => self.client.clone().with_rate_limit(limit),
+            None => self.client.clone(),
+        }
+    }
+
+    /// Read the first bytes of a file for magic-byte detection.
+    async fn read_head(path: &Path) -> Vec<u8> {
+        use tokio::io::AsyncReadExt;
+
+        let mut head = Vec::with_capacity(512);
+        if let Ok(file) = tokio::fs::File::open(path).await {
+            let _ = file.take(512).read_to_end(&mut head).await;
+        }
+        head
+    }
+
+    /// Error for a body whose type doesn't match the asset's media type.
+    fn mismatch_error(asset: &MediaAsset, mime: &str) -> DxError {
+        let hint = if mime.starts_with("text/html") {
+            " (probably an error or login page)"
+        } else {
+            ""
+        };
+        DxError::Download {
+            url: asset.download_url.clone(),
+            message: format!(
+                "Expected {} content but the server sent {}{}",
+                asset.media_type, mime, hint
+            ),
+        }
+    }
+
+    /// Make a single request for `url`, resuming from `part_path` when possible.
+    async fn attempt_download(
         &self,
         asset: &MediaAsset,
-        _on_progress: ProgressCallback,
-    ) -> Result<PathBuf> {
-        // For now, we don't have streaming progress - just download
-        // Future enhancement: implement streaming download with progress
-        self.download(asset).await
+        part_path: &Path,
+        sidecar_path: &Path,
+        on_progress: Option<&ProgressCallback>,
+    ) -> Result<Attempt> {
+        let url = asset.download_url.as_str();
+        let resume = Self::resume_point(url, part_path, sidecar_path).await;
+        let client = self.client_for(url);
+
+        let response = match resume {
+            Some((offset, ref validator)) => {
+                debug!("Resuming {} from byte {}", url, offset);
+                let range = format!("bytes={}-", offset);
+                client
+                    .get_with_headers(url, &[("Range", &range), ("If-Range", validator)])
+                    .await?
+            }
+            None => client.get_raw(url).await?,
+        };
+
+        let meta = ResponseMeta {
+            content_type: header_string(&response, CONTENT_TYPE),
+            content_disposition: header_string(&response, CONTENT_DISPOSITION),
+        };
+
+        // Bail out before reading an HTML error page into an image file
+        if let Some(ref mime) = meta.content_type {
+            let expected = content_type::extension_from_url(url);
+            if response.status().is_success()
+                && !content_type::is_compatible(mime, asset.media_type, expected)
+            {
+                return Err(Self::mismatch_error(asset, mime));
+            }
+        }
+
+        let status = response.status();
+        let offset = match (status, resume) {
+            (StatusCode::PARTIAL_CONTENT, Some((offset, _)))
+                if Self::content_range_start(&response) == Some(offset) =>
+            {
+                offset
+            }
+            (StatusCode::PARTIAL_CONTENT | StatusCode::RANGE_NOT_SATISFIABLE, _) => {
+                // Our partial file no longer lines up with the resource; start over
+                let _ = tokio::fs::remove_file(part_path).await;
+                let _ = tokio::fs::remove_file(sidecar_path).await;
+                return Ok(Attempt::Interrupted(DxError::download(
+                    url,
+                    format!("Server rejected the resume range (HTTP {})", status),
+                )));
+            }
+            _ if status.is_success() => {
+                // Fresh download, or the server ignored the range / the
+                // validator changed: record new validators and restart
+                PartialDownload::from_response(url, &response)
+                    .save(sidecar_path)
+                    .await?;
+                0
+            }
+            _ => {
+                return Err(DxError::Http {
+                    message: format!("Failed to download '{}': HTTP {}", url, status),
+                    status_code: Some(status.as_u16()),
+                    source: None,
+                });
+            }
+        };
+
+        match self
+            .stream_to_file(response, url, part_path, offset, on_progress)
+            .await
+        {
+            Ok(()) => Ok(Attempt::Complete(meta)),
+            Err(e @ DxError::Download { .. }) => Ok(Attempt::Interrupted(e)),
+            Err(e) => Err(e),
+        }
     }
 
-    /// Download a file from URL to a path.
-    async fn download_file(&self, url: &str, path: &Path) -> Result<()> {
-        let response = self.client.get_raw(url).await?;
+    /// Work out whether an existing `.part` file can be resumed.
+    ///
+    /// Returns the byte offset to resume from and the `If-Range` validator.
+    async fn resume_point(
+        url: &str,
+        part_path: &Path,
+        sidecar_path: &Path,
+    ) -> Option<(u64, String)> {
+        let size = tokio::fs::metadata(part_path).await.ok()?.len();
+        if size == 0 {
+            return None;
+        }
 
-        if !response.status().is_success() {
-            return Err(DxError::Download {
-                url: url.to_string(),
-                message: format!("HTTP {}", response.status()),
-            });
+        let partial = PartialDownload::load(sidecar_path).await?;
+        if partial.url != url {
+            return None;
         }
 
-        let bytes = response.bytes().await.map_err(|e| DxError::Download {
-            url: url.to_string(),
-            message: format!("Failed to read response body: {}", e),
-        })?;
+        // Weak ETags can't be used with If-Range, fall back to Last-Modified
+        let validator = partial
+            .etag
+            .filter(|etag| !etag.starts_with("W/"))
+            .or(partial.last_modified)?;
 
-        tokio::fs::write(path, &bytes)
+        Some((size, validator))
+    }
+
+    /// Parse the first byte position from a `Content-Range: bytes N-M/T` header.
+    fn content_range_start(response: &reqwest::Response) -> Option<u64> {
+        response
+            .headers()
+            .get(CONTENT_RANGE)?
+            .to_str()
+            .ok()?
+            .strip_prefix("bytes ")?
+            .split('-')
+            .next()?
+            .trim()
+            .parse()
+            .ok()
+    }
+
+    /// Stream a response body to disk chunk by chunk.
+    ///
+    /// When `offset` is non-zero the body is appended to the existing file.
+    async fn stream_to_file(
+        &self,
+        response: reqwest::Response,
+        url: &str,
+        path: &Path,
+        offset: u64,
+        on_progress: Option<&ProgressCallback>,
+    ) -> Result<()> {
+        let total = response
+            .content_length()
+            .map(|len| len + offset)
+            .unwrap_or(0);
+
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .write(true)
+            .append(offset > 0)
+            .truncate(offset == 0)
+            .open(path)
             .await
             .map_err(|e| DxError::FileIo {
                 path: path.to_path_buf(),
-                message: format!("Failed to write file: {}", e),
+                message: format!("Failed to open file: {}", e),
                 source: Some(e),
             })?;
 
+        let mut received = offset;
+        if let Some(cb) = on_progress {
+            cb(received, total);
+        }
+
+        let mut stream = response.bytes_stream();
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk.map_err(|e| DxError::Download {
+                url: url.to_string(),
+                message: format!("Failed to read response body: {}", e),
+            })?;
+
+            file.write_all(&chunk)
+                .await
+                .map_err(|e| DxError::FileIo {
+                    path: path.to_path_buf(),
+                    message: format!("Failed to write file: {}", e),
+                    source: Some(e),
+                })?;
+
+            received += chunk.len() as u64;
+            if let Some(cb) = on_progress {
+                cb(received, total);
+            }
+        }
+
+        file.flush().await.map_err(|e| DxError::FileIo {
+            path: path.to_path_buf(),
+            message: format!("Failed to flush file: {}", e),
+            source: Some(e),
+        })?;
+
         Ok(())
     }
 
+    /// Path of the temporary file used while a download is in flight.
+    fn part_path(path: &Path) -> PathBuf {
+        let mut name = path.file_name().unwrap_or_default().to_os_string();
+        name.push(".part");
+        path.with_file_name(name)
+    }
+
+    /// Path of the sidecar recording how to resume a `.part` file.
+    fn sidecar_path(path: &Path) -> PathBuf {
+        let mut name = path.file_name().unwrap_or_default().to_os_string();
+        name.push(".part.json");
+        path.with_file_name(name)
+    }
+
+    /// Path an asset will be saved to when downloaded into `dir`.
+    #[must_use]
+    pub fn target_path(&self, dir: &Path, asset: &MediaAsset) -> PathBuf {
+        let dir = match self.template {
+            Some(ref template) => dir.join(self.expand_template(template, asset)),
+            None => dir.to_path_buf(),
+        };
+        dir.join(self.generate_filename(asset))
+    }
+
+    /// Path a download to `path` ends up at once its type is known.
+    ///
+    /// The detected extension replaces the one guessed from the URL, so an
+    /// extensionless API URL guessed as `.jpg` may be saved as `.png`.
+    fn saved_path(path: &Path, detected_extension: Option<&str>) -> PathBuf {
+        match detected_extension {
+            Some(extension) => path.with_extension(extension),
+            None => path.to_path_buf(),
+        }
+    }
+
+    /// File a previous download of `asset` into `dir` was saved to, if any.
+    ///
+    /// Unlike [`target_path`](Self::target_path) this also finds files whose
+    /// extension was corrected from the detected content type.
+    pub async fn existing_path(&self, dir: &Path, asset: &MediaAsset) -> Option<PathBuf> {
+        let target = self.target_path(dir, asset);
+        if tokio::fs::try_exists(&target).await.unwrap_or(false) {
+            return Some(target);
+        }
+
+        let mut entries = tokio::fs::read_dir(target.parent()?).await.ok()?;
+        while let Ok(Some(entry)) = entries.next_entry().await {
+            let path = entry.path();
+            let Some(extension) = path.extension().and_then(|e| e.to_str()) else {
+                continue;
+            };
+            if Self::saved_path(&target, Some(extension)) == path
+                && entry.file_type().await.is_ok_and(|t| t.is_file())
+            {
+                return Some(path);
+            }
+        }
+        None
+    }
+
+    /// Expand a directory template for an asset, one sanitized component per `/`.
+    fn expand_template(&self, template: &str, asset: &MediaAsset) -> PathBuf {
+        template
+            .split('/')
+            .map(|part| {
+                part.replace("{provider}", &asset.provider)
+                    .replace("{media_type}", asset.media_type.as_str())
+                    .replace("{license}", asset.license.as_str())
+                    .replace("{author}", asset.author.as_deref().unwrap_or("unknown"))
+                    .replace("{date}", &chrono::Local::now().format("%Y-%m-%d").to_string())
+            })
+            .map(|part| self.sanitize_filename(&part))
+            .filter(|part| !part.is_empty() && part != "." && part != "..")
+            .collect()
+    }
+
     /// Generate a filename for an asset.
     fn generate_filename(&self, asset: &MediaAsset) -> String {
         // Sanitize the ID to be a valid filename
@@ -157,39 +651,9 @@ impl Downloader {
         }
     }
 
-    /// Extract extension from URL.
+    /// Extract a known extension from the URL path.
     fn extension_from_url(&self, url: &str) -> Option<&'static str> {
-        let url_lower = url.to_lowercase();
-
-        // Check common image formats
-        if url_lower.contains(".jpg") || url_lower.contains(".jpeg") {
-            return Some("jpg");
-        }
-        if url_lower.contains(".png") {
-            return Some("png");
-        }
-        if url_lower.contains(".gif") {
-            return Some("gif");
-        }
-        if url_lower.contains(".webp") {
-            return Some("webp");
-        }
-        if url_lower.contains(".svg") {
-            return Some("svg");
-        }
-
-        // Check video formats
-        if url_lower.contains(".mp4") {
-            return Some("mp4");
-        }
-        if url_lower.contains(".webm") {
-            return Some("webm");
-        }
-        if url_lower.contains(".mov") {
-            return Some("mov");
-        }
-
-        None
+        content_type::extension_from_url(url)
     }
 
     /// Get the default download directory.
@@ -199,9 +663,18 @@ impl Downloader {
     }
 }
 
+/// Read a response header as an owned string.
+fn header_string(response: &reqwest::Response, name: HeaderName) -> Option<String> {
+    response
+        .headers()
+        .get(name)
+        .and_then(|v| v.to_str().ok())
+        .map(String::from)
+}
+
 impl Default for Downloader {
     fn default() -> Self {
-        Self::new(&Config::default())
+        Self::new(&Config::default()).expect("Failed to create default downloader")
     }
 }
 
@@ -226,6 +699,42 @@ mod tests {
         assert_eq!(filename, "unsplash-12345.jpg");
     }
 
+    #[test]
+    fn test_template_path() {
+        let downloader = Downloader::new(&Config::default_for_testing()).unwrap()
+            .with_template(Some("{provider}/{media_type}/../{author}".to_string()));
+        let asset = MediaAsset::builder()
+            .id("7")
+            .provider("nasa")
+            .media_type(MediaType::Image)
+            .title("Moon")
+            .author("A/B")
+            .download_url("https://example.com/moon.png")
+            .source_url("https://example.com/7")
+            .build();
+
+        assert_eq!(
+            downloader.target_path(Path::new("out"), &asset),
+            Path::new("out/nasa/image/A_B/nasa-7.png")
+        );
+    }
+
+    #[test]
+    fn test_part_path() {
+        assert_eq!(
+            Downloader::part_path(Path::new("/tmp/media/nasa-1.jpg")),
+            PathBuf::from("/tmp/media/nasa-1.jpg.part")
+        );
+    }
+
+    #[test]
+    fn test_sidecar_path() {
+        assert_eq!(
+            Downloader::sidecar_path(Path::new("/tmp/media/nasa-1.jpg")),
+            PathBuf::from("/tmp/media/nasa-1.jpg.part.json")
+        );
+    }
+
     #[test]
     fn test_extension_from_url() {
         let downloader = Downloader::default();
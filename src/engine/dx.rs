@@ -6,10 +6,23 @@ use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use crate::config::Config;
-use crate::engine::{Downloader, FileManager, SearchEngine, Scraper, ScrapeOptions};
-use crate::error::Result;
+use crate::engine::{
+    AssetStream, DownloadQueue, DownloadReport, Downloader, FileManager, ProgressCallback,
+    ScrapeOptions, Scraper, SearchEngine,
+};
+use crate::error::{DxError, Result};
 use crate::providers::ProviderRegistry;
-use crate::types::{MediaAsset, MediaType, SearchQuery, SearchResult};
+use crate::scraping::{ScrapingCategory, ScrapingRegistry, ScrapingTarget};
+use crate::types::{
+    CachePolicy, MediaAsset, MediaType, RankingStrategy, SearchQuery, SearchResult,
+};
+
+/// Scraping targets searched by [`DxMedia::search_all_query`] when the query
+/// names none. Other targets are opt-in through `SearchQuery::scrape_targets`.
+const DEFAULT_SCRAPE_TARGETS: &[&str] = &["stocksnap", "publicdomainpictures"];
+
+/// Most scraping targets searched at once.
+const MAX_CONCURRENT_SCRAPES: usize = 4;
 
 /// Main facade for the DX Media library.
 ///
@@ -58,9 +71,9 @@ impl DxMedia {
 
     /// Create a new DxMedia instance with the given configuration.
     pub fn with_config(config: Config) -> Result<Self> {
-        let registry = Arc::new(ProviderRegistry::new(&config));
+        let registry = Arc::new(ProviderRegistry::new(&config)?);
         let search_engine = SearchEngine::new(Arc::clone(&registry));
-        let downloader = Downloader::new(&config);
+        let downloader = Downloader::new(&config)?.with_site_limits(registry.site_limits());
         let file_manager = FileManager::new(&config.download_dir);
 
         Ok(Self {
@@ -93,7 +106,7 @@ impl DxMedia {
     pub fn search(&self, query: impl Into<String>) -> SearchBuilder<'_> {
         SearchBuilder {
             dx: self,
-            query: SearchQuery::new(query),
+            query: SearchQuery::new(query).mode(self.config.search_mode),
         }
     }
 
@@ -102,6 +115,62 @@ impl DxMedia {
         self.search_engine.search(query).await
     }
 
+    /// Execute a search query, calling `on_result` with each provider's
+    /// results as they arrive, before they are aggregated and ranked.
+    ///
+    /// ```no_run
+    /// # use dx_media::{DxMedia, SearchQuery};
+    /// # async fn example() -> dx_media::Result<()> {
+    /// let dx = DxMedia::new()?;
+    /// let query = SearchQuery::new("aurora");
+    /// dx.search_query_with(&query, |provider, result| {
+    ///     println!("{provider}: {} assets", result.assets.len());
+    /// })
+    /// .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn search_query_with(
+        &self,
+        query: &SearchQuery,
+        mut on_result: impl FnMut(&str, &SearchResult) + Send,
+    ) -> Result<SearchResult> {
+        self.search_engine.search_with(query, &mut on_result).await
+    }
+
+    /// Stream assets for a query, paging through each provider on demand.
+    ///
+    /// Each provider is paged from `query.page` in pages of `query.count`,
+    /// respecting its rate limit, until it runs out of results. Pages are only
+    /// fetched as the stream is polled, so a limit applied with
+    /// [`StreamExt::take`](futures::StreamExt::take) stops further requests.
+    ///
+    /// ```no_run
+    /// # use dx_media::{DxMedia, SearchQuery};
+    /// # use futures::StreamExt;
+    /// # async fn example() -> dx_media::Result<()> {
+    /// let dx = DxMedia::new()?;
+    /// let mut assets = dx.search_stream(&SearchQuery::new("aurora")).take(100);
+    /// while let Some(asset) = assets.next().await {
+    ///     println!("{}", asset?.title);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn search_stream(&self, query: &SearchQuery) -> AssetStream {
+        self.search_engine.search_stream(query)
+    }
+
+    /// Look up a single asset by reference in `provider:id` form.
+    ///
+    /// The ID is everything after the first `:`, so IDs may themselves contain colons
+    /// (e.g. `wikimedia:File:Example.jpg`).
+    pub async fn get_asset(&self, asset_ref: &str) -> Result<MediaAsset> {
+        let (provider, id) = parse_asset_ref(asset_ref)?;
+        self.registry.get_asset(provider, id).await
+    }
+
     /// Download a media asset to the default download directory.
     pub async fn download(&self, asset: &MediaAsset) -> Result<PathBuf> {
         self.downloader.download(asset).await
@@ -112,6 +181,41 @@ impl DxMedia {
         self.downloader.download_to(dir, asset).await
     }
 
+    /// Download a media asset, reporting `(bytes_received, total_bytes)` as it streams.
+    ///
+    /// Downloads to `dir` when given, otherwise to the default download directory.
+    pub async fn download_with_progress(
+        &self,
+        asset: &MediaAsset,
+        dir: Option<&Path>,
+        on_progress: ProgressCallback,
+    ) -> Result<PathBuf> {
+        let dir = dir.unwrap_or_else(|| self.downloader.download_dir());
+        self.downloader
+            .download_to_with_progress(dir, asset, Some(on_progress))
+            .await
+    }
+
+    /// Download many assets concurrently.
+    ///
+    /// Runs up to [`Config::concurrent_downloads`] downloads at once, retrying
+    /// retryable errors, and reports the outcome of every asset. Downloads go
+    /// to `dir` when given, otherwise to the default download directory.
+    pub async fn download_many(&self, assets: &[MediaAsset], dir: Option<&Path>) -> DownloadReport {
+        let dir = dir.unwrap_or_else(|| self.downloader.download_dir());
+        self.download_queue().run(assets, dir).await
+    }
+
+    /// Create a [`DownloadQueue`] configured from this instance's settings.
+    ///
+    /// Use this instead of [`download_many`](Self::download_many) to attach
+    /// progress callbacks or tune per-host limits.
+    #[must_use]
+    pub fn download_queue(&self) -> DownloadQueue {
+        DownloadQueue::new(self.downloader.clone(), self.config.concurrent_downloads)
+            .max_retries(self.config.retry_attempts)
+    }
+
     /// Get the provider registry.
     #[must_use]
     pub fn registry(&self) -> &ProviderRegistry {
@@ -167,8 +271,10 @@ impl DxMedia {
     /// 
     /// This is the main unified search function that:
     /// 1. Searches all available API providers concurrently
-    /// 2. Scrapes configured free image sites concurrently
+    /// 2. Searches the scraping targets for the query's media type concurrently
     /// 3. Returns combined results from all sources
+    ///
+    /// Uses the configured default search mode (`search.mode`).
     /// 
     /// # Example
     /// 
@@ -185,8 +291,7 @@ impl DxMedia {
     /// # }
     /// ```
     pub async fn search_all(&self, query: &str, count_per_source: usize) -> Result<SearchResult> {
-        use crate::types::SearchMode;
-        self.search_all_with_mode(query, count_per_source, SearchMode::default()).await
+        self.search_all_with_mode(query, count_per_source, self.config.search_mode).await
     }
 
     /// Search all providers AND scrapers concurrently with explicit search mode.
@@ -211,65 +316,100 @@ impl DxMedia {
         query: &str, 
         count_per_source: usize,
         mode: crate::types::SearchMode,
+    ) -> Result<SearchResult> {
+        let search_query = SearchQuery::new(query).count(count_per_source).mode(mode);
+        self.search_all_query(&search_query).await
+    }
+
+    /// Search all providers AND scrapers concurrently using a full query.
+    ///
+    /// `count` is the number of results per source; `mode` and `cache_policy`
+    /// apply as for [`DxMedia::search_query`]. `scrape_targets` selects the
+    /// scraping targets by ID or category; without it only a short default
+    /// list of image sites is scraped. At most four targets are searched at
+    /// once.
+    pub async fn search_all_query(&self, search_query: &SearchQuery) -> Result<SearchResult> {
+        self.search_all_query_with(search_query, |_, _| {}).await
+    }
+
+    /// Like [`search_all_query`](Self::search_all_query), but calls
+    /// `on_result` with each provider's and scraper's results as they arrive,
+    /// before they are aggregated and ranked. Scrapers are reported as
+    /// `scraper:<target>`.
+    pub async fn search_all_query_with(
+        &self,
+        search_query: &SearchQuery,
+        on_result: impl FnMut(&str, &SearchResult) + Send,
     ) -> Result<SearchResult> {
         use crate::types::SearchMode;
         use std::time::{Instant, Duration};
-        use futures::stream::{FuturesUnordered, StreamExt};
+        use futures::stream::StreamExt;
         
         let start = Instant::now();
+        let query = search_query.query.as_str();
+        let count_per_source = search_query.count;
         
         // Timeout varies by mode: Quantity=fast, Quality=patient
-        let scraper_timeout = match mode {
+        let scraper_timeout = match search_query.mode {
             SearchMode::Quantity => Duration::from_secs(3),
             SearchMode::Quality => Duration::from_secs(6),
         };
 
-        // Build search query for providers with mode
-        let search_query = SearchQuery::new(query).count(count_per_source).mode(mode);
-        
-        // Create scraper for web scraping
-        let scraper = Scraper::new()?;
-        
-        // Define scraping targets (sites that work without Cloudflare protection)
-        let scrape_urls = self.get_scrape_search_urls(query);
-        
-        // Create FuturesUnordered for scraper searches with timeouts
-        let mut scrape_futures: FuturesUnordered<_> = scrape_urls
-            .into_iter()
-            .map(|(name, url)| {
+        let scraper = Scraper::with_config(&self.config)?;
+        let targets = scrape_targets(search_query)?;
+
+        // Scraper searches with timeouts, a few sites at a time
+        let mut scrape_futures = futures::stream::iter(targets)
+            .map(|target| {
                 let scraper = scraper.clone();
                 let options = ScrapeOptions {
-                    max_depth: 0,
-                    pattern: None,
-                    media_types: vec![MediaType::Image],
                     max_assets: count_per_source,
+                    ..ScrapeOptions::default()
                 };
                 async move {
                     let result = tokio::time::timeout(
                         scraper_timeout,
-                        scraper.scrape(&url, &options)
+                        scraper.search_target(target, query, &options)
                     ).await;
-                    
+
+                    let name = target.id.to_string();
                     match result {
                         Ok(r) => (name, r),
                         Err(_) => (name.clone(), Err(crate::error::DxError::ProviderApi {
                             provider: format!("scraper:{}", name),
-                            message: "Scraper timed out (>5s)".to_string(),
+                            message: format!("Scraper timed out (>{}s)", scraper_timeout.as_secs()),
                             status_code: 408,
                         })),
                     }
                 }
             })
-            .collect();
+            .buffer_unordered(MAX_CONCURRENT_SCRAPES);
+
+        // Providers and scrapers report concurrently, so they share the callback
+        let on_result = std::sync::Mutex::new(on_result);
+        let mut report = |source: &str, result: &SearchResult| {
+            let mut on_result = on_result.lock().unwrap_or_else(std::sync::PoisonError::into_inner);
+            on_result(source, result);
+        };
+        let report_scrape = report;
 
         // Execute provider search and scraper searches concurrently
-        let provider_future = self.search_engine.search(&search_query);
+        let provider_future = self.search_engine.search_with(search_query, &mut report);
         
         // Start collecting scraper results in parallel
         let scraper_collector = async {
             let mut results = Vec::new();
-            while let Some(result) = scrape_futures.next().await {
-                results.push(result);
+            while let Some((name, result)) = scrape_futures.next().await {
+                let source = format!("scraper:{}", name);
+                let result = result.map(|sr| {
+                    // Scraped pages can't filter server-side, so every filter applies here
+                    let mut partial = SearchResult::new(query);
+                    partial.assets = sr.assets;
+                    crate::engine::apply_filters(&mut partial, search_query, &source, &[]);
+                    report_scrape(&source, &partial);
+                    partial
+                });
+                results.push((name, result));
             }
             results
         };
@@ -290,6 +430,7 @@ impl DxMedia {
                     result.providers_searched.push(format!("scraper:{}", name));
                     result.total_count += sr.assets.len();
                     result.assets.extend(sr.assets);
+                    result.filters.extend(sr.filters);
                 }
                 Err(e) => {
                     result.provider_errors.push((format!("scraper:{}", name), e.to_string()));
@@ -297,25 +438,41 @@ impl DxMedia {
             }
         }
 
+        // Rank again now that scraper results are mixed in
+        result.assets = crate::ranking::rank(std::mem::take(&mut result.assets), search_query);
+
         result.duration_ms = start.elapsed().as_millis() as u64;
         Ok(result)
     }
+}
 
-    /// Get search URLs for scraping targets.
-    /// 
-    /// Returns a list of (name, url) pairs for sites that support search
-    /// and don't have Cloudflare protection.
-    fn get_scrape_search_urls(&self, _query: &str) -> Vec<(String, String)> {
-        // Only include sites that:
-        // 1. Support search via URL parameter
-        // 2. Don't have Cloudflare/bot protection
-        // 3. Return useful results without JavaScript
-        vec![
-            // Flickr explore (no search, but popular images)
-            ("flickr".to_string(), "https://www.flickr.com/explore".to_string()),
-            // NASA image gallery
-            ("nasa-gallery".to_string(), "https://www.nasa.gov/multimedia/imagegallery/".to_string()),
-        ]
+/// Scraping targets searched for a query.
+///
+/// Uses `query.scrape_targets` when set, otherwise the
+/// [`DEFAULT_SCRAPE_TARGETS`] in the category for the query's media type
+/// (images by default).
+fn scrape_targets(query: &SearchQuery) -> Result<Vec<&'static ScrapingTarget>> {
+    if !query.scrape_targets.is_empty() {
+        return ScrapingRegistry::select(&query.scrape_targets);
+    }
+    let category = query
+        .media_type
+        .and_then(ScrapingCategory::for_media_type)
+        .unwrap_or(ScrapingCategory::Images);
+    Ok(DEFAULT_SCRAPE_TARGETS
+        .iter()
+        .filter_map(|id| ScrapingRegistry::get(id))
+        .filter(|target| target.category == category)
+        .collect())
+}
+
+/// Split a `provider:id` asset reference.
+fn parse_asset_ref(asset_ref: &str) -> Result<(&str, &str)> {
+    match asset_ref.split_once(':') {
+        Some((provider, id)) if !provider.is_empty() && !id.is_empty() => Ok((provider, id)),
+        _ => Err(DxError::InvalidQuery {
+            message: format!("Expected an asset reference like 'provider:id', got '{}'", asset_ref),
+        }),
     }
 }
 
@@ -361,6 +518,20 @@ impl<'a> SearchBuilder<'a> {
         self
     }
 
+    /// Set how results from several providers are deduplicated and ordered.
+    #[must_use]
+    pub fn ranking(mut self, ranking: RankingStrategy) -> Self {
+        self.query.ranking = ranking;
+        self
+    }
+
+    /// Set how the search result cache is used.
+    #[must_use]
+    pub fn cache_policy(mut self, cache_policy: CachePolicy) -> Self {
+        self.query.cache_policy = cache_policy;
+        self
+    }
+
     /// Execute the search.
     pub async fn execute(self) -> Result<SearchResult> {
         self.dx.search_query(&self.query).await
@@ -415,4 +586,40 @@ mod tests {
         assert!(all.contains(&"artic".to_string())); // Added back
         assert!(all.contains(&"archive".to_string())); // Registered but unavailable
     }
+
+    #[test]
+    fn test_parse_asset_ref() {
+        assert_eq!(parse_asset_ref("met:436535").unwrap(), ("met", "436535"));
+        assert_eq!(
+            parse_asset_ref("wikimedia:File:Example.jpg").unwrap(),
+            ("wikimedia", "File:Example.jpg")
+        );
+        assert!(parse_asset_ref("436535").is_err());
+        assert!(parse_asset_ref("met:").is_err());
+    }
+
+    #[test]
+    fn test_scrape_targets() {
+        let ids = |query: &SearchQuery| -> Vec<&str> {
+            scrape_targets(query).unwrap().iter().map(|t| t.id).collect()
+        };
+
+        assert_eq!(ids(&SearchQuery::new("fox")), DEFAULT_SCRAPE_TARGETS);
+        assert!(ids(&SearchQuery::new("fox").media_type(MediaType::Video)).is_empty());
+
+        let mut query = SearchQuery::new("fox");
+        query.scrape_targets = vec!["images".to_string()];
+        assert!(ids(&query).len() > DEFAULT_SCRAPE_TARGETS.len());
+    }
+
+    #[tokio::test]
+    async fn test_get_asset_unsupported_provider() {
+        let dx = DxMedia::with_config(Config::default_for_testing()).unwrap();
+
+        let err = dx.get_asset("dicebear:abc").await.unwrap_err();
+        assert!(matches!(err, DxError::ProviderApi { status_code: 501, .. }));
+
+        let err = dx.get_asset("nope:abc").await.unwrap_err();
+        assert!(matches!(err, DxError::ProviderApi { status_code: 404, .. }));
+    }
 }
@@ -0,0 +1,188 @@
+//! Client-side enforcement of query filters.
+//!
+//! Most providers ignore `min_width`, `min_height`, `orientation` and `color`.
+//! [`apply_filters`] enforces them on a provider's results from
+//! [`MediaAsset::width`]/[`MediaAsset::height`], skipping the filters the
+//! provider declared it handles natively, and records a [`FilterReport`] for
+//! each filter.
+//!
+//! Assets missing the metadata needed to evaluate a filter are kept rather
+//! than dropped, and counted as unknown. Several providers record unknown
+//! dimensions as `0`, so a zero width or height counts as missing too.
+
+use crate::types::{
+    FilterReport, FilterStatus, MediaAsset, Orientation, SearchFilter, SearchQuery, SearchResult,
+};
+
+/// Enforce the query's filters on one provider's results.
+///
+/// `native` lists the filters the provider already applied server-side.
+pub fn apply_filters(
+    result: &mut SearchResult,
+    query: &SearchQuery,
+    provider: &str,
+    native: &[SearchFilter],
+) {
+    for filter in query.active_filters() {
+        let report = if native.contains(&filter) {
+            FilterReport {
+                provider: provider.to_string(),
+                filter,
+                status: FilterStatus::Native,
+                removed: 0,
+                unknown: 0,
+            }
+        } else {
+            apply_filter(result, query, provider, filter)
+        };
+        result.filters.push(report);
+    }
+}
+
+fn apply_filter(
+    result: &mut SearchResult,
+    query: &SearchQuery,
+    provider: &str,
+    filter: SearchFilter,
+) -> FilterReport {
+    let before = result.assets.len();
+    let mut unknown = 0;
+
+    result.assets.retain(|asset| {
+        matches(asset, query, filter).unwrap_or_else(|| {
+            unknown += 1;
+            true
+        })
+    });
+
+    let removed = before - result.assets.len();
+    // Only report client-side enforcement if at least one asset could be checked
+    let status = if unknown > 0 && unknown == before {
+        FilterStatus::Unevaluated
+    } else {
+        FilterStatus::ClientSide
+    };
+
+    FilterReport {
+        provider: provider.to_string(),
+        filter,
+        status,
+        removed,
+        unknown,
+    }
+}
+
+/// Whether an asset passes a filter, or `None` if it can't be evaluated.
+fn matches(asset: &MediaAsset, query: &SearchQuery, filter: SearchFilter) -> Option<bool> {
+    match filter {
+        SearchFilter::MinWidth => {
+            let min = query.min_width?;
+            known(asset.width).map(|width| width >= min)
+        }
+        SearchFilter::MinHeight => {
+            let min = query.min_height?;
+            known(asset.height).map(|height| height >= min)
+        }
+        SearchFilter::Orientation => {
+            let wanted = query.orientation?;
+            let (width, height) = (known(asset.width)?, known(asset.height)?);
+            Some(Orientation::from_dimensions(width, height) == wanted)
+        }
+        // Assets carry no color information to check against
+        SearchFilter::Color => None,
+    }
+}
+
+/// A dimension, treating `0` (how providers record "unknown") as missing.
+fn known(dimension: Option<u32>) -> Option<u32> {
+    dimension.filter(|&d| d > 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::MediaType;
+
+    fn asset(id: &str, dimensions: Option<(u32, u32)>) -> MediaAsset {
+        let mut builder = MediaAsset::builder()
+            .id(id)
+            .provider("test")
+            .media_type(MediaType::Image)
+            .title(id)
+            .download_url(format!("https://example.com/{}.jpg", id))
+            .source_url(format!("https://example.com/{}", id));
+        if let Some((width, height)) = dimensions {
+            builder = builder.dimensions(width, height);
+        }
+        builder.build()
+    }
+
+    fn result(assets: Vec<MediaAsset>) -> SearchResult {
+        let mut result = SearchResult::new("test");
+        result.assets = assets;
+        result
+    }
+
+    #[test]
+    fn test_orientation_and_min_width() {
+        let query = SearchQuery::new("test").orientation(Orientation::Portrait);
+        let query = SearchQuery {
+            min_width: Some(500),
+            ..query
+        };
+        let mut result = result(vec![
+            asset("portrait", Some((600, 900))),
+            asset("landscape", Some((900, 600))),
+            asset("narrow", Some((400, 900))),
+            asset("unknown", None),
+        ]);
+
+        apply_filters(&mut result, &query, "test", &[]);
+
+        let ids: Vec<&str> = result.assets.iter().map(|a| a.id.as_str()).collect();
+        assert_eq!(ids, vec!["portrait", "unknown"]);
+        assert_eq!(result.filters.len(), 2);
+        assert_eq!(result.filters[0].filter, SearchFilter::MinWidth);
+        assert_eq!(result.filters[0].status, FilterStatus::ClientSide);
+        assert_eq!(result.filters[0].removed, 1);
+        assert_eq!(result.filters[0].unknown, 1);
+        assert_eq!(result.filters[1].filter, SearchFilter::Orientation);
+        assert_eq!(result.filters[1].removed, 1);
+    }
+
+    #[test]
+    fn test_native_and_unevaluated() {
+        let query = SearchQuery::new("test")
+            .orientation(Orientation::Landscape)
+            .color("red");
+        let mut result = result(vec![asset("a", None), asset("b", Some((10, 20)))]);
+
+        apply_filters(&mut result, &query, "test", &[SearchFilter::Orientation]);
+
+        assert_eq!(result.assets.len(), 2);
+        assert_eq!(result.filters[0].status, FilterStatus::Native);
+        assert_eq!(result.filters[1].filter, SearchFilter::Color);
+        assert_eq!(result.filters[1].status, FilterStatus::Unevaluated);
+        assert_eq!(result.filters[1].unknown, 2);
+    }
+
+    #[test]
+    fn test_zero_dimensions_are_unknown() {
+        let query = SearchQuery::new("test").orientation(Orientation::Square);
+        let query = SearchQuery {
+            min_width: Some(500),
+            min_height: Some(500),
+            ..query
+        };
+        let mut result = result(vec![asset("zero", Some((0, 0)))]);
+
+        apply_filters(&mut result, &query, "test", &[]);
+
+        assert_eq!(result.assets.len(), 1);
+        for report in &result.filters {
+            assert_eq!(report.status, FilterStatus::Unevaluated);
+            assert_eq!(report.removed, 0);
+            assert_eq!(report.unknown, 1);
+        }
+    }
+}
@@ -3,14 +3,27 @@
 //! This module provides the high-level orchestration layer that coordinates
 //! providers, manages downloads, and handles file operations.
 
+mod content_type;
 mod download;
 mod dx;
 mod filemanager;
+mod filter;
+mod queue;
 mod scraper;
 mod search;
+mod stream;
 
-pub use download::Downloader;
+pub use download::{DownloadedFile, Downloader, ProgressCallback};
 pub use dx::DxMedia;
 pub use filemanager::FileManager;
-pub use scraper::{ScrapeOptions, ScrapeResult, Scraper};
+pub use filter::apply_filters;
+pub use queue::{
+    DownloadOutcome, DownloadQueue, DownloadReport, DownloadStatus, QueueEvent, QueueEventCallback,
+};
+pub use scraper::{
+    CrawlScope, Feed, ROBOTS_AGENT, RobotsTxt, SCRAPER_USER_AGENT, ScrapeOptions, ScrapeResult,
+    Scraper, SitemapOptions, SitemapState,
+};
 pub use search::SearchEngine;
+pub use stream::AssetStream;
+pub(crate) use scraper::matches_query;
@@ -0,0 +1,503 @@
+//! Batch download queue with bounded concurrency.
+//!
+//! [`DownloadQueue`] runs many downloads at once while keeping any single host
+//! from taking every slot, retries transient failures and reports what
+//! happened to each asset.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures::stream::{FuturesUnordered, StreamExt};
+use tokio::sync::Semaphore;
+use tracing::warn;
+
+use crate::engine::{DownloadedFile, Downloader, ProgressCallback};
+use crate::error::DxError;
+use crate::types::MediaAsset;
+
+/// Longest `Retry-After` wait honored before retrying a download. When a
+/// server asks for longer, the asset fails rather than stalling the batch.
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(120);
+
+/// Callback receiving [`QueueEvent`]s while a batch is running.
+pub type QueueEventCallback = Arc<dyn Fn(QueueEvent) + Send + Sync>;
+
+/// Progress events emitted by [`DownloadQueue::run`].
+///
+/// `index` always refers to the asset's position in the slice passed to `run`.
+#[derive(Debug, Clone)]
+pub enum QueueEvent {
+    /// A download acquired a slot and started.
+    Started {
+        /// Asset index.
+        index: usize,
+    },
+    /// Bytes were received for a download.
+    Progress {
+        /// Asset index.
+        index: usize,
+        /// Bytes received so far.
+        received: u64,
+        /// Total bytes (`0` if unknown).
+        total: u64,
+    },
+    /// A download failed with a retryable error and will be tried again.
+    Retrying {
+        /// Asset index.
+        index: usize,
+        /// The attempt about to be made (1-based).
+        attempt: u32,
+        /// Error that caused the retry.
+        error: String,
+    },
+    /// A download finished, was skipped or gave up.
+    Finished {
+        /// Asset index.
+        index: usize,
+        /// Final status.
+        status: DownloadStatus,
+    },
+}
+
+/// Final status of a single asset in a batch.
+#[derive(Debug, Clone)]
+pub enum DownloadStatus {
+    /// Downloaded successfully to the given path.
+    Downloaded(PathBuf),
+    /// Not downloaded, for the given reason.
+    Skipped(String),
+    /// Failed with the given error message.
+    Failed(String),
+}
+
+/// Outcome of one asset in a batch download.
+#[derive(Debug, Clone)]
+pub struct DownloadOutcome {
+    /// The asset that was requested.
+    pub asset: MediaAsset,
+    /// What happened to it.
+    pub status: DownloadStatus,
+    /// Number of attempts made (0 for skipped assets).
+    pub attempts: u32,
+}
+
+/// Per-asset report returned by [`DownloadQueue::run`].
+///
+/// Outcomes are in the same order as the assets passed in.
+#[derive(Debug, Clone, Default)]
+pub struct DownloadReport {
+    /// One outcome per requested asset, with `mime_type` and `file_size`
+    /// filled in from what was received for successful downloads.
+    pub outcomes: Vec<DownloadOutcome>,
+}
+
+impl DownloadReport {
+    /// Paths of successfully downloaded assets.
+    #[must_use]
+    pub fn downloaded(&self) -> Vec<(&MediaAsset, &Path)> {
+        self.outcomes
+            .iter()
+            .filter_map(|o| match &o.status {
+                DownloadStatus::Downloaded(path) => Some((&o.asset, path.as_path())),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Assets that failed, with their error messages.
+    #[must_use]
+    pub fn failed(&self) -> Vec<(&MediaAsset, &str)> {
+        self.outcomes
+            .iter()
+            .filter_map(|o| match &o.status {
+                DownloadStatus::Failed(error) => Some((&o.asset, error.as_str())),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Assets that were skipped, with the reason.
+    #[must_use]
+    pub fn skipped(&self) -> Vec<(&MediaAsset, &str)> {
+        self.outcomes
+            .iter()
+            .filter_map(|o| match &o.status {
+                DownloadStatus::Skipped(reason) => Some((&o.asset, reason.as_str())),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Assets whose file is in the output directory after the run, whether
+    /// downloaded now or skipped as already present, each listed once.
+    #[must_use]
+    pub fn saved(&self) -> Vec<&MediaAsset> {
+        let failed: HashSet<(&str, &str)> = self
+            .failed()
+            .into_iter()
+            .map(|(asset, _)| (asset.provider.as_str(), asset.id.as_str()))
+            .collect();
+        let mut seen = HashSet::new();
+        self.outcomes
+            .iter()
+            .map(|o| &o.asset)
+            .filter(|asset| {
+                let key = (asset.provider.as_str(), asset.id.as_str());
+                !failed.contains(&key) && seen.insert(key)
+            })
+            .collect()
+    }
+
+    /// Returns true if no asset failed.
+    #[must_use]
+    pub fn is_success(&self) -> bool {
+        self.failed().is_empty()
+    }
+}
+
+/// Queue for downloading many assets with bounded concurrency.
+///
+/// # Example
+///
+/// ```no_run
+/// # use dx_media::{Config, Downloader, DownloadQueue, MediaAsset};
+/// # async fn example(assets: Vec<MediaAsset>) -> dx_media::Result<()> {
+/// let downloader = Downloader::new(&Config::default())?;
+/// let report = DownloadQueue::new(downloader, 4)
+///     .per_host_limit(2)
+///     .run(&assets, std::path::Path::new("./media"))
+///     .await;
+/// println!("{} downloaded, {} failed", report.downloaded().len(), report.failed().len());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct DownloadQueue {
+    downloader: Downloader,
+    concurrency: usize,
+    per_host_limit: usize,
+    max_retries: u32,
+    skip_existing: bool,
+    on_event: Option<QueueEventCallback>,
+}
+
+impl std::fmt::Debug for DownloadQueue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DownloadQueue")
+            .field("concurrency", &self.concurrency)
+            .field("per_host_limit", &self.per_host_limit)
+            .field("max_retries", &self.max_retries)
+            .field("skip_existing", &self.skip_existing)
+            .finish_non_exhaustive()
+    }
+}
+
+impl DownloadQueue {
+    /// Create a queue running at most `concurrency` downloads at once.
+    ///
+    /// By default a single host may use up to half of the slots. The
+    /// downloader's own request retries are turned off, as the queue retries
+    /// failed downloads itself (see [`DownloadQueue::max_retries`]).
+    #[must_use]
+    pub fn new(downloader: Downloader, concurrency: usize) -> Self {
+        let concurrency = concurrency.max(1);
+        Self {
+            downloader: downloader.with_request_retries(0),
+            concurrency,
+            per_host_limit: concurrency.div_ceil(2),
+            max_retries: 2,
+            skip_existing: true,
+            on_event: None,
+        }
+    }
+
+    /// Limit the number of simultaneous downloads from one host.
+    #[must_use]
+    pub fn per_host_limit(mut self, limit: usize) -> Self {
+        self.per_host_limit = limit.max(1);
+        self
+    }
+
+    /// Set how many times a retryable failure is retried.
+    #[must_use]
+    pub fn max_retries(mut self, retries: u32) -> Self {
+        self.max_retries = retries;
+        self
+    }
+
+    /// Skip assets whose target file already exists (default: true).
+    #[must_use]
+    pub fn skip_existing(mut self, skip: bool) -> Self {
+        self.skip_existing = skip;
+        self
+    }
+
+    /// Receive progress events while the batch runs.
+    #[must_use]
+    pub fn on_event(mut self, callback: QueueEventCallback) -> Self {
+        self.on_event = Some(callback);
+        self
+    }
+
+    /// Download every asset into `dir` and report the outcome of each.
+    ///
+    /// Individual failures never abort the batch; they are recorded in the
+    /// returned [`DownloadReport`].
+    pub async fn run(&self, assets: &[MediaAsset], dir: &Path) -> DownloadReport {
+        let global = Arc::new(Semaphore::new(self.concurrency));
+        let mut host_slots: HashMap<String, Arc<Semaphore>> = HashMap::new();
+        let mut statuses: Vec<Option<(DownloadStatus, u32)>> = vec![None; assets.len()];
+
+        // Resolve duplicates and existing files up front
+        let mut seen = HashSet::new();
+        let mut pending = Vec::new();
+        for (index, asset) in assets.iter().enumerate() {
+            let skip = if !seen.insert((asset.provider.as_str(), asset.id.as_str())) {
+                Some(format!(
+                    "Duplicate of an earlier {}:{} entry",
+                    asset.provider, asset.id
+                ))
+            } else if self.skip_existing
+                && self.downloader.existing_path(dir, asset).await.is_some()
+            {
+                Some("File already exists".to_string())
+            } else {
+                None
+            };
+
+            match skip {
+                Some(reason) => {
+                    let status = DownloadStatus::Skipped(reason);
+                    self.emit(QueueEvent::Finished {
+                        index,
+                        status: status.clone(),
+                    });
+                    statuses[index] = Some((status, 0));
+                }
+                None => pending.push(index),
+            }
+        }
+
+        // Interleave hosts so the FIFO semaphore hands out slots round-robin
+        let ordered = Self::interleave_by_host(assets, pending);
+
+        let mut futures: FuturesUnordered<_> = ordered
+            .into_iter()
+            .map(|index| {
+                let asset = &assets[index];
+                let host = Self::host_of(asset);
+                let host_slot = Arc::clone(
+                    host_slots
+                        .entry(host)
+                        .or_insert_with(|| Arc::new(Semaphore::new(self.per_host_limit))),
+                );
+                let global = Arc::clone(&global);
+                async move {
+                    // Take the host slot first so a busy host never holds global slots
+                    let _host_permit = host_slot.acquire_owned().await.ok();
+                    let _permit = global.acquire_owned().await.ok();
+                    let (status, attempts, file) = self.download_one(index, asset, dir).await;
+                    (index, status, attempts, file)
+                }
+            })
+            .collect();
+
+        let mut files: HashMap<usize, DownloadedFile> = HashMap::new();
+        while let Some((index, status, attempts, file)) = futures.next().await {
+            self.emit(QueueEvent::Finished {
+                index,
+                status: status.clone(),
+            });
+            statuses[index] = Some((status, attempts));
+            if let Some(file) = file {
+                files.insert(index, file);
+            }
+        }
+
+        DownloadReport {
+            outcomes: assets
+                .iter()
+                .zip(statuses)
+                .enumerate()
+                .map(|(index, (asset, status))| {
+                    let (status, attempts) = status.unwrap_or_else(|| {
+                        (DownloadStatus::Failed("Download never ran".to_string()), 0)
+                    });
+                    let mut asset = asset.clone();
+                    if let Some(file) = files.get(&index) {
+                        file.apply_to(&mut asset);
+                    }
+                    DownloadOutcome {
+                        asset,
+                        status,
+                        attempts,
+                    }
+                })
+                .collect(),
+        }
+    }
+
+    /// Download a single asset, retrying retryable errors with backoff.
+    async fn download_one(
+        &self,
+        index: usize,
+        asset: &MediaAsset,
+        dir: &Path,
+    ) -> (DownloadStatus, u32, Option<DownloadedFile>) {
+        self.emit(QueueEvent::Started { index });
+
+        let on_progress: Option<ProgressCallback> = self.on_event.as_ref().map(|cb| {
+            let cb = Arc::clone(cb);
+            Arc::new(move |received, total| {
+                cb(QueueEvent::Progress {
+                    index,
+                    received,
+                    total,
+                });
+            }) as ProgressCallback
+        });
+
+        let mut attempts = 0;
+        loop {
+            attempts += 1;
+            match self
+                .downloader
+                .fetch(dir, asset, on_progress.clone())
+                .await
+            {
+                Ok(file) => {
+                    return (
+                        DownloadStatus::Downloaded(file.path.clone()),
+                        attempts,
+                        Some(file),
+                    );
+                }
+                Err(e) if e.is_retryable() && attempts <= self.max_retries => {
+                    let Some(delay) = Self::retry_delay(&e, attempts) else {
+                        return (DownloadStatus::Failed(e.to_string()), attempts, None);
+                    };
+                    warn!(
+                        "Download of {}:{} failed ({}), retrying in {:?}",
+                        asset.provider, asset.id, e, delay
+                    );
+                    self.emit(QueueEvent::Retrying {
+                        index,
+                        attempt: attempts + 1,
+                        error: e.to_string(),
+                    });
+                    tokio::time::sleep(delay).await;
+                }
+                Err(e) => return (DownloadStatus::Failed(e.to_string()), attempts, None),
+            }
+        }
+    }
+
+    /// Delay before retrying after `attempts` failed attempts, or `None` if
+    /// the server asked for a longer wait than [`MAX_RETRY_DELAY`].
+    fn retry_delay(error: &DxError, attempts: u32) -> Option<Duration> {
+        match error {
+            DxError::RateLimited {
+                retry_after_secs, ..
+            } => Some(Duration::from_secs(*retry_after_secs)).filter(|d| *d <= MAX_RETRY_DELAY),
+            _ => Some(Duration::from_millis(
+                500 * 2u64.pow(attempts.saturating_sub(1).min(6)),
+            )),
+        }
+    }
+
+    /// Order asset indices so consecutive entries come from different hosts.
+    fn interleave_by_host(assets: &[MediaAsset], indices: Vec<usize>) -> Vec<usize> {
+        let mut hosts: Vec<String> = Vec::new();
+        let mut queues: HashMap<String, VecDeque<usize>> = HashMap::new();
+        for index in indices {
+            let host = Self::host_of(&assets[index]);
+            if !queues.contains_key(&host) {
+                hosts.push(host.clone());
+            }
+            queues.entry(host).or_default().push_back(index);
+        }
+
+        let total = queues.values().map(VecDeque::len).sum();
+        let mut ordered = Vec::with_capacity(total);
+        while ordered.len() < total {
+            for host in &hosts {
+                if let Some(index) = queues.get_mut(host).and_then(VecDeque::pop_front) {
+                    ordered.push(index);
+                }
+            }
+        }
+        ordered
+    }
+
+    /// Host an asset will be downloaded from.
+    fn host_of(asset: &MediaAsset) -> String {
+        url::Url::parse(&asset.download_url)
+            .ok()
+            .and_then(|u| u.host_str().map(str::to_lowercase))
+            .unwrap_or_default()
+    }
+
+    fn emit(&self, event: QueueEvent) {
+        if let Some(cb) = &self.on_event {
+            cb(event);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::MediaType;
+
+    fn asset(id: &str, url: &str) -> MediaAsset {
+        MediaAsset::builder()
+            .id(id)
+            .provider("test")
+            .media_type(MediaType::Image)
+            .title(id)
+            .download_url(url)
+            .source_url(url)
+            .build()
+    }
+
+    #[test]
+    fn test_interleave_by_host() {
+        let assets = vec![
+            asset("a1", "https://a.example/1.jpg"),
+            asset("a2", "https://a.example/2.jpg"),
+            asset("a3", "https://a.example/3.jpg"),
+            asset("b1", "https://b.example/1.jpg"),
+            asset("c1", "https://c.example/1.jpg"),
+        ];
+
+        let ordered = DownloadQueue::interleave_by_host(&assets, (0..assets.len()).collect());
+        let ids: Vec<&str> = ordered.iter().map(|&i| assets[i].id.as_str()).collect();
+
+        assert_eq!(ids, vec!["a1", "b1", "c1", "a2", "a3"]);
+    }
+
+    #[test]
+    fn test_retry_delay_honours_rate_limit() {
+        let error = DxError::RateLimited {
+            provider: "test".to_string(),
+            retry_after_secs: 7,
+        };
+        assert_eq!(
+            DownloadQueue::retry_delay(&error, 1),
+            Some(Duration::from_secs(7))
+        );
+        assert_eq!(
+            DownloadQueue::retry_delay(&DxError::http("boom"), 2),
+            Some(Duration::from_secs(1))
+        );
+
+        // A day-long wait fails the asset instead of stalling the batch
+        let error = DxError::RateLimited {
+            provider: "test".to_string(),
+            retry_after_secs: 86_400,
+        };
+        assert_eq!(DownloadQueue::retry_delay(&error, 1), None);
+    }
+}
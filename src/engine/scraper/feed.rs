@@ -0,0 +1,783 @@
+//! RSS and Atom feeds.
+//!
+//! RSS 2.0, RSS 1.0 and Atom feeds are read, along with the Media RSS
+//! (`media:content`, `media:group`, `media:thumbnail`), iTunes and Creative
+//! Commons (`cc:license`) extensions. Each item's media files become assets:
+//! `media:content` first, then `enclosure`s, falling back to the item's
+//! `media:thumbnail` when it has neither.
+
+use std::collections::HashSet;
+
+use roxmltree::{Document, Node};
+use tracing::debug;
+use url::Url;
+
+use super::{
+    ScrapeOptions, ScrapeResult, Scraper, Source, collapse_whitespace, file_stem,
+    media_type_for_extension,
+};
+use crate::cache::fnv1a;
+use crate::engine::content_type::extension_from_url;
+use crate::error::{DxError, Result};
+use crate::http::HttpClient;
+use crate::scraping::ScrapingTarget;
+use crate::types::{License, MediaAsset, MediaType, RateLimitConfig};
+
+/// Namespace of the Media RSS extension. Some feeds drop the trailing slash.
+const MEDIA_NS: &str = "http://search.yahoo.com/mrss";
+
+/// A parsed RSS or Atom feed.
+#[derive(Debug, Clone, Default)]
+pub struct Feed {
+    title: Option<String>,
+    link: Option<String>,
+    license: Option<License>,
+    items: Vec<FeedItem>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct FeedItem {
+    title: Option<String>,
+    link: Option<String>,
+    author: Option<String>,
+    license: Option<License>,
+    tags: Vec<String>,
+    thumbnail: Option<String>,
+    duration_secs: Option<u32>,
+    media: Vec<FeedMedia>,
+}
+
+/// One media file of an item.
+#[derive(Debug, Clone, Default)]
+struct FeedMedia {
+    url: String,
+    mime_type: Option<String>,
+    media_type: Option<MediaType>,
+    width: Option<u32>,
+    height: Option<u32>,
+    duration_secs: Option<u32>,
+    file_size: Option<u64>,
+    title: Option<String>,
+    thumbnail: Option<String>,
+    license: Option<License>,
+    author: Option<String>,
+}
+
+impl Feed {
+    /// Fetch and parse a feed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails or the response isn't a feed.
+    pub async fn fetch(client: &HttpClient, url: &str) -> Result<Self> {
+        let response = client.get_raw(url).await?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(DxError::http(format!("{} returned {}", url, status)));
+        }
+        Self::parse(&response.text().await?, url)
+    }
+
+    /// Parse an RSS 2.0, RSS 1.0 or Atom document. `url` resolves relative
+    /// links and names the feed in errors.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the document isn't well-formed XML or isn't a feed.
+    pub fn parse(xml: &str, url: &str) -> Result<Self> {
+        let document = Document::parse(xml).map_err(|e| DxError::XmlParse {
+            url: url.to_string(),
+            message: e.to_string(),
+        })?;
+        let root = document.root_element();
+        let base = Url::parse(url).ok();
+
+        let (channel, items): (Option<Node>, Vec<Node>) = match root.tag_name().name() {
+            "rss" => {
+                let channel = element(root, "channel");
+                (
+                    channel,
+                    channel
+                        .map(|c| elements(c, "item").collect())
+                        .unwrap_or_default(),
+                )
+            }
+            // RSS 1.0 lists items next to the channel
+            "RDF" => (element(root, "channel"), elements(root, "item").collect()),
+            "feed" => (Some(root), elements(root, "entry").collect()),
+            other => {
+                return Err(DxError::XmlParse {
+                    url: url.to_string(),
+                    message: format!("expected <rss>, <rdf:RDF> or <feed>, found <{other}>"),
+                });
+            }
+        };
+
+        let resolve = |href: &str| resolve(base.as_ref(), href);
+        Ok(Self {
+            title: channel.and_then(|c| text(c, "title")),
+            link: channel.and_then(|c| link(c, &resolve)),
+            license: channel.and_then(license),
+            items: items
+                .into_iter()
+                .map(|item| parse_item(item, &resolve))
+                .collect(),
+        })
+    }
+
+    /// The feed's title.
+    #[must_use]
+    pub fn title(&self) -> Option<&str> {
+        self.title.as_deref()
+    }
+
+    /// Assets for every media file in the feed, in feed order.
+    ///
+    /// Licenses come from the media file, its item or the feed, falling back
+    /// to `license`.
+    #[must_use]
+    pub fn assets(&self, provider: &str, license: &License) -> Vec<MediaAsset> {
+        let mut seen = HashSet::new();
+        self.items
+            .iter()
+            .flat_map(|item| item.media.iter().map(move |media| (item, media)))
+            .filter(|(_, media)| seen.insert(media.url.clone()))
+            .filter_map(|(item, media)| self.asset(item, media, provider, license))
+            .collect()
+    }
+
+    fn asset(
+        &self,
+        item: &FeedItem,
+        media: &FeedMedia,
+        provider: &str,
+        license: &License,
+    ) -> Option<MediaAsset> {
+        let media_type = media
+            .media_type
+            .or_else(|| media.mime_type.as_deref().and_then(media_type_for_mime))
+            .or_else(|| extension_from_url(&media.url).and_then(media_type_for_extension))?;
+
+        let title = media
+            .title
+            .as_ref()
+            .or(item.title.as_ref())
+            .map(|t| collapse_whitespace(t))
+            .filter(|t| !t.is_empty())
+            .unwrap_or_else(|| file_stem(&media.url).replace(['-', '_'], " "));
+        let license = media
+            .license
+            .as_ref()
+            .or(item.license.as_ref())
+            .or(self.license.as_ref())
+            .unwrap_or(license)
+            .clone();
+        let source_url = item
+            .link
+            .as_ref()
+            .or(self.link.as_ref())
+            .unwrap_or(&media.url);
+
+        let mut builder = MediaAsset::builder()
+            .id(media_id(&media.url))
+            .provider(provider)
+            .media_type(media_type)
+            .title(title)
+            .download_url(&media.url)
+            .source_url(source_url)
+            .license(license)
+            .tags(item.tags.clone());
+        if let Some(thumbnail) = media.thumbnail.as_ref().or(item.thumbnail.as_ref()) {
+            if *thumbnail != media.url {
+                builder = builder.preview_url(thumbnail);
+            }
+        }
+        if let Some(author) = media.author.as_ref().or(item.author.as_ref()) {
+            builder = builder.author(author);
+        }
+        if let (Some(width), Some(height)) = (media.width, media.height) {
+            builder = builder.dimensions(width, height);
+        }
+        if let Some(duration) = media.duration_secs.or(item.duration_secs) {
+            if matches!(media_type, MediaType::Video | MediaType::Audio) {
+                builder = builder.duration(duration);
+            }
+        }
+        if let Some(size) = media.file_size.filter(|size| *size > 0) {
+            builder = builder.file_size(size);
+        }
+        if let Some(ref mime) = media.mime_type {
+            builder = builder.mime_type(mime);
+        }
+        Some(builder.build())
+    }
+}
+
+impl Scraper {
+    /// Read the media in an RSS or Atom feed.
+    ///
+    /// Only `options.media_types`, `options.max_assets` and `options.pattern`
+    /// apply.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the feed can't be fetched or parsed.
+    pub async fn read_feed(&self, url: &str, options: &ScrapeOptions) -> Result<ScrapeResult> {
+        let source = Source {
+            provider: "scraper",
+            license: License::Other("Unknown - Check source".to_string()),
+            rate_limit: RateLimitConfig::default(),
+        };
+        self.read(url, "", &source, options).await
+    }
+
+    /// Read a feed-based target, with the target's license and request pacing.
+    ///
+    /// The query is filled into the target's search URL. Targets without one
+    /// serve a single feed, whose items are matched against the query.
+    ///
+    /// # Errors
+    ///
+    /// As for [`Scraper::read_feed`].
+    pub async fn read_target_feed(
+        &self,
+        target: &ScrapingTarget,
+        query: &str,
+        options: &ScrapeOptions,
+    ) -> Result<ScrapeResult> {
+        let source = Source {
+            provider: target.id,
+            license: target.license(),
+            rate_limit: target.rate_limit(),
+        };
+        let filter = if target.search_url.is_some() {
+            ""
+        } else {
+            query
+        };
+        self.read(&target.search_url_for(query), filter, &source, options)
+            .await
+    }
+
+    async fn read(
+        &self,
+        url: &str,
+        query: &str,
+        source: &Source<'_>,
+        options: &ScrapeOptions,
+    ) -> Result<ScrapeResult> {
+        let feed_url = Url::parse(url).map_err(|e| DxError::InvalidQuery {
+            message: format!("Invalid feed URL: {e}"),
+        })?;
+        let mut result = ScrapeResult {
+            source_url: url.to_string(),
+            assets: Vec::new(),
+            pages_scraped: 0,
+            errors: Vec::new(),
+        };
+
+        let client = self.client.clone().with_rate_limit(source.rate_limit);
+        let Some(client) = self.polite_client(&client, &feed_url, options).await else {
+            result.errors.push(format!("Skipped {url}: disallowed by robots.txt"));
+            return Ok(result);
+        };
+        let feed = Feed::fetch(&client, url).await?;
+        result.pages_scraped = 1;
+
+        self.add_feed_assets(&feed, query, source, options, &mut result);
+        Ok(result)
+    }
+
+    /// Add a feed's assets matching `query` to a scrape, honoring the scrape
+    /// options. An empty query matches every asset.
+    pub(super) fn add_feed_assets(
+        &self,
+        feed: &Feed,
+        query: &str,
+        source: &Source<'_>,
+        options: &ScrapeOptions,
+        result: &mut ScrapeResult,
+    ) {
+        let mut seen: HashSet<String> = result
+            .assets
+            .iter()
+            .map(|a| a.download_url.clone())
+            .collect();
+        let assets = feed.assets(source.provider, &source.license);
+        debug!(
+            "Feed {} lists {} media files",
+            result.source_url,
+            assets.len()
+        );
+
+        for asset in assets {
+            if result.assets.len() >= options.max_assets {
+                break;
+            }
+            let wanted = options.media_types.contains(&asset.media_type)
+                && matches_query(&asset, query)
+                && options
+                    .pattern
+                    .as_ref()
+                    .is_none_or(|pattern| self.matches_pattern(&asset.download_url, pattern));
+            if wanted && seen.insert(asset.download_url.clone()) {
+                result.assets.push(asset);
+            }
+        }
+    }
+}
+
+/// Whether a response looks like an RSS or Atom feed rather than a web page.
+pub(super) fn is_feed(content_type: Option<&str>, body: &str) -> bool {
+    let mime = content_type
+        .and_then(|ct| ct.split(';').next())
+        .map(|mime| mime.trim().to_ascii_lowercase());
+    if let Some(ref mime) = mime {
+        if [
+            "application/rss+xml",
+            "application/atom+xml",
+            "application/rdf+xml",
+        ]
+        .contains(&mime.as_str())
+        {
+            return true;
+        }
+        if mime.contains("html") {
+            return false;
+        }
+    }
+
+    let body = body.trim_start_matches('\u{feff}').trim_start();
+    let head: String = body.chars().take(1024).collect();
+    head.starts_with('<')
+        && ["<rss", "<feed", "<rdf:RDF"]
+            .iter()
+            .any(|tag| head.contains(tag))
+}
+
+fn parse_item(item: Node, resolve: &impl Fn(&str) -> Option<String>) -> FeedItem {
+    let mut parsed = FeedItem {
+        title: text(item, "title"),
+        link: link(item, resolve),
+        author: element(item, "author")
+            .and_then(|author| text(author, "name").or_else(|| node_text(author)))
+            .or_else(|| text(item, "creator"))
+            .or_else(|| media_text(item, "credit")),
+        license: license(item),
+        tags: tags(item),
+        thumbnail: thumbnail(item, resolve).or_else(|| {
+            // <itunes:image href="...">
+            elements(item, "image").find_map(|image| image.attribute("href").and_then(resolve))
+        }),
+        duration_secs: text(item, "duration").as_deref().and_then(parse_duration),
+        media: Vec::new(),
+    };
+
+    // media:content, on its own or grouped with shared details
+    for group in std::iter::once(item).chain(media_elements(item, "group")) {
+        let shared = FeedMedia {
+            title: media_text(group, "title"),
+            thumbnail: thumbnail(group, resolve),
+            license: media_license(group),
+            author: media_text(group, "credit"),
+            ..FeedMedia::default()
+        };
+        for content in media_elements(group, "content") {
+            if let Some(media) = media_content(content, &shared, resolve) {
+                parsed.media.push(media);
+            }
+        }
+    }
+
+    // RSS <enclosure> and Atom <link rel="enclosure">
+    let enclosures = elements(item, "enclosure")
+        .filter_map(|e| Some((e, e.attribute("url")?)))
+        .chain(
+            elements(item, "link")
+                .filter(|l| l.attribute("rel") == Some("enclosure"))
+                .filter_map(|l| Some((l, l.attribute("href")?))),
+        );
+    for (node, href) in enclosures {
+        let Some(url) = resolve(href) else { continue };
+        let mime_type = node.attribute("type").map(String::from);
+        let file_size = node.attribute("length").and_then(|l| l.trim().parse().ok());
+        // Feeds often repeat a media:content file as the enclosure
+        if let Some(media) = parsed.media.iter_mut().find(|m| m.url == url) {
+            media.mime_type = media.mime_type.take().or(mime_type);
+            media.file_size = media.file_size.or(file_size);
+            continue;
+        }
+        parsed.media.push(FeedMedia {
+            url,
+            mime_type,
+            file_size,
+            ..FeedMedia::default()
+        });
+    }
+
+    if parsed.media.is_empty() {
+        if let Some(ref thumbnail) = parsed.thumbnail {
+            parsed.media.push(FeedMedia {
+                url: thumbnail.clone(),
+                media_type: Some(MediaType::Image),
+                ..FeedMedia::default()
+            });
+        }
+    }
+    parsed
+}
+
+fn media_content(
+    content: Node,
+    shared: &FeedMedia,
+    resolve: &impl Fn(&str) -> Option<String>,
+) -> Option<FeedMedia> {
+    let number = |attr: &str| {
+        content
+            .attribute(attr)
+            .and_then(|v| v.trim().parse::<u32>().ok())
+    };
+    Some(FeedMedia {
+        url: content.attribute("url").and_then(resolve)?,
+        mime_type: content.attribute("type").map(String::from),
+        media_type: content.attribute("medium").and_then(|medium| match medium {
+            "image" => Some(MediaType::Image),
+            "video" => Some(MediaType::Video),
+            "audio" => Some(MediaType::Audio),
+            "document" => Some(MediaType::Document),
+            _ => None,
+        }),
+        width: number("width"),
+        height: number("height"),
+        duration_secs: content.attribute("duration").and_then(parse_duration),
+        file_size: content
+            .attribute("fileSize")
+            .and_then(|v| v.trim().parse().ok()),
+        title: media_text(content, "title").or_else(|| shared.title.clone()),
+        thumbnail: thumbnail(content, resolve).or_else(|| shared.thumbnail.clone()),
+        license: media_license(content).or_else(|| shared.license.clone()),
+        author: media_text(content, "credit").or_else(|| shared.author.clone()),
+    })
+}
+
+/// The page link of a channel or item: RSS `<link>` text or Atom's
+/// alternate `<link href>`.
+fn link(node: Node, resolve: &impl Fn(&str) -> Option<String>) -> Option<String> {
+    elements(node, "link").find_map(|link| match link.attribute("href") {
+        Some(href) => matches!(link.attribute("rel"), None | Some("alternate"))
+            .then(|| resolve(href))
+            .flatten(),
+        None => node_text(link).and_then(|text| resolve(&text)),
+    })
+}
+
+/// The license of a channel or item, from `media:license`, `cc:license`,
+/// `creativeCommons:license` or an Atom `<link rel="license">`.
+fn license(node: Node) -> Option<License> {
+    media_license(node)
+        .or_else(|| {
+            elements(node, "license")
+                .find_map(|l| {
+                    attribute(l, "resource")
+                        .map(String::from)
+                        .or_else(|| node_text(l))
+                })
+                .map(|name| License::parse(&name))
+        })
+        .or_else(|| {
+            elements(node, "link")
+                .find(|l| l.attribute("rel") == Some("license"))
+                .and_then(|l| l.attribute("href"))
+                .map(License::parse)
+        })
+}
+
+/// `<media:license href="...">Name</media:license>`, preferring the URL.
+fn media_license(node: Node) -> Option<License> {
+    media_elements(node, "license").find_map(|l| {
+        let href = l.attribute("href").and_then(License::from_url);
+        href.or_else(|| node_text(l).map(|name| License::parse(&name)))
+    })
+}
+
+fn thumbnail(node: Node, resolve: &impl Fn(&str) -> Option<String>) -> Option<String> {
+    media_elements(node, "thumbnail").find_map(|t| t.attribute("url").and_then(resolve))
+}
+
+/// RSS `<category>`, Atom `<category term>` and `media:keywords`.
+fn tags(item: Node) -> Vec<String> {
+    let mut tags: Vec<String> = elements(item, "category")
+        .filter_map(|c| {
+            c.attribute("term")
+                .map(String::from)
+                .or_else(|| node_text(c))
+        })
+        .collect();
+    if let Some(keywords) = media_text(item, "keywords") {
+        tags.extend(
+            keywords
+                .split(',')
+                .map(str::trim)
+                .filter(|k| !k.is_empty())
+                .map(String::from),
+        );
+    }
+    let mut seen = HashSet::new();
+    tags.retain(|tag| seen.insert(tag.to_lowercase()));
+    tags
+}
+
+/// Child elements with a local name, outside the Media RSS namespace.
+fn elements<'a, 'input>(
+    node: Node<'a, 'input>,
+    name: &'static str,
+) -> impl Iterator<Item = Node<'a, 'input>> {
+    node.children().filter(move |child| {
+        child.is_element() && child.tag_name().name() == name && !is_media(*child)
+    })
+}
+
+/// Media RSS child elements with a local name.
+fn media_elements<'a, 'input>(
+    node: Node<'a, 'input>,
+    name: &'static str,
+) -> impl Iterator<Item = Node<'a, 'input>> {
+    node.children().filter(move |child| {
+        child.is_element() && child.tag_name().name() == name && is_media(*child)
+    })
+}
+
+fn is_media(node: Node) -> bool {
+    node.tag_name()
+        .namespace()
+        .is_some_and(|ns| ns.starts_with(MEDIA_NS))
+}
+
+fn element<'a, 'input>(node: Node<'a, 'input>, name: &'static str) -> Option<Node<'a, 'input>> {
+    elements(node, name).next()
+}
+
+fn text(node: Node, name: &'static str) -> Option<String> {
+    elements(node, name).find_map(node_text)
+}
+
+fn media_text(node: Node, name: &'static str) -> Option<String> {
+    media_elements(node, name).find_map(node_text)
+}
+
+/// The trimmed text of an element, including CDATA sections.
+fn node_text(node: Node) -> Option<String> {
+    let text: String = node
+        .descendants()
+        .filter(Node::is_text)
+        .filter_map(|n| n.text())
+        .collect();
+    let text = text.trim();
+    (!text.is_empty()).then(|| text.to_string())
+}
+
+/// An attribute by local name, whatever its namespace (e.g. `rdf:resource`).
+fn attribute<'a>(node: Node<'a, '_>, name: &str) -> Option<&'a str> {
+    node.attributes()
+        .find(|a| a.name() == name)
+        .map(|a| a.value())
+}
+
+fn resolve(base: Option<&Url>, href: &str) -> Option<String> {
+    let href = href.trim();
+    if href.is_empty() {
+        return None;
+    }
+    match base {
+        Some(base) => base.join(href).ok().map(String::from),
+        None => Url::parse(href).ok().map(String::from),
+    }
+}
+
+/// Parse a duration in seconds (`"93"`, `"93.5"`) or as `[HH:]MM:SS`.
+fn parse_duration(value: &str) -> Option<u32> {
+    let mut total = 0.0_f64;
+    for part in value.trim().split(':') {
+        let part: f64 = part.trim().parse().ok()?;
+        if part < 0.0 {
+            return None;
+        }
+        total = total * 60.0 + part;
+    }
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    Some(total.round().min(f64::from(u32::MAX)) as u32)
+}
+
+fn media_type_for_mime(mime: &str) -> Option<MediaType> {
+    let mime = mime.split(';').next()?.trim().to_ascii_lowercase();
+    match mime.as_str() {
+        "image/gif" => Some(MediaType::Gif),
+        "image/svg+xml" => Some(MediaType::Vector),
+        "application/pdf" => Some(MediaType::Document),
+        _ => match mime.split('/').next()? {
+            "image" => Some(MediaType::Image),
+            "video" => Some(MediaType::Video),
+            "audio" => Some(MediaType::Audio),
+            "model" => Some(MediaType::Model3D),
+            _ => None,
+        },
+    }
+}
+
+/// Asset ID for a feed's media file.
+///
+/// Podcast enclosures often share a file name (`audio.mp3`, `episode.mp3`),
+/// so the stem is suffixed with a hash of the full URL to keep IDs unique.
+fn media_id(url: &str) -> String {
+    format!("{}-{:08x}", file_stem(url), fnv1a(url.as_bytes()) & 0xffff_ffff)
+}
+
+/// Whether every word of `query` appears in an asset's title or tags.
+pub(crate) fn matches_query(asset: &MediaAsset, query: &str) -> bool {
+    let haystack = format!("{} {}", asset.title, asset.tags.join(" ")).to_lowercase();
+    query
+        .to_lowercase()
+        .split_whitespace()
+        .all(|word| haystack.contains(word))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RSS: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+        <rss version="2.0"
+             xmlns:media="http://search.yahoo.com/mrss/"
+             xmlns:cc="http://web.resource.org/cc/"
+             xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#"
+             xmlns:itunes="http://www.itunes.com/dtds/podcast-1.0.dtd">
+          <channel>
+            <title>Wildlife</title>
+            <link>https://example.com/</link>
+            <cc:license rdf:resource="https://creativecommons.org/licenses/by-sa/4.0/"/>
+            <item>
+              <title>Red fox</title>
+              <link>https://example.com/fox</link>
+              <category>fox</category>
+              <media:keywords>fox, snow</media:keywords>
+              <media:group>
+                <media:title>Fox in snow</media:title>
+                <media:thumbnail url="/thumbs/fox.jpg"/>
+                <media:license href="https://creativecommons.org/publicdomain/zero/1.0/">CC0</media:license>
+                <media:content url="https://cdn.example.com/fox.jpg" medium="image" width="4000" height="3000" fileSize="123456"/>
+                <media:content url="https://cdn.example.com/fox.mp4" type="video/mp4" duration="12.4"/>
+              </media:group>
+              <enclosure url="https://cdn.example.com/fox.jpg" type="image/jpeg" length="123456"/>
+            </item>
+            <item>
+              <title>Owl calls</title>
+              <itunes:duration>01:02:03</itunes:duration>
+              <enclosure url="https://cdn.example.com/owl.mp3" type="audio/mpeg" length="0"/>
+            </item>
+            <item>
+              <title>Just a thumbnail</title>
+              <media:thumbnail url="https://cdn.example.com/hen.jpg" width="150" height="100"/>
+            </item>
+            <item><title>No media</title><description>Text only</description></item>
+          </channel>
+        </rss>"#;
+
+    #[test]
+    fn test_parse_media_rss() {
+        let feed = Feed::parse(RSS, "https://example.com/feed.xml").unwrap();
+        assert_eq!(feed.title(), Some("Wildlife"));
+
+        let assets = feed.assets("wildlife", &License::default());
+        let urls: Vec<_> = assets.iter().map(|a| a.download_url.as_str()).collect();
+        assert_eq!(
+            urls,
+            [
+                "https://cdn.example.com/fox.jpg",
+                "https://cdn.example.com/fox.mp4",
+                "https://cdn.example.com/owl.mp3",
+                "https://cdn.example.com/hen.jpg",
+            ]
+        );
+
+        let fox = &assets[0];
+        assert_eq!(fox.title, "Fox in snow");
+        assert_eq!(fox.media_type, MediaType::Image);
+        assert_eq!((fox.width, fox.height), (Some(4000), Some(3000)));
+        assert_eq!(fox.file_size, Some(123_456));
+        assert_eq!(fox.license, License::Cc0);
+        assert_eq!(fox.source_url, "https://example.com/fox");
+        assert_eq!(
+            fox.preview_url.as_deref(),
+            Some("https://example.com/thumbs/fox.jpg")
+        );
+        assert_eq!(fox.tags, ["fox", "snow"]);
+
+        assert_eq!(assets[1].media_type, MediaType::Video);
+        assert_eq!(assets[1].duration_secs, Some(12));
+
+        let owl = &assets[2];
+        assert_eq!(owl.media_type, MediaType::Audio);
+        assert_eq!(owl.duration_secs, Some(3723));
+        assert_eq!(owl.file_size, None);
+        assert_eq!(
+            owl.license,
+            License::CcBySa,
+            "falls back to the channel license"
+        );
+        assert_eq!(owl.source_url, "https://example.com/");
+
+        assert_eq!(assets[3].media_type, MediaType::Image);
+        assert_eq!(assets[3].title, "Just a thumbnail");
+    }
+
+    #[test]
+    fn test_parse_atom() {
+        let xml = r#"<?xml version="1.0" encoding="utf-8"?>
+            <feed xmlns="http://www.w3.org/2005/Atom">
+              <title>Podcast</title>
+              <link href="https://example.org/"/>
+              <entry>
+                <title>Episode 1</title>
+                <link rel="alternate" href="https://example.org/ep1"/>
+                <link rel="enclosure" href="https://example.org/ep1.ogg" type="audio/ogg" length="2048"/>
+                <link rel="license" href="https://creativecommons.org/licenses/by/4.0/"/>
+                <author><name>Jo</name></author>
+                <category term="nature"/>
+                <content type="html">&lt;p&gt;Not media&lt;/p&gt;</content>
+              </entry>
+            </feed>"#;
+        let feed = Feed::parse(xml, "https://example.org/atom.xml").unwrap();
+        let assets = feed.assets("podcast", &License::default());
+
+        assert_eq!(assets.len(), 1);
+        assert_eq!(assets[0].title, "Episode 1");
+        assert_eq!(assets[0].source_url, "https://example.org/ep1");
+        assert_eq!(assets[0].media_type, MediaType::Audio);
+        assert_eq!(assets[0].file_size, Some(2048));
+        assert_eq!(assets[0].license, License::CcBy);
+        assert_eq!(assets[0].author.as_deref(), Some("Jo"));
+        assert_eq!(assets[0].tags, ["nature"]);
+
+        assert!(Feed::parse("<html><body/></html>", "https://example.org/").is_err());
+    }
+
+    #[test]
+    fn test_is_feed_and_duration() {
+        assert!(is_feed(Some("application/rss+xml; charset=utf-8"), ""));
+        assert!(is_feed(
+            Some("text/xml"),
+            "\u{feff}<?xml version=\"1.0\"?>\n<rss version=\"2.0\">"
+        ));
+        assert!(is_feed(
+            None,
+            "<feed xmlns=\"http://www.w3.org/2005/Atom\">"
+        ));
+        assert!(!is_feed(Some("text/html"), "<rss>"));
+        assert!(!is_feed(None, "<!DOCTYPE html><html>"));
+
+        assert_eq!(parse_duration("93"), Some(93));
+        assert_eq!(parse_duration("4:05"), Some(245));
+        assert_eq!(parse_duration("1:00:00"), Some(3600));
+        assert_eq!(parse_duration("soon"), None);
+    }
+}
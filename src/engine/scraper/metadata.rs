@@ -0,0 +1,512 @@
+//! Structured metadata embedded in HTML pages.
+//!
+//! Pages often describe their media better than file names do: schema.org
+//! `ImageObject`/`VideoObject`/`AudioObject` JSON-LD, OpenGraph
+//! `og:image`/`og:video` tags, `rel="license"` links and `<figure>` captions.
+//! [`PageMetadata`] gathers these per media URL so the scraper can attach
+//! author, license, dimensions, duration and captions to the assets it emits.
+
+use std::collections::HashMap;
+
+use scraper::{ElementRef, Html, Selector};
+use serde_json::Value;
+use url::{Origin, Url};
+
+use super::collapse_whitespace;
+use crate::types::{License, MediaAsset};
+
+/// What a page says about one of its media files.
+#[derive(Debug, Clone, Default, PartialEq)]
+struct MediaMetadata {
+    caption: Option<String>,
+    author: Option<String>,
+    author_url: Option<String>,
+    license: Option<License>,
+    width: Option<u32>,
+    height: Option<u32>,
+    duration_secs: Option<u32>,
+}
+
+impl MediaMetadata {
+    /// Fill fields not yet known from `other`; earlier sources take precedence.
+    fn merge(&mut self, other: Self) {
+        self.caption = self.caption.take().or(other.caption);
+        self.author = self.author.take().or(other.author);
+        self.author_url = self.author_url.take().or(other.author_url);
+        self.license = self.license.take().or(other.license);
+        self.width = self.width.or(other.width);
+        self.height = self.height.or(other.height);
+        self.duration_secs = self.duration_secs.or(other.duration_secs);
+    }
+}
+
+/// Metadata for the media of one page, keyed by absolute media URL.
+#[derive(Debug, Default)]
+pub(super) struct PageMetadata {
+    /// License of the whole page, from a `rel="license"` link or JSON-LD.
+    license: Option<License>,
+    /// Origin of the page, whose own media the page license covers.
+    origin: Option<Origin>,
+    media: HashMap<String, MediaMetadata>,
+}
+
+impl PageMetadata {
+    /// Read JSON-LD, OpenGraph, `rel="license"` and `<figure>` metadata.
+    ///
+    /// JSON-LD is the most specific source, so it wins over OpenGraph, which
+    /// wins over figure captions.
+    pub(super) fn parse(document: &Html, base_url: &Url) -> Self {
+        let mut page = Self {
+            origin: Some(base_url.origin()),
+            ..Self::default()
+        };
+        page.read_json_ld(document, base_url);
+        page.read_open_graph(document, base_url);
+        page.read_figures(document, base_url);
+
+        if page.license.is_none() {
+            let selector =
+                Selector::parse("a[rel~='license'][href], link[rel~='license'][href]").unwrap();
+            page.license = document
+                .select(&selector)
+                .filter_map(|link| link.value().attr("href"))
+                .find_map(|href| base_url.join(href).ok())
+                .map(|href| License::parse(href.as_str()));
+        }
+        page
+    }
+
+    /// Attach what's known about an asset's URL, and the page license.
+    ///
+    /// The page license only covers media served from the page's own origin
+    /// that has no license of its own; off-site images such as ads, logos or
+    /// media embedded from elsewhere keep theirs.
+    pub(super) fn apply(&self, asset: &mut MediaAsset) {
+        let url = Url::parse(&asset.download_url).ok();
+        if let Some(license) = &self.license {
+            let same_origin = url.as_ref().map(Url::origin) == self.origin;
+            if same_origin && !asset.license.is_known() {
+                asset.license = license.clone();
+            }
+        }
+        let Some(media) = url.and_then(|url| self.media.get(url.as_str())) else {
+            return;
+        };
+
+        if let Some(caption) = &media.caption {
+            asset.title.clone_from(caption);
+        }
+        if media.author.is_some() {
+            asset.author.clone_from(&media.author);
+            asset.author_url.clone_from(&media.author_url);
+        }
+        if let Some(license) = &media.license {
+            asset.license = license.clone();
+        }
+        // Unknown image dimensions are recorded as 0
+        if let (Some(width), Some(height)) = (media.width, media.height) {
+            if asset.width.unwrap_or(0) == 0 || asset.height.unwrap_or(0) == 0 {
+                asset.width = Some(width);
+                asset.height = Some(height);
+            }
+        }
+        asset.duration_secs = asset.duration_secs.or(media.duration_secs);
+    }
+
+    fn add(&mut self, base_url: &Url, url: &str, metadata: MediaMetadata) {
+        if let Ok(url) = base_url.join(url.trim()) {
+            self.media.entry(url.into()).or_default().merge(metadata);
+        }
+    }
+
+    fn read_json_ld(&mut self, document: &Html, base_url: &Url) {
+        let selector = Selector::parse("script[type='application/ld+json']").unwrap();
+        for script in document.select(&selector) {
+            let text = script.text().collect::<String>();
+            // Pages in the wild often carry broken JSON-LD; ignore it
+            if let Ok(json) = serde_json::from_str::<Value>(&text) {
+                self.read_json_ld_value(&json, base_url, 0);
+            }
+        }
+    }
+
+    fn read_json_ld_value(&mut self, value: &Value, base_url: &Url, depth: usize) {
+        if depth > 8 {
+            return;
+        }
+        match value {
+            Value::Array(items) => {
+                for item in items {
+                    self.read_json_ld_value(item, base_url, depth + 1);
+                }
+            }
+            Value::Object(object) => {
+                if is_media_object(value) {
+                    let metadata = MediaMetadata {
+                        caption: text(object.get("caption"))
+                            .or_else(|| text(object.get("name")))
+                            .or_else(|| text(object.get("description"))),
+                        author: object
+                            .get("creator")
+                            .or_else(|| object.get("author"))
+                            .and_then(person_name),
+                        author_url: object
+                            .get("creator")
+                            .or_else(|| object.get("author"))
+                            .and_then(person_url),
+                        license: object.get("license").and_then(license),
+                        width: object.get("width").and_then(pixels),
+                        height: object.get("height").and_then(pixels),
+                        duration_secs: text(object.get("duration"))
+                            .and_then(|d| parse_iso_duration(&d)),
+                    };
+                    for key in ["contentUrl", "url", "embedUrl"] {
+                        if let Some(url) = text(object.get(key)) {
+                            self.add(base_url, &url, metadata.clone());
+                        }
+                    }
+                } else if depth == 0 || object.contains_key("@type") {
+                    // A page-level license, e.g. on a WebPage or Article
+                    if self.license.is_none() {
+                        self.license = object.get("license").and_then(license);
+                    }
+                }
+                for (key, child) in object {
+                    if !key.starts_with('@') || key == "@graph" {
+                        self.read_json_ld_value(child, base_url, depth + 1);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// `og:image`, `og:video` and `og:audio` tags, whose structured properties
+    /// (`og:image:width` etc.) describe the most recent media tag.
+    fn read_open_graph(&mut self, document: &Html, base_url: &Url) {
+        let selector = Selector::parse("meta[property][content]").unwrap();
+        let mut current: Option<(String, MediaMetadata)> = None;
+
+        for meta in document.select(&selector) {
+            let property = meta
+                .value()
+                .attr("property")
+                .unwrap_or_default()
+                .to_ascii_lowercase();
+            let content = meta.value().attr("content").unwrap_or_default().trim();
+            let Some(rest) = property.strip_prefix("og:") else {
+                if property == "video:duration" {
+                    if let Some((_, metadata)) = &mut current {
+                        metadata.duration_secs = content.parse().ok();
+                    }
+                }
+                continue;
+            };
+
+            match rest.split_once(':') {
+                None if matches!(rest, "image" | "video" | "audio") => {
+                    if let Some((url, metadata)) = current.take() {
+                        self.add(base_url, &url, metadata);
+                    }
+                    current = Some((content.to_string(), MediaMetadata::default()));
+                }
+                Some(("image" | "video" | "audio", field)) => {
+                    let Some((url, metadata)) = &mut current else {
+                        continue;
+                    };
+                    match field {
+                        "url" | "secure_url" if url.is_empty() => *url = content.to_string(),
+                        "width" => metadata.width = content.parse().ok(),
+                        "height" => metadata.height = content.parse().ok(),
+                        "alt" if !content.is_empty() => {
+                            metadata.caption = Some(collapse_whitespace(content));
+                        }
+                        _ => {}
+                    }
+                }
+                _ => {}
+            }
+        }
+        if let Some((url, metadata)) = current {
+            self.add(base_url, &url, metadata);
+        }
+    }
+
+    /// Media inside `<figure>`, captioned by its `<figcaption>`. A `<cite>` or
+    /// `.credit` element in the caption names the author, and a
+    /// `rel="license"` link in the figure licenses its media.
+    fn read_figures(&mut self, document: &Html, base_url: &Url) {
+        let figure = Selector::parse("figure").unwrap();
+        let media = Selector::parse("img, video, audio, source").unwrap();
+        let caption = Selector::parse("figcaption").unwrap();
+        let credit = Selector::parse("cite, .credit, .author, [rel~='author']").unwrap();
+        let license_link = Selector::parse("a[rel~='license'][href]").unwrap();
+
+        for element in document.select(&figure) {
+            let figcaption = element.select(&caption).next();
+            let credit_element = figcaption.and_then(|c| c.select(&credit).next());
+            let metadata = MediaMetadata {
+                caption: figcaption
+                    .map(|c| caption_text(c, &[&credit, &license_link]))
+                    .filter(|c| !c.is_empty()),
+                author: credit_element
+                    .map(|c| collapse_whitespace(&c.text().collect::<String>()))
+                    .filter(|c| !c.is_empty()),
+                author_url: credit_element
+                    .and_then(|c| c.value().attr("href"))
+                    .and_then(|href| base_url.join(href).ok())
+                    .map(String::from),
+                license: element
+                    .select(&license_link)
+                    .filter_map(|link| link.value().attr("href"))
+                    .find_map(|href| base_url.join(href).ok())
+                    .map(|href| License::parse(href.as_str())),
+                ..MediaMetadata::default()
+            };
+
+            for media in element.select(&media) {
+                for url in media_urls(media) {
+                    self.add(base_url, url, metadata.clone());
+                }
+            }
+        }
+    }
+}
+
+/// The text of a caption without its credit and license links, or the
+/// "Photo:" style label left in front of them.
+fn caption_text(caption: ElementRef, skip: &[&Selector]) -> String {
+    const LABELS: [&str; 6] = ["photo by", "photo", "image", "credit", "by", "©"];
+    let is_separator = |c: char| c.is_whitespace() || "-–—|·,;:©".contains(c);
+
+    let text: String = caption
+        .descendants()
+        .filter_map(|node| {
+            let text = node.value().as_text()?;
+            let skipped = node
+                .ancestors()
+                .take_while(|ancestor| ancestor.id() != caption.id())
+                .filter_map(ElementRef::wrap)
+                .any(|element| skip.iter().any(|selector| selector.matches(&element)));
+            (!skipped).then_some(&**text)
+        })
+        .collect();
+    let text = collapse_whitespace(&text);
+    let mut text = text.trim_end_matches(is_separator);
+    if let Some(label) = LABELS.iter().find(|label| {
+        text.len() > label.len()
+            && text.is_char_boundary(text.len() - label.len())
+            && text[text.len() - label.len()..].eq_ignore_ascii_case(label)
+            && text[..text.len() - label.len()].ends_with(is_separator)
+    }) {
+        text = text[..text.len() - label.len()].trim_end_matches(is_separator);
+    }
+    text.to_string()
+}
+
+/// The `src` and `srcset` URLs of a media element.
+fn media_urls<'a>(element: ElementRef<'a>) -> impl Iterator<Item = &'a str> {
+    let srcset = element
+        .value()
+        .attr("srcset")
+        .or_else(|| element.value().attr("srcSet"))
+        .unwrap_or_default();
+    element.value().attr("src").into_iter().chain(
+        srcset
+            .split(',')
+            .filter_map(|candidate| candidate.split_whitespace().next()),
+    )
+}
+
+/// Whether a JSON-LD object is a schema.org media object.
+fn is_media_object(value: &Value) -> bool {
+    let is_media = |t: &Value| {
+        matches!(
+            t.as_str().map(|t| t.rsplit('/').next().unwrap_or(t)),
+            Some("ImageObject" | "VideoObject" | "AudioObject" | "MediaObject" | "Photograph")
+        )
+    };
+    match value.get("@type") {
+        Some(Value::Array(types)) => types.iter().any(is_media),
+        Some(t) => is_media(t),
+        None => false,
+    }
+}
+
+/// A non-empty string, or the first of an array of strings.
+fn text(value: Option<&Value>) -> Option<String> {
+    match value? {
+        Value::String(s) => Some(collapse_whitespace(s)).filter(|s| !s.is_empty()),
+        Value::Array(items) => items.iter().find_map(|item| text(Some(item))),
+        _ => None,
+    }
+}
+
+/// A `Person`/`Organization` name, or a plain string.
+fn person_name(value: &Value) -> Option<String> {
+    match value {
+        Value::Array(items) => items.iter().find_map(person_name),
+        Value::Object(object) => text(object.get("name")),
+        _ => text(Some(value)),
+    }
+}
+
+fn person_url(value: &Value) -> Option<String> {
+    match value {
+        Value::Array(items) => items.iter().find_map(person_url),
+        Value::Object(object) => text(object.get("url")).or_else(|| text(object.get("@id"))),
+        _ => None,
+    }
+}
+
+/// A license URL or name, or a `CreativeWork` describing one.
+fn license(value: &Value) -> Option<License> {
+    match value {
+        Value::Array(items) => items.iter().find_map(license),
+        Value::Object(object) => text(object.get("url"))
+            .or_else(|| text(object.get("@id")))
+            .or_else(|| text(object.get("name")))
+            .map(|l| License::parse(&l)),
+        _ => text(Some(value)).map(|l| License::parse(&l)),
+    }
+}
+
+/// A pixel size given as a number, a string such as `"1920 px"`, or a
+/// `QuantitativeValue`.
+fn pixels(value: &Value) -> Option<u32> {
+    match value {
+        Value::Number(n) => n.as_u64().and_then(|n| u32::try_from(n).ok()),
+        Value::String(s) => s.trim().trim_end_matches("px").trim().parse().ok(),
+        Value::Object(object) => object.get("value").and_then(pixels),
+        _ => None,
+    }
+}
+
+/// Parse an ISO 8601 duration such as `PT1H2M30S` into whole seconds.
+fn parse_iso_duration(value: &str) -> Option<u32> {
+    let rest = value.trim().strip_prefix('P')?;
+    let mut total = 0.0_f64;
+    let mut in_time = false;
+    let mut number = String::new();
+    for c in rest.chars() {
+        match c {
+            'T' => in_time = true,
+            '0'..='9' | '.' | ',' => number.push(if c == ',' { '.' } else { c }),
+            unit => {
+                let n: f64 = number.parse().ok()?;
+                number.clear();
+                total += n * match (unit, in_time) {
+                    ('D', false) => 86_400.0,
+                    ('W', false) => 604_800.0,
+                    ('H', true) => 3_600.0,
+                    ('M', true) => 60.0,
+                    ('S', true) => 1.0,
+                    _ => return None,
+                };
+            }
+        }
+    }
+    if !number.is_empty() {
+        return None;
+    }
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    Some(total.round().min(f64::from(u32::MAX)) as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::MediaType;
+
+    fn asset(url: &str) -> MediaAsset {
+        MediaAsset::builder()
+            .id("scraped-0")
+            .provider("scraper")
+            .media_type(MediaType::Image)
+            .title("IMG photo")
+            .download_url(url)
+            .source_url("https://example.com/gallery/")
+            .license(License::Other("Unknown - Check source".to_string()))
+            .dimensions(0, 0)
+            .build()
+    }
+
+    #[test]
+    fn test_page_metadata() {
+        let html = r#"<html><head>
+            <script type="application/ld+json">
+            {"@context": "https://schema.org", "@graph": [
+              {"@type": "ImageObject", "contentUrl": "/img/heron.jpg",
+               "caption": "A grey heron", "width": {"@type": "QuantitativeValue", "value": 1600},
+               "height": "1067 px", "creator": {"@type": "Person", "name": "Ann Lee",
+               "url": "https://example.com/ann"},
+               "license": "https://creativecommons.org/licenses/by-sa/4.0/"},
+              {"@type": "VideoObject", "contentUrl": "https://cdn.example.com/v.mp4",
+               "name": "Heron fishing", "duration": "PT1M30S"}
+            ]}
+            </script>
+            <meta property="og:image" content="https://example.com/og.jpg">
+            <meta property="og:image:width" content="1200">
+            <meta property="og:image:height" content="630">
+            <meta property="og:image:alt" content="Lake at dawn">
+            <meta property="og:image" content="/img/heron.jpg">
+            <meta property="og:image:alt" content="Ignored, JSON-LD wins">
+            <link rel="license" href="https://creativecommons.org/publicdomain/zero/1.0/">
+          </head><body>
+            <figure>
+              <img src="/img/swan.jpg" srcset="/img/swan-2x.jpg 2x">
+              <figcaption>Mute swan. Photo: <cite>Bo Chen</cite>
+                <a rel="license" href="https://creativecommons.org/licenses/by/4.0/">CC BY</a>
+              </figcaption>
+            </figure>
+          </body></html>"#;
+        let base = Url::parse("https://example.com/gallery/").unwrap();
+        let page = PageMetadata::parse(&Html::parse_document(html), &base);
+
+        let mut heron = asset("https://example.com/img/heron.jpg");
+        page.apply(&mut heron);
+        assert_eq!(heron.title, "A grey heron");
+        assert_eq!(heron.author.as_deref(), Some("Ann Lee"));
+        assert_eq!(heron.author_url.as_deref(), Some("https://example.com/ann"));
+        assert_eq!(heron.license, License::CcBySa);
+        assert_eq!((heron.width, heron.height), (Some(1600), Some(1067)));
+
+        let mut video = asset("https://cdn.example.com/v.mp4");
+        page.apply(&mut video);
+        assert_eq!(video.title, "Heron fishing");
+        assert_eq!(video.duration_secs, Some(90));
+        // Served from another origin, so the page's rel=license doesn't apply
+        assert!(!video.license.is_known());
+
+        let mut og = asset("https://example.com/og.jpg");
+        page.apply(&mut og);
+        assert_eq!(og.title, "Lake at dawn");
+        assert_eq!((og.width, og.height), (Some(1200), Some(630)));
+
+        let mut swan = asset("https://example.com/img/swan-2x.jpg");
+        page.apply(&mut swan);
+        assert_eq!(swan.title, "Mute swan.");
+        assert_eq!(swan.author.as_deref(), Some("Bo Chen"));
+        assert_eq!(swan.license, License::CcBy);
+
+        let mut other = asset("https://example.com/img/other.jpg");
+        page.apply(&mut other);
+        assert_eq!(other.title, "IMG photo");
+        assert_eq!(other.license, License::Cc0);
+
+        // A license found on the media element itself is kept
+        let mut licensed = asset("https://example.com/img/licensed.jpg");
+        licensed.license = License::CcByNc;
+        page.apply(&mut licensed);
+        assert_eq!(licensed.license, License::CcByNc);
+    }
+
+    #[test]
+    fn test_parse_iso_duration() {
+        assert_eq!(parse_iso_duration("PT1H2M30S"), Some(3750));
+        assert_eq!(parse_iso_duration("PT45.6S"), Some(46));
+        assert_eq!(parse_iso_duration("P1DT1S"), Some(86_401));
+        assert_eq!(parse_iso_duration("PT5"), None);
+        assert_eq!(parse_iso_duration("1:30"), None);
+    }
+}
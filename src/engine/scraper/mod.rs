@@ -1,16 +1,29 @@
 //! Web scraper for extracting media from any website.
 //!
 //! This module provides functionality to scrape images, videos, and other media
-//! from arbitrary web pages without requiring API keys.
+//! from arbitrary web pages without requiring API keys, and for searching the
+//! pre-configured [`ScrapingTarget`](crate::scraping::ScrapingTarget)s.
+
+mod feed;
+mod metadata;
+mod robots;
+mod sitemap;
+mod target;
+
+pub use feed::Feed;
+pub(crate) use feed::matches_query;
+pub use robots::{ROBOTS_AGENT, RobotsTxt, SCRAPER_USER_AGENT};
+pub use sitemap::{SitemapOptions, SitemapState};
 
 use regex::Regex;
 use scraper::{Html, Selector};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use url::Url;
 
+use crate::config::Config;
 use crate::error::{DxError, Result};
-use crate::http::HttpClient;
-use crate::types::{License, MediaAsset, MediaType};
+use crate::http::{HttpClient, registrable_domain};
+use crate::types::{License, MediaAsset, MediaType, RateLimitConfig};
 
 /// Web scraper for extracting media from websites.
 #[derive(Debug, Clone)]
@@ -18,6 +31,7 @@ pub struct Scraper {
     client: HttpClient,
     max_depth: usize,
     follow_links: bool,
+    robots: robots::RobotsCache,
 }
 
 /// Options for scraping.
@@ -31,6 +45,13 @@ pub struct ScrapeOptions {
     pub media_types: Vec<MediaType>,
     /// Maximum number of assets to find.
     pub max_assets: usize,
+    /// Whether crawling honours each host's robots.txt `Disallow`/`Allow`
+    /// rules and `Crawl-delay`.
+    pub respect_robots: bool,
+    /// Which links are followed when crawling.
+    pub scope: CrawlScope,
+    /// Most pages fetched from any one host (`None` = no limit).
+    pub max_pages_per_host: Option<usize>,
 }
 
 impl Default for ScrapeOptions {
@@ -40,10 +61,53 @@ impl Default for ScrapeOptions {
             pattern: None,
             media_types: vec![MediaType::Image],
             max_assets: 100,
+            respect_robots: true,
+            scope: CrawlScope::default(),
+            max_pages_per_host: None,
         }
     }
 }
 
+/// Which links a crawl follows, relative to the URL it started from.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CrawlScope {
+    /// Only the starting host.
+    #[default]
+    Host,
+    /// Any host under the starting registrable domain (e.g. `cdn.example.com`
+    /// from `www.example.com`).
+    Domain,
+    /// Any site.
+    Any,
+}
+
+impl CrawlScope {
+    /// Whether `url` is in scope for a crawl that started at `start`.
+    #[must_use]
+    pub fn contains(self, start: &Url, url: &Url) -> bool {
+        let host = |url: &Url| url.host_str().unwrap_or_default().to_ascii_lowercase();
+        match self {
+            Self::Host => host(url) == host(start) && url.port_or_known_default() == start.port_or_known_default(),
+            Self::Domain => registrable_domain(&host(url)) == registrable_domain(&host(start)),
+            Self::Any => true,
+        }
+    }
+}
+
+/// Bookkeeping for one crawl.
+struct Crawl {
+    start: Url,
+    visited: HashSet<String>,
+    pages_per_host: HashMap<String, usize>,
+}
+
+/// Where assets read from sitemaps and feeds come from.
+struct Source<'a> {
+    provider: &'a str,
+    license: License,
+    rate_limit: RateLimitConfig,
+}
+
 /// Result of a scrape operation.
 #[derive(Debug, Clone)]
 pub struct ScrapeResult {
@@ -58,13 +122,21 @@ pub struct ScrapeResult {
 }
 
 impl Scraper {
-    /// Create a new scraper with default settings.
+    /// Create a new scraper with the settings of the loaded configuration.
     pub fn new() -> Result<Self> {
-        let client = HttpClient::new()?;
+        Self::with_config(&Config::default())
+    }
+
+    /// Create a new scraper with the retry, timeout and rate limiting
+    /// settings of `config`.
+    pub fn with_config(config: &Config) -> Result<Self> {
+        let client = HttpClient::from_config(RateLimitConfig::default(), config)?
+            .with_user_agent(SCRAPER_USER_AGENT)?;
         Ok(Self {
             client,
             max_depth: 0,
             follow_links: false,
+            robots: robots::RobotsCache::default(),
         })
     }
 
@@ -76,7 +148,16 @@ impl Scraper {
         self
     }
 
-    /// Scrape media from a URL.
+    /// Scrape media from a URL, following links up to the scraper's depth.
+    ///
+    /// Links outside `options.scope`, pages over `options.max_pages_per_host`
+    /// and (with `options.respect_robots`) pages disallowed by robots.txt are
+    /// skipped and recorded in [`ScrapeResult::errors`]. A host's
+    /// `Crawl-delay` is applied through the shared per-host rate limiter.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `url` is not a valid URL.
     pub async fn scrape(&self, url: &str, options: &ScrapeOptions) -> Result<ScrapeResult> {
         let base_url = Url::parse(url).map_err(|e| DxError::InvalidQuery {
             message: format!("Invalid URL: {e}"),
@@ -89,8 +170,12 @@ impl Scraper {
             errors: Vec::new(),
         };
 
-        let mut visited: HashSet<String> = HashSet::new();
-        self.scrape_page(&base_url, options, &mut result, &mut visited, 0)
+        let mut crawl = Crawl {
+            start: base_url.clone(),
+            visited: HashSet::new(),
+            pages_per_host: HashMap::new(),
+        };
+        self.scrape_page(&base_url, options, &mut result, &mut crawl, 0)
             .await?;
 
         Ok(result)
@@ -102,19 +187,39 @@ impl Scraper {
         url: &Url,
         options: &ScrapeOptions,
         result: &mut ScrapeResult,
-        visited: &mut HashSet<String>,
+        crawl: &mut Crawl,
         depth: usize,
     ) -> Result<()> {
         let url_str = url.to_string();
 
         // Skip if already visited or too deep
-        if visited.contains(&url_str) || depth > options.max_depth {
+        if crawl.visited.contains(&url_str) || depth > options.max_depth {
+            return Ok(());
+        }
+        crawl.visited.insert(url_str.clone());
+
+        if !options.scope.contains(&crawl.start, url) {
+            result.errors.push(format!("Skipped {url_str}: outside the crawl scope"));
+            return Ok(());
+        }
+        let Some(client) = self.polite_client(&self.client, url, options).await else {
+            result.errors.push(format!("Skipped {url_str}: disallowed by robots.txt"));
+            return Ok(());
+        };
+
+        let host = match url.port() {
+            Some(port) => format!("{}:{}", url.host_str().unwrap_or_default(), port),
+            None => url.host_str().unwrap_or_default().to_string(),
+        };
+        let pages = crawl.pages_per_host.entry(host.clone()).or_default();
+        if options.max_pages_per_host.is_some_and(|max| *pages >= max) {
+            result.errors.push(format!("Skipped {url_str}: page limit for {host} reached"));
             return Ok(());
         }
-        visited.insert(url_str.clone());
+        *pages += 1;
 
         // Fetch the page
-        let response = match self.client.get_raw(&url_str).await {
+        let response = match client.get_raw(&url_str).await {
             Ok(r) => r,
             Err(e) => {
                 result
@@ -124,6 +229,11 @@ impl Scraper {
             }
         };
 
+        let content_type = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .map(String::from);
         let html = match response.text().await {
             Ok(t) => t,
             Err(e) => {
@@ -134,8 +244,25 @@ impl Scraper {
 
         result.pages_scraped += 1;
 
+        // Feeds list their media directly
+        if feed::is_feed(content_type.as_deref(), &html) {
+            match Feed::parse(&html, &url_str) {
+                Ok(parsed) => {
+                    let source = Source {
+                        provider: "scraper",
+                        license: License::Other("Unknown - Check source".to_string()),
+                        rate_limit: RateLimitConfig::default(),
+                    };
+                    self.add_feed_assets(&parsed, "", &source, options, result);
+                }
+                Err(e) => result.errors.push(e.to_string()),
+            }
+            return Ok(());
+        }
+
         // Parse HTML
         let document = Html::parse_document(&html);
+        let first_asset = result.assets.len();
 
         // Extract images from various sources
         if options.media_types.contains(&MediaType::Image) {
@@ -154,6 +281,12 @@ impl Scraper {
             self.extract_audio(&document, url, options, result);
         }
 
+        // Attach what the page says about its media
+        let page = metadata::PageMetadata::parse(&document, url);
+        for asset in &mut result.assets[first_asset..] {
+            page.apply(asset);
+        }
+
         // Stop if we have enough assets
         if result.assets.len() >= options.max_assets {
             return Ok(());
@@ -166,13 +299,41 @@ impl Scraper {
                 if result.assets.len() >= options.max_assets {
                     break;
                 }
-                Box::pin(self.scrape_page(&link, options, result, visited, depth + 1)).await?;
+                Box::pin(self.scrape_page(&link, options, result, crawl, depth + 1)).await?;
             }
         }
 
         Ok(())
     }
 
+    /// The client to fetch `url` with under its host's robots.txt: `None`
+    /// if the URL is disallowed, else `client` paced by any `Crawl-delay`.
+    ///
+    /// Every scraper request (pages, target results and sitemaps) goes
+    /// through here. Without `options.respect_robots`, `client` is used as is.
+    async fn polite_client(
+        &self,
+        client: &HttpClient,
+        url: &Url,
+        options: &ScrapeOptions,
+    ) -> Option<HttpClient> {
+        if !options.respect_robots {
+            return Some(client.clone());
+        }
+        let robots = self.robots.get(client, url).await;
+        if !robots.is_allowed(url) {
+            return None;
+        }
+        // The host's bucket keeps the stricter of this and `client`'s own limit
+        Some(match robots.crawl_delay() {
+            Some(delay) => {
+                let ms = u64::try_from(delay.as_millis()).unwrap_or(u64::MAX);
+                client.clone().with_rate_limit(RateLimitConfig::from_interval_ms(ms))
+            }
+            None => client.clone(),
+        })
+    }
+
     /// Extract image URLs from raw HTML text (for JS-heavy sites).
     /// This finds image URLs in JSON data, script tags, and other places
     /// that normal HTML parsing misses.
@@ -585,9 +746,10 @@ impl Scraper {
 
         for element in document.select(&link_selector) {
             if let Some(href) = element.value().attr("href") {
-                if let Ok(url) = base_url.join(href) {
-                    // Only follow links to the same domain
-                    if url.host() == base_url.host() {
+                if let Ok(mut url) = base_url.join(href) {
+                    // The crawl scope decides which of these are followed
+                    if matches!(url.scheme(), "http" | "https") {
+                        url.set_fragment(None);
                         links.push(url);
                     }
                 }
@@ -650,12 +812,38 @@ impl Scraper {
     }
 }
 
+/// Media type for a file extension, if it identifies one.
+fn media_type_for_extension(ext: &str) -> Option<MediaType> {
+    match ext {
+        "jpg" | "jpeg" | "png" | "webp" | "avif" | "bmp" | "tif" | "tiff" => Some(MediaType::Image),
+        "gif" => Some(MediaType::Gif),
+        "svg" => Some(MediaType::Vector),
+        "mp4" | "webm" | "mov" | "m4v" | "mkv" => Some(MediaType::Video),
+        "mp3" | "wav" | "ogg" | "flac" | "aac" | "m4a" => Some(MediaType::Audio),
+        "glb" | "gltf" | "obj" | "fbx" | "stl" | "blend" => Some(MediaType::Model3D),
+        "pdf" => Some(MediaType::Document),
+        _ => None,
+    }
+}
+
+/// Last path segment of a URL without its extension.
+fn file_stem(url: &str) -> String {
+    let path = url.split(['?', '#']).next().unwrap_or(url);
+    let name = path.trim_end_matches('/').rsplit('/').next().unwrap_or(path);
+    name.split('.').next().unwrap_or(name).to_string()
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
 impl Default for Scraper {
     fn default() -> Self {
         Self::new().unwrap_or_else(|_| Self {
             client: HttpClient::new().unwrap(),
             max_depth: 0,
             follow_links: false,
+            robots: robots::RobotsCache::default(),
         })
     }
 }
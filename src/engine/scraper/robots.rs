@@ -0,0 +1,285 @@
+//! robots.txt rules (RFC 9309).
+//!
+//! Each host's robots.txt is fetched once per [`Scraper`](super::Scraper) and
+//! cached. The group for our product token applies, falling back to the `*`
+//! group; within it the longest matching `Allow`/`Disallow` pattern wins.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Duration;
+
+use tracing::debug;
+use url::Url;
+
+use crate::http::HttpClient;
+
+/// Product token matched against `User-agent` lines.
+pub const ROBOTS_AGENT: &str = "dx-media";
+
+/// User agent sent by the [`Scraper`](super::Scraper), starting with
+/// [`ROBOTS_AGENT`] so sites can address it in robots.txt.
+pub const SCRAPER_USER_AGENT: &str = concat!("dx-media/", env!("CARGO_PKG_VERSION"));
+
+/// Robots files larger than this are truncated, as RFC 9309 allows.
+const MAX_ROBOTS_BYTES: usize = 500 * 1024;
+
+/// The rules of a robots.txt file that apply to [`ROBOTS_AGENT`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RobotsTxt {
+    /// `(allow, pattern)` pairs.
+    rules: Vec<(bool, String)>,
+    crawl_delay: Option<Duration>,
+    sitemaps: Vec<String>,
+}
+
+/// A `User-agent` group while parsing.
+#[derive(Default)]
+struct Group {
+    agents: Vec<String>,
+    rules: Vec<(bool, String)>,
+    crawl_delay: Option<Duration>,
+}
+
+impl RobotsTxt {
+    /// Parse a robots.txt file, keeping the rules for [`ROBOTS_AGENT`].
+    #[must_use]
+    pub fn parse(text: &str) -> Self {
+        let mut groups: Vec<Group> = Vec::new();
+        let mut sitemaps = Vec::new();
+        // Consecutive User-agent lines share one group
+        let mut in_agents = false;
+
+        for line in text.lines() {
+            let line = line.split('#').next().unwrap_or_default();
+            let Some((field, value)) = line.split_once(':') else {
+                continue;
+            };
+            let value = value.trim();
+            match field.trim().to_ascii_lowercase().as_str() {
+                "user-agent" => {
+                    if !in_agents {
+                        groups.push(Group::default());
+                    }
+                    in_agents = true;
+                    if let Some(group) = groups.last_mut() {
+                        group.agents.push(value.to_ascii_lowercase());
+                    }
+                }
+                field @ ("allow" | "disallow") => {
+                    in_agents = false;
+                    // An empty Disallow allows everything, so it's no rule at all
+                    if let (Some(group), false) = (groups.last_mut(), value.is_empty()) {
+                        group.rules.push((field == "allow", value.to_string()));
+                    }
+                }
+                "crawl-delay" => {
+                    in_agents = false;
+                    if let (Some(group), Ok(secs)) = (groups.last_mut(), value.parse::<f64>()) {
+                        if secs.is_finite() && secs >= 0.0 {
+                            group.crawl_delay = Some(Duration::from_secs_f64(secs));
+                        }
+                    }
+                }
+                "sitemap" if !value.is_empty() => sitemaps.push(value.to_string()),
+                _ => {}
+            }
+        }
+
+        let ours: Vec<Group> = {
+            let (ours, rest): (Vec<Group>, Vec<Group>) = groups
+                .into_iter()
+                .partition(|g| g.agents.iter().any(|a| a == ROBOTS_AGENT));
+            if ours.is_empty() {
+                rest.into_iter()
+                    .filter(|g| g.agents.iter().any(|a| a == "*"))
+                    .collect()
+            } else {
+                ours
+            }
+        };
+
+        Self {
+            crawl_delay: ours.iter().find_map(|g| g.crawl_delay),
+            rules: ours.into_iter().flat_map(|g| g.rules).collect(),
+            sitemaps,
+        }
+    }
+
+    /// Rules disallowing everything, for sites whose robots.txt is unreachable.
+    #[must_use]
+    pub fn disallow_all() -> Self {
+        Self {
+            rules: vec![(false, "/".to_string())],
+            ..Self::default()
+        }
+    }
+
+    /// Whether a URL may be crawled.
+    #[must_use]
+    pub fn is_allowed(&self, url: &Url) -> bool {
+        let path = match url.query() {
+            Some(query) => format!("{}?{}", url.path(), query),
+            None => url.path().to_string(),
+        };
+        if path == "/robots.txt" {
+            return true;
+        }
+
+        // The longest match wins; Allow wins ties
+        self.rules
+            .iter()
+            .filter(|(_, pattern)| pattern_matches(pattern, &path))
+            .max_by_key(|(allow, pattern)| (pattern.len(), *allow))
+            .is_none_or(|(allow, _)| *allow)
+    }
+
+    /// The requested delay between requests.
+    #[must_use]
+    pub fn crawl_delay(&self) -> Option<Duration> {
+        self.crawl_delay
+    }
+
+    /// URLs from `Sitemap:` lines.
+    #[must_use]
+    pub fn sitemaps(&self) -> &[String] {
+        &self.sitemaps
+    }
+}
+
+/// robots.txt files fetched so far, keyed by origin.
+#[derive(Debug, Clone, Default)]
+pub(super) struct RobotsCache {
+    files: Arc<Mutex<HashMap<String, Arc<RobotsTxt>>>>,
+}
+
+impl RobotsCache {
+    /// The robots.txt rules for a URL's origin, fetching them on first use.
+    ///
+    /// A missing file (4xx) allows everything; a server error or failed
+    /// request disallows everything, as RFC 9309 asks.
+    pub(super) async fn get(&self, client: &HttpClient, url: &Url) -> Arc<RobotsTxt> {
+        let origin = url.origin().ascii_serialization();
+        if let Some(robots) = self.lock().get(&origin) {
+            return Arc::clone(robots);
+        }
+
+        let robots_url = format!("{origin}/robots.txt");
+        let robots = match client.get_raw(&robots_url).await {
+            Ok(response) if response.status().is_success() => {
+                let bytes = response.bytes().await.unwrap_or_default();
+                let text = String::from_utf8_lossy(&bytes[..bytes.len().min(MAX_ROBOTS_BYTES)]);
+                RobotsTxt::parse(&text)
+            }
+            Ok(response) if response.status().is_client_error() => RobotsTxt::default(),
+            Ok(response) => {
+                debug!(
+                    "{} returned {}; treating the site as disallowed",
+                    robots_url,
+                    response.status()
+                );
+                RobotsTxt::disallow_all()
+            }
+            Err(e) => {
+                debug!(
+                    "Failed to fetch {}: {}; treating the site as disallowed",
+                    robots_url, e
+                );
+                RobotsTxt::disallow_all()
+            }
+        };
+
+        let robots = Arc::new(robots);
+        self.lock().insert(origin, Arc::clone(&robots));
+        robots
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, Arc<RobotsTxt>>> {
+        self.files.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Match a robots.txt path pattern, where `*` matches any characters and a
+/// trailing `$` anchors the end.
+fn pattern_matches(pattern: &str, path: &str) -> bool {
+    let (pattern, anchored) = match pattern.strip_suffix('$') {
+        Some(pattern) => (pattern, true),
+        None => (pattern, false),
+    };
+    let mut parts = pattern.split('*');
+    let Some(mut rest) = path.strip_prefix(parts.next().unwrap_or_default()) else {
+        return false;
+    };
+    let parts: Vec<&str> = parts.collect();
+    let Some((last, middle)) = parts.split_last() else {
+        return !anchored || rest.is_empty();
+    };
+
+    for part in middle {
+        match rest.find(part) {
+            Some(pos) => rest = &rest[pos + part.len()..],
+            None => return false,
+        }
+    }
+    if anchored {
+        rest.ends_with(last)
+    } else {
+        rest.contains(last)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_robots_rules() {
+        let robots = RobotsTxt::parse(
+            "# example\n\
+             User-agent: Googlebot\n\
+             Disallow: /\n\
+             \n\
+             User-agent: *\n\
+             Disallow: /private/\n\
+             Allow: /private/public\n\
+             Disallow: /*.pdf$\n\
+             Disallow:\n\
+             Crawl-delay: 2.5\n\
+             \n\
+             Sitemap: https://example.com/sitemap.xml\n",
+        );
+        let allowed = |path: &str| {
+            robots.is_allowed(&Url::parse(&format!("https://example.com{path}")).unwrap())
+        };
+
+        assert!(allowed("/"));
+        assert!(!allowed("/private/notes"));
+        assert!(allowed("/private/public/a.jpg"));
+        assert!(!allowed("/docs/report.pdf"));
+        assert!(allowed("/docs/report.pdf?download=1"));
+        assert!(allowed("/robots.txt"));
+        assert_eq!(robots.crawl_delay(), Some(Duration::from_millis(2500)));
+        assert_eq!(robots.sitemaps(), ["https://example.com/sitemap.xml"]);
+
+        // A group naming our agent replaces the * group
+        let ours = RobotsTxt::parse(
+            "User-agent: *\nDisallow: /\n\nUser-agent: other\nUser-agent: DX-Media\nDisallow: /tmp\n",
+        );
+        assert!(ours.is_allowed(&Url::parse("https://example.com/photos").unwrap()));
+        assert!(!ours.is_allowed(&Url::parse("https://example.com/tmp/x").unwrap()));
+
+        assert!(
+            !RobotsTxt::disallow_all().is_allowed(&Url::parse("https://example.com/a").unwrap())
+        );
+    }
+
+    #[test]
+    fn test_pattern_matches() {
+        assert!(pattern_matches("/fish", "/fish.html"));
+        assert!(!pattern_matches("/fish", "/Fish"));
+        assert!(pattern_matches("/fish*.php", "/fish/salmon.php?id=1"));
+        assert!(pattern_matches("/*.php$", "/a/b.php"));
+        assert!(!pattern_matches("/*.php$", "/a/b.php5"));
+        assert!(pattern_matches("/$", "/"));
+        assert!(!pattern_matches("/$", "/a"));
+    }
+}
@@ -0,0 +1,554 @@
+//! Sitemap harvesting.
+//!
+//! Sitemaps are discovered from `robots.txt` (falling back to `/sitemap.xml`),
+//! sitemap indexes are followed, and the `<image:image>` and `<video:video>`
+//! extensions of each `<url>` become assets. A [`SitemapState`] file records
+//! the `lastmod` of every sitemap and entry harvested in full, so a later run
+//! skips sitemaps and entries that haven't changed. Entries cut short by
+//! `max_assets` or `pattern` aren't recorded.
+
+use std::collections::{BTreeMap, HashSet, VecDeque};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, NaiveDate, Utc};
+use flate2::read::GzDecoder;
+use roxmltree::{Document, Node};
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+use url::Url;
+
+use super::{
+    ScrapeOptions, ScrapeResult, Scraper, Source, collapse_whitespace, file_stem, media_type_for_extension,
+};
+use crate::error::{DxError, Result};
+use crate::http::HttpClient;
+use crate::scraping::ScrapingTarget;
+use crate::types::{License, MediaAsset, MediaType, RateLimitConfig};
+
+/// Most sitemap files fetched in one harvest.
+const MAX_SITEMAPS: usize = 200;
+
+/// Options for a sitemap harvest.
+#[derive(Debug, Clone, Default)]
+pub struct SitemapOptions {
+    /// Only harvest sitemaps and entries modified at or after this time.
+    /// Entries without a `lastmod` are always included.
+    pub since: Option<DateTime<Utc>>,
+    /// File recording earlier harvests of the site. Without one every entry
+    /// is harvested.
+    pub state_path: Option<PathBuf>,
+}
+
+/// What earlier harvests of a site have seen, keyed by URL.
+///
+/// Values are the `lastmod` last seen, or an empty string when there was none.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SitemapState {
+    /// Sitemaps whose entries were all harvested.
+    #[serde(default)]
+    pub sitemaps: BTreeMap<String, String>,
+    /// Harvested `<url>` entries.
+    #[serde(default)]
+    pub entries: BTreeMap<String, String>,
+}
+
+impl SitemapState {
+    /// Default state file for a site: `{cache_dir}/sitemaps/{host}.json`.
+    #[must_use]
+    pub fn default_path(cache_dir: &Path, site: &Url) -> PathBuf {
+        let host = site.host_str().unwrap_or("unknown");
+        cache_dir.join("sitemaps").join(format!("{}.json", sanitize_filename::sanitize(host)))
+    }
+
+    /// Load state, or start empty if the file doesn't exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file can't be read or parsed.
+    pub fn load(path: &Path) -> Result<Self> {
+        match std::fs::read_to_string(path) {
+            Ok(json) => Ok(serde_json::from_str(&json)?),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(DxError::file_io(path, e.to_string())),
+        }
+    }
+
+    /// Write state, creating parent directories.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file can't be written.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| DxError::file_io(parent, e.to_string()))?;
+        }
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, serde_json::to_string(self)?)
+            .map_err(|e| DxError::file_io(&tmp, e.to_string()))?;
+        std::fs::rename(&tmp, path).map_err(|e| DxError::file_io(path, e.to_string()))
+    }
+
+    fn is_current(seen: &BTreeMap<String, String>, loc: &str, lastmod: Option<&str>) -> bool {
+        seen.get(loc).is_some_and(|seen| seen == lastmod.unwrap_or_default())
+    }
+}
+
+/// A parsed sitemap file.
+#[derive(Debug, PartialEq)]
+enum Sitemap {
+    /// A `<sitemapindex>` listing other sitemaps.
+    Index(Vec<SitemapRef>),
+    /// A `<urlset>`.
+    Urls(Vec<UrlEntry>),
+}
+
+#[derive(Debug, PartialEq)]
+struct SitemapRef {
+    loc: String,
+    lastmod: Option<String>,
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct UrlEntry {
+    loc: String,
+    lastmod: Option<String>,
+    images: Vec<ImageEntry>,
+    videos: Vec<VideoEntry>,
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct ImageEntry {
+    loc: String,
+    title: Option<String>,
+    caption: Option<String>,
+    license: Option<String>,
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct VideoEntry {
+    content_loc: Option<String>,
+    thumbnail_loc: Option<String>,
+    title: Option<String>,
+    tags: Vec<String>,
+}
+
+impl Scraper {
+    /// Harvest media listed in a site's sitemaps.
+    ///
+    /// `site` is the site's URL, or a sitemap URL to start from. Only
+    /// `options.max_assets` and `options.pattern` apply.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `site` is not a valid URL or the harvest state
+    /// can't be read or written. Sitemaps that fail to load are recorded in
+    /// [`ScrapeResult::errors`].
+    pub async fn harvest_sitemaps(
+        &self,
+        site: &str,
+        options: &ScrapeOptions,
+        sitemap: &SitemapOptions,
+    ) -> Result<ScrapeResult> {
+        let source = Source {
+            provider: "scraper",
+            license: License::Other("Unknown - Check source".to_string()),
+            rate_limit: RateLimitConfig::default(),
+        };
+        self.harvest(site, &source, options, sitemap).await
+    }
+
+    /// Harvest media listed in a target's sitemaps, with the target's license
+    /// and request pacing.
+    ///
+    /// # Errors
+    ///
+    /// As for [`Scraper::harvest_sitemaps`].
+    pub async fn harvest_target(
+        &self,
+        target: &ScrapingTarget,
+        options: &ScrapeOptions,
+        sitemap: &SitemapOptions,
+    ) -> Result<ScrapeResult> {
+        let source = Source {
+            provider: target.id,
+            license: target.license(),
+            rate_limit: target.rate_limit(),
+        };
+        self.harvest(target.base_url, &source, options, sitemap).await
+    }
+
+    async fn harvest(
+        &self,
+        site: &str,
+        source: &Source<'_>,
+        options: &ScrapeOptions,
+        sitemap: &SitemapOptions,
+    ) -> Result<ScrapeResult> {
+        let site = Url::parse(site).map_err(|e| DxError::InvalidQuery {
+            message: format!("Invalid URL: {e}"),
+        })?;
+        let client = self.client.clone().with_rate_limit(source.rate_limit);
+        let mut state = match sitemap.state_path {
+            Some(ref path) => SitemapState::load(path)?,
+            None => SitemapState::default(),
+        };
+
+        let mut result = ScrapeResult {
+            source_url: site.to_string(),
+            assets: Vec::new(),
+            pages_scraped: 0,
+            errors: Vec::new(),
+        };
+        let mut queue: VecDeque<SitemapRef> = self
+            .discover_sitemaps(&client, &site)
+            .await
+            .into_iter()
+            .map(|loc| SitemapRef { loc, lastmod: None })
+            .collect();
+        let mut fetched = HashSet::new();
+        let mut seen_downloads = HashSet::new();
+
+        while let Some(next) = queue.pop_front() {
+            if result.assets.len() >= options.max_assets || fetched.len() >= MAX_SITEMAPS {
+                break;
+            }
+            if !fetched.insert(next.loc.clone()) {
+                continue;
+            }
+
+            let Ok(loc) = Url::parse(&next.loc) else {
+                result.errors.push(format!("Invalid sitemap URL: {}", next.loc));
+                continue;
+            };
+            let Some(sitemap_client) = self.polite_client(&client, &loc, options).await else {
+                result.errors.push(format!("Skipped {}: disallowed by robots.txt", next.loc));
+                continue;
+            };
+            let parsed = match fetch_sitemap(&sitemap_client, &next.loc).await {
+                Ok(xml) => parse_sitemap(&xml, &next.loc),
+                Err(e) => Err(e),
+            };
+            let parsed = match parsed {
+                Ok(parsed) => parsed,
+                Err(e) => {
+                    result.errors.push(format!("Failed to load sitemap: {e}"));
+                    continue;
+                }
+            };
+            result.pages_scraped += 1;
+
+            match parsed {
+                Sitemap::Index(children) => {
+                    for child in children {
+                        let lastmod = child.lastmod.as_deref();
+                        if is_before(lastmod, sitemap.since) {
+                            debug!("Skipping sitemap {} (older than --since)", child.loc);
+                        } else if child.lastmod.is_some()
+                            && SitemapState::is_current(&state.sitemaps, &child.loc, lastmod)
+                        {
+                            debug!("Skipping sitemap {} (unchanged)", child.loc);
+                        } else {
+                            queue.push_back(child);
+                        }
+                    }
+                }
+                Sitemap::Urls(entries) => {
+                    let mut complete = true;
+                    for entry in entries {
+                        let lastmod = entry.lastmod.as_deref();
+                        if is_before(lastmod, sitemap.since) {
+                            // Left for a harvest without --since
+                            complete = false;
+                            continue;
+                        }
+                        if SitemapState::is_current(&state.entries, &entry.loc, lastmod) {
+                            continue;
+                        }
+
+                        // Assets left out by --pattern are still to be harvested
+                        let (assets, unmatched): (Vec<MediaAsset>, Vec<MediaAsset>) = self
+                            .entry_assets(&entry, source)
+                            .into_iter()
+                            .filter(|asset| !seen_downloads.contains(&asset.download_url))
+                            .partition(|asset| {
+                                options.pattern.as_ref().is_none_or(|pattern| {
+                                    self.matches_pattern(&asset.download_url, pattern)
+                                })
+                            });
+                        let room = options.max_assets - result.assets.len();
+                        if assets.len() > room && !result.assets.is_empty() {
+                            complete = false;
+                            break;
+                        }
+
+                        // Only an entry whose every asset was taken counts as harvested
+                        let whole = unmatched.is_empty() && assets.len() <= room;
+                        for asset in assets.into_iter().take(room) {
+                            seen_downloads.insert(asset.download_url.clone());
+                            result.assets.push(asset);
+                        }
+                        if whole {
+                            state
+                                .entries
+                                .insert(entry.loc, entry.lastmod.unwrap_or_default());
+                        } else {
+                            complete = false;
+                        }
+                        if result.assets.len() >= options.max_assets {
+                            complete = false;
+                            break;
+                        }
+                    }
+                    if complete {
+                        state
+                            .sitemaps
+                            .insert(next.loc, next.lastmod.unwrap_or_default());
+                    }
+                }
+            }
+        }
+
+        if let Some(ref path) = sitemap.state_path {
+            state.save(path)?;
+        }
+        Ok(result)
+    }
+
+    /// Assets for one `<url>` entry: its images and videos, or the page URL
+    /// itself when it points at a media file.
+    fn entry_assets(&self, entry: &UrlEntry, source: &Source<'_>) -> Vec<MediaAsset> {
+        let asset = |download_url: &str, title: Option<&String>, fallback: MediaType| {
+            let media_type = self
+                .get_extension(download_url)
+                .and_then(|ext| media_type_for_extension(&ext))
+                .unwrap_or(fallback);
+            let title = title
+                .map(|t| collapse_whitespace(t))
+                .filter(|t| !t.is_empty())
+                .unwrap_or_else(|| file_stem(download_url).replace(['-', '_'], " "));
+            MediaAsset::builder()
+                .id(file_stem(download_url))
+                .provider(source.provider)
+                .media_type(media_type)
+                .title(title)
+                .download_url(download_url)
+                .source_url(&entry.loc)
+                .license(source.license.clone())
+        };
+
+        let mut assets = Vec::new();
+        for image in &entry.images {
+            let license = image
+                .license
+                .as_deref()
+                .map_or_else(|| source.license.clone(), License::parse);
+            let title = image.title.as_ref().or(image.caption.as_ref());
+            assets.push(asset(&image.loc, title, MediaType::Image).license(license).build());
+        }
+        for video in &entry.videos {
+            // Player pages aren't downloadable; only videos with a file URL count
+            let Some(ref content) = video.content_loc else {
+                continue;
+            };
+            let mut builder = asset(content, video.title.as_ref(), MediaType::Video)
+                .tags(video.tags.clone());
+            if let Some(ref thumbnail) = video.thumbnail_loc {
+                builder = builder.preview_url(thumbnail);
+            }
+            assets.push(builder.build());
+        }
+        if assets.is_empty() {
+            if let Some(media_type) = self
+                .get_extension(&entry.loc)
+                .and_then(|ext| media_type_for_extension(&ext))
+            {
+                assets.push(asset(&entry.loc, None, media_type).build());
+            }
+        }
+        assets
+    }
+
+    /// Sitemap URLs for a site: those listed in `robots.txt`, else
+    /// `/sitemap.xml`. A URL that already points at an XML file is used as is.
+    async fn discover_sitemaps(&self, client: &HttpClient, site: &Url) -> Vec<String> {
+        let path = site.path().to_lowercase();
+        if [".xml", ".xml.gz"].iter().any(|ext| path.ends_with(ext)) {
+            return vec![site.to_string()];
+        }
+
+        let robots = self.robots.get(client, site).await;
+        if !robots.sitemaps().is_empty() {
+            return robots.sitemaps().to_vec();
+        }
+        site.join("/sitemap.xml").map(|url| vec![url.to_string()]).unwrap_or_default()
+    }
+}
+
+/// Fetch a sitemap, decompressing gzipped files.
+async fn fetch_sitemap(client: &HttpClient, url: &str) -> Result<String> {
+    let response = client.get_raw(url).await?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(DxError::http(format!("{} returned {}", url, status)));
+    }
+    let bytes = response.bytes().await?;
+
+    if bytes.starts_with(&[0x1f, 0x8b]) {
+        let mut xml = String::new();
+        GzDecoder::new(bytes.as_ref())
+            .read_to_string(&mut xml)
+            .map_err(|e| DxError::http(format!("Invalid gzip data from {}: {}", url, e)))?;
+        Ok(xml)
+    } else {
+        Ok(String::from_utf8_lossy(&bytes).into_owned())
+    }
+}
+
+fn parse_sitemap(xml: &str, url: &str) -> Result<Sitemap> {
+    let document = Document::parse(xml).map_err(|e| DxError::XmlParse {
+        url: url.to_string(),
+        message: e.to_string(),
+    })?;
+    let root = document.root_element();
+
+    match root.tag_name().name() {
+        "sitemapindex" => Ok(Sitemap::Index(
+            children(root, "sitemap")
+                .filter_map(|node| {
+                    Some(SitemapRef {
+                        loc: child_text(node, "loc")?,
+                        lastmod: child_text(node, "lastmod"),
+                    })
+                })
+                .collect(),
+        )),
+        "urlset" => Ok(Sitemap::Urls(
+            children(root, "url")
+                .filter_map(|node| {
+                    Some(UrlEntry {
+                        loc: child_text(node, "loc")?,
+                        lastmod: child_text(node, "lastmod"),
+                        images: children(node, "image")
+                            .filter_map(|image| {
+                                Some(ImageEntry {
+                                    loc: child_text(image, "loc")?,
+                                    title: child_text(image, "title"),
+                                    caption: child_text(image, "caption"),
+                                    license: child_text(image, "license"),
+                                })
+                            })
+                            .collect(),
+                        videos: children(node, "video")
+                            .map(|video| VideoEntry {
+                                content_loc: child_text(video, "content_loc"),
+                                thumbnail_loc: child_text(video, "thumbnail_loc"),
+                                title: child_text(video, "title"),
+                                tags: children(video, "tag")
+                                    .filter_map(|tag| tag.text().map(|t| t.trim().to_string()))
+                                    .collect(),
+                            })
+                            .collect(),
+                    })
+                })
+                .collect(),
+        )),
+        other => Err(DxError::XmlParse {
+            url: url.to_string(),
+            message: format!("expected <urlset> or <sitemapindex>, found <{other}>"),
+        }),
+    }
+}
+
+/// Child elements with a local name, ignoring namespace prefixes.
+fn children<'a, 'input>(
+    node: Node<'a, 'input>,
+    name: &'static str,
+) -> impl Iterator<Item = Node<'a, 'input>> {
+    node.children()
+        .filter(move |child| child.is_element() && child.tag_name().name() == name)
+}
+
+fn child_text(node: Node, name: &'static str) -> Option<String> {
+    let text = children(node, name).next()?.text()?.trim();
+    (!text.is_empty()).then(|| text.to_string())
+}
+
+/// Parse a W3C datetime `lastmod` (a date or an RFC 3339 timestamp).
+fn parse_lastmod(lastmod: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(lastmod)
+        .map(|dt| dt.with_timezone(&Utc))
+        .ok()
+        .or_else(|| {
+            let date = NaiveDate::parse_from_str(lastmod.get(..10)?, "%Y-%m-%d").ok()?;
+            Some(date.and_hms_opt(0, 0, 0)?.and_utc())
+        })
+}
+
+/// Whether a `lastmod` is known to be before `since`.
+fn is_before(lastmod: Option<&str>, since: Option<DateTime<Utc>>) -> bool {
+    match (lastmod.and_then(parse_lastmod), since) {
+        (Some(lastmod), Some(since)) => lastmod < since,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::RobotsTxt;
+
+    #[test]
+    fn test_parse_urlset_with_extensions() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+            <urlset xmlns="http://www.sitemaps.org/schemas/sitemap/0.9"
+                    xmlns:image="http://www.google.com/schemas/sitemap-image/1.1"
+                    xmlns:video="http://www.google.com/schemas/sitemap-video/1.1">
+              <url>
+                <loc>https://example.com/photo/fox</loc>
+                <lastmod>2024-05-01</lastmod>
+                <image:image>
+                  <image:loc>https://cdn.example.com/fox.jpg</image:loc>
+                  <image:title>Red fox</image:title>
+                </image:image>
+                <video:video>
+                  <video:thumbnail_loc>https://cdn.example.com/fox-thumb.jpg</video:thumbnail_loc>
+                  <video:title>Fox running</video:title>
+                  <video:content_loc>https://cdn.example.com/fox.mp4</video:content_loc>
+                  <video:tag>fox</video:tag>
+                </video:video>
+              </url>
+              <url><loc>https://example.com/about</loc></url>
+            </urlset>"#;
+
+        let Sitemap::Urls(entries) = parse_sitemap(xml, "https://example.com/sitemap.xml").unwrap() else {
+            panic!("expected a urlset");
+        };
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].loc, "https://example.com/photo/fox");
+        assert_eq!(entries[0].lastmod.as_deref(), Some("2024-05-01"));
+        assert_eq!(entries[0].images[0].loc, "https://cdn.example.com/fox.jpg");
+        assert_eq!(entries[0].images[0].title.as_deref(), Some("Red fox"));
+        assert_eq!(
+            entries[0].videos[0].content_loc.as_deref(),
+            Some("https://cdn.example.com/fox.mp4")
+        );
+        assert_eq!(entries[0].videos[0].tags, ["fox"]);
+        assert!(entries[1].images.is_empty());
+    }
+
+    #[test]
+    fn test_robots_and_lastmod() {
+        let robots = "User-agent: *\nDisallow: /admin\nSitemap: https://example.com/a.xml\nsitemap:https://example.com/b.xml.gz\n";
+        assert_eq!(
+            RobotsTxt::parse(robots).sitemaps(),
+            ["https://example.com/a.xml", "https://example.com/b.xml.gz"]
+        );
+
+        let since = parse_lastmod("2024-03-01");
+        assert!(is_before(Some("2024-02-28T23:00:00+00:00"), since));
+        assert!(!is_before(Some("2024-03-01T10:00:00Z"), since));
+        assert!(!is_before(None, since));
+        assert!(!is_before(Some("2020-01-01"), None));
+    }
+}
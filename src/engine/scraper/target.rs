@@ -0,0 +1,384 @@
+//! Searching pre-configured scraping targets.
+
+use std::collections::HashSet;
+
+use scraper::{ElementRef, Html, Selector};
+use url::Url;
+
+use super::{
+    ScrapeOptions, ScrapeResult, Scraper, collapse_whitespace, file_stem, media_type_for_extension,
+};
+use crate::error::{DxError, Result};
+use crate::http::HttpClient;
+use crate::scraping::ScrapingTarget;
+use crate::types::{MediaAsset, MediaType};
+
+/// Most result pages followed through a target's pagination links.
+const MAX_TARGET_PAGES: usize = 10;
+
+/// Attributes that may hold an item's media URL, most specific first.
+/// Lazy-loading attributes come before `src`, which often holds a placeholder.
+const URL_ATTRS: [&str; 7] = [
+    "data-src",
+    "data-lazy-src",
+    "data-original",
+    "data-url",
+    "src",
+    "href",
+    "content",
+];
+
+/// A target's selectors, parsed once per search.
+struct TargetSelectors {
+    container: Option<Selector>,
+    item: Selector,
+    media: Selector,
+    title: Option<Selector>,
+    download: Option<Selector>,
+    pagination: Option<Selector>,
+}
+
+impl TargetSelectors {
+    fn parse(target: &ScrapingTarget) -> Result<Self> {
+        let parse = |selector: &str| {
+            Selector::parse(selector).map_err(|e| DxError::InvalidQuery {
+                message: format!("Invalid selector '{}' for {}: {}", selector, target.id, e),
+            })
+        };
+        let parse_opt = |selector: Option<&str>| selector.map(parse).transpose();
+
+        Ok(Self {
+            container: parse_opt(target.container_selector)?,
+            item: parse(target.item_selector)?,
+            media: parse(target.media_selector)?,
+            title: parse_opt(target.title_selector)?,
+            download: parse_opt(target.download_selector)?,
+            pagination: parse_opt(target.pagination_selector)?,
+        })
+    }
+}
+
+impl Scraper {
+    /// Search a pre-configured target for `query`.
+    ///
+    /// The query is filled into the target's search URL (targets without one
+    /// are browsed from their base URL). Items are extracted with the target's
+    /// selectors and its pagination links are followed until
+    /// `options.max_assets` assets are found. Requests to the site are paced by
+    /// its `rate_limit_ms` and, with `options.respect_robots`, follow its
+    /// robots.txt. Assets carry the target's license.
+    ///
+    /// Only `options.max_assets` and `options.pattern` apply; the media type
+    /// comes from each file, falling back to the target's category.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the target's selectors are invalid or the first
+    /// result page can't be fetched. Failures on later pages are recorded in
+    /// [`ScrapeResult::errors`].
+    pub async fn search_target(
+        &self,
+        target: &ScrapingTarget,
+        query: &str,
+        options: &ScrapeOptions,
+    ) -> Result<ScrapeResult> {
+        let selectors = TargetSelectors::parse(target)?;
+        let client = self.client.clone().with_rate_limit(target.rate_limit());
+        let first = target.search_url_for(query);
+
+        let mut result = ScrapeResult {
+            source_url: first.clone(),
+            assets: Vec::new(),
+            pages_scraped: 0,
+            errors: Vec::new(),
+        };
+        let mut seen = HashSet::new();
+        let mut visited = HashSet::new();
+        let mut next = Some(Url::parse(&first).map_err(|e| DxError::InvalidQuery {
+            message: format!("Invalid search URL for {}: {}", target.id, e),
+        })?);
+
+        while let Some(url) = next.take() {
+            if result.pages_scraped >= MAX_TARGET_PAGES || !visited.insert(url.to_string()) {
+                break;
+            }
+
+            let Some(page_client) = self.polite_client(&client, &url, options).await else {
+                result.errors.push(format!("Skipped {url}: disallowed by robots.txt"));
+                break;
+            };
+            let html = match fetch_page(&page_client, target, &url).await {
+                Ok(html) => html,
+                Err(e) if result.pages_scraped == 0 => return Err(e),
+                Err(e) => {
+                    result.errors.push(format!("Failed to fetch {url}: {e}"));
+                    break;
+                }
+            };
+            result.pages_scraped += 1;
+
+            let document = Html::parse_document(&html);
+            for asset in self.extract_target_items(target, &selectors, &document, &url, options) {
+                if result.assets.len() >= options.max_assets {
+                    break;
+                }
+                if seen.insert(asset.download_url.clone()) {
+                    result.assets.push(asset);
+                }
+            }
+            if result.assets.len() >= options.max_assets {
+                break;
+            }
+
+            next = selectors
+                .pagination
+                .as_ref()
+                .and_then(|selector| next_page(&document, selector, &url));
+        }
+
+        Ok(result)
+    }
+
+    /// Extract an asset from every item on a target's result page.
+    fn extract_target_items(
+        &self,
+        target: &ScrapingTarget,
+        selectors: &TargetSelectors,
+        document: &Html,
+        page_url: &Url,
+        options: &ScrapeOptions,
+    ) -> Vec<MediaAsset> {
+        let scopes: Vec<ElementRef> = match selectors.container {
+            Some(ref container) => document.select(container).collect(),
+            None => vec![document.root_element()],
+        };
+
+        scopes
+            .iter()
+            .flat_map(|scope| scope.select(&selectors.item))
+            .filter_map(|item| self.target_asset(target, selectors, item, page_url, options))
+            .collect()
+    }
+
+    /// Build an asset from one result item.
+    fn target_asset(
+        &self,
+        target: &ScrapingTarget,
+        selectors: &TargetSelectors,
+        item: ElementRef,
+        page_url: &Url,
+        options: &ScrapeOptions,
+    ) -> Option<MediaAsset> {
+        let media = find(item, &selectors.media)?;
+        let media_url = self.element_url(media, page_url)?;
+        let download = selectors
+            .download
+            .as_ref()
+            .and_then(|selector| find(item, selector))
+            .and_then(|element| Some((element, self.element_url(element, page_url)?)));
+        // A thumbnail's size says nothing about a separate download
+        let (sized, download_url) = match download {
+            Some((element, url)) if url != media_url => (element, url),
+            _ => (media, media_url.clone()),
+        };
+
+        if let Some(ref pattern) = options.pattern {
+            if !self.matches_pattern(&download_url, pattern) {
+                return None;
+            }
+        }
+
+        let media_type = self
+            .get_extension(&download_url)
+            .and_then(|ext| media_type_for_extension(&ext))
+            .unwrap_or_else(|| target.category.media_type());
+
+        let title = selectors
+            .title
+            .as_ref()
+            .and_then(|selector| find(item, selector))
+            .map(|element| collapse_whitespace(&element.text().collect::<String>()))
+            .or_else(|| {
+                ["alt", "title"]
+                    .iter()
+                    .find_map(|attr| media.value().attr(attr))
+                    .map(collapse_whitespace)
+            })
+            .filter(|title| !title.is_empty())
+            .unwrap_or_else(|| file_stem(&download_url).replace(['-', '_'], " "));
+
+        let source_url = if item.value().name() == "a" {
+            Some(item)
+        } else {
+            item.select(&Selector::parse("a[href]").ok()?).next()
+        }
+        .and_then(|link| link.value().attr("href"))
+        .and_then(|href| page_url.join(href).ok())
+        .map_or_else(|| page_url.to_string(), |url| url.to_string());
+
+        let dimension = |attr| sized.value().attr(attr).and_then(|v| v.parse::<u32>().ok());
+
+        let mut builder = MediaAsset::builder()
+            .id(file_stem(&download_url))
+            .provider(target.id)
+            .media_type(media_type)
+            .title(title)
+            .download_url(download_url)
+            .source_url(source_url)
+            .license(target.license());
+        if media_url_is_preview(&media_url, media_type) {
+            builder = builder.preview_url(media_url);
+        }
+        if let (Some(width), Some(height)) = (dimension("width"), dimension("height")) {
+            builder = builder.dimensions(width, height);
+        }
+        Some(builder.build())
+    }
+
+    /// The absolute URL an element points at, preferring the largest `srcset` candidate.
+    fn element_url(&self, element: ElementRef, page_url: &Url) -> Option<String> {
+        let attrs = element.value();
+        let srcset = ["data-srcset", "srcset", "srcSet"]
+            .iter()
+            .find_map(|attr| attrs.attr(attr))
+            .and_then(|srcset| {
+                self.parse_srcset(srcset)
+                    .into_iter()
+                    .next()
+                    .or_else(|| srcset.split(',').next_back()?.split_whitespace().next().map(String::from))
+            });
+
+        let raw = srcset
+            .or_else(|| URL_ATTRS.iter().find_map(|attr| attrs.attr(attr)).map(String::from))
+            .or_else(|| {
+                // <video>/<audio> elements keep their files in <source> children
+                let source = Selector::parse("source[src]").ok()?;
+                element.select(&source).next()?.value().attr("src").map(String::from)
+            })?;
+
+        let raw = raw.trim();
+        if raw.is_empty() || raw.starts_with("data:") || raw.starts_with('#') {
+            return None;
+        }
+        page_url.join(raw).ok().map(|url| url.to_string())
+    }
+}
+
+/// Fetch a result page as HTML.
+async fn fetch_page(client: &HttpClient, target: &ScrapingTarget, url: &Url) -> Result<String> {
+    let response = client.get_raw(url.as_str()).await?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(DxError::ProviderApi {
+            provider: format!("scraper:{}", target.id),
+            message: format!("{} returned {}", url, status),
+            status_code: status.as_u16(),
+        });
+    }
+    Ok(response.text().await?)
+}
+
+/// The element itself if it matches `selector`, else its first matching descendant.
+fn find<'a>(element: ElementRef<'a>, selector: &Selector) -> Option<ElementRef<'a>> {
+    if selector.matches(&element) {
+        Some(element)
+    } else {
+        element.select(selector).next()
+    }
+}
+
+/// The next result page linked from `document`, if it's on the same site.
+fn next_page(document: &Html, selector: &Selector, page_url: &Url) -> Option<Url> {
+    let link = document.select(selector).next()?;
+    let href = link.value().attr("href").or_else(|| {
+        let anchor = Selector::parse("a[href]").ok()?;
+        link.select(&anchor).next()?.value().attr("href")
+    })?;
+    let next = page_url.join(href).ok()?;
+    (next.host() == page_url.host()).then_some(next)
+}
+
+/// Whether the item's media element is a preview distinct from the download.
+fn media_url_is_preview(media_url: &str, media_type: MediaType) -> bool {
+    let ext = media_url.split('?').next().and_then(|path| path.rsplit('.').next());
+    matches!(media_type, MediaType::Video | MediaType::Audio | MediaType::Model3D)
+        || ext.and_then(media_type_for_extension) == Some(MediaType::Image)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scraping::ScrapingCategory;
+
+    const TARGET: ScrapingTarget = ScrapingTarget::new(
+        "example",
+        "Example Photos",
+        "https://photos.example.com",
+        ".card",
+        "img",
+        ScrapingCategory::Images,
+        "CC0",
+        "100+",
+    )
+    .with_title_selector(".card-title")
+    .with_download_selector("a.download")
+    .with_pagination("a.next");
+
+    #[test]
+    fn test_extract_target_items() {
+        let html = r#"
+            <div class="card">
+              <a href="/photo/red-fox"><img data-src="/thumbs/red-fox.jpg" src="/blank.gif" width="640" height="480"></a>
+              <span class="card-title"> Red   fox </span>
+              <a class="download" href="https://cdn.example.com/red-fox.jpg?dl=1">Download</a>
+            </div>
+            <div class="card">
+              <img srcset="/b-400.jpg 400w, /b-1200.jpg 1200w" alt="Barn owl">
+            </div>
+            <div class="card"><img src="data:image/png;base64,AAAA"></div>
+            <div class="card">
+              <img src="/thumbs/heron.jpg" width="320" height="240">
+              <a class="download" href="/full/heron.jpg" width="3200" height="2400">Download</a>
+            </div>
+            <a class="next" href="?page=2">Next</a>
+        "#;
+        let scraper = Scraper::default();
+        let selectors = TargetSelectors::parse(&TARGET).unwrap();
+        let page = Url::parse("https://photos.example.com/search/fox").unwrap();
+        let document = Html::parse_document(html);
+
+        let assets = scraper.extract_target_items(
+            &TARGET,
+            &selectors,
+            &document,
+            &page,
+            &ScrapeOptions::default(),
+        );
+
+        assert_eq!(assets.len(), 3);
+        assert_eq!(assets[0].title, "Red fox");
+        assert_eq!(assets[0].id, "red-fox");
+        assert_eq!(assets[0].provider, "example");
+        assert_eq!(assets[0].download_url, "https://cdn.example.com/red-fox.jpg?dl=1");
+        assert_eq!(assets[0].source_url, "https://photos.example.com/photo/red-fox");
+        assert_eq!(
+            assets[0].preview_url.as_deref(),
+            Some("https://photos.example.com/thumbs/red-fox.jpg")
+        );
+        assert_eq!(assets[0].width, None);
+        assert_eq!(assets[0].license.as_str(), "CC0");
+
+        assert_eq!(assets[1].title, "Barn owl");
+        assert_eq!(assets[1].download_url, "https://photos.example.com/b-1200.jpg");
+        assert_eq!(assets[1].source_url, page.as_str());
+
+        assert_eq!(assets[2].download_url, "https://photos.example.com/full/heron.jpg");
+        assert_eq!((assets[2].width, assets[2].height), (Some(3200), Some(2400)));
+
+        let next = next_page(&document, selectors.pagination.as_ref().unwrap(), &page);
+        assert_eq!(
+            next.unwrap().as_str(),
+            "https://photos.example.com/search/fox?page=2"
+        );
+    }
+}
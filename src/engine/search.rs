@@ -3,6 +3,7 @@
 use std::sync::Arc;
 use std::time::Instant;
 
+use super::AssetStream;
 use crate::error::Result;
 use crate::providers::ProviderRegistry;
 use crate::types::{MediaType, SearchQuery, SearchResult};
@@ -31,37 +32,52 @@ impl SearchEngine {
 
     /// Execute a search query.
     pub async fn search(&self, query: &SearchQuery) -> Result<SearchResult> {
+        self.search_with(query, &mut |_, _| {}).await
+    }
+
+    /// Execute a search query, calling `on_result` with each provider's
+    /// results as they arrive.
+    pub async fn search_with(
+        &self,
+        query: &SearchQuery,
+        on_result: &mut (dyn FnMut(&str, &SearchResult) + Send),
+    ) -> Result<SearchResult> {
         let start = Instant::now();
 
         // If specific providers requested, search only those
         let mut result = if !query.providers.is_empty() {
-            self.search_specific_providers(query).await?
+            self.search_specific_providers(query, on_result).await?
         } else {
             // Search all available providers
-            self.registry.search_all(query).await?
+            self.registry.search_all_with(query, on_result).await?
         };
 
         result.duration_ms = start.elapsed().as_millis() as u64;
         Ok(result)
     }
 
-    /// Search specific providers by name (concurrently with timeouts).
-    async fn search_specific_providers(&self, query: &SearchQuery) -> Result<SearchResult> {
-        use crate::types::SearchMode;
+    /// Stream assets for a query, paging through each provider lazily.
+    ///
+    /// See [`DxMedia::search_stream`](crate::DxMedia::search_stream).
+    #[must_use]
+    pub fn search_stream(&self, query: &SearchQuery) -> AssetStream {
+        super::stream::search_stream(&self.registry, query)
+    }
+
+    /// Search specific providers by name (concurrently, with each provider's
+    /// circuit breaker and adaptive timeout).
+    async fn search_specific_providers(
+        &self,
+        query: &SearchQuery,
+        on_result: &mut (dyn FnMut(&str, &SearchResult) + Send),
+    ) -> Result<SearchResult> {
         use futures::stream::{FuturesUnordered, StreamExt};
-        use std::time::Duration;
-        
-        // Timeout varies by mode: Quantity=fast (5s), Quality=patient (8s)
-        let provider_timeout = match query.mode {
-            SearchMode::Quantity => Duration::from_secs(5),
-            SearchMode::Quality => Duration::from_secs(8),
-        };
         
         // Early exit only in Quantity mode
         let early_exit_threshold = query.count * 3;
         let use_early_exit = query.mode.is_quantity();
         
-        // Create FuturesUnordered for concurrent execution
+        // Circuit breakers and adaptive timeouts are applied by the registry
         let mut futures: FuturesUnordered<_> = query
             .providers
             .iter()
@@ -70,20 +86,8 @@ impl SearchEngine {
                 let provider_name = provider_name.clone();
                 let query = query.clone();
                 async move {
-                    let result = tokio::time::timeout(
-                        provider_timeout,
-                        registry.search_provider(&provider_name, &query)
-                    ).await;
-                    
-                    let timeout_msg = format!("Provider timed out (>{}s)", provider_timeout.as_secs());
-                    match result {
-                        Ok(search_result) => (provider_name, search_result),
-                        Err(_) => (provider_name.clone(), Err(crate::error::DxError::ProviderApi {
-                            provider: provider_name,
-                            message: timeout_msg,
-                            status_code: 408,
-                        })),
-                    }
+                    let result = registry.search_provider(&provider_name, &query).await;
+                    (provider_name, result)
                 }
             })
             .collect();
@@ -92,6 +96,9 @@ impl SearchEngine {
         let mut all_assets = Vec::new();
         let mut providers_searched = Vec::new();
         let mut provider_errors = Vec::new();
+        let mut cache_hits = Vec::new();
+        let mut cache_misses = Vec::new();
+        let mut filters = Vec::new();
         let mut total_count = 0;
         let mut skipped_slow_providers = 0;
 
@@ -100,8 +107,12 @@ impl SearchEngine {
 
             match result {
                 Ok(search_result) => {
+                    on_result(&provider_name, &search_result);
                     total_count += search_result.total_count;
                     all_assets.extend(search_result.assets);
+                    cache_hits.extend(search_result.cache_hits);
+                    cache_misses.extend(search_result.cache_misses);
+                    filters.extend(search_result.filters);
                 }
                 Err(e) => {
                     provider_errors.push((provider_name, e.to_string()));
@@ -122,6 +133,7 @@ impl SearchEngine {
                 format!("Skipped {} slow providers (had {} results)", skipped_slow_providers, all_assets.len())
             ));
         }
+        self.registry.save_health().await;
 
         if all_assets.is_empty() && !provider_errors.is_empty() {
             // All providers failed
@@ -141,10 +153,13 @@ impl SearchEngine {
             query: query.query.clone(),
             media_type: query.media_type,
             total_count,
-            assets: all_assets,
+            assets: crate::ranking::rank(all_assets, query),
             providers_searched,
             provider_errors,
             duration_ms: 0,
+            cache_hits,
+            cache_misses,
+            filters,
         })
     }
 
@@ -217,7 +232,7 @@ mod tests {
     #[test]
     fn test_search_query_builder() {
         let config = Config::default();
-        let registry = Arc::new(ProviderRegistry::new(&config));
+        let registry = Arc::new(ProviderRegistry::new(&config).unwrap());
         let engine = SearchEngine::new(registry);
 
         let query = engine
@@ -0,0 +1,360 @@
+//! Lazily paged search across providers.
+//!
+//! [`search_stream`] pages through every selected provider on demand and
+//! merges the results as they arrive. A provider's next page is only
+//! requested once the consumer has drained its previous one, so capping the
+//! stream with [`StreamExt::take`] also caps the number of requests made.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use futures::stream::{self, BoxStream, StreamExt};
+
+use crate::error::{DxError, Result};
+use crate::providers::ProviderRegistry;
+use crate::providers::traits::Provider;
+use crate::ranking::dedupe_key;
+use crate::types::{MediaAsset, SearchQuery, SearchResult};
+
+/// Stream of assets returned by [`DxMedia::search_stream`](crate::DxMedia::search_stream).
+pub type AssetStream = BoxStream<'static, Result<MediaAsset>>;
+
+/// Pages in a row whose assets were all removed by client-side filters
+/// before a provider is considered exhausted.
+const MAX_FILTERED_PAGES: usize = 5;
+
+/// Stream assets for a query from every selected provider.
+///
+/// Providers are those named in `query.providers`, or else every available
+/// provider for the query's media type. Each is paged from `query.page`
+/// onward in pages of `query.count`, spacing requests according to its
+/// [`RateLimitConfig`](crate::types::RateLimitConfig), until it runs out of
+/// results or fails. A failure is yielded as an `Err` item and ends that
+/// provider's results without affecting the others.
+///
+/// Assets already yielded (by normalized download URL) are skipped.
+pub fn search_stream(registry: &Arc<ProviderRegistry>, query: &SearchQuery) -> AssetStream {
+    let mut streams: Vec<AssetStream> = Vec::new();
+
+    if query.providers.is_empty() {
+        let providers = match query.media_type {
+            Some(media_type) => registry.for_media_type(media_type),
+            None => registry.available(),
+        };
+        for provider in providers {
+            streams.push(provider_stream(Arc::clone(registry), provider, query.clone()));
+        }
+    } else {
+        for name in &query.providers {
+            match registry.get(name) {
+                Some(provider) => {
+                    streams.push(provider_stream(Arc::clone(registry), provider, query.clone()));
+                }
+                None => streams.push(
+                    stream::once(std::future::ready(Err(DxError::ProviderApi {
+                        provider: name.clone(),
+                        message: "Provider not found".to_string(),
+                        status_code: 404,
+                    })))
+                    .boxed(),
+                ),
+            }
+        }
+    }
+
+    // Save provider health once the stream is exhausted, not after every page
+    let registry = Arc::clone(registry);
+    let save_health = stream::once(async move { registry.save_health().await })
+        .filter_map(|()| std::future::ready(None));
+
+    let mut seen = HashSet::new();
+    stream::select_all(streams)
+        .chain(save_health)
+        .filter(move |item| {
+            let fresh = match item {
+                Ok(asset) => seen.insert(dedupe_key(asset)),
+                Err(_) => true,
+            };
+            std::future::ready(fresh)
+        })
+        .boxed()
+}
+
+/// Paging state for one provider.
+struct Pager {
+    registry: Arc<ProviderRegistry>,
+    provider: Arc<dyn Provider>,
+    query: SearchQuery,
+    next_page: usize,
+    interval: Duration,
+    last_request: Option<Instant>,
+    seen: HashSet<String>,
+    filtered_pages: usize,
+    done: bool,
+}
+
+fn provider_stream(
+    registry: Arc<ProviderRegistry>,
+    provider: Arc<dyn Provider>,
+    mut query: SearchQuery,
+) -> AssetStream {
+    // Page by the provider's configured count so offsets line up with what it returns
+    if let Some(count) = registry.settings(provider.name()).count {
+        query.count = count;
+    }
+    let limit = provider.rate_limit();
+    let interval = Duration::from_secs(limit.period_secs) / limit.requests.max(1);
+
+    let pager = Pager {
+        registry,
+        next_page: query.page.max(1),
+        query,
+        provider,
+        interval,
+        last_request: None,
+        seen: HashSet::new(),
+        filtered_pages: 0,
+        done: false,
+    };
+
+    stream::unfold(pager, |mut pager| async move {
+        if pager.done {
+            return None;
+        }
+        let batch = if pager.provider.pages_locally() {
+            pager.fetch_all().await
+        } else {
+            pager.fetch_page().await
+        };
+        Some((stream::iter(batch), pager))
+    })
+    .flatten()
+    .boxed()
+}
+
+impl Pager {
+    /// Fetch the whole catalog once and skip to the requested page locally.
+    async fn fetch_all(&mut self) -> Vec<Result<MediaAsset>> {
+        self.done = true;
+        let offset = (self.next_page - 1) * self.query.count;
+        let query = self.query.clone().page(1).count(usize::MAX);
+
+        match self.search(&query).await {
+            Ok(result) => result.assets.into_iter().skip(offset).map(Ok).collect(),
+            Err(e) => vec![Err(e)],
+        }
+    }
+
+    /// Fetch the next page, waiting out the provider's rate limit first.
+    async fn fetch_page(&mut self) -> Vec<Result<MediaAsset>> {
+        if let Some(last) = self.last_request {
+            let wait = self.interval.saturating_sub(last.elapsed());
+            if !wait.is_zero() {
+                tokio::time::sleep(wait).await;
+            }
+        }
+
+        let page = self.next_page;
+        let query = self.query.clone().page(page);
+        let result = match self.search(&query).await {
+            Ok(result) => result,
+            Err(e) => {
+                self.done = true;
+                return vec![Err(e)];
+            }
+        };
+        self.next_page += 1;
+
+        let removed: usize = result.filters.iter().map(|f| f.removed).sum();
+        let fetched = result.assets.len() + removed;
+        let total = result.total_count;
+        let filtered_out = fetched > 0 && result.assets.is_empty();
+
+        let fresh: Vec<MediaAsset> = result
+            .assets
+            .into_iter()
+            .filter(|a| self.seen.insert(a.id.clone()))
+            .collect();
+
+        self.filtered_pages = if filtered_out { self.filtered_pages + 1 } else { 0 };
+        // Some providers report the page size as the total, so a total is
+        // only trusted if it's larger than the page or the page came up short
+        let trusted_total = total > fetched || fetched < self.query.count;
+        let offset = (page - 1).saturating_mul(self.query.count);
+
+        // A page of nothing but repeats means the provider ignores paging
+        self.done = fetched == 0
+            || (fresh.is_empty() && !filtered_out)
+            || self.filtered_pages >= MAX_FILTERED_PAGES
+            || (trusted_total && offset + fetched >= total);
+
+        fresh.into_iter().map(Ok).collect()
+    }
+
+    async fn search(&mut self, query: &SearchQuery) -> Result<SearchResult> {
+        let name = self.provider.name();
+        // The configured count would cap a fetch of the whole catalog
+        let result = if self.provider.pages_locally() {
+            self.registry.search_provider_uncapped(name, query).await?
+        } else {
+            self.registry.search_provider(name, query).await?
+        };
+        // Cache hits don't count against the rate limit
+        if result.cache_hits.is_empty() {
+            self.last_request = Some(Instant::now());
+        }
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{MediaType, RateLimitConfig};
+    use async_trait::async_trait;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Serves `total` numbered assets in pages, counting requests.
+    struct Paged {
+        name: &'static str,
+        total: usize,
+        local: bool,
+        calls: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl Provider for Paged {
+        fn name(&self) -> &'static str {
+            self.name
+        }
+        fn display_name(&self) -> &'static str {
+            self.name
+        }
+        fn supported_media_types(&self) -> &[MediaType] {
+            &[MediaType::Image]
+        }
+        fn requires_api_key(&self) -> bool {
+            false
+        }
+        fn rate_limit(&self) -> RateLimitConfig {
+            RateLimitConfig::new(1000, 1)
+        }
+        fn is_available(&self) -> bool {
+            true
+        }
+        fn pages_locally(&self) -> bool {
+            self.local
+        }
+        fn base_url(&self) -> &'static str {
+            "https://example.com"
+        }
+
+        async fn search(&self, query: &SearchQuery) -> Result<SearchResult> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            let start = (query.page - 1).saturating_mul(query.count).min(self.total);
+            let end = start.saturating_add(query.count).min(self.total);
+            let mut result = SearchResult::new(&query.query);
+            result.total_count = self.total;
+            result.assets = (start..end)
+                .map(|i| {
+                    MediaAsset::builder()
+                        .id(i.to_string())
+                        .provider(self.name)
+                        .media_type(MediaType::Image)
+                        .title(format!("{} {}", self.name, i))
+                        .download_url(format!("https://example.com/{}/{}.jpg", self.name, i))
+                        .source_url(format!("https://example.com/{}/{}", self.name, i))
+                        .build()
+                })
+                .collect();
+            Ok(result)
+        }
+    }
+
+    fn registry(providers: Vec<Arc<Paged>>) -> Arc<ProviderRegistry> {
+        let mut registry = ProviderRegistry::empty();
+        for provider in providers {
+            registry.register(provider);
+        }
+        Arc::new(registry)
+    }
+
+    fn paged(name: &'static str, total: usize, local: bool) -> Arc<Paged> {
+        Arc::new(Paged {
+            name,
+            total,
+            local,
+            calls: AtomicUsize::new(0),
+        })
+    }
+
+    #[tokio::test]
+    async fn test_pages_until_exhausted() {
+        let a = paged("a", 25, false);
+        let b = paged("b", 3, false);
+        let query = SearchQuery::new("x").count(10);
+
+        let assets: Vec<_> = search_stream(&registry(vec![a.clone(), b.clone()]), &query)
+            .collect()
+            .await;
+
+        assert_eq!(assets.len(), 28);
+        assert!(assets.iter().all(Result::is_ok));
+        assert_eq!(a.calls.load(Ordering::SeqCst), 3);
+        assert_eq!(b.calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_limit_stops_paging() {
+        let a = paged("a", 1000, false);
+        let query = SearchQuery::new("x").count(10);
+
+        let assets: Vec<_> = search_stream(&registry(vec![a.clone()]), &query)
+            .take(15)
+            .collect()
+            .await;
+
+        assert_eq!(assets.len(), 15);
+        assert_eq!(a.calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_local_paging_fetches_once() {
+        let a = paged("a", 40, true);
+        let query = SearchQuery::new("x").count(10).page(2);
+
+        let assets: Vec<_> = search_stream(&registry(vec![a.clone()]), &query)
+            .collect()
+            .await;
+
+        assert_eq!(assets.len(), 30);
+        assert_eq!(assets[0].as_ref().unwrap().id, "10");
+        assert_eq!(a.calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_local_paging_ignores_configured_count() {
+        let a = paged("a", 40, true);
+        let mut registry = ProviderRegistry::empty();
+        registry.register(Arc::clone(&a) as Arc<dyn Provider>);
+        let settings = crate::config::ProviderSettings {
+            count: Some(5),
+            ..Default::default()
+        };
+        let registry = Arc::new(registry.with_settings([("a".to_string(), settings)].into()));
+
+        let assets: Vec<_> = search_stream(&registry, &SearchQuery::new("x")).collect().await;
+
+        assert_eq!(assets.len(), 40);
+        assert_eq!(a.calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_unknown_provider_yields_error() {
+        let query = SearchQuery::new("x").providers(vec!["missing".to_string()]);
+        let items: Vec<_> = search_stream(&registry(vec![]), &query).collect().await;
+        assert_eq!(items.len(), 1);
+        assert!(items[0].is_err());
+    }
+}
@@ -133,6 +133,15 @@ pub enum DxError {
         source: Option<serde_json::Error>,
     },
 
+    /// XML parsing failed (sitemaps, feeds).
+    #[error("Failed to parse XML from '{url}': {message}")]
+    XmlParse {
+        /// URL of the document.
+        url: String,
+        /// Error description.
+        message: String,
+    },
+
     /// Invalid media type.
     #[error("Invalid media type: '{value}'")]
     InvalidMediaType {
@@ -186,6 +195,15 @@ impl DxError {
         }
     }
 
+    /// Create an error for an asset ID the provider doesn't know.
+    pub fn asset_not_found(provider: impl Into<String>, id: impl Into<String>) -> Self {
+        Self::ProviderApi {
+            provider: provider.into(),
+            message: format!("Asset '{}' not found", id.into()),
+            status_code: 404,
+        }
+    }
+
     /// Check if this error is retryable.
     #[must_use]
     pub fn is_retryable(&self) -> bool {
@@ -0,0 +1,512 @@
+//! Recorded HTTP responses for offline tests.
+//!
+//! In record mode, every API response (JSON, XML or text of at most 2 MiB)
+//! an [`HttpClient`](super::HttpClient) receives is saved as a JSON fixture
+//! file. Media and other large bodies pass through unrecorded and keep
+//! streaming. In replay mode, requests are
+//! answered from those files and never reach the network. Query parameters
+//! holding credentials are dropped before a request is matched or saved, so
+//! fixtures recorded with real API keys replay without them and never
+//! contain them.
+//!
+//! Clients use the process-wide fixtures installed when they are created:
+//! set `DX_HTTP_RECORD=<dir>` or `DX_HTTP_REPLAY=<dir>`, or call
+//! [`Fixtures::install`] before building providers.
+//!
+//! ```no_run
+//! use dx_media::http::Fixtures;
+//!
+//! Fixtures::replay("tests/fixtures/providers").install();
+//! ```
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, LazyLock, PoisonError, RwLock};
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use futures::StreamExt;
+use reqwest::header::{CONTENT_LENGTH, CONTENT_TYPE, HeaderMap};
+use reqwest::{Body, Request, Response};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, warn};
+use url::Url;
+
+use crate::cache::fnv1a;
+use crate::error::{DxError, Result};
+
+/// Environment variable naming a directory to record fixtures into.
+pub const RECORD_ENV: &str = "DX_HTTP_RECORD";
+
+/// Environment variable naming a directory to replay fixtures from.
+pub const REPLAY_ENV: &str = "DX_HTTP_REPLAY";
+
+/// Query parameters whose values are credentials.
+const SECRET_PARAMS: &[&str] = &[
+    "api_key",
+    "apikey",
+    "api-key",
+    "key",
+    "wskey",
+    "token",
+    "access_token",
+    "client_id",
+    "client_secret",
+];
+
+/// Response headers that change on every request or describe the transfer
+/// rather than the content.
+const SKIPPED_HEADERS: &[&str] = &[
+    "age",
+    "connection",
+    "content-encoding",
+    "content-length",
+    "date",
+    "keep-alive",
+    "set-cookie",
+    "transfer-encoding",
+];
+
+/// Largest body saved as a fixture.
+const MAX_FIXTURE_BYTES: usize = 2 * 1024 * 1024;
+
+static GLOBAL: LazyLock<RwLock<Option<Arc<Fixtures>>>> =
+    LazyLock::new(|| RwLock::new(Fixtures::from_env().map(Arc::new)));
+
+/// Whether fixtures are written or read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FixtureMode {
+    /// Make real requests and save each response.
+    Record,
+    /// Serve saved responses without touching the network.
+    Replay,
+}
+
+/// A directory of recorded responses.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fixtures {
+    dir: PathBuf,
+    mode: FixtureMode,
+}
+
+/// One recorded response, as stored on disk.
+///
+/// JSON bodies are stored as JSON so fixtures stay readable and editable;
+/// other text as a string and anything else as base64.
+#[derive(Debug, Serialize, Deserialize)]
+struct Fixture {
+    method: String,
+    url: String,
+    status: u16,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    headers: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    json: Option<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    body: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    body_base64: Option<String>,
+}
+
+/// What a request is matched on: its method, its URL without credentials
+/// and with sorted query parameters, and its body.
+#[derive(Debug, Clone)]
+pub(super) struct FixtureKey {
+    method: String,
+    url: String,
+    body: Option<Vec<u8>>,
+}
+
+impl FixtureKey {
+    pub(super) fn new(request: &Request) -> Self {
+        Self {
+            method: request.method().as_str().to_string(),
+            url: redact(request.url()),
+            body: request
+                .body()
+                .and_then(reqwest::Body::as_bytes)
+                .map(<[u8]>::to_vec),
+        }
+    }
+
+    /// A stable hash, so file names survive compiler upgrades.
+    fn hash(&self) -> u64 {
+        let mut bytes = format!("{} {}", self.method, self.url).into_bytes();
+        bytes.extend(self.body.iter().flatten());
+        fnv1a(&bytes)
+    }
+}
+
+impl Fixtures {
+    /// Record responses into `dir`.
+    #[must_use]
+    pub fn record(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            mode: FixtureMode::Record,
+        }
+    }
+
+    /// Replay responses from `dir`.
+    #[must_use]
+    pub fn replay(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            mode: FixtureMode::Replay,
+        }
+    }
+
+    /// Fixtures from `DX_HTTP_REPLAY` or `DX_HTTP_RECORD`, replay first.
+    #[must_use]
+    pub fn from_env() -> Option<Self> {
+        let dir = |name| std::env::var_os(name).filter(|dir| !dir.is_empty());
+        dir(REPLAY_ENV)
+            .map(Self::replay)
+            .or_else(|| dir(RECORD_ENV).map(Self::record))
+    }
+
+    /// Use these fixtures for every client created from now on.
+    pub fn install(self) {
+        *GLOBAL.write().unwrap_or_else(PoisonError::into_inner) = Some(Arc::new(self));
+    }
+
+    /// Stop using fixtures for clients created from now on.
+    pub fn uninstall() {
+        *GLOBAL.write().unwrap_or_else(PoisonError::into_inner) = None;
+    }
+
+    /// The installed fixtures, if any.
+    #[must_use]
+    pub fn global() -> Option<Arc<Self>> {
+        GLOBAL
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// The fixture directory.
+    #[must_use]
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Whether responses are recorded or replayed.
+    #[must_use]
+    pub fn mode(&self) -> FixtureMode {
+        self.mode
+    }
+
+    /// The file holding the response to `request`:
+    /// `<dir>/<host>/<last path segment>-<hash>.json`.
+    #[must_use]
+    pub fn path_for(&self, request: &Request) -> PathBuf {
+        self.path(&FixtureKey::new(request))
+    }
+
+    fn path(&self, key: &FixtureKey) -> PathBuf {
+        let url = Url::parse(&key.url).ok();
+        let host = url
+            .as_ref()
+            .and_then(|url| {
+                let host = url.host_str()?;
+                Some(match url.port() {
+                    Some(port) => format!("{host}_{port}"),
+                    None => host.to_string(),
+                })
+            })
+            .unwrap_or_else(|| "unknown".to_string());
+        let name: String = url
+            .as_ref()
+            .and_then(|url| url.path_segments()?.rfind(|s| !s.is_empty()))
+            .unwrap_or("index")
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .take(48)
+            .collect();
+
+        self.dir
+            .join(host)
+            .join(format!("{name}-{:016x}.json", key.hash()))
+    }
+
+    /// Answer a request from its fixture.
+    pub(super) fn replay_response(&self, request: &Request) -> Result<Response> {
+        let key = FixtureKey::new(request);
+        let path = self.path(&key);
+        let text = std::fs::read_to_string(&path).map_err(|_| {
+            DxError::http(format!(
+                "No fixture for {} {} (expected {})",
+                key.method,
+                key.url,
+                path.display()
+            ))
+        })?;
+        let fixture: Fixture = serde_json::from_str(&text).map_err(|e| DxError::JsonParse {
+            message: format!("Invalid fixture {}: {e}", path.display()),
+            source: Some(e),
+        })?;
+        debug!(
+            "Replaying {} {} from {}",
+            key.method,
+            key.url,
+            path.display()
+        );
+        fixture.into_response()
+    }
+
+    /// Save an API response and hand back an equivalent one.
+    ///
+    /// Responses that aren't JSON, XML or text, or whose body turns out to be
+    /// larger than [`MAX_FIXTURE_BYTES`], are handed back unrecorded with
+    /// their body still streaming.
+    pub(super) async fn record_response(
+        &self,
+        key: &FixtureKey,
+        mut response: Response,
+    ) -> Result<Response> {
+        let status = response.status();
+        let headers = response.headers().clone();
+
+        let too_long = headers
+            .get(CONTENT_LENGTH)
+            .and_then(|v| v.to_str().ok()?.parse::<usize>().ok())
+            .is_some_and(|len| len > MAX_FIXTURE_BYTES);
+        if too_long || !is_textual(&headers) {
+            debug!("Not recording {} {}: not an API response", key.method, key.url);
+            return Ok(response);
+        }
+
+        let mut chunks = Vec::new();
+        let mut len = 0;
+        while let Some(chunk) = response.chunk().await? {
+            len += chunk.len();
+            chunks.push(chunk);
+            if len > MAX_FIXTURE_BYTES {
+                debug!("Not recording {} {}: body too large", key.method, key.url);
+                let read = futures::stream::iter(chunks.into_iter().map(Ok::<_, reqwest::Error>));
+                let body = Body::wrap_stream(read.chain(response.bytes_stream()));
+                return Ok(rebuild(status, headers, body));
+            }
+        }
+        let bytes = chunks.concat();
+
+        let fixture = Fixture::new(key, status.as_u16(), &headers, &bytes);
+        let path = self.path(key);
+        let saved = path
+            .parent()
+            .map_or(Ok(()), std::fs::create_dir_all)
+            .and_then(|()| {
+                let json = serde_json::to_string_pretty(&fixture).map_err(std::io::Error::other)?;
+                std::fs::write(&path, json + "\n")
+            });
+        match saved {
+            Ok(()) => debug!("Recorded {} {} to {}", key.method, key.url, path.display()),
+            Err(e) => warn!("Failed to record fixture {}: {}", path.display(), e),
+        }
+
+        Ok(rebuild(status, headers, bytes))
+    }
+}
+
+/// A response with the given parts, in place of one whose body was consumed.
+fn rebuild(status: reqwest::StatusCode, headers: HeaderMap, body: impl Into<Body>) -> Response {
+    let mut rebuilt = http::Response::new(body.into());
+    *rebuilt.status_mut() = status;
+    *rebuilt.headers_mut() = headers;
+    Response::from(rebuilt)
+}
+
+/// Whether a response is JSON, XML or text, as API responses are.
+fn is_textual(headers: &HeaderMap) -> bool {
+    let Some(mime) = headers.get(CONTENT_TYPE).and_then(|v| v.to_str().ok()) else {
+        return false;
+    };
+    let mime = mime.to_ascii_lowercase();
+    mime.starts_with("text/") || mime.contains("json") || mime.contains("xml")
+}
+
+impl Fixture {
+    fn new(key: &FixtureKey, status: u16, headers: &HeaderMap, body: &[u8]) -> Self {
+        let headers: BTreeMap<String, String> = headers
+            .iter()
+            .filter(|(name, _)| !SKIPPED_HEADERS.contains(&name.as_str()))
+            .filter_map(|(name, value)| Some((name.to_string(), value.to_str().ok()?.to_string())))
+            .collect();
+        let is_json = headers
+            .get(CONTENT_TYPE.as_str())
+            .is_some_and(|t| t.contains("json"));
+
+        let mut fixture = Self {
+            method: key.method.clone(),
+            url: key.url.clone(),
+            status,
+            headers,
+            json: None,
+            body: None,
+            body_base64: None,
+        };
+        match std::str::from_utf8(body) {
+            Ok(text) => match serde_json::from_str(text) {
+                Ok(json) if is_json => fixture.json = Some(json),
+                _ => fixture.body = Some(text.to_string()),
+            },
+            Err(_) => fixture.body_base64 = Some(BASE64.encode(body)),
+        }
+        fixture
+    }
+
+    fn into_response(self) -> Result<Response> {
+        let body = match (self.json, self.body, self.body_base64) {
+            (Some(json), _, _) => serde_json::to_vec(&json)?,
+            (None, Some(text), _) => text.into_bytes(),
+            (None, None, Some(encoded)) => BASE64
+                .decode(encoded)
+                .map_err(|e| DxError::http(format!("Invalid fixture body: {e}")))?,
+            (None, None, None) => Vec::new(),
+        };
+
+        let mut builder = http::Response::builder().status(self.status);
+        for (name, value) in &self.headers {
+            builder = builder.header(name, value);
+        }
+        let response = builder
+            .body(body)
+            .map_err(|e| DxError::http(format!("Invalid fixture for {}: {e}", self.url)))?;
+        Ok(Response::from(response))
+    }
+}
+
+/// A URL without credential parameters, with the rest sorted.
+fn redact(url: &Url) -> String {
+    let mut url = url.clone();
+    let mut pairs: Vec<(String, String)> = url
+        .query_pairs()
+        .filter(|(name, _)| !SECRET_PARAMS.contains(&name.to_ascii_lowercase().as_str()))
+        .map(|(name, value)| (name.into_owned(), value.into_owned()))
+        .collect();
+    pairs.sort();
+    url.set_fragment(None);
+    if pairs.is_empty() {
+        url.set_query(None);
+    } else {
+        url.query_pairs_mut().clear().extend_pairs(pairs);
+    }
+    url.into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redact() {
+        let url =
+            Url::parse("https://api.example.com/v1/search?q=cat&api_key=SECRET&page=2&Key=x#top")
+                .unwrap();
+        assert_eq!(
+            redact(&url),
+            "https://api.example.com/v1/search?page=2&q=cat"
+        );
+
+        let url = Url::parse("https://api.example.com/items?key=SECRET").unwrap();
+        assert_eq!(redact(&url), "https://api.example.com/items");
+    }
+
+    #[tokio::test]
+    async fn test_fixture_round_trip() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let fixtures = Fixtures::record(dir.path());
+        let client = reqwest::Client::new();
+        let request = client
+            .get("https://api.example.com/v1/photos/?q=heron&api_key=SECRET")
+            .build()
+            .unwrap();
+        let key = FixtureKey::new(&request);
+
+        let mut original = http::Response::new(br#"{"results": [1, 2]}"#.to_vec());
+        original
+            .headers_mut()
+            .insert(CONTENT_TYPE, "application/json".parse().unwrap());
+        let recorded = fixtures
+            .record_response(&key, Response::from(original))
+            .await
+            .unwrap();
+        assert_eq!(recorded.text().await.unwrap(), r#"{"results": [1, 2]}"#);
+
+        let path = fixtures.path_for(&request);
+        assert!(path.starts_with(dir.path().join("api.example.com")));
+        assert!(
+            path.file_name()
+                .unwrap()
+                .to_string_lossy()
+                .starts_with("photos-")
+        );
+        let saved = std::fs::read_to_string(&path).unwrap();
+        assert!(saved.contains(r#""url": "https://api.example.com/v1/photos/?q=heron""#));
+        assert!(!saved.contains("SECRET"));
+
+        // Replay matches whatever key the request carries
+        let replay = Fixtures::replay(dir.path());
+        let other_key = client
+            .get("https://api.example.com/v1/photos/?api_key=OTHER&q=heron")
+            .build()
+            .unwrap();
+        let response = replay.replay_response(&other_key).unwrap();
+        assert_eq!(response.status(), 200);
+        assert_eq!(response.headers()[CONTENT_TYPE], "application/json");
+        let json: serde_json::Value = response.json().await.unwrap();
+        assert_eq!(json["results"][1], 2);
+
+        let missing = client
+            .get("https://api.example.com/v1/none")
+            .build()
+            .unwrap();
+        assert!(
+            replay
+                .replay_response(&missing)
+                .unwrap_err()
+                .to_string()
+                .contains("No fixture for GET https://api.example.com/v1/none")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_media_and_large_bodies_are_not_recorded() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let fixtures = Fixtures::record(dir.path());
+        let client = reqwest::Client::new();
+
+        let response = |mime: &str, body: Vec<u8>| {
+            let mut response = http::Response::new(body);
+            response
+                .headers_mut()
+                .insert(CONTENT_TYPE, mime.parse().unwrap());
+            Response::from(response)
+        };
+
+        let image = client.get("https://cdn.example.com/a.jpg").build().unwrap();
+        let key = FixtureKey::new(&image);
+        let passed = fixtures
+            .record_response(&key, response("image/jpeg", vec![0xff, 0xd8, 0xff]))
+            .await
+            .unwrap();
+        assert_eq!(passed.bytes().await.unwrap().as_ref(), [0xff, 0xd8, 0xff]);
+        assert!(!fixtures.path_for(&image).exists());
+
+        let text = client.get("https://example.com/big.txt").build().unwrap();
+        let key = FixtureKey::new(&text);
+        let body = vec![b'a'; MAX_FIXTURE_BYTES + 1];
+        let passed = fixtures
+            .record_response(&key, response("text/plain", body.clone()))
+            .await
+            .unwrap();
+        assert_eq!(passed.bytes().await.unwrap().len(), body.len());
+        assert!(!fixtures.path_for(&text).exists());
+    }
+}
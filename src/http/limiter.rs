@@ -0,0 +1,398 @@
+//! Process-wide rate limiting keyed by host.
+//!
+//! Every [`HttpClient`](super::HttpClient) shares one [`HostRateLimiter`], so
+//! provider API calls, downloads and scraping all draw from the same budget
+//! for a site. Hosts are grouped by registrable domain, so downloads from
+//! `upload.wikimedia.org` count against the limit of `commons.wikimedia.org`.
+//!
+//! Each host gets a token bucket sized from the strictest
+//! [`RateLimitConfig`] any client has used for it. Servers can tighten it
+//! further: `Retry-After` blocks the host outright, and low
+//! `X-RateLimit-Remaining` values spread the remaining requests evenly until
+//! `X-RateLimit-Reset`.
+
+use std::collections::HashMap;
+use std::sync::{Arc, LazyLock, Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+use reqwest::header::HeaderMap;
+use tracing::debug;
+use url::Url;
+
+use crate::config::Config;
+use crate::types::RateLimitConfig;
+
+/// Largest burst a bucket allows, however generous the configured rate.
+const MAX_BURST: u32 = 10;
+
+/// Below this many remaining requests, pace them out until the reset.
+const LOW_REMAINING: u64 = 10;
+
+/// Reset values above this are Unix timestamps rather than delays.
+const EPOCH_THRESHOLD: u64 = 1_000_000_000;
+
+static GLOBAL: LazyLock<Arc<HostRateLimiter>> = LazyLock::new(|| Arc::new(HostRateLimiter::new()));
+
+static PASSIVE: LazyLock<Arc<HostRateLimiter>> =
+    LazyLock::new(|| Arc::new(HostRateLimiter::passive()));
+
+/// Token-bucket rate limiter shared across clients, keyed by host.
+#[derive(Debug)]
+pub struct HostRateLimiter {
+    buckets: Mutex<HashMap<String, Bucket>>,
+    enabled: bool,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    capacity: f64,
+    per_sec: f64,
+    tokens: f64,
+    refilled: Instant,
+    blocked_until: Option<Instant>,
+    pace: Option<(Duration, Instant)>,
+    last_request: Option<Instant>,
+}
+
+impl Bucket {
+    fn unlimited(now: Instant) -> Self {
+        Self {
+            capacity: f64::INFINITY,
+            per_sec: f64::INFINITY,
+            tokens: f64::INFINITY,
+            refilled: now,
+            blocked_until: None,
+            pace: None,
+            last_request: None,
+        }
+    }
+
+    /// Adopt `config` if it is stricter than the current rate.
+    fn tighten(&mut self, config: RateLimitConfig) {
+        if !config.is_limited() || config.requests == 0 || config.period_secs == 0 {
+            return;
+        }
+        #[allow(clippy::cast_precision_loss)]
+        let per_sec = f64::from(config.requests) / config.period_secs as f64;
+        if per_sec < self.per_sec {
+            self.per_sec = per_sec;
+            self.capacity = f64::from(config.requests.min(MAX_BURST));
+            self.tokens = self.tokens.min(self.capacity);
+        }
+    }
+
+    /// Take a token, or return how long to wait for one.
+    fn try_acquire(&mut self, now: Instant, enforce: bool) -> Option<Duration> {
+        if let Some(until) = self.blocked_until {
+            if until > now {
+                return Some(until - now);
+            }
+            self.blocked_until = None;
+        }
+
+        if enforce {
+            if let Some((interval, until)) = self.pace {
+                if now >= until {
+                    self.pace = None;
+                } else if let Some(next) = self.last_request.map(|last| last + interval) {
+                    if next > now {
+                        return Some(next - now);
+                    }
+                }
+            }
+
+            if self.per_sec.is_finite() {
+                let elapsed = now.duration_since(self.refilled).as_secs_f64();
+                self.tokens = (self.tokens + elapsed * self.per_sec).min(self.capacity);
+                self.refilled = now;
+                if self.tokens < 1.0 {
+                    return Some(Duration::from_secs_f64((1.0 - self.tokens) / self.per_sec));
+                }
+                self.tokens -= 1.0;
+            }
+        }
+
+        self.last_request = Some(now);
+        None
+    }
+}
+
+impl HostRateLimiter {
+    /// Create an empty limiter. Most callers want [`HostRateLimiter::global`].
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            buckets: Mutex::new(HashMap::new()),
+            enabled: true,
+        }
+    }
+
+    /// Create an empty limiter without proactive limiting: only explicit
+    /// `Retry-After` blocks from servers are honored.
+    #[must_use]
+    pub fn passive() -> Self {
+        Self {
+            buckets: Mutex::new(HashMap::new()),
+            enabled: false,
+        }
+    }
+
+    /// The limiter shared by every client in the process.
+    #[must_use]
+    pub fn global() -> Arc<Self> {
+        Arc::clone(&GLOBAL)
+    }
+
+    /// The process-wide limiter matching `Config::respect_rate_limits`:
+    /// [`HostRateLimiter::global`], or a shared passive limiter when off.
+    #[must_use]
+    pub fn for_config(config: &Config) -> Arc<Self> {
+        if config.respect_rate_limits {
+            Self::global()
+        } else {
+            Arc::clone(&PASSIVE)
+        }
+    }
+
+    /// Whether proactive limiting is on.
+    #[must_use]
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Wait until a request to `url` is allowed under `config` and any
+    /// limits learned from the server.
+    pub async fn acquire(&self, url: &Url, config: RateLimitConfig) {
+        let key = host_key(url);
+        loop {
+            let wait = {
+                let mut buckets = self.buckets.lock().unwrap_or_else(PoisonError::into_inner);
+                let now = Instant::now();
+                let bucket = buckets
+                    .entry(key.clone())
+                    .or_insert_with(|| Bucket::unlimited(now));
+                bucket.tighten(config);
+                bucket.try_acquire(now, self.is_enabled())
+            };
+
+            match wait {
+                None => return,
+                Some(wait) => {
+                    debug!("Rate limiting {}: waiting {:?}", key, wait);
+                    tokio::time::sleep(wait).await;
+                }
+            }
+        }
+    }
+
+    /// Block all requests to `url`'s host for `duration`.
+    pub fn block_for(&self, url: &Url, duration: Duration) {
+        let until = Instant::now() + duration;
+        self.update(url, |bucket| {
+            bucket.blocked_until = Some(bucket.blocked_until.map_or(until, |b| b.max(until)));
+        });
+    }
+
+    /// Learn from `X-RateLimit-Remaining` / `X-RateLimit-Reset` response headers.
+    ///
+    /// No requests are allowed once the remaining count hits zero until the
+    /// reset time, and the last few are spaced evenly across the window.
+    pub fn observe(&self, url: &Url, headers: &HeaderMap) {
+        if !self.is_enabled() {
+            return;
+        }
+
+        let header = |names: &[&str]| {
+            names.iter().find_map(|name| {
+                headers
+                    .get(*name)
+                    .and_then(|v| v.to_str().ok())
+                    .and_then(|v| v.trim().parse::<u64>().ok())
+            })
+        };
+        let Some(remaining) = header(&["x-ratelimit-remaining", "ratelimit-remaining"]) else {
+            return;
+        };
+        if remaining >= LOW_REMAINING {
+            return;
+        }
+        let Some(reset) = header(&["x-ratelimit-reset", "ratelimit-reset"]).map(parse_reset) else {
+            return;
+        };
+
+        let now = Instant::now();
+        self.update(url, |bucket| {
+            if remaining == 0 {
+                bucket.blocked_until = Some(now + reset);
+            } else {
+                #[allow(clippy::cast_possible_truncation)]
+                let interval = reset / remaining as u32;
+                bucket.pace = Some((interval, now + reset));
+            }
+        });
+    }
+
+    fn update(&self, url: &Url, f: impl FnOnce(&mut Bucket)) {
+        let mut buckets = self.buckets.lock().unwrap_or_else(PoisonError::into_inner);
+        let bucket = buckets
+            .entry(host_key(url))
+            .or_insert_with(|| Bucket::unlimited(Instant::now()));
+        f(bucket);
+    }
+}
+
+impl Default for HostRateLimiter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Parse a `Retry-After` value, given either as seconds or as an HTTP date.
+#[must_use]
+pub fn parse_retry_after(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(secs) = value.parse::<u64>() {
+        return Some(Duration::from_secs(secs));
+    }
+
+    let date = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    let delta = date.with_timezone(&chrono::Utc) - chrono::Utc::now();
+    Some(delta.to_std().unwrap_or(Duration::ZERO))
+}
+
+/// Convert an `X-RateLimit-Reset` value (delay or Unix timestamp) into a delay.
+fn parse_reset(value: u64) -> Duration {
+    if value > EPOCH_THRESHOLD {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        Duration::from_secs(value.saturating_sub(now))
+    } else {
+        Duration::from_secs(value)
+    }
+}
+
+/// Key under which a URL's requests are limited: its registrable domain,
+/// plus the port when one is given explicitly.
+#[must_use]
+pub fn host_key(url: &Url) -> String {
+    let host = url.host_str().unwrap_or_default().to_lowercase();
+    let domain = registrable_domain(&host);
+    match url.port() {
+        Some(port) => format!("{}:{}", domain, port),
+        None => domain.to_string(),
+    }
+}
+
+/// Approximate the registrable domain of a host (`upload.wikimedia.org` →
+/// `wikimedia.org`, `www.bbc.co.uk` → `bbc.co.uk`).
+///
+/// Uses a short list of common two-label public suffixes rather than the
+/// full Public Suffix List. IP addresses are returned unchanged.
+#[must_use]
+pub fn registrable_domain(host: &str) -> &str {
+    const SECOND_LEVEL: &[&str] = &["co", "com", "org", "net", "ac", "gov", "edu", "or", "ne"];
+
+    if host.parse::<std::net::IpAddr>().is_ok() || host.starts_with('[') {
+        return host;
+    }
+
+    let labels: Vec<&str> = host.split('.').collect();
+    if labels.len() <= 2 {
+        return host;
+    }
+
+    let n = labels.len();
+    let keep = if labels[n - 1].len() == 2 && SECOND_LEVEL.contains(&labels[n - 2]) {
+        3
+    } else {
+        2
+    };
+    let skip: usize = labels[..n - keep].iter().map(|l| l.len() + 1).sum();
+    &host[skip..]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn url(s: &str) -> Url {
+        Url::parse(s).unwrap()
+    }
+
+    #[test]
+    fn test_host_key() {
+        assert_eq!(host_key(&url("https://upload.wikimedia.org/a.jpg")), "wikimedia.org");
+        assert_eq!(host_key(&url("https://commons.wikimedia.org/w/api.php")), "wikimedia.org");
+        assert_eq!(host_key(&url("https://API.Unsplash.com/photos")), "unsplash.com");
+        assert_eq!(host_key(&url("http://127.0.0.1:8080/x")), "127.0.0.1:8080");
+        assert_eq!(host_key(&url("https://example.com/")), "example.com");
+    }
+
+    #[test]
+    fn test_registrable_domain() {
+        assert_eq!(registrable_domain("upload.wikimedia.org"), "wikimedia.org");
+        assert_eq!(registrable_domain("www.bbc.co.uk"), "bbc.co.uk");
+        assert_eq!(registrable_domain("example.com"), "example.com");
+        assert_eq!(registrable_domain("127.0.0.1"), "127.0.0.1");
+    }
+
+    #[test]
+    fn test_parse_retry_after() {
+        assert_eq!(parse_retry_after("120"), Some(Duration::from_secs(120)));
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2015 07:28:00 GMT"),
+            Some(Duration::ZERO)
+        );
+
+        let future = (chrono::Utc::now() + chrono::Duration::seconds(90)).to_rfc2822();
+        let wait = parse_retry_after(&future).unwrap();
+        assert!(wait > Duration::from_secs(85) && wait <= Duration::from_secs(90));
+
+        assert_eq!(parse_retry_after("soon"), None);
+    }
+
+    #[tokio::test]
+    async fn test_bucket_limits_burst() {
+        let limiter = HostRateLimiter::new();
+        let target = url("https://api.example.com/");
+        let config = RateLimitConfig::new(2, 1);
+
+        let start = Instant::now();
+        for _ in 0..3 {
+            limiter.acquire(&target, config).await;
+        }
+        // Two tokens up front, the third refills after half a second
+        assert!(start.elapsed() >= Duration::from_millis(400));
+
+        // Another subdomain shares the bucket even without its own config
+        let start = Instant::now();
+        limiter
+            .acquire(&url("https://cdn.example.com/x.jpg"), RateLimitConfig::unlimited())
+            .await;
+        assert!(start.elapsed() >= Duration::from_millis(400));
+    }
+
+    #[tokio::test]
+    async fn test_remaining_zero_blocks_until_reset() {
+        let limiter = HostRateLimiter::new();
+        let target = url("https://api.example.com/");
+        let mut headers = HeaderMap::new();
+        headers.insert("x-ratelimit-remaining", "0".parse().unwrap());
+        headers.insert("x-ratelimit-reset", "1".parse().unwrap());
+        limiter.observe(&target, &headers);
+
+        let start = Instant::now();
+        limiter.acquire(&target, RateLimitConfig::unlimited()).await;
+        assert!(start.elapsed() >= Duration::from_millis(900));
+
+        // Disabled limiters ignore the headers
+        let limiter = HostRateLimiter::passive();
+        limiter.observe(&target, &headers);
+        let start = Instant::now();
+        limiter.acquire(&target, RateLimitConfig::new(1, 60)).await;
+        limiter.acquire(&target, RateLimitConfig::new(1, 60)).await;
+        assert!(start.elapsed() < Duration::from_millis(100));
+    }
+}
@@ -1,24 +1,40 @@
 //! HTTP client with rate limiting and retry logic.
 //!
-//! Provides a shared HTTP client for all provider implementations.
+//! Provides a shared HTTP client for all provider implementations, which can
+//! record its responses to fixture files and replay them offline.
 
+mod fixtures;
+mod limiter;
+
+pub use fixtures::{FixtureMode, Fixtures, RECORD_ENV, REPLAY_ENV};
+pub use limiter::{HostRateLimiter, host_key, parse_retry_after, registrable_domain};
+
+use self::fixtures::FixtureKey;
 use crate::USER_AGENT;
+use crate::config::Config;
 use crate::error::{DxError, Result};
 use crate::types::RateLimitConfig;
 use reqwest::{Client, Response, StatusCode};
 use std::sync::Arc;
-use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::Duration;
 use tokio::time::sleep;
 use tracing::{debug, warn};
 
+/// Longest `Retry-After` the client will wait out before giving up with
+/// [`DxError::RateLimited`].
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(120);
+
 /// HTTP client with built-in rate limiting and retry logic.
+///
+/// Requests are limited per host through the process-wide
+/// [`HostRateLimiter`], using the client's [`RateLimitConfig`].
 #[derive(Debug, Clone)]
 pub struct HttpClient {
     client: Client,
-    rate_limiter: Arc<RateLimiter>,
+    rate_limit: RateLimitConfig,
+    limiter: Arc<HostRateLimiter>,
+    fixtures: Option<Arc<Fixtures>>,
     max_retries: u32,
-    #[allow(dead_code)] // stored for potential future use
     timeout: Duration,
 }
 
@@ -42,44 +58,69 @@ impl HttpClient {
         max_retries: u32,
         timeout: Duration,
     ) -> Result<Self> {
-        use reqwest::header::{ACCEPT_LANGUAGE, HeaderMap, HeaderValue};
-        
-        let mut headers = HeaderMap::new();
-        // NOTE: Don't set Accept header globally - let each request specify it
-        // API providers need application/json while scrapers need text/html
-        headers.insert(ACCEPT_LANGUAGE, HeaderValue::from_static("en-US,en;q=0.9"));
-        
-        // PERFORMANCE OPTIMIZATIONS:
-        // - pool_max_idle_per_host: Keep 10 connections warm per API host
-        // - pool_idle_timeout: Keep connections alive for 30s between requests
-        // - tcp_nodelay: Disable Nagle's algorithm for faster small requests
-        // - http2_adaptive_window: Optimize HTTP/2 flow control
-        // - connection_verbose: Disabled for production
-        let client = Client::builder()
-            .user_agent(USER_AGENT)
-            .default_headers(headers)
-            .timeout(timeout)
-            .connect_timeout(Duration::from_secs(5))  // Fast connection or fail
-            .pool_max_idle_per_host(10)               // Keep 10 connections warm per host
-            .pool_idle_timeout(Duration::from_secs(30)) // Connections stay alive 30s
-            .tcp_nodelay(true)                        // Disable Nagle's algorithm
-            .gzip(true)
-            .brotli(true)
-            .build()
-            .map_err(|e| DxError::http(e.to_string()))?;
+        let client = build_client(USER_AGENT, timeout)?;
 
         Ok(Self {
             client,
-            rate_limiter: Arc::new(RateLimiter::new(rate_limit)),
+            rate_limit,
+            limiter: HostRateLimiter::global(),
+            fixtures: Fixtures::global(),
             max_retries,
             timeout,
         })
     }
 
+    /// Create a client with the retry, timeout and rate limiting settings of
+    /// `config`, limited to `rate_limit` per host.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the client cannot be created.
+    pub fn from_config(rate_limit: RateLimitConfig, config: &Config) -> Result<Self> {
+        Ok(Self::with_config(
+            rate_limit,
+            config.retry_attempts,
+            Duration::from_secs(config.timeout_secs),
+        )?
+        .with_limiter(HostRateLimiter::for_config(config)))
+    }
+
     /// Create a client with a specific rate limit.
     #[must_use]
     pub fn with_rate_limit(mut self, config: RateLimitConfig) -> Self {
-        self.rate_limiter = Arc::new(RateLimiter::new(config));
+        self.rate_limit = config;
+        self
+    }
+
+    /// Retry failed requests up to `retries` times.
+    #[must_use]
+    pub fn with_max_retries(mut self, retries: u32) -> Self {
+        self.max_retries = retries;
+        self
+    }
+
+    /// Send `user_agent` instead of the default browser-like one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the client cannot be rebuilt.
+    pub fn with_user_agent(mut self, user_agent: &str) -> Result<Self> {
+        self.client = build_client(user_agent, self.timeout)?;
+        Ok(self)
+    }
+
+    /// Use a separate limiter instead of the process-wide one.
+    #[must_use]
+    pub fn with_limiter(mut self, limiter: Arc<HostRateLimiter>) -> Self {
+        self.limiter = limiter;
+        self
+    }
+
+    /// Record responses to, or replay them from, `fixtures` instead of the
+    /// process-wide fixtures (see [`Fixtures`]).
+    #[must_use]
+    pub fn with_fixtures(mut self, fixtures: Fixtures) -> Self {
+        self.fixtures = Some(Arc::new(fixtures));
         self
     }
 
@@ -164,32 +205,51 @@ impl HttpClient {
     {
         let mut last_error = None;
 
+        if let Some(fixtures) = &self.fixtures {
+            if fixtures.mode() == FixtureMode::Replay {
+                return fixtures.replay_response(&build_request().build()?);
+            }
+        }
+
         for attempt in 0..=self.max_retries {
-            // Wait for rate limit
-            self.rate_limiter.acquire().await;
+            let request = build_request().build()?;
+            let url = request.url().clone();
+            let fixture_key = self.fixtures.as_ref().map(|_| FixtureKey::new(&request));
+
+            // Wait for the host's rate limit
+            self.limiter.acquire(&url, self.rate_limit).await;
 
-            let request = build_request();
             debug!(
-                "HTTP request attempt {}/{}",
+                "HTTP request attempt {}/{}: {}",
                 attempt + 1,
-                self.max_retries + 1
+                self.max_retries + 1,
+                url
             );
 
-            match request.send().await {
+            match self.client.execute(request).await {
                 Ok(response) => {
                     let status = response.status();
+                    self.limiter.observe(&url, response.headers());
 
                     // Handle rate limiting
                     if status == StatusCode::TOO_MANY_REQUESTS {
                         let retry_after = response
                             .headers()
-                            .get("retry-after")
+                            .get(reqwest::header::RETRY_AFTER)
                             .and_then(|h| h.to_str().ok())
-                            .and_then(|s| s.parse::<u64>().ok())
-                            .unwrap_or(60);
-
-                        warn!("Rate limited, waiting {} seconds", retry_after);
-                        sleep(Duration::from_secs(retry_after)).await;
+                            .and_then(parse_retry_after)
+                            .unwrap_or_else(|| Self::exponential_backoff(attempt));
+
+                        if attempt == self.max_retries || retry_after > MAX_RETRY_AFTER {
+                            return Err(DxError::RateLimited {
+                                provider: host_key(&url),
+                                retry_after_secs: retry_after.as_secs(),
+                            });
+                        }
+
+                        warn!("Rate limited by {}, waiting {:?}", host_key(&url), retry_after);
+                        // Blocks every client talking to this host, not just this one
+                        self.limiter.block_for(&url, retry_after);
                         continue;
                     }
 
@@ -201,6 +261,9 @@ impl HttpClient {
                         continue;
                     }
 
+                    if let (Some(fixtures), Some(key)) = (&self.fixtures, &fixture_key) {
+                        return fixtures.record_response(key, response).await;
+                    }
                     return Ok(response);
                 }
                 Err(e) => {
@@ -240,54 +303,41 @@ impl HttpClient {
     }
 }
 
+/// Build the underlying client, sending `user_agent`.
+fn build_client(user_agent: &str, timeout: Duration) -> Result<Client> {
+    use reqwest::header::{ACCEPT_LANGUAGE, HeaderMap, HeaderValue};
+    
+    let mut headers = HeaderMap::new();
+    // NOTE: Don't set Accept header globally - let each request specify it
+    // API providers need application/json while scrapers need text/html
+    headers.insert(ACCEPT_LANGUAGE, HeaderValue::from_static("en-US,en;q=0.9"));
+    
+    // PERFORMANCE OPTIMIZATIONS:
+    // - pool_max_idle_per_host: Keep 10 connections warm per API host
+    // - pool_idle_timeout: Keep connections alive for 30s between requests
+    // - tcp_nodelay: Disable Nagle's algorithm for faster small requests
+    // - http2_adaptive_window: Optimize HTTP/2 flow control
+    // - connection_verbose: Disabled for production
+    Client::builder()
+        .user_agent(user_agent)
+        .default_headers(headers)
+        .timeout(timeout)
+        .connect_timeout(Duration::from_secs(5))  // Fast connection or fail
+        .pool_max_idle_per_host(10)               // Keep 10 connections warm per host
+        .pool_idle_timeout(Duration::from_secs(30)) // Connections stay alive 30s
+        .tcp_nodelay(true)                        // Disable Nagle's algorithm
+        .gzip(true)
+        .brotli(true)
+        .build()
+        .map_err(|e| DxError::http(e.to_string()))
+}
+
 impl Default for HttpClient {
     fn default() -> Self {
         Self::new().expect("Failed to create default HTTP client")
     }
 }
 
-// ═══════════════════════════════════════════════════════════════════════════════
-// RATE LIMITER
-// ═══════════════════════════════════════════════════════════════════════════════
-
-/// Simple token bucket rate limiter.
-#[derive(Debug)]
-struct RateLimiter {
-    config: RateLimitConfig,
-    last_request: AtomicU64,
-}
-
-impl RateLimiter {
-    fn new(config: RateLimitConfig) -> Self {
-        Self {
-            config,
-            last_request: AtomicU64::new(0),
-        }
-    }
-
-    async fn acquire(&self) {
-        let delay_ms = self.config.delay_ms();
-        if delay_ms == 0 {
-            return;
-        }
-
-        let now = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap()
-            .as_millis() as u64;
-
-        let last = self.last_request.load(Ordering::Relaxed);
-        let elapsed = now.saturating_sub(last);
-
-        if elapsed < delay_ms {
-            let wait = delay_ms - elapsed;
-            sleep(Duration::from_millis(wait)).await;
-        }
-
-        self.last_request.store(now, Ordering::Relaxed);
-    }
-}
-
 // ═══════════════════════════════════════════════════════════════════════════════
 // RESPONSE HELPERS
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -72,11 +72,14 @@
 // MODULE DECLARATIONS
 // ═══════════════════════════════════════════════════════════════════════════════
 
+pub mod attribution;
+pub mod cache;
 pub mod config;
 pub mod engine;
 pub mod error;
 pub mod http;
 pub mod providers;
+pub mod ranking;
 pub mod scraping;
 pub mod tools;
 pub mod types;
@@ -91,10 +94,17 @@ pub mod cli;
 pub use config::Config;
 pub use engine::DxMedia;
 pub use error::{DxError, Result};
-pub use types::{License, MediaAsset, MediaType, SearchMode, SearchQuery, SearchResult};
+pub use cache::SearchCache;
+pub use types::{
+    CachePolicy, License, MediaAsset, MediaType, RankingStrategy, SearchMode, SearchQuery,
+    SearchResult,
+};
 
 // Re-export engine components
-pub use engine::{Downloader, FileManager, ScrapeOptions, ScrapeResult, Scraper, SearchEngine};
+pub use engine::{
+    AssetStream, DownloadQueue, DownloadReport, Downloader, FileManager, ScrapeOptions, ScrapeResult, Scraper,
+    SearchEngine,
+};
 
 // Re-export FREE providers (10 providers with 890M+ assets - NO API KEYS REQUIRED)
 pub use providers::{
@@ -6,7 +6,6 @@
 
 use async_trait::async_trait;
 use serde::Deserialize;
-use std::time::Duration;
 
 use crate::config::Config;
 use crate::error::Result;
@@ -23,16 +22,14 @@ pub struct InternetArchiveProvider {
 
 impl InternetArchiveProvider {
     /// Create a new Internet Archive provider.
-    #[must_use]
-    pub fn new(config: &Config) -> Self {
-        let client = HttpClient::with_config(
-            Self::RATE_LIMIT,
-            config.retry_attempts,
-            Duration::from_secs(config.timeout_secs),
-        )
-        .unwrap_or_default();
-
-        Self { client }
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP client cannot be created.
+    pub fn new(config: &Config) -> Result<Self> {
+        let client = HttpClient::from_config(Self::RATE_LIMIT, config)?;
+
+        Ok(Self { client })
     }
 
     /// Rate limit: Unlimited but be respectful
@@ -177,6 +174,9 @@ impl Provider for InternetArchiveProvider {
             providers_searched: vec!["archive".to_string()],
             provider_errors: vec![],
             duration_ms: 0,
+            cache_hits: Vec::new(),
+            cache_misses: Vec::new(),
+            filters: Vec::new(),
         })
     }
 }
@@ -232,7 +232,7 @@ mod tests {
     #[test]
     fn test_provider_metadata() {
         let config = Config::default_for_testing();
-        let provider = InternetArchiveProvider::new(&config);
+        let provider = InternetArchiveProvider::new(&config).unwrap();
 
         assert_eq!(provider.name(), "archive");
         assert_eq!(provider.display_name(), "Internet Archive");
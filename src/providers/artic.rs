@@ -6,10 +6,9 @@
 
 use async_trait::async_trait;
 use serde::Deserialize;
-use std::time::Duration;
 
 use crate::config::Config;
-use crate::error::Result;
+use crate::error::{DxError, Result};
 use crate::http::{HttpClient, ResponseExt};
 use crate::providers::traits::{Provider, ProviderInfo};
 use crate::types::{License, MediaAsset, MediaType, RateLimitConfig, SearchQuery, SearchResult};
@@ -23,16 +22,14 @@ pub struct ArtInstituteChicagoProvider {
 
 impl ArtInstituteChicagoProvider {
     /// Create a new Art Institute of Chicago provider.
-    #[must_use]
-    pub fn new(config: &Config) -> Self {
-        let client = HttpClient::with_config(
-            Self::RATE_LIMIT,
-            config.retry_attempts,
-            Duration::from_secs(config.timeout_secs),
-        )
-        .unwrap_or_default();
-
-        Self { client }
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP client cannot be created.
+    pub fn new(config: &Config) -> Result<Self> {
+        let client = HttpClient::from_config(Self::RATE_LIMIT, config)?;
+
+        Ok(Self { client })
     }
 
     /// Rate limit: Unlimited but be respectful
@@ -40,6 +37,29 @@ impl ArtInstituteChicagoProvider {
 
     /// IIIF image base URL for the Art Institute
     const IIIF_BASE: &'static str = "https://www.artic.edu/iiif/2";
+
+    /// Convert an artwork record into a media asset, if it has an image
+    fn artwork_asset(artwork: ArticArtwork) -> Option<MediaAsset> {
+        let image_id = artwork.image_id?;
+        let download_url =
+            format!("{}/{}/full/843,/0/default.jpg", Self::IIIF_BASE, image_id);
+        let preview_url =
+            format!("{}/{}/full/200,/0/default.jpg", Self::IIIF_BASE, image_id);
+
+        Some(
+            MediaAsset::builder()
+                .id(artwork.id.to_string())
+                .provider("artic")
+                .media_type(MediaType::Image)
+                .title(artwork.title.unwrap_or_else(|| "Untitled".to_string()))
+                .download_url(download_url)
+                .preview_url(preview_url)
+                .source_url(format!("https://www.artic.edu/artworks/{}", artwork.id))
+                .author(artwork.artist_title.unwrap_or_default())
+                .license(License::Cc0)
+                .build(),
+        )
+    }
 }
 
 #[async_trait]
@@ -95,27 +115,7 @@ impl Provider for ArtInstituteChicagoProvider {
         let assets: Vec<MediaAsset> = api_response
             .data
             .into_iter()
-            .filter_map(|artwork| {
-                let image_id = artwork.image_id?;
-                let download_url =
-                    format!("{}/{}/full/843,/0/default.jpg", Self::IIIF_BASE, image_id);
-                let preview_url =
-                    format!("{}/{}/full/200,/0/default.jpg", Self::IIIF_BASE, image_id);
-
-                Some(
-                    MediaAsset::builder()
-                        .id(artwork.id.to_string())
-                        .provider("artic")
-                        .media_type(MediaType::Image)
-                        .title(artwork.title.unwrap_or_else(|| "Untitled".to_string()))
-                        .download_url(download_url)
-                        .preview_url(preview_url)
-                        .source_url(format!("https://www.artic.edu/artworks/{}", artwork.id))
-                        .author(artwork.artist_title.unwrap_or_default())
-                        .license(License::Cc0)
-                        .build(),
-                )
-            })
+            .filter_map(Self::artwork_asset)
             .collect();
 
         Ok(SearchResult {
@@ -126,8 +126,30 @@ impl Provider for ArtInstituteChicagoProvider {
             providers_searched: vec!["artic".to_string()],
             provider_errors: vec![],
             duration_ms: 0,
+            cache_hits: Vec::new(),
+            cache_misses: Vec::new(),
+            filters: Vec::new(),
         })
     }
+
+    fn supports_get_asset(&self) -> bool {
+        true
+    }
+
+    async fn get_asset(&self, id: &str) -> Result<MediaAsset> {
+        let url = format!("{}/artworks/{}", self.base_url(), id);
+        let params = [("fields", "id,title,artist_title,image_id")];
+
+        let response = self.client.get_with_query(&url, &params, &[]).await?;
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Err(DxError::asset_not_found(self.name(), id));
+        }
+
+        let api_response: ArticArtworkResponse = response.json_or_error().await?;
+
+        Self::artwork_asset(api_response.data)
+            .ok_or_else(|| DxError::asset_not_found(self.name(), id))
+    }
 }
 
 impl ProviderInfo for ArtInstituteChicagoProvider {
@@ -154,6 +176,11 @@ struct ArticSearchResponse {
     pagination: ArticPagination,
 }
 
+#[derive(Debug, Deserialize)]
+struct ArticArtworkResponse {
+    data: ArticArtwork,
+}
+
 #[derive(Debug, Deserialize)]
 struct ArticArtwork {
     id: i64,
@@ -178,7 +205,7 @@ mod tests {
     #[test]
     fn test_provider_metadata() {
         let config = Config::default();
-        let provider = ArtInstituteChicagoProvider::new(&config);
+        let provider = ArtInstituteChicagoProvider::new(&config).unwrap();
 
         assert_eq!(provider.name(), "artic");
         assert_eq!(provider.display_name(), "Art Institute of Chicago");
@@ -189,7 +216,7 @@ mod tests {
     #[test]
     fn test_supported_media_types() {
         let config = Config::default();
-        let provider = ArtInstituteChicagoProvider::new(&config);
+        let provider = ArtInstituteChicagoProvider::new(&config).unwrap();
 
         let types = provider.supported_media_types();
         assert!(types.contains(&MediaType::Image));
@@ -6,7 +6,6 @@
 
 use async_trait::async_trait;
 use serde::Deserialize;
-use std::time::Duration;
 
 use crate::config::Config;
 use crate::error::Result;
@@ -23,16 +22,14 @@ pub struct CatApiProvider {
 
 impl CatApiProvider {
     /// Create a new Cat API provider.
-    #[must_use]
-    pub fn new(config: &Config) -> Self {
-        let client = HttpClient::with_config(
-            Self::RATE_LIMIT,
-            config.retry_attempts,
-            Duration::from_secs(config.timeout_secs),
-        )
-        .unwrap_or_default();
-
-        Self { client }
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP client cannot be created.
+    pub fn new(config: &Config) -> Result<Self> {
+        let client = HttpClient::from_config(Self::RATE_LIMIT, config)?;
+
+        Ok(Self { client })
     }
 
     /// Rate limit
@@ -119,6 +116,9 @@ impl Provider for CatApiProvider {
             providers_searched: vec!["catapi".to_string()],
             provider_errors: vec![],
             duration_ms: 0,
+            cache_hits: Vec::new(),
+            cache_misses: Vec::new(),
+            filters: Vec::new(),
         })
     }
 }
@@ -159,7 +159,7 @@ mod tests {
     #[test]
     fn test_provider_info() {
         let config = Config::default();
-        let provider = CatApiProvider::new(&config);
+        let provider = CatApiProvider::new(&config).unwrap();
         assert_eq!(provider.name(), "catapi");
         assert!(provider.is_available());
         assert!(!provider.requires_api_key());
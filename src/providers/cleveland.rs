@@ -6,10 +6,9 @@
 
 use async_trait::async_trait;
 use serde::Deserialize;
-use std::time::Duration;
 
 use crate::config::Config;
-use crate::error::Result;
+use crate::error::{DxError, Result};
 use crate::http::{HttpClient, ResponseExt};
 use crate::providers::traits::{Provider, ProviderInfo};
 use crate::types::{License, MediaAsset, MediaType, RateLimitConfig, SearchQuery, SearchResult};
@@ -23,20 +22,52 @@ pub struct ClevelandMuseumProvider {
 
 impl ClevelandMuseumProvider {
     /// Create a new Cleveland Museum of Art provider.
-    #[must_use]
-    pub fn new(config: &Config) -> Self {
-        let client = HttpClient::with_config(
-            Self::RATE_LIMIT,
-            config.retry_attempts,
-            Duration::from_secs(config.timeout_secs),
-        )
-        .unwrap_or_default();
-
-        Self { client }
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP client cannot be created.
+    pub fn new(config: &Config) -> Result<Self> {
+        let client = HttpClient::from_config(Self::RATE_LIMIT, config)?;
+
+        Ok(Self { client })
     }
 
     /// Rate limit: Unlimited but be respectful
     const RATE_LIMIT: RateLimitConfig = RateLimitConfig::new(100, 60);
+
+    /// Convert an artwork record into a media asset, if it has a web image
+    fn artwork_asset(artwork: ClevelandArtwork) -> Option<MediaAsset> {
+        let images = artwork.images?;
+        let web_image = images.web?;
+
+        Some(
+            MediaAsset::builder()
+                .id(artwork.id.to_string())
+                .provider("cleveland")
+                .media_type(MediaType::Image)
+                .title(artwork.title.unwrap_or_else(|| "Untitled".to_string()))
+                .download_url(web_image.url.clone())
+                .preview_url(web_image.url)
+                .source_url(artwork.url.unwrap_or_default())
+                .author(
+                    artwork
+                        .creators
+                        .map(|c| {
+                            c.into_iter()
+                                .map(|cr| cr.description)
+                                .collect::<Vec<_>>()
+                                .join(", ")
+                        })
+                        .unwrap_or_default(),
+                )
+                .license(License::Cc0)
+                .dimensions(
+                    web_image.width.unwrap_or(0) as u32,
+                    web_image.height.unwrap_or(0) as u32,
+                )
+                .build(),
+        )
+    }
 }
 
 #[async_trait]
@@ -89,38 +120,7 @@ impl Provider for ClevelandMuseumProvider {
         let assets: Vec<MediaAsset> = api_response
             .data
             .into_iter()
-            .filter_map(|artwork| {
-                let images = artwork.images?;
-                let web_image = images.web?;
-
-                Some(
-                    MediaAsset::builder()
-                        .id(artwork.id.to_string())
-                        .provider("cleveland")
-                        .media_type(MediaType::Image)
-                        .title(artwork.title.unwrap_or_else(|| "Untitled".to_string()))
-                        .download_url(web_image.url.clone())
-                        .preview_url(web_image.url)
-                        .source_url(artwork.url.unwrap_or_default())
-                        .author(
-                            artwork
-                                .creators
-                                .map(|c| {
-                                    c.into_iter()
-                                        .map(|cr| cr.description)
-                                        .collect::<Vec<_>>()
-                                        .join(", ")
-                                })
-                                .unwrap_or_default(),
-                        )
-                        .license(License::Cc0)
-                        .dimensions(
-                            web_image.width.unwrap_or(0) as u32,
-                            web_image.height.unwrap_or(0) as u32,
-                        )
-                        .build(),
-                )
-            })
+            .filter_map(Self::artwork_asset)
             .collect();
 
         Ok(SearchResult {
@@ -131,8 +131,29 @@ impl Provider for ClevelandMuseumProvider {
             providers_searched: vec!["cleveland".to_string()],
             provider_errors: vec![],
             duration_ms: 0,
+            cache_hits: Vec::new(),
+            cache_misses: Vec::new(),
+            filters: Vec::new(),
         })
     }
+
+    fn supports_get_asset(&self) -> bool {
+        true
+    }
+
+    async fn get_asset(&self, id: &str) -> Result<MediaAsset> {
+        let url = format!("{}/{}", self.base_url(), id);
+
+        let response = self.client.get(&url).await?;
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Err(DxError::asset_not_found(self.name(), id));
+        }
+
+        let api_response: ClevelandArtworkResponse = response.json_or_error().await?;
+
+        Self::artwork_asset(api_response.data)
+            .ok_or_else(|| DxError::asset_not_found(self.name(), id))
+    }
 }
 
 impl ProviderInfo for ClevelandMuseumProvider {
@@ -159,6 +180,11 @@ struct ClevelandResponse {
     data: Vec<ClevelandArtwork>,
 }
 
+#[derive(Debug, Deserialize)]
+struct ClevelandArtworkResponse {
+    data: ClevelandArtwork,
+}
+
 #[derive(Debug, Deserialize)]
 struct ClevelandInfo {
     total: Option<usize>,
@@ -216,7 +242,7 @@ mod tests {
     #[test]
     fn test_provider_metadata() {
         let config = Config::default();
-        let provider = ClevelandMuseumProvider::new(&config);
+        let provider = ClevelandMuseumProvider::new(&config).unwrap();
 
         assert_eq!(provider.name(), "cleveland");
         assert_eq!(provider.display_name(), "Cleveland Museum of Art");
@@ -227,7 +253,7 @@ mod tests {
     #[test]
     fn test_supported_media_types() {
         let config = Config::default();
-        let provider = ClevelandMuseumProvider::new(&config);
+        let provider = ClevelandMuseumProvider::new(&config).unwrap();
 
         let types = provider.supported_media_types();
         assert!(types.contains(&MediaType::Image));
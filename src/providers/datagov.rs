@@ -7,7 +7,6 @@
 
 use async_trait::async_trait;
 use serde::Deserialize;
-use std::time::Duration;
 
 use crate::config::Config;
 use crate::error::Result;
@@ -24,16 +23,14 @@ pub struct DataGovProvider {
 
 impl DataGovProvider {
     /// Create a new Data.gov provider.
-    #[must_use]
-    pub fn new(config: &Config) -> Self {
-        let client = HttpClient::with_config(
-            Self::RATE_LIMIT,
-            config.retry_attempts,
-            Duration::from_secs(config.timeout_secs),
-        )
-        .unwrap_or_default();
-
-        Self { client }
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP client cannot be created.
+    pub fn new(config: &Config) -> Result<Self> {
+        let client = HttpClient::from_config(Self::RATE_LIMIT, config)?;
+
+        Ok(Self { client })
     }
 
     /// Rate limit: Be respectful - 30 requests/minute
@@ -202,6 +199,9 @@ impl Provider for DataGovProvider {
             providers_searched: vec!["datagov".to_string()],
             provider_errors: vec![],
             duration_ms: 0,
+            cache_hits: Vec::new(),
+            cache_misses: Vec::new(),
+            filters: Vec::new(),
         })
     }
 }
@@ -294,7 +294,7 @@ mod tests {
     #[test]
     fn test_provider_info() {
         let config = Config::default();
-        let provider = DataGovProvider::new(&config);
+        let provider = DataGovProvider::new(&config).unwrap();
 
         assert_eq!(provider.name(), "datagov");
         assert_eq!(provider.display_name(), "Data.gov");
@@ -305,7 +305,7 @@ mod tests {
     #[test]
     fn test_supported_media_types() {
         let config = Config::default();
-        let provider = DataGovProvider::new(&config);
+        let provider = DataGovProvider::new(&config).unwrap();
 
         let types = provider.supported_media_types();
         assert!(types.contains(&MediaType::Data));
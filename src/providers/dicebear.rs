@@ -5,7 +5,6 @@
 //! Free avatar generation API - unlimited SVG/PNG avatars, no API key required.
 
 use async_trait::async_trait;
-use std::time::Duration;
 
 use crate::config::Config;
 use crate::error::Result;
@@ -23,16 +22,14 @@ pub struct DiceBearProvider {
 
 impl DiceBearProvider {
     /// Create a new DiceBear provider.
-    #[must_use]
-    pub fn new(config: &Config) -> Self {
-        let client = HttpClient::with_config(
-            Self::RATE_LIMIT,
-            config.retry_attempts,
-            Duration::from_secs(config.timeout_secs),
-        )
-        .unwrap_or_default();
-
-        Self { client }
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP client cannot be created.
+    pub fn new(config: &Config) -> Result<Self> {
+        let client = HttpClient::from_config(Self::RATE_LIMIT, config)?;
+
+        Ok(Self { client })
     }
 
     /// Rate limit: Generous
@@ -148,6 +145,9 @@ impl Provider for DiceBearProvider {
             providers_searched: vec!["dicebear".to_string()],
             provider_errors: vec![],
             duration_ms: 0,
+            cache_hits: Vec::new(),
+            cache_misses: Vec::new(),
+            filters: Vec::new(),
         })
     }
 }
@@ -173,7 +173,7 @@ mod tests {
     #[test]
     fn test_provider_info() {
         let config = Config::default();
-        let provider = DiceBearProvider::new(&config);
+        let provider = DiceBearProvider::new(&config).unwrap();
         assert_eq!(provider.name(), "dicebear");
         assert!(provider.is_available());
         assert!(!provider.requires_api_key());
@@ -6,7 +6,6 @@
 
 use async_trait::async_trait;
 use serde::Deserialize;
-use std::time::Duration;
 
 use crate::config::Config;
 use crate::error::Result;
@@ -23,16 +22,14 @@ pub struct DogCeoProvider {
 
 impl DogCeoProvider {
     /// Create a new Dog CEO provider.
-    #[must_use]
-    pub fn new(config: &Config) -> Self {
-        let client = HttpClient::with_config(
-            Self::RATE_LIMIT,
-            config.retry_attempts,
-            Duration::from_secs(config.timeout_secs),
-        )
-        .unwrap_or_default();
-
-        Self { client }
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP client cannot be created.
+    pub fn new(config: &Config) -> Result<Self> {
+        let client = HttpClient::from_config(Self::RATE_LIMIT, config)?;
+
+        Ok(Self { client })
     }
 
     /// Rate limit: Generous
@@ -142,6 +139,9 @@ impl DogCeoProvider {
             providers_searched: vec!["dogceo".to_string()],
             provider_errors: vec![],
             duration_ms: 0,
+            cache_hits: Vec::new(),
+            cache_misses: Vec::new(),
+            filters: Vec::new(),
         })
     }
 }
@@ -174,7 +174,7 @@ mod tests {
     #[test]
     fn test_provider_info() {
         let config = Config::default();
-        let provider = DogCeoProvider::new(&config);
+        let provider = DogCeoProvider::new(&config).unwrap();
         assert_eq!(provider.name(), "dogceo");
         assert!(provider.is_available());
         assert!(!provider.requires_api_key());
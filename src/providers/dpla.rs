@@ -6,7 +6,6 @@
 
 use async_trait::async_trait;
 use serde::Deserialize;
-use std::time::Duration;
 
 use crate::config::Config;
 use crate::error::Result;
@@ -23,16 +22,14 @@ pub struct DplaProvider {
 
 impl DplaProvider {
     /// Create a new DPLA provider.
-    #[must_use]
-    pub fn new(config: &Config) -> Self {
-        let client = HttpClient::with_config(
-            Self::RATE_LIMIT,
-            config.retry_attempts,
-            Duration::from_secs(config.timeout_secs),
-        )
-        .unwrap_or_default();
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP client cannot be created.
+    pub fn new(config: &Config) -> Result<Self> {
+        let client = HttpClient::from_config(Self::RATE_LIMIT, config)?;
 
-        Self { client }
+        Ok(Self { client })
     }
 
     /// Rate limit: Unlimited but be respectful
@@ -147,6 +144,9 @@ impl Provider for DplaProvider {
             providers_searched: vec!["dpla".to_string()],
             provider_errors: vec![],
             duration_ms: 0,
+            cache_hits: Vec::new(),
+            cache_misses: Vec::new(),
+            filters: Vec::new(),
         })
     }
 }
@@ -202,7 +202,7 @@ mod tests {
     #[test]
     fn test_provider_metadata() {
         let config = Config::default();
-        let provider = DplaProvider::new(&config);
+        let provider = DplaProvider::new(&config).unwrap();
 
         assert_eq!(provider.name(), "dpla");
         assert_eq!(provider.display_name(), "Digital Public Library of America");
@@ -214,7 +214,7 @@ mod tests {
     #[test]
     fn test_supported_media_types() {
         let config = Config::default();
-        let provider = DplaProvider::new(&config);
+        let provider = DplaProvider::new(&config).unwrap();
 
         let types = provider.supported_media_types();
         assert!(types.contains(&MediaType::Image));
@@ -6,7 +6,6 @@
 
 use async_trait::async_trait;
 use serde::Deserialize;
-use std::time::Duration;
 
 use crate::config::Config;
 use crate::error::Result;
@@ -23,16 +22,14 @@ pub struct EuropeanaProvider {
 
 impl EuropeanaProvider {
     /// Create a new Europeana provider.
-    #[must_use]
-    pub fn new(config: &Config) -> Self {
-        let client = HttpClient::with_config(
-            Self::RATE_LIMIT,
-            config.retry_attempts,
-            Duration::from_secs(config.timeout_secs),
-        )
-        .unwrap_or_default();
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP client cannot be created.
+    pub fn new(config: &Config) -> Result<Self> {
+        let client = HttpClient::from_config(Self::RATE_LIMIT, config)?;
 
-        Self { client }
+        Ok(Self { client })
     }
 
     /// Rate limit: 10000 requests per day (generous)
@@ -155,6 +152,9 @@ impl Provider for EuropeanaProvider {
             providers_searched: vec!["europeana".to_string()],
             provider_errors: vec![],
             duration_ms: 0,
+            cache_hits: Vec::new(),
+            cache_misses: Vec::new(),
+            filters: Vec::new(),
         })
     }
 }
@@ -207,7 +207,7 @@ mod tests {
     #[test]
     fn test_provider_metadata() {
         let config = Config::default();
-        let provider = EuropeanaProvider::new(&config);
+        let provider = EuropeanaProvider::new(&config).unwrap();
 
         assert_eq!(provider.name(), "europeana");
         assert_eq!(provider.display_name(), "Europeana");
@@ -218,7 +218,7 @@ mod tests {
     #[test]
     fn test_supported_media_types() {
         let config = Config::default();
-        let provider = EuropeanaProvider::new(&config);
+        let provider = EuropeanaProvider::new(&config).unwrap();
 
         let types = provider.supported_media_types();
         assert!(types.contains(&MediaType::Image));
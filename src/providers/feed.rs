@@ -0,0 +1,151 @@
+//! RSS and Atom feed providers.
+//!
+//! A `[providers.<name>]` table with a `feed` URL registers a [`FeedProvider`]
+//! under that name. Feeds can't be searched server-side, so each search
+//! reads the whole feed and matches its items locally.
+//!
+//! ```toml
+//! [providers.nasa-iotd]
+//! feed = "https://www.nasa.gov/feeds/iotd-feed/"
+//! license = "public-domain"
+//! ```
+
+use async_trait::async_trait;
+
+use crate::config::Config;
+use crate::engine::{Feed, matches_query};
+use crate::error::{DxError, Result};
+use crate::http::HttpClient;
+use crate::providers::leak_str;
+use crate::providers::traits::Provider;
+use crate::types::{License, MediaAsset, MediaType, RateLimitConfig, SearchQuery, SearchResult};
+
+/// A provider serving the media of one RSS or Atom feed.
+#[derive(Debug)]
+pub struct FeedProvider {
+    name: &'static str,
+    url: String,
+    base_url: &'static str,
+    license: License,
+    client: HttpClient,
+}
+
+impl FeedProvider {
+    /// Rate limit: feeds change slowly, so a few requests a minute is plenty.
+    const RATE_LIMIT: RateLimitConfig = RateLimitConfig::new(10, 60);
+
+    /// Create a provider for the feed at `url`.
+    ///
+    /// `license` applies to items that don't state their own.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP client cannot be created.
+    pub fn new(name: &str, url: &str, license: Option<&str>, config: &Config) -> Result<Self> {
+        let client = HttpClient::from_config(Self::RATE_LIMIT, config)?;
+
+        let base_url = url::Url::parse(url)
+            .map(|url| url.origin().ascii_serialization())
+            .unwrap_or_default();
+
+        Ok(Self {
+            name: leak_str(name.to_string()),
+            url: url.to_string(),
+            base_url: leak_str(base_url),
+            license: license.map_or_else(
+                || License::Other("Unknown - Check source".to_string()),
+                License::parse,
+            ),
+            client,
+        })
+    }
+
+    /// The feed URL.
+    #[must_use]
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    async fn assets(&self) -> Result<Vec<MediaAsset>> {
+        let feed = Feed::fetch(&self.client, &self.url).await?;
+        Ok(feed.assets(self.name, &self.license))
+    }
+}
+
+#[async_trait]
+impl Provider for FeedProvider {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn display_name(&self) -> &'static str {
+        self.name
+    }
+
+    fn supported_media_types(&self) -> &[MediaType] {
+        &[
+            MediaType::Image,
+            MediaType::Video,
+            MediaType::Audio,
+            MediaType::Gif,
+            MediaType::Document,
+        ]
+    }
+
+    fn requires_api_key(&self) -> bool {
+        false
+    }
+
+    fn rate_limit(&self) -> RateLimitConfig {
+        Self::RATE_LIMIT
+    }
+
+    fn is_available(&self) -> bool {
+        true
+    }
+
+    fn base_url(&self) -> &'static str {
+        self.base_url
+    }
+
+    fn pages_locally(&self) -> bool {
+        true
+    }
+
+    fn supports_get_asset(&self) -> bool {
+        true
+    }
+
+    async fn search(&self, query: &SearchQuery) -> Result<SearchResult> {
+        let text = match query.query.trim() {
+            "*" => "",
+            text => text,
+        };
+        let matching: Vec<MediaAsset> = self
+            .assets()
+            .await?
+            .into_iter()
+            .filter(|asset| query.media_type.is_none_or(|t| asset.media_type == t))
+            .filter(|asset| matches_query(asset, text))
+            .collect();
+
+        let mut result = SearchResult::new(&query.query);
+        result.media_type = query.media_type;
+        result.total_count = matching.len();
+        result.assets = matching
+            .into_iter()
+            .skip(query.page.saturating_sub(1).saturating_mul(query.count))
+            .take(query.count)
+            .collect();
+        result.providers_searched = vec![self.name.to_string()];
+        Ok(result)
+    }
+
+    async fn get_asset(&self, id: &str) -> Result<MediaAsset> {
+        self.assets()
+            .await?
+            .into_iter()
+            .find(|asset| asset.id == id)
+            .ok_or_else(|| DxError::asset_not_found(self.name, id))
+    }
+}
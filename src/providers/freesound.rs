@@ -6,7 +6,6 @@
 
 use async_trait::async_trait;
 use serde::Deserialize;
-use std::time::Duration;
 
 use crate::config::Config;
 use crate::error::Result;
@@ -24,24 +23,26 @@ pub struct FreesoundProvider {
 
 impl FreesoundProvider {
     /// Create a new Freesound provider.
-    #[must_use]
-    pub fn new(config: &Config) -> Self {
-        let client = HttpClient::with_config(
-            Self::RATE_LIMIT,
-            config.retry_attempts,
-            Duration::from_secs(config.timeout_secs),
-        )
-        .unwrap_or_default();
-
-        Self {
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP client cannot be created.
+    pub fn new(config: &Config) -> Result<Self> {
+        let client = HttpClient::from_config(Self::RATE_LIMIT, config)?;
+
+        Ok(Self {
             api_key: config.freesound_api_key.clone(),
             client,
-        }
+        })
     }
 
     /// Rate limit: 2000 requests per day
     const RATE_LIMIT: RateLimitConfig = RateLimitConfig::new(2000, 86400);
 
+    /// Sound fields requested from the API
+    const FIELDS: &'static str =
+        "id,name,description,tags,license,username,previews,download,duration,filesize";
+
     /// Parse license from Freesound license string
     fn parse_license(license: &str) -> License {
         match license {
@@ -51,6 +52,35 @@ impl FreesoundProvider {
             _ => License::Other(license.to_string()),
         }
     }
+
+    /// Convert a sound record into a media asset
+    fn sound_asset(sound: FreesoundSound) -> MediaAsset {
+        let license = Self::parse_license(&sound.license);
+
+        // Get preview URL (prefer HQ MP3)
+        let preview_url = sound
+            .previews
+            .as_ref()
+            .and_then(|p| p.preview_hq_mp3.clone().or(p.preview_lq_mp3.clone()))
+            .unwrap_or_default();
+
+        let author_url = format!("https://freesound.org/people/{}/", sound.username);
+
+        MediaAsset::builder()
+            .id(sound.id.to_string())
+            .provider("freesound")
+            .media_type(MediaType::Audio)
+            .title(sound.name)
+            .download_url(sound.download.unwrap_or_else(|| preview_url.clone()))
+            .preview_url(preview_url)
+            .source_url(format!("https://freesound.org/s/{}/", sound.id))
+            .author(sound.username)
+            .author_url(author_url)
+            .license(license)
+            .tags(sound.tags)
+            .file_size(sound.filesize.unwrap_or(0))
+            .build()
+    }
 }
 
 #[async_trait]
@@ -100,10 +130,7 @@ impl Provider for FreesoundProvider {
             ("query", query.query.as_str()),
             ("page", &page_str),
             ("page_size", &page_size_str),
-            (
-                "fields",
-                "id,name,description,tags,license,username,previews,download,duration,filesize",
-            ),
+            ("fields", Self::FIELDS),
             ("token", api_key.as_str()),
         ];
 
@@ -114,33 +141,7 @@ impl Provider for FreesoundProvider {
         let assets: Vec<MediaAsset> = api_response
             .results
             .into_iter()
-            .map(|sound| {
-                let license = Self::parse_license(&sound.license);
-
-                // Get preview URL (prefer HQ MP3)
-                let preview_url = sound
-                    .previews
-                    .as_ref()
-                    .and_then(|p| p.preview_hq_mp3.clone().or(p.preview_lq_mp3.clone()))
-                    .unwrap_or_default();
-
-                let author_url = format!("https://freesound.org/people/{}/", sound.username);
-
-                MediaAsset::builder()
-                    .id(sound.id.to_string())
-                    .provider("freesound")
-                    .media_type(MediaType::Audio)
-                    .title(sound.name)
-                    .download_url(sound.download.unwrap_or_else(|| preview_url.clone()))
-                    .preview_url(preview_url)
-                    .source_url(format!("https://freesound.org/s/{}/", sound.id))
-                    .author(sound.username)
-                    .author_url(author_url)
-                    .license(license)
-                    .tags(sound.tags)
-                    .file_size(sound.filesize.unwrap_or(0))
-                    .build()
-            })
+            .map(Self::sound_asset)
             .collect();
 
         Ok(SearchResult {
@@ -151,8 +152,35 @@ impl Provider for FreesoundProvider {
             providers_searched: vec!["freesound".to_string()],
             provider_errors: vec![],
             duration_ms: 0,
+            cache_hits: Vec::new(),
+            cache_misses: Vec::new(),
+            filters: Vec::new(),
         })
     }
+
+    fn supports_get_asset(&self) -> bool {
+        true
+    }
+
+    async fn get_asset(&self, id: &str) -> Result<MediaAsset> {
+        let Some(ref api_key) = self.api_key else {
+            return Err(crate::error::DxError::MissingApiKey {
+                provider: "freesound".to_string(),
+                env_var: "FREESOUND_API_KEY".to_string(),
+            });
+        };
+
+        let url = format!("{}/sounds/{}/", self.base_url(), id);
+        let params = [("fields", Self::FIELDS), ("token", api_key.as_str())];
+
+        let response = self.client.get_with_query(&url, &params, &[]).await?;
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Err(crate::error::DxError::asset_not_found(self.name(), id));
+        }
+
+        let sound: FreesoundSound = response.json_or_error().await?;
+        Ok(Self::sound_asset(sound))
+    }
 }
 
 impl ProviderInfo for FreesoundProvider {
@@ -218,7 +246,7 @@ mod tests {
     #[test]
     fn test_provider_metadata() {
         let config = Config::default_for_testing();
-        let provider = FreesoundProvider::new(&config);
+        let provider = FreesoundProvider::new(&config).unwrap();
 
         assert_eq!(provider.name(), "freesound");
         assert_eq!(provider.display_name(), "Freesound");
@@ -240,7 +268,7 @@ mod tests {
     #[test]
     fn test_supported_media_types() {
         let config = Config::default_for_testing();
-        let provider = FreesoundProvider::new(&config);
+        let provider = FreesoundProvider::new(&config).unwrap();
 
         let types = provider.supported_media_types();
         assert!(types.contains(&MediaType::Audio));
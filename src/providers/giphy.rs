@@ -6,7 +6,6 @@
 
 use async_trait::async_trait;
 use serde::Deserialize;
-use std::time::Duration;
 
 use crate::config::Config;
 use crate::error::Result;
@@ -24,23 +23,53 @@ pub struct GiphyProvider {
 
 impl GiphyProvider {
     /// Create a new Giphy provider.
-    #[must_use]
-    pub fn new(config: &Config) -> Self {
-        let client = HttpClient::with_config(
-            Self::RATE_LIMIT,
-            config.retry_attempts,
-            Duration::from_secs(config.timeout_secs),
-        )
-        .unwrap_or_default();
-
-        Self {
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP client cannot be created.
+    pub fn new(config: &Config) -> Result<Self> {
+        let client = HttpClient::from_config(Self::RATE_LIMIT, config)?;
+
+        Ok(Self {
             api_key: config.giphy_api_key.clone(),
             client,
-        }
+        })
     }
 
     /// Rate limit: 42 requests per hour for free tier, 1000 for production
     const RATE_LIMIT: RateLimitConfig = RateLimitConfig::new(42, 3600);
+
+    /// Convert a GIF record into a media asset.
+    fn gif_asset(gif: GiphyGif) -> MediaAsset {
+        // Prefer original size, fall back to downsized
+        let original = &gif.images.original;
+        let download_url = original.url.clone().unwrap_or_default();
+        let preview_url = gif.images.fixed_height.url.clone().unwrap_or_default();
+
+        let width = original
+            .width
+            .as_ref()
+            .and_then(|w| w.parse::<u32>().ok())
+            .unwrap_or(0);
+        let height = original
+            .height
+            .as_ref()
+            .and_then(|h| h.parse::<u32>().ok())
+            .unwrap_or(0);
+
+        MediaAsset::builder()
+            .id(gif.id)
+            .provider("giphy")
+            .media_type(MediaType::Gif)
+            .title(gif.title.unwrap_or_else(|| "Giphy GIF".to_string()))
+            .download_url(download_url)
+            .preview_url(preview_url)
+            .source_url(gif.url)
+            .author(gif.username.unwrap_or_else(|| "Unknown".to_string()))
+            .license(License::Other("Giphy".to_string()))
+            .dimensions(width, height)
+            .build()
+    }
 }
 
 #[async_trait]
@@ -101,36 +130,7 @@ impl Provider for GiphyProvider {
         let assets: Vec<MediaAsset> = api_response
             .data
             .into_iter()
-            .map(|gif| {
-                // Prefer original size, fall back to downsized
-                let original = &gif.images.original;
-                let download_url = original.url.clone().unwrap_or_default();
-                let preview_url = gif.images.fixed_height.url.clone().unwrap_or_default();
-
-                let width = original
-                    .width
-                    .as_ref()
-                    .and_then(|w| w.parse::<u32>().ok())
-                    .unwrap_or(0);
-                let height = original
-                    .height
-                    .as_ref()
-                    .and_then(|h| h.parse::<u32>().ok())
-                    .unwrap_or(0);
-
-                MediaAsset::builder()
-                    .id(gif.id)
-                    .provider("giphy")
-                    .media_type(MediaType::Gif)
-                    .title(gif.title.unwrap_or_else(|| "Giphy GIF".to_string()))
-                    .download_url(download_url)
-                    .preview_url(preview_url)
-                    .source_url(gif.url)
-                    .author(gif.username.unwrap_or_else(|| "Unknown".to_string()))
-                    .license(License::Other("Giphy".to_string()))
-                    .dimensions(width, height)
-                    .build()
-            })
+            .map(Self::gif_asset)
             .collect();
 
         Ok(SearchResult {
@@ -141,8 +141,35 @@ impl Provider for GiphyProvider {
             providers_searched: vec!["giphy".to_string()],
             provider_errors: vec![],
             duration_ms: 0,
+            cache_hits: Vec::new(),
+            cache_misses: Vec::new(),
+            filters: Vec::new(),
         })
     }
+
+    fn supports_get_asset(&self) -> bool {
+        true
+    }
+
+    async fn get_asset(&self, id: &str) -> Result<MediaAsset> {
+        let Some(ref api_key) = self.api_key else {
+            return Err(crate::error::DxError::MissingApiKey {
+                provider: "giphy".to_string(),
+                env_var: "GIPHY_API_KEY".to_string(),
+            });
+        };
+
+        let url = format!("{}/gifs/{}", self.base_url(), id);
+        let params = [("api_key", api_key.as_str())];
+
+        let response = self.client.get_with_query(&url, &params, &[]).await?;
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Err(crate::error::DxError::asset_not_found(self.name(), id));
+        }
+
+        let api_response: GiphyGifResponse = response.json_or_error().await?;
+        Ok(Self::gif_asset(api_response.data))
+    }
 }
 
 impl ProviderInfo for GiphyProvider {
@@ -163,6 +190,11 @@ impl ProviderInfo for GiphyProvider {
 // API RESPONSE TYPES
 // ═══════════════════════════════════════════════════════════════════════════════
 
+#[derive(Debug, Deserialize)]
+struct GiphyGifResponse {
+    data: GiphyGif,
+}
+
 #[derive(Debug, Deserialize)]
 #[allow(dead_code)]
 struct GiphySearchResponse {
@@ -211,7 +243,7 @@ mod tests {
     #[test]
     fn test_provider_metadata() {
         let config = Config::default_for_testing();
-        let provider = GiphyProvider::new(&config);
+        let provider = GiphyProvider::new(&config).unwrap();
 
         assert_eq!(provider.name(), "giphy");
         assert_eq!(provider.display_name(), "Giphy");
@@ -221,7 +253,7 @@ mod tests {
     #[test]
     fn test_supported_media_types() {
         let config = Config::default_for_testing();
-        let provider = GiphyProvider::new(&config);
+        let provider = GiphyProvider::new(&config).unwrap();
 
         let types = provider.supported_media_types();
         assert!(types.contains(&MediaType::Gif));
@@ -0,0 +1,476 @@
+//! Provider health: rolling latency and error rates, circuit breakers and
+//! adaptive timeouts.
+//!
+//! After [`FAILURE_THRESHOLD`] consecutive failures a provider's circuit opens
+//! and searches across providers skip it. Once the cooldown passes the circuit
+//! is half-open: the next search is a probe that closes it again on success,
+//! or reopens it with twice the cooldown on failure.
+//!
+//! With caching enabled, health is saved to `{cache_dir}/health.json` so it
+//! carries over between runs.
+
+use std::collections::{BTreeMap, VecDeque};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use crate::config::Config;
+use crate::error::{DxError, Result};
+use crate::types::SearchResult;
+
+/// Consecutive failures that open a provider's circuit.
+pub const FAILURE_THRESHOLD: u32 = 5;
+
+/// Requests kept per provider for latency and error rates.
+const WINDOW: usize = 50;
+
+/// Cooldown after the circuit first opens; doubled on each failed probe.
+const BASE_COOLDOWN_SECS: i64 = 60;
+
+/// Longest cooldown between probes.
+const MAX_COOLDOWN_SECS: i64 = 30 * 60;
+
+/// Successful requests needed before the timeout adapts to observed latency.
+const MIN_SAMPLES: usize = 5;
+
+/// Shortest adaptive timeout.
+const MIN_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Saves so far, naming each save's temporary file.
+static SAVES: AtomicUsize = AtomicUsize::new(0);
+
+/// State of a provider's circuit breaker.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "state", rename_all = "snake_case")]
+pub enum CircuitState {
+    /// Requests flow normally.
+    #[default]
+    Closed,
+    /// Requests are skipped until `retry_at`.
+    Open {
+        /// When the next probe is allowed.
+        retry_at: DateTime<Utc>,
+    },
+    /// The next request is a probe deciding whether to close the circuit.
+    HalfOpen,
+}
+
+impl CircuitState {
+    /// Short name for display.
+    #[must_use]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Closed => "closed",
+            Self::Open { .. } => "open",
+            Self::HalfOpen => "half-open",
+        }
+    }
+}
+
+/// Snapshot of a provider's health.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProviderHealth {
+    /// Provider name.
+    pub name: String,
+    /// Circuit breaker state.
+    pub circuit: CircuitState,
+    /// Requests in the rolling window.
+    pub requests: usize,
+    /// Failed requests in the rolling window.
+    pub failures: usize,
+    /// Failures since the last success.
+    pub consecutive_failures: u32,
+    /// Median latency of successful requests.
+    pub p50: Option<Duration>,
+    /// 95th percentile latency of successful requests.
+    pub p95: Option<Duration>,
+    /// Most recent error, if the last request failed.
+    pub last_error: Option<String>,
+}
+
+impl ProviderHealth {
+    /// Fraction of requests in the window that failed (0 with no requests).
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)] // at most WINDOW requests
+    pub fn error_rate(&self) -> f64 {
+        if self.requests == 0 {
+            0.0
+        } else {
+            self.failures as f64 / self.requests as f64
+        }
+    }
+
+    /// Timeout for this provider given the default for the search mode.
+    ///
+    /// Once enough requests succeeded this is 1.5 × p95, kept between one
+    /// second and twice `base`.
+    #[must_use]
+    pub fn timeout(&self, base: Duration) -> Duration {
+        match self.p95 {
+            Some(p95) if self.requests - self.failures >= MIN_SAMPLES => {
+                (p95 * 3 / 2).clamp(MIN_TIMEOUT, base * 2)
+            }
+            _ => base,
+        }
+    }
+}
+
+/// A completed request.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Sample {
+    at: DateTime<Utc>,
+    latency_ms: u64,
+    ok: bool,
+}
+
+/// Recorded health of one provider.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct ProviderState {
+    samples: VecDeque<Sample>,
+    consecutive_failures: u32,
+    circuit: CircuitState,
+    cooldown_secs: i64,
+    last_error: Option<String>,
+}
+
+/// Health of every provider, shared by clones.
+#[derive(Debug, Clone, Default)]
+pub struct HealthTracker {
+    states: Arc<Mutex<BTreeMap<String, ProviderState>>>,
+    path: Option<PathBuf>,
+}
+
+impl HealthTracker {
+    /// Create an empty tracker kept in memory only.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a tracker saved to `path`, loading any health recorded there.
+    #[must_use]
+    pub fn load(path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        let states = std::fs::read(&path)
+            .ok()
+            .and_then(|bytes| match serde_json::from_slice(&bytes) {
+                Ok(states) => Some(states),
+                Err(e) => {
+                    debug!("Ignoring unreadable {}: {}", path.display(), e);
+                    None
+                }
+            })
+            .unwrap_or_default();
+
+        Self {
+            states: Arc::new(Mutex::new(states)),
+            path: Some(path),
+        }
+    }
+
+    /// Create the tracker described by the configuration: saved next to the
+    /// search cache when caching is enabled, in memory otherwise.
+    #[must_use]
+    pub fn from_config(config: &Config) -> Self {
+        if config.cache_enabled {
+            Self::load(config.cache_dir.join("health.json"))
+        } else {
+            Self::new()
+        }
+    }
+
+    /// Whether a search may be sent to a provider now.
+    ///
+    /// Moves an open circuit whose cooldown has passed to half-open.
+    #[must_use]
+    pub fn allows(&self, name: &str) -> bool {
+        self.allows_at(name, Utc::now())
+    }
+
+    fn allows_at(&self, name: &str, now: DateTime<Utc>) -> bool {
+        let mut states = self.lock();
+        let Some(state) = states.get_mut(name) else {
+            return true;
+        };
+        match state.circuit {
+            CircuitState::Open { retry_at } if now < retry_at => false,
+            CircuitState::Open { .. } => {
+                debug!("Probing {} after its circuit cooldown", name);
+                state.circuit = CircuitState::HalfOpen;
+                true
+            }
+            CircuitState::Closed | CircuitState::HalfOpen => true,
+        }
+    }
+
+    /// Record a successful request.
+    pub fn record_success(&self, name: &str, latency: Duration) {
+        self.record_at(name, latency, None, Utc::now());
+    }
+
+    /// Record a failed request.
+    pub fn record_failure(&self, name: &str, latency: Duration, error: &str) {
+        self.record_at(name, latency, Some(error), Utc::now());
+    }
+
+    /// Record the outcome of a provider search.
+    ///
+    /// Cache hits say nothing about the provider and are ignored, as are
+    /// errors that aren't the provider's fault, such as a missing API key.
+    pub fn record_result(&self, name: &str, latency: Duration, result: &Result<SearchResult>) {
+        match result {
+            Ok(result) if result.cache_hits.is_empty() => self.record_success(name, latency),
+            Ok(_) => {}
+            Err(e) if is_provider_failure(e) => self.record_failure(name, latency, &e.to_string()),
+            Err(_) => {}
+        }
+    }
+
+    fn record_at(&self, name: &str, latency: Duration, error: Option<&str>, now: DateTime<Utc>) {
+        let mut states = self.lock();
+        let state = states.entry(name.to_string()).or_default();
+
+        if state.samples.len() == WINDOW {
+            state.samples.pop_front();
+        }
+        state.samples.push_back(Sample {
+            at: now,
+            latency_ms: u64::try_from(latency.as_millis()).unwrap_or(u64::MAX),
+            ok: error.is_none(),
+        });
+
+        let Some(error) = error else {
+            state.consecutive_failures = 0;
+            state.circuit = CircuitState::Closed;
+            state.cooldown_secs = 0;
+            state.last_error = None;
+            return;
+        };
+
+        state.consecutive_failures += 1;
+        state.last_error = Some(error.to_string());
+        let cooldown = match state.circuit {
+            CircuitState::HalfOpen => {
+                (state.cooldown_secs * 2).clamp(BASE_COOLDOWN_SECS, MAX_COOLDOWN_SECS)
+            }
+            CircuitState::Closed if state.consecutive_failures >= FAILURE_THRESHOLD => {
+                BASE_COOLDOWN_SECS
+            }
+            _ => return,
+        };
+        debug!("Opening circuit for {} for {}s: {}", name, cooldown, error);
+        state.cooldown_secs = cooldown;
+        state.circuit = CircuitState::Open {
+            retry_at: now + chrono::Duration::seconds(cooldown),
+        };
+    }
+
+    /// Current health of a provider (empty if nothing was recorded).
+    #[must_use]
+    pub fn get(&self, name: &str) -> ProviderHealth {
+        let states = self.lock();
+        let Some(state) = states.get(name) else {
+            return ProviderHealth {
+                name: name.to_string(),
+                circuit: CircuitState::Closed,
+                requests: 0,
+                failures: 0,
+                consecutive_failures: 0,
+                p50: None,
+                p95: None,
+                last_error: None,
+            };
+        };
+
+        let mut latencies: Vec<u64> = state
+            .samples
+            .iter()
+            .filter(|s| s.ok)
+            .map(|s| s.latency_ms)
+            .collect();
+        latencies.sort_unstable();
+
+        ProviderHealth {
+            name: name.to_string(),
+            circuit: state.circuit,
+            requests: state.samples.len(),
+            failures: state.samples.iter().filter(|s| !s.ok).count(),
+            consecutive_failures: state.consecutive_failures,
+            p50: percentile(&latencies, 50),
+            p95: percentile(&latencies, 95),
+            last_error: state.last_error.clone(),
+        }
+    }
+
+    /// Timeout for a provider given the default for the search mode.
+    ///
+    /// See [`ProviderHealth::timeout`].
+    #[must_use]
+    pub fn timeout(&self, name: &str, base: Duration) -> Duration {
+        self.get(name).timeout(base)
+    }
+
+    /// Save the recorded health, if the tracker has a file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file can't be written.
+    pub async fn save(&self) -> Result<()> {
+        let Some(ref path) = self.path else {
+            return Ok(());
+        };
+        let json = serde_json::to_vec_pretty(&*self.lock())?;
+
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent)
+                .await
+                .map_err(|e| DxError::FileIo {
+                    path: parent.to_path_buf(),
+                    message: format!("Failed to create cache directory: {}", e),
+                    source: Some(e),
+                })?;
+        }
+        // Write then rename, so concurrent saves never leave a torn file
+        let tmp = path.with_extension(format!(
+            "json.{}-{}.tmp",
+            std::process::id(),
+            SAVES.fetch_add(1, Ordering::Relaxed)
+        ));
+        let write = async {
+            tokio::fs::write(&tmp, json).await?;
+            tokio::fs::rename(&tmp, path).await
+        };
+        write.await.map_err(|e| DxError::FileIo {
+            path: path.clone(),
+            message: format!("Failed to save provider health: {}", e),
+            source: Some(e),
+        })
+    }
+
+    fn lock(&self) -> MutexGuard<'_, BTreeMap<String, ProviderState>> {
+        self.states.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Whether an error says something about the provider's health.
+fn is_provider_failure(error: &DxError) -> bool {
+    match error {
+        DxError::Http { .. }
+        | DxError::RateLimited { .. }
+        | DxError::JsonParse { .. }
+        | DxError::XmlParse { .. } => true,
+        DxError::ProviderApi { status_code, .. } => *status_code != 404,
+        _ => false,
+    }
+}
+
+/// The `p`th percentile of sorted millisecond values.
+fn percentile(sorted: &[u64], p: usize) -> Option<Duration> {
+    let rank = (sorted.len() * p).div_ceil(100).max(1);
+    sorted.get(rank - 1).map(|&ms| Duration::from_millis(ms))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_circuit_breaker() {
+        let health = HealthTracker::new();
+        let now = Utc::now();
+        let fail = |at| health.record_at("nasa", Duration::from_secs(5), Some("timed out"), at);
+
+        for _ in 0..FAILURE_THRESHOLD - 1 {
+            fail(now);
+        }
+        assert!(health.allows_at("nasa", now));
+        fail(now);
+        assert!(matches!(
+            health.get("nasa").circuit,
+            CircuitState::Open { .. }
+        ));
+        assert!(!health.allows_at("nasa", now));
+        assert!(health.allows_at("met", now));
+
+        // After the cooldown one probe goes through; its failure doubles the cooldown
+        let later = now + chrono::Duration::seconds(61);
+        assert!(health.allows_at("nasa", later));
+        assert_eq!(health.get("nasa").circuit, CircuitState::HalfOpen);
+        fail(later);
+        assert_eq!(
+            health.get("nasa").circuit,
+            CircuitState::Open {
+                retry_at: later + chrono::Duration::seconds(120)
+            }
+        );
+
+        // A successful probe closes the circuit
+        let probe = later + chrono::Duration::seconds(121);
+        assert!(health.allows_at("nasa", probe));
+        health.record_at("nasa", Duration::from_millis(300), None, probe);
+        let nasa = health.get("nasa");
+        assert_eq!(nasa.circuit, CircuitState::Closed);
+        assert_eq!(nasa.consecutive_failures, 0);
+        assert_eq!((nasa.requests, nasa.failures), (7, 6));
+        assert!(nasa.last_error.is_none());
+    }
+
+    #[test]
+    fn test_adaptive_timeout() {
+        let health = HealthTracker::new();
+        let base = Duration::from_secs(5);
+        for ms in [200, 250, 300] {
+            health.record_success("met", Duration::from_millis(ms));
+        }
+        // Too few samples to adapt
+        assert_eq!(health.timeout("met", base), base);
+
+        for ms in [220, 240, 260, 280, 2000] {
+            health.record_success("met", Duration::from_millis(ms));
+        }
+        let met = health.get("met");
+        assert_eq!(met.p50, Some(Duration::from_millis(250)));
+        assert_eq!(met.p95, Some(Duration::from_secs(2)));
+        assert_eq!(met.timeout(base), Duration::from_secs(3));
+
+        // Fast providers still get a second; slow ones at most twice the default
+        assert_eq!(
+            ProviderHealth {
+                p95: Some(Duration::from_millis(50)),
+                ..met.clone()
+            }
+            .timeout(base),
+            MIN_TIMEOUT
+        );
+        assert_eq!(
+            ProviderHealth {
+                p95: Some(Duration::from_secs(30)),
+                ..met
+            }
+            .timeout(base),
+            base * 2
+        );
+    }
+
+    #[tokio::test]
+    async fn test_health_is_saved() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("health.json");
+        let health = HealthTracker::load(&path);
+        health.record_success("met", Duration::from_millis(120));
+        health.record_failure("nasa", Duration::from_secs(5), "timed out");
+        health.save().await.unwrap();
+
+        let reloaded = HealthTracker::load(&path);
+        assert_eq!(reloaded.get("met"), health.get("met"));
+        assert_eq!(
+            reloaded.get("nasa").last_error.as_deref(),
+            Some("timed out")
+        );
+    }
+}
@@ -6,7 +6,6 @@
 
 use async_trait::async_trait;
 use serde::Deserialize;
-use std::time::Duration;
 
 use crate::config::Config;
 use crate::error::Result;
@@ -23,16 +22,14 @@ pub struct LibraryOfCongressProvider {
 
 impl LibraryOfCongressProvider {
     /// Create a new Library of Congress provider.
-    #[must_use]
-    pub fn new(config: &Config) -> Self {
-        let client = HttpClient::with_config(
-            Self::RATE_LIMIT,
-            config.retry_attempts,
-            Duration::from_secs(config.timeout_secs),
-        )
-        .unwrap_or_default();
-
-        Self { client }
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP client cannot be created.
+    pub fn new(config: &Config) -> Result<Self> {
+        let client = HttpClient::from_config(Self::RATE_LIMIT, config)?;
+
+        Ok(Self { client })
     }
 
     /// Rate limit: Unlimited but be respectful
@@ -133,6 +130,9 @@ impl Provider for LibraryOfCongressProvider {
             providers_searched: vec!["loc".to_string()],
             provider_errors: vec![],
             duration_ms: 0,
+            cache_hits: Vec::new(),
+            cache_misses: Vec::new(),
+            filters: Vec::new(),
         })
     }
 }
@@ -188,7 +188,7 @@ mod tests {
     #[test]
     fn test_provider_metadata() {
         let config = Config::default();
-        let provider = LibraryOfCongressProvider::new(&config);
+        let provider = LibraryOfCongressProvider::new(&config).unwrap();
 
         assert_eq!(provider.name(), "loc");
         assert_eq!(provider.display_name(), "Library of Congress");
@@ -199,7 +199,7 @@ mod tests {
     #[test]
     fn test_supported_media_types() {
         let config = Config::default();
-        let provider = LibraryOfCongressProvider::new(&config);
+        let provider = LibraryOfCongressProvider::new(&config).unwrap();
 
         let types = provider.supported_media_types();
         assert!(types.contains(&MediaType::Image));
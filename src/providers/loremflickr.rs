@@ -10,7 +10,6 @@
 //! URL: https://loremflickr.com
 
 use async_trait::async_trait;
-use std::time::Duration;
 
 use crate::config::Config;
 use crate::error::Result;
@@ -27,16 +26,14 @@ pub struct LoremFlickrProvider {
 
 impl LoremFlickrProvider {
     /// Create a new LoremFlickr provider.
-    #[must_use]
-    pub fn new(config: &Config) -> Self {
-        let client = HttpClient::with_config(
-            Self::RATE_LIMIT,
-            config.retry_attempts,
-            Duration::from_secs(config.timeout_secs),
-        )
-        .unwrap_or_default();
-
-        Self { client }
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP client cannot be created.
+    pub fn new(config: &Config) -> Result<Self> {
+        let client = HttpClient::from_config(Self::RATE_LIMIT, config)?;
+
+        Ok(Self { client })
     }
 
     /// Rate limit: generous
@@ -126,6 +123,9 @@ impl Provider for LoremFlickrProvider {
             providers_searched: vec![self.name().to_string()],
             provider_errors: vec![],
             duration_ms: 0,
+            cache_hits: Vec::new(),
+            cache_misses: Vec::new(),
+            filters: Vec::new(),
         })
     }
 }
@@ -137,7 +137,7 @@ mod tests {
     #[test]
     fn test_provider_info() {
         let config = Config::default();
-        let provider = LoremFlickrProvider::new(&config);
+        let provider = LoremFlickrProvider::new(&config).unwrap();
         assert_eq!(provider.name(), "loremflickr");
         assert_eq!(provider.display_name(), "LoremFlickr");
         assert!(provider.is_available());
@@ -6,10 +6,9 @@
 
 use async_trait::async_trait;
 use serde::Deserialize;
-use std::time::Duration;
 
 use crate::config::Config;
-use crate::error::Result;
+use crate::error::{DxError, Result};
 use crate::http::{HttpClient, ResponseExt};
 use crate::providers::traits::{Provider, ProviderInfo};
 use crate::types::{License, MediaAsset, MediaType, RateLimitConfig, SearchQuery, SearchResult};
@@ -23,16 +22,14 @@ pub struct MetMuseumProvider {
 
 impl MetMuseumProvider {
     /// Create a new Met Museum provider.
-    #[must_use]
-    pub fn new(config: &Config) -> Self {
-        let client = HttpClient::with_config(
-            Self::RATE_LIMIT,
-            config.retry_attempts,
-            Duration::from_secs(config.timeout_secs),
-        )
-        .unwrap_or_default();
-
-        Self { client }
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP client cannot be created.
+    pub fn new(config: &Config) -> Result<Self> {
+        let client = HttpClient::from_config(Self::RATE_LIMIT, config)?;
+
+        Ok(Self { client })
     }
 
     /// Rate limit: Unlimited
@@ -50,13 +47,47 @@ impl MetMuseumProvider {
 
         let obj: MetObject = response.json_or_error().await?;
 
-        // Only return objects that have images and are public domain
-        if obj.is_public_domain && obj.primary_image.is_some() {
+        // Only return objects that have images and are public domain; the API
+        // sends "" rather than null for a missing image
+        if obj.is_public_domain && obj.primary_image.as_deref().is_some_and(|u| !u.is_empty()) {
             Ok(Some(obj))
         } else {
             Ok(None)
         }
     }
+
+    /// Convert an object record into a media asset
+    fn object_asset(obj: MetObject) -> MediaAsset {
+        let tags: Vec<String> = obj
+            .tags
+            .unwrap_or_default()
+            .into_iter()
+            .map(|t| t.term)
+            .collect();
+
+        MediaAsset::builder()
+            .id(obj.object_id.to_string())
+            .provider("met")
+            .media_type(MediaType::Image)
+            .title(
+                obj.title
+                    .unwrap_or_else(|| "Met Museum Artwork".to_string()),
+            )
+            .download_url(obj.primary_image.clone().unwrap_or_default())
+            .preview_url(
+                obj.primary_image_small
+                    .filter(|url| !url.is_empty())
+                    .unwrap_or_else(|| obj.primary_image.unwrap_or_default()),
+            )
+            .source_url(obj.object_url)
+            .author(
+                obj.artist_display_name
+                    .unwrap_or_else(|| "Unknown Artist".to_string()),
+            )
+            .license(License::Cc0)
+            .tags(tags)
+            .build()
+    }
 }
 
 #[async_trait]
@@ -119,43 +150,15 @@ impl Provider for MetMuseumProvider {
         let object_ids = search_response
             .object_ids
             .as_ref()
-            .map(|ids| ids[start..end].to_vec())
+            .and_then(|ids| ids.get(start..end))
+            .map(<[u64]>::to_vec)
             .unwrap_or_default();
 
         // Fetch details for each object (limit concurrent requests)
         let mut assets = Vec::new();
         for object_id in object_ids.into_iter().take(query.count) {
             if let Ok(Some(obj)) = self.fetch_object(object_id).await {
-                let tags: Vec<String> = obj
-                    .tags
-                    .unwrap_or_default()
-                    .into_iter()
-                    .map(|t| t.term)
-                    .collect();
-
-                let asset = MediaAsset::builder()
-                    .id(obj.object_id.to_string())
-                    .provider("met")
-                    .media_type(MediaType::Image)
-                    .title(
-                        obj.title
-                            .unwrap_or_else(|| "Met Museum Artwork".to_string()),
-                    )
-                    .download_url(obj.primary_image.clone().unwrap_or_default())
-                    .preview_url(
-                        obj.primary_image_small
-                            .unwrap_or_else(|| obj.primary_image.unwrap_or_default()),
-                    )
-                    .source_url(obj.object_url)
-                    .author(
-                        obj.artist_display_name
-                            .unwrap_or_else(|| "Unknown Artist".to_string()),
-                    )
-                    .license(License::Cc0)
-                    .tags(tags)
-                    .build();
-
-                assets.push(asset);
+                assets.push(Self::object_asset(obj));
             }
         }
 
@@ -167,8 +170,26 @@ impl Provider for MetMuseumProvider {
             providers_searched: vec!["met".to_string()],
             provider_errors: vec![],
             duration_ms: 0,
+            cache_hits: Vec::new(),
+            cache_misses: Vec::new(),
+            filters: Vec::new(),
         })
     }
+
+    fn supports_get_asset(&self) -> bool {
+        true
+    }
+
+    async fn get_asset(&self, id: &str) -> Result<MediaAsset> {
+        let object_id: u64 = id
+            .parse()
+            .map_err(|_| DxError::asset_not_found(self.name(), id))?;
+
+        self.fetch_object(object_id)
+            .await?
+            .map(Self::object_asset)
+            .ok_or_else(|| DxError::asset_not_found(self.name(), id))
+    }
 }
 
 impl ProviderInfo for MetMuseumProvider {
@@ -235,7 +256,7 @@ mod tests {
     #[test]
     fn test_provider_metadata() {
         let config = Config::default_for_testing();
-        let provider = MetMuseumProvider::new(&config);
+        let provider = MetMuseumProvider::new(&config).unwrap();
 
         assert_eq!(provider.name(), "met");
         assert_eq!(provider.display_name(), "Met Museum");
@@ -246,7 +267,7 @@ mod tests {
     #[test]
     fn test_supported_media_types() {
         let config = Config::default_for_testing();
-        let provider = MetMuseumProvider::new(&config);
+        let provider = MetMuseumProvider::new(&config).unwrap();
 
         let types = provider.supported_media_types();
         assert!(types.contains(&MediaType::Image));
@@ -50,8 +50,15 @@ mod pixabay; // 4.2M+ images, videos, music (free API key)
 mod smithsonian;
 mod unsplash; // 5M+ high-quality photos (free API key) // 4.5M+ CC0 images (free API key)
 
+mod health;
 mod registry;
 
+/// Providers serving configured RSS and Atom feeds.
+pub mod feed;
+
+/// Declarative REST providers defined in TOML or JSON.
+pub mod rest;
+
 /// Provider traits module.
 pub mod traits;
 
@@ -100,5 +107,16 @@ pub use unsplash::UnsplashProvider;
 // ═══════════════════════════════════════════════════════════════════════════════
 // REGISTRY & TRAITS
 // ═══════════════════════════════════════════════════════════════════════════════
-pub use registry::ProviderRegistry;
+pub use feed::FeedProvider;
+pub use health::{CircuitState, FAILURE_THRESHOLD, HealthTracker, ProviderHealth};
+pub use registry::{PROBE_QUERY, ProbeResult, ProviderRegistry, ProviderStats};
+pub use rest::{RestDefinition, RestProvider};
 pub use traits::{Provider, ProviderInfo};
+
+/// Give a runtime-configured name a `'static` lifetime.
+///
+/// The [`Provider`] trait hands out `'static` names. Feed and REST providers
+/// are configured once at startup, so leaking their few strings is bounded.
+pub(crate) fn leak_str(s: String) -> &'static str {
+    Box::leak(s.into_boxed_str())
+}
@@ -6,10 +6,9 @@
 
 use async_trait::async_trait;
 use serde::Deserialize;
-use std::time::Duration;
 
 use crate::config::Config;
-use crate::error::Result;
+use crate::error::{DxError, Result};
 use crate::http::{HttpClient, ResponseExt};
 use crate::providers::traits::{Provider, ProviderInfo};
 use crate::types::{License, MediaAsset, MediaType, RateLimitConfig, SearchQuery, SearchResult};
@@ -23,16 +22,14 @@ pub struct NasaImagesProvider {
 
 impl NasaImagesProvider {
     /// Create a new NASA Images provider.
-    #[must_use]
-    pub fn new(config: &Config) -> Self {
-        let client = HttpClient::with_config(
-            Self::RATE_LIMIT,
-            config.retry_attempts,
-            Duration::from_secs(config.timeout_secs),
-        )
-        .unwrap_or_default();
-
-        Self { client }
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP client cannot be created.
+    pub fn new(config: &Config) -> Result<Self> {
+        let client = HttpClient::from_config(Self::RATE_LIMIT, config)?;
+
+        Ok(Self { client })
     }
 
     /// Rate limit: Unlimited (but be respectful)
@@ -56,6 +53,30 @@ impl NasaImagesProvider {
             _ => MediaType::Image,
         }
     }
+
+    /// Convert a collection item into a media asset
+    fn item_asset(item: NasaItem) -> Option<MediaAsset> {
+        let data = item.data.into_iter().next()?;
+        let link = item.links.and_then(|l| l.into_iter().next());
+
+        let preview_url = link.as_ref().map(|l| l.href.clone());
+
+        // NASA assets are all public domain
+        let asset = MediaAsset::builder()
+            .id(data.nasa_id)
+            .provider("nasa")
+            .media_type(Self::parse_media_type(&data.media_type))
+            .title(data.title)
+            .download_url(preview_url.clone().unwrap_or_default())
+            .preview_url(preview_url.unwrap_or_default())
+            .source_url(item.href)
+            .author(data.center.unwrap_or_else(|| "NASA".to_string()))
+            .license(License::PublicDomain)
+            .tags(data.keywords.unwrap_or_default())
+            .build();
+
+        Some(asset)
+    }
 }
 
 #[async_trait]
@@ -110,28 +131,7 @@ impl Provider for NasaImagesProvider {
             .collection
             .items
             .into_iter()
-            .filter_map(|item| {
-                let data = item.data.into_iter().next()?;
-                let link = item.links.and_then(|l| l.into_iter().next());
-
-                let preview_url = link.as_ref().map(|l| l.href.clone());
-
-                // NASA assets are all public domain
-                let asset = MediaAsset::builder()
-                    .id(data.nasa_id)
-                    .provider("nasa")
-                    .media_type(Self::parse_media_type(&data.media_type))
-                    .title(data.title)
-                    .download_url(preview_url.clone().unwrap_or_default())
-                    .preview_url(preview_url.unwrap_or_default())
-                    .source_url(item.href)
-                    .author(data.center.unwrap_or_else(|| "NASA".to_string()))
-                    .license(License::PublicDomain)
-                    .tags(data.keywords.unwrap_or_default())
-                    .build();
-
-                Some(asset)
-            })
+            .filter_map(Self::item_asset)
             .collect();
 
         Ok(SearchResult {
@@ -142,8 +142,31 @@ impl Provider for NasaImagesProvider {
             providers_searched: vec!["nasa".to_string()],
             provider_errors: vec![],
             duration_ms: 0,
+            cache_hits: Vec::new(),
+            cache_misses: Vec::new(),
+            filters: Vec::new(),
         })
     }
+
+    fn supports_get_asset(&self) -> bool {
+        true
+    }
+
+    async fn get_asset(&self, id: &str) -> Result<MediaAsset> {
+        let url = format!("{}/search", self.base_url());
+        let params = [("nasa_id", id)];
+
+        let response = self.client.get_with_query(&url, &params, &[]).await?;
+
+        let api_response: NasaSearchResponse = response.json_or_error().await?;
+
+        api_response
+            .collection
+            .items
+            .into_iter()
+            .find_map(Self::item_asset)
+            .ok_or_else(|| DxError::asset_not_found(self.name(), id))
+    }
 }
 
 impl ProviderInfo for NasaImagesProvider {
@@ -219,7 +242,7 @@ mod tests {
     #[test]
     fn test_provider_metadata() {
         let config = Config::default_for_testing();
-        let provider = NasaImagesProvider::new(&config);
+        let provider = NasaImagesProvider::new(&config).unwrap();
 
         assert_eq!(provider.name(), "nasa");
         assert_eq!(provider.display_name(), "NASA Images");
@@ -230,7 +253,7 @@ mod tests {
     #[test]
     fn test_supported_media_types() {
         let config = Config::default_for_testing();
-        let provider = NasaImagesProvider::new(&config);
+        let provider = NasaImagesProvider::new(&config).unwrap();
 
         let types = provider.supported_media_types();
         assert!(types.contains(&MediaType::Image));
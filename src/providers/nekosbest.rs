@@ -11,7 +11,6 @@
 
 use async_trait::async_trait;
 use serde::Deserialize;
-use std::time::Duration;
 
 use crate::config::Config;
 use crate::error::Result;
@@ -57,16 +56,14 @@ const GIF_CATEGORIES: &[&str] = &[
 
 impl NekosBestProvider {
     /// Create a new Nekos.best provider.
-    #[must_use]
-    pub fn new(config: &Config) -> Self {
-        let client = HttpClient::with_config(
-            Self::RATE_LIMIT,
-            config.retry_attempts,
-            Duration::from_secs(config.timeout_secs),
-        )
-        .unwrap_or_default();
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP client cannot be created.
+    pub fn new(config: &Config) -> Result<Self> {
+        let client = HttpClient::from_config(Self::RATE_LIMIT, config)?;
 
-        Self { client }
+        Ok(Self { client })
     }
 
     /// Rate limit: generous
@@ -236,6 +233,9 @@ impl Provider for NekosBestProvider {
             providers_searched: vec![self.name().to_string()],
             provider_errors: vec![],
             duration_ms: 0,
+            cache_hits: Vec::new(),
+            cache_misses: Vec::new(),
+            filters: Vec::new(),
         })
     }
 }
@@ -247,7 +247,7 @@ mod tests {
     #[test]
     fn test_provider_info() {
         let config = Config::default();
-        let provider = NekosBestProvider::new(&config);
+        let provider = NekosBestProvider::new(&config).unwrap();
         assert_eq!(provider.name(), "nekosbest");
         assert_eq!(provider.display_name(), "Nekos.best");
         // NOTE: Provider is disabled due to Cloudflare bot detection
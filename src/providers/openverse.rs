@@ -7,10 +7,9 @@
 
 use async_trait::async_trait;
 use serde::Deserialize;
-use std::time::Duration;
 
 use crate::config::Config;
-use crate::error::Result;
+use crate::error::{DxError, Result};
 use crate::http::{HttpClient, ResponseExt};
 use crate::providers::traits::{Provider, ProviderInfo};
 use crate::types::{License, MediaAsset, MediaType, RateLimitConfig, SearchQuery, SearchResult};
@@ -24,16 +23,14 @@ pub struct OpenverseProvider {
 
 impl OpenverseProvider {
     /// Create a new Openverse provider.
-    #[must_use]
-    pub fn new(config: &Config) -> Self {
-        let client = HttpClient::with_config(
-            Self::RATE_LIMIT,
-            config.retry_attempts,
-            Duration::from_secs(config.timeout_secs),
-        )
-        .unwrap_or_default();
-
-        Self { client }
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP client cannot be created.
+    pub fn new(config: &Config) -> Result<Self> {
+        let client = HttpClient::from_config(Self::RATE_LIMIT, config)?;
+
+        Ok(Self { client })
     }
 
     /// Rate limit: 100 requests per day (anonymous), more with API key
@@ -59,24 +56,7 @@ impl OpenverseProvider {
         let assets: Vec<MediaAsset> = api_response
             .results
             .into_iter()
-            .map(|item| {
-                let license = Self::parse_license(&item.license, &item.license_version);
-
-                MediaAsset::builder()
-                    .id(item.id)
-                    .provider("openverse")
-                    .media_type(MediaType::Image)
-                    .title(item.title.unwrap_or_else(|| "Openverse Image".to_string()))
-                    .download_url(item.url)
-                    .preview_url(item.thumbnail.unwrap_or_default())
-                    .source_url(item.foreign_landing_url)
-                    .author(item.creator.unwrap_or_default())
-                    .author_url(item.creator_url.unwrap_or_default())
-                    .license(license)
-                    .dimensions(item.width.unwrap_or(0), item.height.unwrap_or(0))
-                    .tags(item.tags.into_iter().map(|t| t.name).collect())
-                    .build()
-            })
+            .map(Self::image_asset)
             .collect();
 
         Ok(SearchResult {
@@ -87,6 +67,9 @@ impl OpenverseProvider {
             providers_searched: vec!["openverse".to_string()],
             provider_errors: vec![],
             duration_ms: 0,
+            cache_hits: Vec::new(),
+            cache_misses: Vec::new(),
+            filters: Vec::new(),
         })
     }
 
@@ -110,23 +93,7 @@ impl OpenverseProvider {
         let assets: Vec<MediaAsset> = api_response
             .results
             .into_iter()
-            .map(|item| {
-                let license = Self::parse_license(&item.license, &item.license_version);
-
-                MediaAsset::builder()
-                    .id(item.id)
-                    .provider("openverse")
-                    .media_type(MediaType::Audio)
-                    .title(item.title.unwrap_or_else(|| "Openverse Audio".to_string()))
-                    .download_url(item.url)
-                    .preview_url(item.thumbnail.unwrap_or_default())
-                    .source_url(item.foreign_landing_url)
-                    .author(item.creator.unwrap_or_default())
-                    .author_url(item.creator_url.unwrap_or_default())
-                    .license(license)
-                    .tags(item.tags.into_iter().map(|t| t.name).collect())
-                    .build()
-            })
+            .map(Self::audio_asset)
             .collect();
 
         Ok(SearchResult {
@@ -137,9 +104,61 @@ impl OpenverseProvider {
             providers_searched: vec!["openverse".to_string()],
             provider_errors: vec![],
             duration_ms: 0,
+            cache_hits: Vec::new(),
+            cache_misses: Vec::new(),
+            filters: Vec::new(),
         })
     }
 
+    /// Convert an image result into a media asset
+    fn image_asset(item: OpenverseImageResult) -> MediaAsset {
+        let license = Self::parse_license(&item.license, &item.license_version);
+
+        let mut builder = MediaAsset::builder()
+            .id(item.id)
+            .provider("openverse")
+            .media_type(MediaType::Image)
+            .title(item.title.unwrap_or_else(|| "Openverse Image".to_string()))
+            .download_url(item.url)
+            .source_url(item.foreign_landing_url)
+            .author(item.creator.unwrap_or_default())
+            .license(license)
+            .dimensions(item.width.unwrap_or(0), item.height.unwrap_or(0))
+            .tags(item.tags.into_iter().map(|t| t.name).collect());
+
+        if let Some(thumbnail) = item.thumbnail {
+            builder = builder.preview_url(thumbnail);
+        }
+        if let Some(creator_url) = item.creator_url {
+            builder = builder.author_url(creator_url);
+        }
+        builder.build()
+    }
+
+    /// Convert an audio result into a media asset
+    fn audio_asset(item: OpenverseAudioResult) -> MediaAsset {
+        let license = Self::parse_license(&item.license, &item.license_version);
+
+        let mut builder = MediaAsset::builder()
+            .id(item.id)
+            .provider("openverse")
+            .media_type(MediaType::Audio)
+            .title(item.title.unwrap_or_else(|| "Openverse Audio".to_string()))
+            .download_url(item.url)
+            .source_url(item.foreign_landing_url)
+            .author(item.creator.unwrap_or_default())
+            .license(license)
+            .tags(item.tags.into_iter().map(|t| t.name).collect());
+
+        if let Some(thumbnail) = item.thumbnail {
+            builder = builder.preview_url(thumbnail);
+        }
+        if let Some(creator_url) = item.creator_url {
+            builder = builder.author_url(creator_url);
+        }
+        builder.build()
+    }
+
     /// Parse license string into License enum
     fn parse_license(license: &str, _version: &Option<String>) -> License {
         match license.to_lowercase().as_str() {
@@ -189,6 +208,30 @@ impl Provider for OpenverseProvider {
             _ => self.search_images(query).await,
         }
     }
+
+    fn supports_get_asset(&self) -> bool {
+        true
+    }
+
+    async fn get_asset(&self, id: &str) -> Result<MediaAsset> {
+        // Image and audio IDs share one UUID namespace, so try images first
+        let params = [("format", "json")];
+
+        let url = format!("{}/images/{}/", self.base_url(), id);
+        let response = self.client.get_with_query(&url, &params, &[]).await?;
+        if response.status() != reqwest::StatusCode::NOT_FOUND {
+            let item: OpenverseImageResult = response.json_or_error().await?;
+            return Ok(Self::image_asset(item));
+        }
+
+        let url = format!("{}/audio/{}/", self.base_url(), id);
+        let response = self.client.get_with_query(&url, &params, &[]).await?;
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Err(DxError::asset_not_found(self.name(), id));
+        }
+        let item: OpenverseAudioResult = response.json_or_error().await?;
+        Ok(Self::audio_asset(item))
+    }
 }
 
 impl ProviderInfo for OpenverseProvider {
@@ -274,7 +317,7 @@ mod tests {
     #[test]
     fn test_provider_metadata() {
         let config = Config::default_for_testing();
-        let provider = OpenverseProvider::new(&config);
+        let provider = OpenverseProvider::new(&config).unwrap();
 
         assert_eq!(provider.name(), "openverse");
         assert_eq!(provider.display_name(), "Openverse");
@@ -285,7 +328,7 @@ mod tests {
     #[test]
     fn test_supported_media_types() {
         let config = Config::default_for_testing();
-        let provider = OpenverseProvider::new(&config);
+        let provider = OpenverseProvider::new(&config).unwrap();
 
         let types = provider.supported_media_types();
         assert!(types.contains(&MediaType::Image));
@@ -4,7 +4,6 @@
 
 use async_trait::async_trait;
 use serde::Deserialize;
-use std::time::Duration;
 
 use crate::config::Config;
 use crate::error::Result;
@@ -21,19 +20,17 @@ pub struct PexelsProvider {
 
 impl PexelsProvider {
     /// Create a new Pexels provider.
-    #[must_use]
-    pub fn new(config: &Config) -> Self {
-        let client = HttpClient::with_config(
-            Self::RATE_LIMIT,
-            config.retry_attempts,
-            Duration::from_secs(config.timeout_secs),
-        )
-        .unwrap_or_default();
-
-        Self {
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP client cannot be created.
+    pub fn new(config: &Config) -> Result<Self> {
+        let client = HttpClient::from_config(Self::RATE_LIMIT, config)?;
+
+        Ok(Self {
             api_key: config.pexels_api_key.clone(),
             client,
-        }
+        })
     }
 
     /// Rate limit: 200 requests per hour
@@ -134,6 +131,9 @@ impl Provider for PexelsProvider {
             providers_searched: vec!["pexels".to_string()],
             provider_errors: vec![],
             duration_ms: 0,
+            cache_hits: Vec::new(),
+            cache_misses: Vec::new(),
+            filters: Vec::new(),
         })
     }
 }
@@ -205,6 +205,9 @@ impl PexelsProvider {
             providers_searched: vec!["pexels".to_string()],
             provider_errors: vec![],
             duration_ms: 0,
+            cache_hits: Vec::new(),
+            cache_misses: Vec::new(),
+            filters: Vec::new(),
         })
     }
 }
@@ -327,7 +330,7 @@ mod tests {
     #[test]
     fn test_provider_metadata() {
         let config = Config::default_for_testing();
-        let provider = PexelsProvider::new(&config);
+        let provider = PexelsProvider::new(&config).unwrap();
 
         assert_eq!(provider.name(), "pexels");
         assert_eq!(provider.display_name(), "Pexels");
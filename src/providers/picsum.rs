@@ -6,10 +6,9 @@
 
 use async_trait::async_trait;
 use serde::Deserialize;
-use std::time::Duration;
 
 use crate::config::Config;
-use crate::error::Result;
+use crate::error::{DxError, Result};
 use crate::http::{HttpClient, ResponseExt};
 use crate::providers::traits::{Provider, ProviderInfo};
 use crate::types::{License, MediaAsset, MediaType, RateLimitConfig, SearchQuery, SearchResult};
@@ -23,20 +22,44 @@ pub struct LoremPicsumProvider {
 
 impl LoremPicsumProvider {
     /// Create a new Lorem Picsum provider.
-    #[must_use]
-    pub fn new(config: &Config) -> Self {
-        let client = HttpClient::with_config(
-            Self::RATE_LIMIT,
-            config.retry_attempts,
-            Duration::from_secs(config.timeout_secs),
-        )
-        .unwrap_or_default();
-
-        Self { client }
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP client cannot be created.
+    pub fn new(config: &Config) -> Result<Self> {
+        let client = HttpClient::from_config(Self::RATE_LIMIT, config)?;
+
+        Ok(Self { client })
     }
 
     /// Rate limit: Unlimited
     const RATE_LIMIT: RateLimitConfig = RateLimitConfig::new(1000, 60);
+
+    /// Convert an image record into a media asset
+    fn image_asset(&self, img: PicsumImage) -> MediaAsset {
+        // Construct download URL with original dimensions
+        let download_url = format!(
+            "{}/id/{}/{}/{}",
+            self.base_url(),
+            img.id,
+            img.width,
+            img.height
+        );
+        let preview_url = format!("{}/id/{}/400/300", self.base_url(), img.id);
+
+        MediaAsset::builder()
+            .id(img.id.clone())
+            .provider("picsum")
+            .media_type(MediaType::Image)
+            .title(format!("Photo by {}", img.author))
+            .download_url(download_url)
+            .preview_url(preview_url)
+            .source_url(img.url)
+            .author(img.author)
+            .license(License::Other("Unsplash".to_string()))
+            .dimensions(img.width, img.height)
+            .build()
+    }
 }
 
 #[async_trait]
@@ -94,30 +117,7 @@ impl Provider for LoremPicsumProvider {
 
         let assets: Vec<MediaAsset> = filtered_images
             .into_iter()
-            .map(|img| {
-                // Construct download URL with original dimensions
-                let download_url = format!(
-                    "{}/id/{}/{}/{}",
-                    self.base_url(),
-                    img.id,
-                    img.width,
-                    img.height
-                );
-                let preview_url = format!("{}/id/{}/400/300", self.base_url(), img.id);
-
-                MediaAsset::builder()
-                    .id(img.id.clone())
-                    .provider("picsum")
-                    .media_type(MediaType::Image)
-                    .title(format!("Photo by {}", img.author))
-                    .download_url(download_url)
-                    .preview_url(preview_url)
-                    .source_url(img.url)
-                    .author(img.author)
-                    .license(License::Other("Unsplash".to_string()))
-                    .dimensions(img.width, img.height)
-                    .build()
-            })
+            .map(|img| self.image_asset(img))
             .collect();
 
         let total = assets.len();
@@ -130,8 +130,27 @@ impl Provider for LoremPicsumProvider {
             providers_searched: vec!["picsum".to_string()],
             provider_errors: vec![],
             duration_ms: 0,
+            cache_hits: Vec::new(),
+            cache_misses: Vec::new(),
+            filters: Vec::new(),
         })
     }
+
+    fn supports_get_asset(&self) -> bool {
+        true
+    }
+
+    async fn get_asset(&self, id: &str) -> Result<MediaAsset> {
+        let url = format!("{}/id/{}/info", self.base_url(), id);
+
+        let response = self.client.get(&url).await?;
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Err(DxError::asset_not_found(self.name(), id));
+        }
+
+        let img: PicsumImage = response.json_or_error().await?;
+        Ok(self.image_asset(img))
+    }
 }
 
 impl ProviderInfo for LoremPicsumProvider {
@@ -170,7 +189,7 @@ mod tests {
     #[test]
     fn test_provider_metadata() {
         let config = Config::default_for_testing();
-        let provider = LoremPicsumProvider::new(&config);
+        let provider = LoremPicsumProvider::new(&config).unwrap();
 
         assert_eq!(provider.name(), "picsum");
         assert_eq!(provider.display_name(), "Lorem Picsum");
@@ -181,7 +200,7 @@ mod tests {
     #[test]
     fn test_supported_media_types() {
         let config = Config::default_for_testing();
-        let provider = LoremPicsumProvider::new(&config);
+        let provider = LoremPicsumProvider::new(&config).unwrap();
 
         let types = provider.supported_media_types();
         assert!(types.contains(&MediaType::Image));
@@ -4,7 +4,6 @@
 
 use async_trait::async_trait;
 use serde::Deserialize;
-use std::time::Duration;
 
 use crate::config::Config;
 use crate::error::Result;
@@ -21,19 +20,17 @@ pub struct PixabayProvider {
 
 impl PixabayProvider {
     /// Create a new Pixabay provider.
-    #[must_use]
-    pub fn new(config: &Config) -> Self {
-        let client = HttpClient::with_config(
-            Self::RATE_LIMIT,
-            config.retry_attempts,
-            Duration::from_secs(config.timeout_secs),
-        )
-        .unwrap_or_default();
-
-        Self {
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP client cannot be created.
+    pub fn new(config: &Config) -> Result<Self> {
+        let client = HttpClient::from_config(Self::RATE_LIMIT, config)?;
+
+        Ok(Self {
             api_key: config.pixabay_api_key.clone(),
             client,
-        }
+        })
     }
 
     /// Rate limit: 100 requests per minute (generous for free tier).
@@ -156,6 +153,9 @@ impl Provider for PixabayProvider {
             providers_searched: vec!["pixabay".to_string()],
             provider_errors: vec![],
             duration_ms: 0,
+            cache_hits: Vec::new(),
+            cache_misses: Vec::new(),
+            filters: Vec::new(),
         })
     }
 }
@@ -238,6 +238,9 @@ impl PixabayProvider {
             providers_searched: vec!["pixabay".to_string()],
             provider_errors: vec![],
             duration_ms: 0,
+            cache_hits: Vec::new(),
+            cache_misses: Vec::new(),
+            filters: Vec::new(),
         })
     }
 }
@@ -393,7 +396,7 @@ mod tests {
     #[test]
     fn test_provider_metadata() {
         let config = Config::default_for_testing();
-        let provider = PixabayProvider::new(&config);
+        let provider = PixabayProvider::new(&config).unwrap();
 
         assert_eq!(provider.name(), "pixabay");
         assert_eq!(provider.display_name(), "Pixabay");
@@ -404,7 +407,7 @@ mod tests {
     #[test]
     fn test_supported_media_types() {
         let config = Config::default_for_testing();
-        let provider = PixabayProvider::new(&config);
+        let provider = PixabayProvider::new(&config).unwrap();
 
         let types = provider.supported_media_types();
         assert!(types.contains(&MediaType::Image));
@@ -7,10 +7,9 @@
 use async_trait::async_trait;
 use serde::Deserialize;
 use std::collections::HashMap;
-use std::time::Duration;
 
 use crate::config::Config;
-use crate::error::Result;
+use crate::error::{DxError, Result};
 use crate::http::{HttpClient, ResponseExt};
 use crate::providers::traits::{Provider, ProviderInfo};
 use crate::types::{License, MediaAsset, MediaType, RateLimitConfig, SearchQuery, SearchResult};
@@ -24,16 +23,14 @@ pub struct PolyHavenProvider {
 
 impl PolyHavenProvider {
     /// Create a new Poly Haven provider.
-    #[must_use]
-    pub fn new(config: &Config) -> Self {
-        let client = HttpClient::with_config(
-            Self::RATE_LIMIT,
-            config.retry_attempts,
-            Duration::from_secs(config.timeout_secs),
-        )
-        .unwrap_or_default();
-
-        Self { client }
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP client cannot be created.
+    pub fn new(config: &Config) -> Result<Self> {
+        let client = HttpClient::from_config(Self::RATE_LIMIT, config)?;
+
+        Ok(Self { client })
     }
 
     /// Rate limit: Unlimited but be respectful
@@ -48,6 +45,35 @@ impl PolyHavenProvider {
             _ => "all",
         }
     }
+
+    /// Convert an asset listing entry into a media asset
+    fn media_asset(id: &str, asset: PolyHavenAsset) -> MediaAsset {
+        let media_type = match asset.r#type {
+            0 => MediaType::Image, // HDRI
+            1 => MediaType::Image, // Texture (mapped to Image)
+            2 => MediaType::Model3D,
+            _ => MediaType::Image,
+        };
+
+        let preview_url = format!(
+            "https://cdn.polyhaven.com/asset_img/thumbs/{}.png?height=256",
+            id
+        );
+        let download_url = format!("https://polyhaven.com/a/{}", id);
+
+        MediaAsset::builder()
+            .id(id)
+            .provider("polyhaven")
+            .media_type(media_type)
+            .title(asset.name)
+            .download_url(download_url)
+            .preview_url(preview_url)
+            .source_url(format!("https://polyhaven.com/a/{}", id))
+            .author(asset.authors.into_keys().collect::<Vec<_>>().join(", "))
+            .license(License::Cc0)
+            .tags(asset.tags)
+            .build()
+    }
 }
 
 #[async_trait]
@@ -94,7 +120,7 @@ impl Provider for PolyHavenProvider {
         let query_lower = query.query.to_lowercase();
         let start = (query.page - 1) * query.count;
 
-        let filtered_assets: Vec<MediaAsset> = assets_map
+        let mut matching: Vec<(String, PolyHavenAsset)> = assets_map
             .into_iter()
             .filter(|(id, asset)| {
                 if query.query.is_empty() || query.query == "*" {
@@ -111,39 +137,18 @@ impl Provider for PolyHavenProvider {
                         .iter()
                         .any(|c| c.to_lowercase().contains(&query_lower))
             })
+            .collect();
+        // The listing is a JSON object, so sort for stable pages
+        matching.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let total_count = matching.len();
+        let filtered_assets: Vec<MediaAsset> = matching
+            .into_iter()
             .skip(start)
             .take(query.count)
-            .map(|(id, asset)| {
-                let media_type = match asset.r#type {
-                    0 => MediaType::Image, // HDRI
-                    1 => MediaType::Image, // Texture (mapped to Image)
-                    2 => MediaType::Model3D,
-                    _ => MediaType::Image,
-                };
-
-                let preview_url = format!(
-                    "https://cdn.polyhaven.com/asset_img/thumbs/{}.png?height=256",
-                    id
-                );
-                let download_url = format!("https://polyhaven.com/a/{}", id);
-
-                MediaAsset::builder()
-                    .id(id.clone())
-                    .provider("polyhaven")
-                    .media_type(media_type)
-                    .title(asset.name)
-                    .download_url(download_url)
-                    .preview_url(preview_url)
-                    .source_url(format!("https://polyhaven.com/a/{}", id))
-                    .author(asset.authors.into_keys().collect::<Vec<_>>().join(", "))
-                    .license(License::Cc0)
-                    .tags(asset.tags)
-                    .build()
-            })
+            .map(|(id, asset)| Self::media_asset(&id, asset))
             .collect();
 
-        let total_count = filtered_assets.len();
-
         Ok(SearchResult {
             query: query.query.clone(),
             media_type: query.media_type,
@@ -152,8 +157,31 @@ impl Provider for PolyHavenProvider {
             providers_searched: vec!["polyhaven".to_string()],
             provider_errors: vec![],
             duration_ms: 0,
+            cache_hits: Vec::new(),
+            cache_misses: Vec::new(),
+            filters: Vec::new(),
         })
     }
+
+    fn pages_locally(&self) -> bool {
+        true
+    }
+
+    fn supports_get_asset(&self) -> bool {
+        true
+    }
+
+    async fn get_asset(&self, id: &str) -> Result<MediaAsset> {
+        let url = format!("{}/info/{}", self.base_url(), id);
+
+        let response = self.client.get(&url).await?;
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Err(DxError::asset_not_found(self.name(), id));
+        }
+
+        let asset: PolyHavenAsset = response.json_or_error().await?;
+        Ok(Self::media_asset(id, asset))
+    }
 }
 
 impl ProviderInfo for PolyHavenProvider {
@@ -197,7 +225,7 @@ mod tests {
     #[test]
     fn test_provider_metadata() {
         let config = Config::default();
-        let provider = PolyHavenProvider::new(&config);
+        let provider = PolyHavenProvider::new(&config).unwrap();
 
         assert_eq!(provider.name(), "polyhaven");
         assert_eq!(provider.display_name(), "Poly Haven");
@@ -208,7 +236,7 @@ mod tests {
     #[test]
     fn test_supported_media_types() {
         let config = Config::default();
-        let provider = PolyHavenProvider::new(&config);
+        let provider = PolyHavenProvider::new(&config).unwrap();
 
         let types = provider.supported_media_types();
         assert!(types.contains(&MediaType::Model3D));
@@ -3,11 +3,20 @@
 //! Supports both FREE providers (no API keys) and PREMIUM providers (optional API keys).
 //! Premium providers gracefully degrade when API keys are not configured.
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
-use crate::config::Config;
+use tracing::warn;
+
+use crate::cache::SearchCache;
+use crate::config::{Config, ProviderSettings};
 use crate::error::Result;
+use crate::http::registrable_domain;
+use crate::providers::feed::FeedProvider;
+use crate::providers::health::{CircuitState, HealthTracker, ProviderHealth};
+use crate::providers::rest::{RestProvider, load_definitions};
 use crate::providers::traits::Provider;
 use crate::providers::{
     // FREE providers (no API key required)
@@ -47,7 +56,9 @@ use crate::providers::{
     WikimediaCommonsProvider,
     XkcdProvider,
 };
-use crate::types::{MediaType, SearchQuery, SearchResult};
+use crate::types::{
+    CachePolicy, MediaAsset, MediaType, RateLimitConfig, SearchQuery, SearchResult,
+};
 
 /// Registry for managing and querying media providers.
 ///
@@ -76,20 +87,29 @@ use crate::types::{MediaType, SearchQuery, SearchResult};
 /// - DPLA: 40M+ American cultural heritage items (free API key)
 pub struct ProviderRegistry {
     providers: HashMap<String, Arc<dyn Provider>>,
+    cache: Option<SearchCache>,
+    settings: BTreeMap<String, ProviderSettings>,
+    health: HealthTracker,
 }
 
 impl std::fmt::Debug for ProviderRegistry {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("ProviderRegistry")
             .field("providers", &self.providers.keys().collect::<Vec<_>>())
+            .field("cache", &self.cache)
+            .field("settings", &self.settings)
+            .field("health", &self.health)
             .finish()
     }
 }
 
 impl ProviderRegistry {
     /// Create a new registry with all available providers.
-    #[must_use]
-    pub fn new(config: &Config) -> Self {
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an HTTP client cannot be created.
+    pub fn new(config: &Config) -> Result<Self> {
         let mut providers: HashMap<String, Arc<dyn Provider>> = HashMap::new();
 
         // ═══════════════════════════════════════════════════════════════════
@@ -97,27 +117,27 @@ impl ProviderRegistry {
         // ═══════════════════════════════════════════════════════════════════
 
         // Openverse - 700M+ images and audio (no API key required)
-        let openverse = OpenverseProvider::new(config);
+        let openverse = OpenverseProvider::new(config)?;
         providers.insert(openverse.name().to_string(), Arc::new(openverse));
 
         // Wikimedia Commons - 92M+ files (no API key required)
-        let wikimedia = WikimediaCommonsProvider::new(config);
+        let wikimedia = WikimediaCommonsProvider::new(config)?;
         providers.insert(wikimedia.name().to_string(), Arc::new(wikimedia));
 
         // Europeana - 50M+ European cultural heritage items
-        let europeana = EuropeanaProvider::new(config);
+        let europeana = EuropeanaProvider::new(config)?;
         providers.insert(europeana.name().to_string(), Arc::new(europeana));
 
         // DPLA - 40M+ American cultural heritage items (requires API key now)
-        let dpla = DplaProvider::new(config);
+        let dpla = DplaProvider::new(config)?;
         providers.insert(dpla.name().to_string(), Arc::new(dpla));
 
         // Library of Congress - 3M+ public domain images
-        let loc = LibraryOfCongressProvider::new(config);
+        let loc = LibraryOfCongressProvider::new(config)?;
         providers.insert(loc.name().to_string(), Arc::new(loc));
 
         // Internet Archive - 26M+ media items (images, video, audio, docs)
-        let archive = InternetArchiveProvider::new(config);
+        let archive = InternetArchiveProvider::new(config)?;
         providers.insert(archive.name().to_string(), Arc::new(archive));
 
         // ═══════════════════════════════════════════════════════════════════
@@ -125,23 +145,23 @@ impl ProviderRegistry {
         // ═══════════════════════════════════════════════════════════════════
 
         // Rijksmuseum - 700K+ Dutch masterpieces (CC0)
-        let rijksmuseum = RijksmuseumProvider::new(config);
+        let rijksmuseum = RijksmuseumProvider::new(config)?;
         providers.insert(rijksmuseum.name().to_string(), Arc::new(rijksmuseum));
 
         // Met Museum - 500K+ artworks (no API key required)
-        let met = MetMuseumProvider::new(config);
+        let met = MetMuseumProvider::new(config)?;
         providers.insert(met.name().to_string(), Arc::new(met));
 
         // NASA Images - 140K+ space images (no API key required)
-        let nasa = NasaImagesProvider::new(config);
+        let nasa = NasaImagesProvider::new(config)?;
         providers.insert(nasa.name().to_string(), Arc::new(nasa));
 
         // Cleveland Museum - 61K+ artworks (CC0)
-        let cleveland = ClevelandMuseumProvider::new(config);
+        let cleveland = ClevelandMuseumProvider::new(config)?;
         providers.insert(cleveland.name().to_string(), Arc::new(cleveland));
 
         // Art Institute of Chicago - 50K+ artworks (CC0)
-        let artic = ArtInstituteChicagoProvider::new(config);
+        let artic = ArtInstituteChicagoProvider::new(config)?;
         providers.insert(artic.name().to_string(), Arc::new(artic));
 
         // ═══════════════════════════════════════════════════════════════════
@@ -149,11 +169,11 @@ impl ProviderRegistry {
         // ═══════════════════════════════════════════════════════════════════
 
         // Poly Haven - 3.7K+ 3D models, textures, HDRIs (CC0)
-        let polyhaven = PolyHavenProvider::new(config);
+        let polyhaven = PolyHavenProvider::new(config)?;
         providers.insert(polyhaven.name().to_string(), Arc::new(polyhaven));
 
         // Lorem Picsum - Unlimited placeholder images (no API key required)
-        let picsum = LoremPicsumProvider::new(config);
+        let picsum = LoremPicsumProvider::new(config)?;
         providers.insert(picsum.name().to_string(), Arc::new(picsum));
 
         // ═══════════════════════════════════════════════════════════════════
@@ -161,23 +181,23 @@ impl ProviderRegistry {
         // ═══════════════════════════════════════════════════════════════════
 
         // Dog CEO - 20K+ dog images
-        let dogceo = DogCeoProvider::new(config);
+        let dogceo = DogCeoProvider::new(config)?;
         providers.insert(dogceo.name().to_string(), Arc::new(dogceo));
 
         // Cat API - 60K+ cat images
-        let catapi = CatApiProvider::new(config);
+        let catapi = CatApiProvider::new(config)?;
         providers.insert(catapi.name().to_string(), Arc::new(catapi));
 
         // Random Fox - Unlimited fox images
-        let randomfox = RandomFoxProvider::new(config);
+        let randomfox = RandomFoxProvider::new(config)?;
         providers.insert(randomfox.name().to_string(), Arc::new(randomfox));
 
         // DiceBear - Unlimited avatar generation (25+ styles)
-        let dicebear = DiceBearProvider::new(config);
+        let dicebear = DiceBearProvider::new(config)?;
         providers.insert(dicebear.name().to_string(), Arc::new(dicebear));
 
         // RoboHash - Unlimited robot/monster avatars
-        let robohash = RoboHashProvider::new(config);
+        let robohash = RoboHashProvider::new(config)?;
         providers.insert(robohash.name().to_string(), Arc::new(robohash));
 
         // ═══════════════════════════════════════════════════════════════════
@@ -185,15 +205,15 @@ impl ProviderRegistry {
         // ═══════════════════════════════════════════════════════════════════
 
         // V&A Museum - 1.2M+ art and design objects
-        let vanda = VandAMuseumProvider::new(config);
+        let vanda = VandAMuseumProvider::new(config)?;
         providers.insert(vanda.name().to_string(), Arc::new(vanda));
 
         // Walters Art Museum - 25K+ artworks (CC0)
-        let walters = WaltersArtMuseumProvider::new(config);
+        let walters = WaltersArtMuseumProvider::new(config)?;
         providers.insert(walters.name().to_string(), Arc::new(walters));
 
         // Open Library - 30M+ book covers
-        let openlibrary = OpenLibraryProvider::new(config);
+        let openlibrary = OpenLibraryProvider::new(config)?;
         providers.insert(openlibrary.name().to_string(), Arc::new(openlibrary));
 
         // ═══════════════════════════════════════════════════════════════════
@@ -201,11 +221,11 @@ impl ProviderRegistry {
         // ═══════════════════════════════════════════════════════════════════
 
         // Waifu.pics - Unlimited anime images and GIFs
-        let waifupics = WaifuPicsProvider::new(config);
+        let waifupics = WaifuPicsProvider::new(config)?;
         providers.insert(waifupics.name().to_string(), Arc::new(waifupics));
 
         // Nekos.best - High-quality anime images and GIFs
-        let nekosbest = NekosBestProvider::new(config);
+        let nekosbest = NekosBestProvider::new(config)?;
         providers.insert(nekosbest.name().to_string(), Arc::new(nekosbest));
 
         // ═══════════════════════════════════════════════════════════════════
@@ -213,15 +233,15 @@ impl ProviderRegistry {
         // ═══════════════════════════════════════════════════════════════════
 
         // Scryfall - 80K+ Magic: The Gathering cards
-        let scryfall = ScryfallProvider::new(config);
+        let scryfall = ScryfallProvider::new(config)?;
         providers.insert(scryfall.name().to_string(), Arc::new(scryfall));
 
         // xkcd - 2,900+ webcomics
-        let xkcd = XkcdProvider::new(config);
+        let xkcd = XkcdProvider::new(config)?;
         providers.insert(xkcd.name().to_string(), Arc::new(xkcd));
 
         // LoremFlickr - Unlimited Flickr CC photos by keyword
-        let loremflickr = LoremFlickrProvider::new(config);
+        let loremflickr = LoremFlickrProvider::new(config)?;
         providers.insert(loremflickr.name().to_string(), Arc::new(loremflickr));
 
         // ═══════════════════════════════════════════════════════════════════
@@ -229,11 +249,11 @@ impl ProviderRegistry {
         // ═══════════════════════════════════════════════════════════════════
 
         // Data.gov - 300K+ US Government datasets (JSON, CSV, XML)
-        let datagov = DataGovProvider::new(config);
+        let datagov = DataGovProvider::new(config)?;
         providers.insert(datagov.name().to_string(), Arc::new(datagov));
 
         // GitHub - Data files (JSON, CSV, PDF, Excel) from public repos
-        let github = GitHubProvider::new(config);
+        let github = GitHubProvider::new(config)?;
         providers.insert(github.name().to_string(), Arc::new(github));
 
         // ═══════════════════════════════════════════════════════════════════
@@ -243,30 +263,166 @@ impl ProviderRegistry {
         // ═══════════════════════════════════════════════════════════════════
 
         // Unsplash - 5M+ high-quality photos (free API key at unsplash.com/developers)
-        let unsplash = UnsplashProvider::new(config);
+        let unsplash = UnsplashProvider::new(config)?;
         providers.insert(unsplash.name().to_string(), Arc::new(unsplash));
 
         // Pexels - 3.5M+ photos & videos (free API key at pexels.com/api)
-        let pexels = PexelsProvider::new(config);
+        let pexels = PexelsProvider::new(config)?;
         providers.insert(pexels.name().to_string(), Arc::new(pexels));
 
         // Pixabay - 4.2M+ images, videos, music (free API key at pixabay.com/api/docs)
-        let pixabay = PixabayProvider::new(config);
+        let pixabay = PixabayProvider::new(config)?;
         providers.insert(pixabay.name().to_string(), Arc::new(pixabay));
 
         // Freesound - 600K+ sound effects (free API key at freesound.org/apiv2/apply)
-        let freesound = FreesoundProvider::new(config);
+        let freesound = FreesoundProvider::new(config)?;
         providers.insert(freesound.name().to_string(), Arc::new(freesound));
 
         // Giphy - Millions of GIFs (free API key at developers.giphy.com)
-        let giphy = GiphyProvider::new(config);
+        let giphy = GiphyProvider::new(config)?;
         providers.insert(giphy.name().to_string(), Arc::new(giphy));
 
         // Smithsonian - 4.5M+ CC0 images (free API key at api.si.edu)
-        let smithsonian = SmithsonianProvider::new(config);
+        let smithsonian = SmithsonianProvider::new(config)?;
         providers.insert(smithsonian.name().to_string(), Arc::new(smithsonian));
 
-        Self { providers }
+        let mut registry = Self {
+            providers,
+            cache: SearchCache::from_config(config),
+            settings: config.providers.clone(),
+            health: HealthTracker::from_config(config),
+        };
+
+        // Declarative REST providers from the providers directory
+        if let Some(ref dir) = config.rest_providers_dir {
+            registry.load_rest_providers(dir, config);
+        }
+
+        // Feeds configured as `[providers.<name>] feed = "..."`
+        registry.load_feed_providers(config);
+
+        Ok(registry)
+    }
+
+    /// Create a registry with no providers and no cache.
+    #[must_use]
+    pub fn empty() -> Self {
+        Self {
+            providers: HashMap::new(),
+            cache: None,
+            settings: BTreeMap::new(),
+            health: HealthTracker::new(),
+        }
+    }
+
+    /// Register a provider, replacing any existing provider with the same name.
+    pub fn register(&mut self, provider: Arc<dyn Provider>) {
+        self.providers.insert(provider.name().to_string(), provider);
+    }
+
+    /// Register a [`RestProvider`] for every definition in `dir`.
+    ///
+    /// Definitions that fail to parse or load, or whose name is already
+    /// taken, are skipped with a warning. Returns the number of providers registered.
+    pub fn load_rest_providers(&mut self, dir: &Path, config: &Config) -> usize {
+        let mut loaded = 0;
+        for (path, definition) in load_definitions(dir) {
+            match definition {
+                Ok(definition) if self.providers.contains_key(&definition.name) => {
+                    warn!(
+                        "Skipping {}: provider '{}' already exists",
+                        path.display(),
+                        definition.name
+                    );
+                }
+                Ok(definition) => match RestProvider::new(definition, config) {
+                    Ok(provider) => {
+                        self.register(Arc::new(provider));
+                        loaded += 1;
+                    }
+                    Err(e) => warn!("Skipping {}: {}", path.display(), e),
+                },
+                Err(e) => warn!("Skipping provider definition: {}", e),
+            }
+        }
+        loaded
+    }
+
+    /// Register a [`FeedProvider`] for every provider configured with a `feed` URL.
+    ///
+    /// Feeds whose name is already taken, or that fail to load, are skipped
+    /// with a warning. Returns
+    /// the number of providers registered.
+    pub fn load_feed_providers(&mut self, config: &Config) -> usize {
+        let mut loaded = 0;
+        for (name, settings) in &config.providers {
+            let Some(ref url) = settings.feed else {
+                continue;
+            };
+            if self.providers.contains_key(name) {
+                warn!("Skipping feed {}: provider '{}' already exists", url, name);
+                continue;
+            }
+            match FeedProvider::new(name, url, settings.license.as_deref(), config) {
+                Ok(provider) => {
+                    self.register(Arc::new(provider));
+                    loaded += 1;
+                }
+                Err(e) => warn!("Skipping feed {}: {}", url, e),
+            }
+        }
+        loaded
+    }
+
+    /// Replace the search result cache (`None` disables caching).
+    #[must_use]
+    pub fn with_cache(mut self, cache: Option<SearchCache>) -> Self {
+        self.cache = cache;
+        self
+    }
+
+    /// Replace the per-provider settings.
+    #[must_use]
+    pub fn with_settings(mut self, settings: BTreeMap<String, ProviderSettings>) -> Self {
+        self.settings = settings;
+        self
+    }
+
+    /// Replace the provider health tracker.
+    #[must_use]
+    pub fn with_health(mut self, health: HealthTracker) -> Self {
+        self.health = health;
+        self
+    }
+
+    /// Get the provider health tracker.
+    #[must_use]
+    pub fn health(&self) -> &HealthTracker {
+        &self.health
+    }
+
+    /// Configured overrides for a provider (empty if none).
+    #[must_use]
+    pub fn settings(&self, name: &str) -> ProviderSettings {
+        self.settings.get(name).cloned().unwrap_or_default()
+    }
+
+    /// Whether a provider is enabled in the configuration.
+    ///
+    /// Disabled providers are left out of searches across providers but can
+    /// still be searched by name.
+    #[must_use]
+    pub fn is_enabled(&self, name: &str) -> bool {
+        self.settings
+            .get(name)
+            .and_then(|s| s.enabled)
+            .unwrap_or(true)
+    }
+
+    /// Get the search result cache, if caching is enabled.
+    #[must_use]
+    pub fn cache(&self) -> Option<&SearchCache> {
+        self.cache.as_ref()
     }
 
     /// Get a provider by name.
@@ -281,12 +437,12 @@ impl ProviderRegistry {
         self.providers.values().cloned().collect()
     }
 
-    /// Get all available providers (with valid API keys).
+    /// Get all available providers (with valid API keys and not disabled).
     #[must_use]
     pub fn available(&self) -> Vec<Arc<dyn Provider>> {
         self.providers
             .values()
-            .filter(|p| p.is_available())
+            .filter(|p| p.is_available() && self.is_enabled(p.name()))
             .cloned()
             .collect()
     }
@@ -296,7 +452,11 @@ impl ProviderRegistry {
     pub fn for_media_type(&self, media_type: MediaType) -> Vec<Arc<dyn Provider>> {
         self.providers
             .values()
-            .filter(|p| p.is_available() && p.supported_media_types().contains(&media_type))
+            .filter(|p| {
+                p.is_available()
+                    && self.is_enabled(p.name())
+                    && p.supported_media_types().contains(&media_type)
+            })
             .cloned()
             .collect()
     }
@@ -312,7 +472,7 @@ impl ProviderRegistry {
     pub fn available_provider_names(&self) -> Vec<String> {
         self.providers
             .iter()
-            .filter(|(_, p)| p.is_available())
+            .filter(|(name, p)| p.is_available() && self.is_enabled(name))
             .map(|(name, _)| name.clone())
             .collect()
     }
@@ -323,11 +483,66 @@ impl ProviderRegistry {
         self.providers.contains_key(name)
     }
 
+    /// Each provider's rate limit, keyed by the registrable domain of its
+    /// base URL, for limiting downloads from the same site (see
+    /// [`Downloader::with_site_limits`](crate::engine::Downloader::with_site_limits)).
+    /// Where providers share a domain, the strictest limit wins.
+    #[must_use]
+    pub fn site_limits(&self) -> HashMap<String, RateLimitConfig> {
+        let mut limits: HashMap<String, RateLimitConfig> = HashMap::new();
+        for provider in self.providers.values() {
+            let limit = provider.rate_limit();
+            let Some(host) = url::Url::parse(provider.base_url())
+                .ok()
+                .and_then(|u| u.host_str().map(str::to_lowercase))
+            else {
+                continue;
+            };
+            if !limit.is_limited() {
+                continue;
+            }
+            limits
+                .entry(registrable_domain(&host).to_string())
+                .and_modify(|current| {
+                    if limit.delay_ms() > current.delay_ms() {
+                        *current = limit;
+                    }
+                })
+                .or_insert(limit);
+        }
+        limits
+    }
+
     /// Search a specific provider.
+    ///
+    /// Like searches across providers, this is refused while the provider's
+    /// circuit is open, and times out after the provider's adaptive timeout.
+    /// Health is recorded but not saved; call
+    /// [`save_health`](Self::save_health) once the search is over.
     pub async fn search_provider(
         &self,
         provider_name: &str,
         query: &SearchQuery,
+    ) -> Result<SearchResult> {
+        self.search_named(provider_name, query, true).await
+    }
+
+    /// Search a provider for everything matching the query, as
+    /// [`search_provider`](Self::search_provider) does but without capping
+    /// the results at the provider's configured `count`.
+    pub(crate) async fn search_provider_uncapped(
+        &self,
+        provider_name: &str,
+        query: &SearchQuery,
+    ) -> Result<SearchResult> {
+        self.search_named(provider_name, query, false).await
+    }
+
+    async fn search_named(
+        &self,
+        provider_name: &str,
+        query: &SearchQuery,
+        configured_count: bool,
     ) -> Result<SearchResult> {
         let provider =
             self.get(provider_name)
@@ -337,7 +552,63 @@ impl ProviderRegistry {
                     status_code: 404,
                 })?;
 
-        provider.search(query).await
+        if !self.health.allows(provider_name) {
+            return Err(crate::error::DxError::ProviderApi {
+                provider: provider_name.to_string(),
+                message: circuit_open_message(&self.health.get(provider_name)),
+                status_code: 503,
+            });
+        }
+
+        let count = if configured_count {
+            self.settings(provider_name).count
+        } else {
+            None
+        };
+        self.search_with_health(provider, query, query.mode.provider_timeout(), count)
+            .await
+    }
+
+    /// Search a provider within its timeout and record the outcome in its health.
+    ///
+    /// The timeout is the configured one, or else `base` adapted to the
+    /// provider's observed latency. `count` replaces the query's count.
+    async fn search_with_health(
+        &self,
+        provider: Arc<dyn Provider>,
+        query: &SearchQuery,
+        base: Duration,
+        count: Option<usize>,
+    ) -> Result<SearchResult> {
+        let name = provider.name();
+        let settings = self.settings(name);
+        let timeout = settings
+            .timeout()
+            .unwrap_or_else(|| self.health.timeout(name, base));
+
+        let start = Instant::now();
+        let result = tokio::time::timeout(
+            timeout,
+            search_cached(Arc::clone(&provider), self.cache.as_ref(), query, count),
+        )
+        .await
+        .unwrap_or_else(|_| Err(timeout_error(name, timeout)));
+
+        self.health.record_result(name, start.elapsed(), &result);
+        result
+    }
+
+    /// Look up a single asset by ID from a specific provider.
+    pub async fn get_asset(&self, provider_name: &str, id: &str) -> Result<MediaAsset> {
+        let provider =
+            self.get(provider_name)
+                .ok_or_else(|| crate::error::DxError::ProviderApi {
+                    provider: provider_name.to_string(),
+                    message: "Provider not found".to_string(),
+                    status_code: 404,
+                })?;
+
+        provider.get_asset(id).await
     }
 
     /// Search all available providers and aggregate results.
@@ -345,14 +616,25 @@ impl ProviderRegistry {
     /// This searches all providers **concurrently** with aggressive timeouts.
     /// Uses `FuturesUnordered` for optimal performance - results are processed
     /// as they arrive, and slow providers are timed out after 5 seconds.
+    ///
+    /// Each provider's timeout adapts to its observed latency, and providers
+    /// whose circuit breaker is open are skipped (see [`HealthTracker`]).
     /// 
     /// # Search Modes
     /// - **Quantity** (default): Early exit after 3x results - FAST but may skip slow providers
     /// - **Quality**: Waits for ALL providers to respond - thorough but slower
     pub async fn search_all(&self, query: &SearchQuery) -> Result<SearchResult> {
-        use crate::types::SearchMode;
+        self.search_all_with(query, &mut |_, _| {}).await
+    }
+
+    /// Like [`search_all`](Self::search_all), but calls `on_result` with each
+    /// provider's results as they arrive, before they are aggregated and ranked.
+    pub async fn search_all_with(
+        &self,
+        query: &SearchQuery,
+        on_result: &mut (dyn FnMut(&str, &SearchResult) + Send),
+    ) -> Result<SearchResult> {
         use futures::stream::{FuturesUnordered, StreamExt};
-        use std::time::Duration;
         
         let providers = match query.media_type {
             Some(media_type) => self.for_media_type(media_type),
@@ -365,48 +647,43 @@ impl ProviderRegistry {
             });
         }
 
-        // AGGRESSIVE TIMEOUT: 5 seconds max per provider
+        // AGGRESSIVE TIMEOUT: 5 seconds per provider until its latency is known
         // In Quality mode, we use 8 seconds to give slow providers more time
-        let provider_timeout = match query.mode {
-            SearchMode::Quantity => Duration::from_secs(5),
-            SearchMode::Quality => Duration::from_secs(8),
-        };
+        let provider_timeout = query.mode.provider_timeout();
         
         // Early exit threshold (only used in Quantity mode)
         let early_exit_threshold = query.count * 3;
         let use_early_exit = query.mode.is_quantity();
 
+        // Providers with an open circuit are skipped until their next probe
+        let mut provider_errors = Vec::new();
+        let (providers, skipped): (Vec<_>, Vec<_>) = providers
+            .into_iter()
+            .partition(|p| self.health.allows(p.name()));
+        for provider in skipped {
+            let health = self.health.get(provider.name());
+            provider_errors.push((health.name.clone(), circuit_open_message(&health)));
+        }
+
         // Create a FuturesUnordered for concurrent execution with early returns
         let mut futures: FuturesUnordered<_> = providers
             .iter()
-            .map(|provider| {
-                let provider = Arc::clone(provider);
-                let query = query.clone();
-                async move {
-                    let name = provider.name().to_string();
-                    // Wrap each provider search in a timeout
-                    let result = tokio::time::timeout(
-                        provider_timeout,
-                        provider.search(&query)
-                    ).await;
-                    
-                    let timeout_msg = format!("Provider timed out (>{}s)", provider_timeout.as_secs());
-                    match result {
-                        Ok(search_result) => (name, search_result),
-                        Err(_) => (name.clone(), Err(crate::error::DxError::ProviderApi {
-                            provider: name,
-                            message: timeout_msg,
-                            status_code: 408,
-                        })),
-                    }
-                }
+            .map(|provider| async move {
+                let name = provider.name().to_string();
+                let count = self.settings(provider.name()).count;
+                let result = self
+                    .search_with_health(Arc::clone(provider), query, provider_timeout, count)
+                    .await;
+                (name, result)
             })
             .collect();
 
         // Collect results as they complete (not waiting for all in Quantity mode)
         let mut all_assets = Vec::new();
         let mut providers_searched = Vec::new();
-        let mut provider_errors = Vec::new();
+        let mut cache_hits = Vec::new();
+        let mut cache_misses = Vec::new();
+        let mut filters = Vec::new();
         let mut total_count = 0;
         let mut skipped_slow_providers = 0;
 
@@ -415,8 +692,12 @@ impl ProviderRegistry {
 
             match result {
                 Ok(search_result) => {
+                    on_result(&provider_name, &search_result);
                     total_count += search_result.total_count;
                     all_assets.extend(search_result.assets);
+                    cache_hits.extend(search_result.cache_hits);
+                    cache_misses.extend(search_result.cache_misses);
+                    filters.extend(search_result.filters);
                 }
                 Err(e) => {
                     provider_errors.push((provider_name, e.to_string()));
@@ -438,35 +719,200 @@ impl ProviderRegistry {
                 format!("Skipped {} slow providers (had {} results)", skipped_slow_providers, all_assets.len())
             ));
         }
+        self.save_health().await;
 
         Ok(SearchResult {
             query: query.query.clone(),
             media_type: query.media_type,
             total_count,
-            assets: all_assets,
+            assets: crate::ranking::rank(all_assets, query),
             providers_searched,
             provider_errors,
             duration_ms: 0,
+            cache_hits,
+            cache_misses,
+            filters,
         })
     }
 
-    /// Get provider count statistics.
+    /// Probe providers with [`PROBE_QUERY`], in parallel.
+    ///
+    /// Probes bypass the result cache and circuit breakers; their outcomes are
+    /// recorded in the providers' health, so a successful probe closes an open
+    /// circuit. Unknown names are reported as failed probes.
+    pub async fn probe(&self, names: &[String]) -> Vec<ProbeResult> {
+        let query = SearchQuery::new(PROBE_QUERY)
+            .count(3)
+            .cache_policy(CachePolicy::Bypass);
+
+        let probes = names.iter().map(|name| {
+            let query = query.clone();
+            async move {
+                let start = Instant::now();
+                let result = match self.get(name) {
+                    Some(provider) => {
+                        let timeout = self.settings(name).timeout().unwrap_or(PROBE_TIMEOUT);
+                        tokio::time::timeout(timeout, provider.search(&query))
+                            .await
+                            .unwrap_or_else(|_| Err(timeout_error(name, timeout)))
+                    }
+                    None => Err(crate::error::DxError::ProviderApi {
+                        provider: name.clone(),
+                        message: "Provider not found".to_string(),
+                        status_code: 404,
+                    }),
+                };
+                let latency = start.elapsed();
+                self.health.record_result(name, latency, &result);
+
+                ProbeResult {
+                    provider: name.clone(),
+                    latency,
+                    assets: result.as_ref().map_or(0, |r| r.assets.len()),
+                    error: result.err().map(|e| e.to_string()),
+                }
+            }
+        });
+        let results = futures::future::join_all(probes).await;
+
+        self.save_health().await;
+        results
+    }
+
+    /// Save provider health, logging rather than failing a search on errors.
+    pub async fn save_health(&self) {
+        if let Err(e) = self.health.save().await {
+            warn!("{}", e);
+        }
+    }
+
+    /// Get provider count and health statistics.
     #[must_use]
     pub fn stats(&self) -> ProviderStats {
         let total = self.providers.len();
-        let available = self.providers.values().filter(|p| p.is_available()).count();
-        let unavailable = total - available;
+        let usable: Vec<_> = self.providers.values().filter(|p| p.is_available()).collect();
+        let available = usable.iter().filter(|p| self.is_enabled(p.name())).count();
+
+        let mut health: Vec<ProviderHealth> =
+            self.providers.keys().map(|name| self.health.get(name)).collect();
+        health.sort_by(|a, b| a.name.cmp(&b.name));
 
         ProviderStats {
             total,
             available,
-            unavailable,
+            unavailable: total - usable.len(),
+            disabled: usable.len() - available,
+            open_circuits: health
+                .iter()
+                .filter(|h| matches!(h.circuit, CircuitState::Open { .. }))
+                .count(),
+            health,
         }
     }
 }
 
+/// Query sent by [`ProviderRegistry::probe`].
+pub const PROBE_QUERY: &str = "cat";
+
+/// Time a probe may take unless the provider configures its own timeout.
+const PROBE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Outcome of probing a provider with [`PROBE_QUERY`].
+#[derive(Debug, Clone)]
+pub struct ProbeResult {
+    /// Provider name.
+    pub provider: String,
+    /// Time taken to answer or fail.
+    pub latency: Duration,
+    /// Number of assets returned.
+    pub assets: usize,
+    /// Error, if the probe failed.
+    pub error: Option<String>,
+}
+
+impl ProbeResult {
+    /// Whether the provider answered without an error.
+    #[must_use]
+    pub fn is_ok(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+/// Error for a provider skipped because its circuit is open.
+fn circuit_open_message(health: &ProviderHealth) -> String {
+    let retry = match health.circuit {
+        CircuitState::Open { retry_at } => format!(
+            "; retrying after {}",
+            retry_at.with_timezone(&chrono::Local).format("%H:%M:%S")
+        ),
+        _ => String::new(),
+    };
+    format!(
+        "Skipped: circuit open after {} consecutive failures{}",
+        health.consecutive_failures, retry
+    )
+}
+
+/// Error for a provider that didn't answer within `timeout`.
+fn timeout_error(provider: &str, timeout: Duration) -> crate::error::DxError {
+    crate::error::DxError::ProviderApi {
+        provider: provider.to_string(),
+        message: format!("Provider timed out (>{:.1}s)", timeout.as_secs_f64()),
+        status_code: 408,
+    }
+}
+
+/// Search a provider through the result cache, then enforce the query's filters.
+///
+/// Results are cached before filtering; the cache key already covers the filters.
+/// A configured `count` replaces the query's count for this provider.
+async fn search_cached(
+    provider: Arc<dyn Provider>,
+    cache: Option<&SearchCache>,
+    query: &SearchQuery,
+    count: Option<usize>,
+) -> Result<SearchResult> {
+    let query = match count {
+        Some(count) => &query.clone().count(count),
+        None => query,
+    };
+    let mut result = fetch_cached(provider.as_ref(), cache, query).await?;
+    crate::engine::apply_filters(&mut result, query, provider.name(), provider.native_filters());
+    Ok(result)
+}
+
+/// Search a provider according to the query's cache policy.
+async fn fetch_cached(
+    provider: &dyn Provider,
+    cache: Option<&SearchCache>,
+    query: &SearchQuery,
+) -> Result<SearchResult> {
+    let name = provider.name();
+    let Some(cache) = cache.filter(|_| query.cache_policy != CachePolicy::Bypass) else {
+        return provider.search(query).await;
+    };
+
+    if query.cache_policy == CachePolicy::Use {
+        if let Some(mut cached) = cache.get(name, query).await {
+            cached.cache_hits = vec![name.to_string()];
+            cached.cache_misses.clear();
+            return Ok(cached);
+        }
+    }
+
+    let mut result = provider.search(query).await?;
+    // Empty pages are not cached so a transient upstream hiccup isn't replayed for a day
+    if !result.assets.is_empty() {
+        if let Err(e) = cache.put(name, query, &result).await {
+            tracing::warn!("Failed to cache results from {}: {}", name, e);
+        }
+    }
+    result.cache_misses = vec![name.to_string()];
+    Ok(result)
+}
+
 /// Statistics about registered providers.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub struct ProviderStats {
     /// Total number of registered providers.
     pub total: usize,
@@ -474,11 +920,17 @@ pub struct ProviderStats {
     pub available: usize,
     /// Number of unavailable providers.
     pub unavailable: usize,
+    /// Number of usable providers disabled in the configuration.
+    pub disabled: usize,
+    /// Number of providers whose circuit breaker is open.
+    pub open_circuits: usize,
+    /// Health of every registered provider, by name.
+    pub health: Vec<ProviderHealth>,
 }
 
 impl Default for ProviderRegistry {
     fn default() -> Self {
-        Self::new(&Config::default())
+        Self::new(&Config::default()).expect("Failed to create default provider registry")
     }
 }
 
@@ -489,7 +941,7 @@ mod tests {
     #[test]
     fn test_registry_creation() {
         let config = Config::default();
-        let registry = ProviderRegistry::new(&config);
+        let registry = ProviderRegistry::new(&config).unwrap();
 
         // FREE providers should be registered (no API keys required)
         // Tier 1: High-volume providers
@@ -535,7 +987,7 @@ mod tests {
     #[test]
     fn test_provider_stats() {
         let config = Config::default();
-        let registry = ProviderRegistry::new(&config);
+        let registry = ProviderRegistry::new(&config).unwrap();
 
         let stats = registry.stats();
         // Total: 26 FREE + 8 PREMIUM = 34 providers
@@ -547,10 +999,163 @@ mod tests {
         assert_eq!(stats.unavailable, 11);
     }
 
+    #[test]
+    fn test_site_limits() {
+        let registry = ProviderRegistry::new(&Config::default_for_testing()).unwrap();
+        let limits = registry.site_limits();
+
+        // commons.wikimedia.org limits downloads from upload.wikimedia.org too
+        assert_eq!(limits["wikimedia.org"].requests, 200);
+        assert_eq!(limits["unsplash.com"].requests, 50);
+    }
+
+    #[test]
+    fn test_disabled_provider() {
+        let mut config = Config::default_for_testing();
+        config.providers.insert(
+            "nasa".to_string(),
+            ProviderSettings {
+                enabled: Some(false),
+                ..ProviderSettings::default()
+            },
+        );
+        let registry = ProviderRegistry::new(&config).unwrap();
+
+        assert!(!registry.is_enabled("nasa"));
+        assert!(registry.available().iter().all(|p| p.name() != "nasa"));
+        assert!(registry.get("nasa").is_some());
+        assert_eq!(registry.stats().disabled, 1);
+        assert_eq!(registry.stats().available, 22);
+    }
+
+    #[tokio::test]
+    async fn test_open_circuit_skips_provider() {
+        use crate::types::RateLimitConfig;
+        use async_trait::async_trait;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        /// Fails every search, counting them.
+        #[derive(Default)]
+        struct Down(AtomicUsize);
+
+        #[async_trait]
+        impl Provider for Down {
+            fn name(&self) -> &'static str {
+                "down"
+            }
+            fn display_name(&self) -> &'static str {
+                "Down"
+            }
+            fn supported_media_types(&self) -> &[MediaType] {
+                &[MediaType::Image]
+            }
+            fn requires_api_key(&self) -> bool {
+                false
+            }
+            fn rate_limit(&self) -> RateLimitConfig {
+                RateLimitConfig::new(1000, 1)
+            }
+            fn is_available(&self) -> bool {
+                true
+            }
+            fn base_url(&self) -> &'static str {
+                "https://example.com"
+            }
+            async fn search(&self, _query: &SearchQuery) -> Result<SearchResult> {
+                self.0.fetch_add(1, Ordering::SeqCst);
+                Err(crate::error::DxError::http("connection refused"))
+            }
+        }
+
+        let down = Arc::new(Down::default());
+        let mut registry = ProviderRegistry::empty();
+        registry.register(Arc::clone(&down) as Arc<dyn Provider>);
+        let query = SearchQuery::new("cat");
+
+        for _ in 0..crate::providers::FAILURE_THRESHOLD {
+            registry.search_all(&query).await.unwrap();
+        }
+        let result = registry.search_all(&query).await.unwrap();
+
+        assert_eq!(down.0.load(Ordering::SeqCst), 5);
+        assert!(result.providers_searched.is_empty());
+        assert!(result.provider_errors[0].1.contains("circuit open after 5 consecutive failures"));
+        let stats = registry.stats();
+        assert_eq!(stats.open_circuits, 1);
+        assert_eq!(stats.health[0].failures, 5);
+
+        // Naming the provider doesn't get around the open circuit
+        let err = registry.search_provider("down", &query).await.unwrap_err();
+        assert!(err.to_string().contains("circuit open"), "{err}");
+        assert_eq!(down.0.load(Ordering::SeqCst), 5);
+    }
+
+    #[tokio::test]
+    async fn test_search_all_with_reports_each_provider() {
+        use crate::types::RateLimitConfig;
+        use async_trait::async_trait;
+
+        /// Returns one asset per search.
+        struct Up;
+
+        #[async_trait]
+        impl Provider for Up {
+            fn name(&self) -> &'static str {
+                "up"
+            }
+            fn display_name(&self) -> &'static str {
+                "Up"
+            }
+            fn supported_media_types(&self) -> &[MediaType] {
+                &[MediaType::Image]
+            }
+            fn requires_api_key(&self) -> bool {
+                false
+            }
+            fn rate_limit(&self) -> RateLimitConfig {
+                RateLimitConfig::new(1000, 1)
+            }
+            fn is_available(&self) -> bool {
+                true
+            }
+            fn base_url(&self) -> &'static str {
+                "https://example.com"
+            }
+            async fn search(&self, query: &SearchQuery) -> Result<SearchResult> {
+                let mut result = SearchResult::new(&query.query);
+                result.assets.push(
+                    MediaAsset::builder()
+                        .id("1")
+                        .provider("up")
+                        .media_type(MediaType::Image)
+                        .title("Cat")
+                        .download_url("https://example.com/cat.jpg")
+                        .source_url("https://example.com/cat")
+                        .build(),
+                );
+                Ok(result)
+            }
+        }
+
+        let mut registry = ProviderRegistry::empty();
+        registry.register(Arc::new(Up));
+
+        let mut seen = Vec::new();
+        let result = registry
+            .search_all_with(&SearchQuery::new("cat"), &mut |provider, result| {
+                seen.push((provider.to_string(), result.assets.len()));
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(seen, [("up".to_string(), 1)]);
+        assert_eq!(result.assets.len(), 1);
+    }
+
     #[test]
     fn test_get_provider() {
         let config = Config::default();
-        let registry = ProviderRegistry::new(&config);
+        let registry = ProviderRegistry::new(&config).unwrap();
 
         let provider = registry.get("openverse");
         assert!(provider.is_some());
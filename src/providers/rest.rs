@@ -0,0 +1,820 @@
+//! Declarative REST providers.
+//!
+//! A [`RestProvider`] is defined by a TOML or JSON file instead of Rust code:
+//! the search endpoint, how to authenticate and paginate, and where each
+//! [`MediaAsset`] field is found in the JSON response. Definitions in the
+//! configured providers directory (`rest.dir`, by default `providers/` next
+//! to the user `dx.toml`) are loaded into the [`ProviderRegistry`] at
+//! startup.
+//!
+//! ```toml
+//! name = "acme"
+//! display_name = "ACME Asset Library"
+//! media_types = ["image", "video"]
+//! license = "cc-by"
+//! rate_limit = { requests = 60, period_secs = 60 }
+//!
+//! [search]
+//! url = "https://assets.acme.example/api/v2/search"
+//! params = { q = "{query}", type = "{media_type}" }
+//! results = "$.data.items"
+//! total = "$.data.total"
+//!
+//! [lookup]
+//! url = "https://assets.acme.example/api/v2/assets/{id}"
+//! results = "$.data"
+//!
+//! [auth]
+//! env = "ACME_TOKEN"
+//! header = "Authorization"
+//! format = "Bearer {key}"
+//!
+//! [pagination]
+//! style = "page"
+//! param = "page"
+//! size_param = "per_page"
+//! max_size = 100
+//!
+//! [mapping]
+//! id = "id"
+//! title = "name"
+//! download_url = "files.original.url"
+//! preview_url = "files.thumb.url"
+//! source_url = "https://assets.acme.example/a/{id}"
+//! author = "owner.display_name"
+//! width = "files.original.width"
+//! height = "files.original.height"
+//! tags = "tags[*].label"
+//! ```
+//!
+//! Mappings are paths into each result: dot-separated keys, `[n]` indexes
+//! and `[*]` wildcards, optionally starting at `$`. A mapping containing
+//! `{...}` is a template whose placeholders are paths.
+//!
+//! [`ProviderRegistry`]: super::ProviderRegistry
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::Value;
+use tracing::debug;
+
+use crate::config::Config;
+use crate::error::{DxError, Result};
+use crate::http::{HttpClient, ResponseExt};
+use crate::providers::leak_str;
+use crate::providers::traits::Provider;
+use crate::types::{License, MediaAsset, MediaType, RateLimitConfig, SearchQuery, SearchResult};
+
+/// A REST provider definition, as read from a TOML or JSON file.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RestDefinition {
+    /// Provider name used on the command line (lowercase letters, digits, `-`, `_`).
+    pub name: String,
+    /// Human-readable name (defaults to `name`).
+    #[serde(default)]
+    pub display_name: Option<String>,
+    /// Media types the provider returns; the first is used for assets
+    /// without a `media_type` mapping.
+    #[serde(default = "default_media_types")]
+    pub media_types: Vec<MediaType>,
+    /// License for assets without a `license` mapping, e.g. `"cc0"`.
+    #[serde(default)]
+    pub license: Option<String>,
+    /// Request budget for the provider's host.
+    #[serde(default)]
+    pub rate_limit: Option<RateLimitConfig>,
+    /// Search endpoint.
+    pub search: RestEndpoint,
+    /// Endpoint for looking up a single asset by ID.
+    #[serde(default)]
+    pub lookup: Option<RestEndpoint>,
+    /// Credential to send with every request.
+    #[serde(default)]
+    pub auth: Option<RestAuth>,
+    /// How result pages are requested.
+    #[serde(default)]
+    pub pagination: Pagination,
+    /// Where asset fields are found in each result.
+    pub mapping: FieldMapping,
+}
+
+/// An HTTP GET endpoint.
+///
+/// `url`, `params` and `headers` are templates: `{query}`, `{count}`,
+/// `{page}`, `{offset}` and `{media_type}` are filled in for searches, `{id}`
+/// for lookups.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RestEndpoint {
+    /// URL template.
+    pub url: String,
+    /// Query parameters.
+    #[serde(default)]
+    pub params: BTreeMap<String, String>,
+    /// Extra request headers.
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+    /// Path to the result array (search) or asset object (lookup); defaults to the whole body.
+    #[serde(default)]
+    pub results: Option<String>,
+    /// Path to the total number of matches.
+    #[serde(default)]
+    pub total: Option<String>,
+}
+
+/// A credential read from the environment.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RestAuth {
+    /// Environment variable holding the key. The provider is unavailable without it.
+    pub env: String,
+    /// Header to send the key in.
+    #[serde(default)]
+    pub header: Option<String>,
+    /// Query parameter to send the key in.
+    #[serde(default)]
+    pub param: Option<String>,
+    /// Value template, e.g. `"Bearer {key}"`.
+    #[serde(default = "default_auth_format")]
+    pub format: String,
+}
+
+/// Pagination style.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(tag = "style", rename_all = "lowercase", deny_unknown_fields)]
+pub enum Pagination {
+    /// A single page; any paging placeholders are in the endpoint templates.
+    #[default]
+    None,
+    /// Page numbers.
+    Page {
+        /// Page number parameter.
+        param: String,
+        /// Number of the first page.
+        #[serde(default = "default_first_page")]
+        first: usize,
+        /// Page size parameter.
+        #[serde(default)]
+        size_param: Option<String>,
+        /// Largest page size the API accepts.
+        #[serde(default)]
+        max_size: Option<usize>,
+    },
+    /// Item offsets.
+    Offset {
+        /// Offset parameter.
+        param: String,
+        /// Page size parameter.
+        #[serde(default)]
+        size_param: Option<String>,
+        /// Largest page size the API accepts.
+        #[serde(default)]
+        max_size: Option<usize>,
+    },
+}
+
+/// Where each asset field is found in a result.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FieldMapping {
+    /// Asset ID (required).
+    pub id: String,
+    /// Download URL (required).
+    pub download_url: String,
+    /// Title (defaults to the ID).
+    #[serde(default)]
+    pub title: Option<String>,
+    /// Page URL (defaults to the download URL).
+    #[serde(default)]
+    pub source_url: Option<String>,
+    /// Preview URL.
+    #[serde(default)]
+    pub preview_url: Option<String>,
+    /// Author name.
+    #[serde(default)]
+    pub author: Option<String>,
+    /// Author page URL.
+    #[serde(default)]
+    pub author_url: Option<String>,
+    /// Width in pixels.
+    #[serde(default)]
+    pub width: Option<String>,
+    /// Height in pixels.
+    #[serde(default)]
+    pub height: Option<String>,
+    /// License name, parsed with [`License::parse`].
+    #[serde(default)]
+    pub license: Option<String>,
+    /// Tags; every value matched is used.
+    #[serde(default)]
+    pub tags: Option<String>,
+    /// Media type name, e.g. `"video"`.
+    #[serde(default)]
+    pub media_type: Option<String>,
+    /// MIME type.
+    #[serde(default)]
+    pub mime_type: Option<String>,
+    /// File size in bytes.
+    #[serde(default)]
+    pub file_size: Option<String>,
+}
+
+fn default_media_types() -> Vec<MediaType> {
+    vec![MediaType::Image]
+}
+
+fn default_auth_format() -> String {
+    "{key}".to_string()
+}
+
+const fn default_first_page() -> usize {
+    1
+}
+
+impl RestDefinition {
+    /// Parse a definition from TOML.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the TOML doesn't describe a valid definition.
+    pub fn from_toml(text: &str) -> Result<Self> {
+        let definition: Self = toml::from_str(text).map_err(|e| DxError::Config {
+            message: format!("Invalid provider definition: {}", e.message()),
+            source: Some(Box::new(e)),
+        })?;
+        definition.validate()?;
+        Ok(definition)
+    }
+
+    /// Parse a definition from JSON.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the JSON doesn't describe a valid definition.
+    pub fn from_json(text: &str) -> Result<Self> {
+        let definition: Self = serde_json::from_str(text).map_err(|e| DxError::Config {
+            message: format!("Invalid provider definition: {}", e),
+            source: Some(Box::new(e)),
+        })?;
+        definition.validate()?;
+        Ok(definition)
+    }
+
+    /// Read a definition from a `.toml` or `.json` file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file can't be read or isn't a valid definition.
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let text = std::fs::read_to_string(path).map_err(|e| DxError::FileIo {
+            path: path.to_path_buf(),
+            message: format!("Failed to read provider definition: {}", e),
+            source: Some(e),
+        })?;
+        let result = match path.extension().and_then(|e| e.to_str()) {
+            Some("json") => Self::from_json(&text),
+            _ => Self::from_toml(&text),
+        };
+        result.map_err(|e| DxError::config(format!("{}: {}", path.display(), e)))
+    }
+
+    fn validate(&self) -> Result<()> {
+        let valid_name = !self.name.is_empty()
+            && self
+                .name
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_');
+        if !valid_name {
+            return Err(DxError::config(format!(
+                "Provider name '{}' must be lowercase letters, digits, '-' or '_'",
+                self.name
+            )));
+        }
+        if self.media_types.is_empty() {
+            return Err(DxError::config("media_types must not be empty"));
+        }
+        if let Some(ref auth) = self.auth {
+            if auth.header.is_none() && auth.param.is_none() {
+                return Err(DxError::config("auth needs a header or a param"));
+            }
+        }
+        url::Url::parse(&self.search.url)
+            .map_err(|e| DxError::config(format!("Invalid search url: {}", e)))?;
+        Ok(())
+    }
+}
+
+/// Read every `.toml` and `.json` definition in a directory, sorted by file name.
+///
+/// A missing directory yields no definitions. Each file is parsed
+/// independently so one broken definition doesn't hide the others.
+#[must_use]
+pub fn load_definitions(dir: &Path) -> Vec<(PathBuf, Result<RestDefinition>)> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| {
+            path.is_file()
+                && matches!(path.extension().and_then(|e| e.to_str()), Some("toml" | "json"))
+        })
+        .collect();
+    paths.sort();
+
+    paths
+        .into_iter()
+        .map(|path| {
+            let definition = RestDefinition::from_file(&path);
+            (path, definition)
+        })
+        .collect()
+}
+
+/// A provider driven by a [`RestDefinition`].
+#[derive(Debug)]
+pub struct RestProvider {
+    definition: RestDefinition,
+    name: &'static str,
+    display_name: &'static str,
+    base_url: &'static str,
+    key: Option<String>,
+    client: HttpClient,
+}
+
+impl RestProvider {
+    /// Create a provider from a definition.
+    ///
+    /// The key named by `auth.env` is read from the environment now.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP client cannot be created.
+    pub fn new(definition: RestDefinition, config: &Config) -> Result<Self> {
+        let rate_limit = definition.rate_limit.unwrap_or_default();
+        let client = HttpClient::from_config(rate_limit, config)?;
+
+        let key = definition
+            .auth
+            .as_ref()
+            .and_then(|auth| std::env::var(&auth.env).ok())
+            .filter(|key| !key.is_empty());
+
+        let base_url = url::Url::parse(&definition.search.url)
+            .map(|url| url.origin().ascii_serialization())
+            .unwrap_or_default();
+
+        Ok(Self {
+            name: leak_str(definition.name.clone()),
+            display_name: leak_str(
+                definition
+                    .display_name
+                    .clone()
+                    .unwrap_or_else(|| definition.name.clone()),
+            ),
+            base_url: leak_str(base_url),
+            definition,
+            key,
+            client,
+        })
+    }
+
+    /// The definition this provider was created from.
+    #[must_use]
+    pub fn definition(&self) -> &RestDefinition {
+        &self.definition
+    }
+
+    /// Number of items requested per page, capped at the API's `max_size`.
+    fn page_size(&self, count: usize) -> usize {
+        let max_size = match self.definition.pagination {
+            Pagination::None => None,
+            Pagination::Page { max_size, .. } | Pagination::Offset { max_size, .. } => max_size,
+        };
+        max_size.map_or(count, |max| count.min(max))
+    }
+
+    /// Send a request to an endpoint with the placeholders in `vars`.
+    async fn fetch(
+        &self,
+        endpoint: &RestEndpoint,
+        vars: &[(&str, String)],
+        extra_params: Vec<(String, String)>,
+    ) -> Result<Value> {
+        let url = fill(&endpoint.url, vars, true);
+        let mut params: Vec<(String, String)> = endpoint
+            .params
+            .iter()
+            .map(|(k, v)| (k.clone(), fill(v, vars, false)))
+            .chain(extra_params)
+            .collect();
+        let mut headers: Vec<(String, String)> = endpoint
+            .headers
+            .iter()
+            .map(|(k, v)| (k.clone(), fill(v, vars, false)))
+            .collect();
+
+        if let (Some(auth), Some(key)) = (&self.definition.auth, &self.key) {
+            let value = auth.format.replace("{key}", key);
+            if let Some(ref header) = auth.header {
+                headers.push((header.clone(), value.clone()));
+            }
+            if let Some(ref param) = auth.param {
+                params.push((param.clone(), value));
+            }
+        }
+
+        let headers: Vec<(&str, &str)> = headers
+            .iter()
+            .map(|(k, v)| (k.as_str(), v.as_str()))
+            .collect();
+        let response = self.client.get_with_query(&url, &params, &headers).await?;
+        if !response.status().is_success() {
+            return Err(DxError::ProviderApi {
+                provider: self.name.to_string(),
+                message: format!("HTTP {}", response.status().as_u16()),
+                status_code: response.status().as_u16(),
+            });
+        }
+        response.json_or_error().await
+    }
+
+    /// Convert one result into an asset, or `None` if it lacks an ID or download URL.
+    fn map_asset(&self, item: &Value) -> Option<MediaAsset> {
+        let mapping = &self.definition.mapping;
+        let get = |field: &Option<String>| field.as_deref().and_then(|m| extract(item, m));
+
+        let id = extract(item, &mapping.id)?;
+        let download_url = extract(item, &mapping.download_url)?;
+        let media_type = get(&mapping.media_type)
+            .and_then(|t| MediaType::from_str(&t.to_lowercase()).ok())
+            .unwrap_or(self.definition.media_types[0]);
+        let license = get(&mapping.license)
+            .or_else(|| self.definition.license.clone())
+            .map(|name| License::parse(&name))
+            .unwrap_or_default();
+
+        let mut builder = MediaAsset::builder()
+            .title(get(&mapping.title).unwrap_or_else(|| id.clone()))
+            .source_url(get(&mapping.source_url).unwrap_or_else(|| download_url.clone()))
+            .id(id)
+            .provider(self.name)
+            .media_type(media_type)
+            .download_url(download_url)
+            .license(license);
+        if let Some(url) = get(&mapping.preview_url) {
+            builder = builder.preview_url(url);
+        }
+        if let Some(author) = get(&mapping.author) {
+            builder = builder.author(author);
+        }
+        if let Some(url) = get(&mapping.author_url) {
+            builder = builder.author_url(url);
+        }
+        if let Some(mime) = get(&mapping.mime_type) {
+            builder = builder.mime_type(mime);
+        }
+        if let Some(size) = get(&mapping.file_size).and_then(|s| s.parse().ok()) {
+            builder = builder.file_size(size);
+        }
+        if let Some(ref path) = mapping.tags {
+            builder = builder.tags(select(item, path).into_iter().filter_map(scalar).collect());
+        }
+
+        let mut asset = builder.build();
+        asset.width = get(&mapping.width).and_then(|w| w.parse().ok());
+        asset.height = get(&mapping.height).and_then(|h| h.parse().ok());
+        Some(asset)
+    }
+
+    fn map_assets(&self, items: &[&Value]) -> Vec<MediaAsset> {
+        let assets: Vec<MediaAsset> = items.iter().filter_map(|item| self.map_asset(item)).collect();
+        if assets.len() < items.len() {
+            debug!(
+                "{}: skipped {} results without an id or download url",
+                self.name,
+                items.len() - assets.len()
+            );
+        }
+        assets
+    }
+}
+
+#[async_trait]
+impl Provider for RestProvider {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn display_name(&self) -> &'static str {
+        self.display_name
+    }
+
+    fn supported_media_types(&self) -> &[MediaType] {
+        &self.definition.media_types
+    }
+
+    fn requires_api_key(&self) -> bool {
+        self.definition.auth.is_some()
+    }
+
+    fn rate_limit(&self) -> RateLimitConfig {
+        self.definition.rate_limit.unwrap_or_default()
+    }
+
+    fn is_available(&self) -> bool {
+        self.definition.auth.is_none() || self.key.is_some()
+    }
+
+    fn supports_get_asset(&self) -> bool {
+        self.definition.lookup.is_some()
+    }
+
+    fn base_url(&self) -> &'static str {
+        self.base_url
+    }
+
+    async fn search(&self, query: &SearchQuery) -> Result<SearchResult> {
+        let page_size = self.page_size(query.count);
+        let offset = query.page.saturating_sub(1).saturating_mul(page_size);
+        let extra_params = match self.definition.pagination {
+            Pagination::None => Vec::new(),
+            Pagination::Page {
+                ref param,
+                first,
+                ref size_param,
+                ..
+            } => {
+                let mut params = vec![(param.clone(), (query.page + first - 1).to_string())];
+                params.extend(size_param.iter().map(|p| (p.clone(), page_size.to_string())));
+                params
+            }
+            Pagination::Offset {
+                ref param,
+                ref size_param,
+                ..
+            } => {
+                let mut params = vec![(param.clone(), offset.to_string())];
+                params.extend(size_param.iter().map(|p| (p.clone(), page_size.to_string())));
+                params
+            }
+        };
+
+        let media_type = query
+            .media_type
+            .unwrap_or(self.definition.media_types[0]);
+        let vars = [
+            ("query", query.query.clone()),
+            ("count", page_size.to_string()),
+            ("page", query.page.to_string()),
+            ("offset", offset.to_string()),
+            ("media_type", media_type.as_str().to_string()),
+        ];
+
+        let body = self.fetch(&self.definition.search, &vars, extra_params).await?;
+        let root = self.definition.search.results.as_deref().unwrap_or("$");
+        let items: Vec<&Value> = select(&body, root)
+            .into_iter()
+            .flat_map(|v| match v {
+                Value::Array(items) => items.iter().collect(),
+                other => vec![other],
+            })
+            .collect();
+
+        let mut assets = self.map_assets(&items);
+        assets.truncate(query.count);
+        let total = self
+            .definition
+            .search
+            .total
+            .as_deref()
+            .and_then(|path| extract(&body, path))
+            .and_then(|t| t.parse().ok())
+            .unwrap_or(assets.len());
+
+        let mut result = SearchResult::new(&query.query);
+        result.media_type = query.media_type;
+        result.total_count = total;
+        result.assets = assets;
+        result.providers_searched = vec![self.name.to_string()];
+        Ok(result)
+    }
+
+    async fn get_asset(&self, id: &str) -> Result<MediaAsset> {
+        let Some(ref lookup) = self.definition.lookup else {
+            return Err(DxError::ProviderApi {
+                provider: self.name.to_string(),
+                message: format!("Looking up assets by ID is not supported (requested '{}')", id),
+                status_code: 501,
+            });
+        };
+
+        let body = self.fetch(lookup, &[("id", id.to_string())], Vec::new()).await?;
+        let root = lookup.results.as_deref().unwrap_or("$");
+        select(&body, root)
+            .first()
+            .and_then(|item| self.map_asset(item))
+            .ok_or_else(|| DxError::ProviderApi {
+                provider: self.name.to_string(),
+                message: format!("Asset '{}' not found", id),
+                status_code: 404,
+            })
+    }
+}
+
+/// Fill `{name}` placeholders, percent-encoding values substituted into URLs.
+fn fill(template: &str, vars: &[(&str, String)], encode: bool) -> String {
+    let mut out = template.to_string();
+    for (name, value) in vars {
+        let value = if encode { percent_encode(value) } else { value.clone() };
+        out = out.replace(&format!("{{{}}}", name), &value);
+    }
+    out
+}
+
+fn percent_encode(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'_' | b'.' | b'~') {
+            out.push(char::from(byte));
+        } else {
+            let _ = write!(out, "%{byte:02X}");
+        }
+    }
+    out
+}
+
+/// Evaluate a mapping: a path, or a template of `{path}` placeholders.
+fn extract(item: &Value, mapping: &str) -> Option<String> {
+    if !mapping.contains('{') {
+        return select(item, mapping).into_iter().find_map(scalar);
+    }
+
+    let mut out = String::new();
+    let mut rest = mapping;
+    while let Some(start) = rest.find('{') {
+        let end = rest[start..].find('}')? + start;
+        out.push_str(&rest[..start]);
+        out.push_str(&select(item, &rest[start + 1..end]).into_iter().find_map(scalar)?);
+        rest = &rest[end + 1..];
+    }
+    out.push_str(rest);
+    Some(out)
+}
+
+/// Select values by path: `a.b`, `a[0].b`, `a[*].b` or `$.a` (`$` is the root).
+fn select<'a>(value: &'a Value, path: &str) -> Vec<&'a Value> {
+    let path = path.trim().trim_start_matches('$').trim_start_matches('.');
+    let mut current = vec![value];
+
+    for segment in path.split('.').filter(|s| !s.is_empty()) {
+        let (key, indexes) = segment.split_once('[').map_or((segment, ""), |(k, i)| (k, i));
+        if !key.is_empty() {
+            current = current
+                .into_iter()
+                .flat_map(|v| match (key, v) {
+                    ("*", Value::Object(map)) => map.values().collect(),
+                    ("*", Value::Array(items)) => items.iter().collect(),
+                    (_, Value::Object(map)) => map.get(key).into_iter().collect(),
+                    _ => Vec::new(),
+                })
+                .collect();
+        }
+        for index in indexes
+            .split('[')
+            .map(|i| i.trim_end_matches(']'))
+            .filter(|i| !i.is_empty())
+        {
+            current = current
+                .into_iter()
+                .flat_map(|v| match (index, v) {
+                    ("*", Value::Array(items)) => items.iter().collect(),
+                    (_, Value::Array(items)) => index
+                        .parse::<usize>()
+                        .ok()
+                        .and_then(|i| items.get(i))
+                        .into_iter()
+                        .collect(),
+                    _ => Vec::new(),
+                })
+                .collect();
+        }
+    }
+
+    current
+}
+
+/// A scalar JSON value as a string (`None` for null, arrays and objects).
+fn scalar(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) if !s.is_empty() => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        Value::Bool(b) => Some(b.to_string()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const DEFINITION: &str = r#"
+name = "acme"
+media_types = ["image"]
+license = "cc0"
+
+[search]
+url = "https://assets.example.com/api/search"
+params = { q = "{query}" }
+results = "$.data.items"
+total = "data.total"
+
+[pagination]
+style = "page"
+param = "p"
+size_param = "per_page"
+max_size = 50
+
+[mapping]
+id = "id"
+title = "name"
+download_url = "files[0].url"
+source_url = "https://assets.example.com/a/{id}"
+author = "owner.name"
+width = "files[0].w"
+tags = "tags[*].label"
+"#;
+
+    #[test]
+    fn test_select_paths() {
+        let value = json!({
+            "a": {"b": [{"c": 1}, {"c": 2}]},
+            "list": ["x", "y"],
+        });
+        assert_eq!(select(&value, "$.a.b[1].c"), vec![&json!(2)]);
+        assert_eq!(select(&value, "a.b[*].c").len(), 2);
+        assert_eq!(select(&value, "list[*]"), vec![&json!("x"), &json!("y")]);
+        assert!(select(&value, "a.missing.c").is_empty());
+        assert_eq!(select(&value, "$").len(), 1);
+        assert_eq!(
+            extract(&value, "item-{a.b[0].c}-{list[1]}").as_deref(),
+            Some("item-1-y")
+        );
+        assert_eq!(extract(&value, "{a.nope}"), None);
+    }
+
+    #[test]
+    fn test_definition_formats() {
+        let definition = RestDefinition::from_toml(DEFINITION).unwrap();
+        assert_eq!(definition.name, "acme");
+        assert!(matches!(definition.pagination, Pagination::Page { first: 1, .. }));
+
+        let json = r#"{
+            "name": "acme-json",
+            "search": {"url": "https://example.com/search?q={query}"},
+            "mapping": {"id": "id", "download_url": "url"}
+        }"#;
+        let definition = RestDefinition::from_json(json).unwrap();
+        assert_eq!(definition.media_types, vec![MediaType::Image]);
+
+        assert!(RestDefinition::from_toml(&DEFINITION.replace("\"acme\"", "\"Not Valid\"")).is_err());
+        assert!(RestDefinition::from_toml(&format!("{}\nbogus = 1\n", DEFINITION)).is_err());
+    }
+
+    #[test]
+    fn test_map_results() {
+        let definition = RestDefinition::from_toml(DEFINITION).unwrap();
+        let provider = RestProvider::new(definition, &Config::default_for_testing()).unwrap();
+        let body = json!({"data": {"total": 120, "items": [
+            {
+                "id": 7,
+                "name": "Harbor",
+                "owner": {"name": "Ana"},
+                "files": [{"url": "https://cdn.example.com/7.jpg", "w": 1600}],
+                "tags": [{"label": "sea"}, {"label": "boats"}]
+            },
+            {"id": 8, "name": "No files"}
+        ]}});
+
+        let items = select(&body, "$.data.items[*]");
+        let assets = provider.map_assets(&items);
+        assert_eq!(assets.len(), 1);
+        let asset = &assets[0];
+        assert_eq!(asset.id, "7");
+        assert_eq!(asset.provider, "acme");
+        assert_eq!(asset.source_url, "https://assets.example.com/a/7");
+        assert_eq!(asset.author.as_deref(), Some("Ana"));
+        assert_eq!(asset.width, Some(1600));
+        assert_eq!(asset.height, None);
+        assert_eq!(asset.license, License::Cc0);
+        assert_eq!(asset.tags, vec!["sea", "boats"]);
+        assert_eq!(provider.base_url(), "https://assets.example.com");
+    }
+}
@@ -5,7 +5,6 @@
 //! Free robot/monster avatar generation - unlimited, no API key required.
 
 use async_trait::async_trait;
-use std::time::Duration;
 
 use crate::config::Config;
 use crate::error::Result;
@@ -23,16 +22,14 @@ pub struct RoboHashProvider {
 
 impl RoboHashProvider {
     /// Create a new RoboHash provider.
-    #[must_use]
-    pub fn new(config: &Config) -> Self {
-        let client = HttpClient::with_config(
-            Self::RATE_LIMIT,
-            config.retry_attempts,
-            Duration::from_secs(config.timeout_secs),
-        )
-        .unwrap_or_default();
-
-        Self { client }
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP client cannot be created.
+    pub fn new(config: &Config) -> Result<Self> {
+        let client = HttpClient::from_config(Self::RATE_LIMIT, config)?;
+
+        Ok(Self { client })
     }
 
     /// Rate limit: Generous
@@ -144,6 +141,9 @@ impl Provider for RoboHashProvider {
             providers_searched: vec!["robohash".to_string()],
             provider_errors: vec![],
             duration_ms: 0,
+            cache_hits: Vec::new(),
+            cache_misses: Vec::new(),
+            filters: Vec::new(),
         })
     }
 }
@@ -169,7 +169,7 @@ mod tests {
     #[test]
     fn test_provider_info() {
         let config = Config::default();
-        let provider = RoboHashProvider::new(&config);
+        let provider = RoboHashProvider::new(&config).unwrap();
         assert_eq!(provider.name(), "robohash");
         assert!(provider.is_available());
         assert!(!provider.requires_api_key());
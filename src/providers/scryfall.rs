@@ -11,10 +11,9 @@
 
 use async_trait::async_trait;
 use serde::Deserialize;
-use std::time::Duration;
 
 use crate::config::Config;
-use crate::error::Result;
+use crate::error::{DxError, Result};
 use crate::http::{HttpClient, ResponseExt};
 use crate::providers::traits::Provider;
 use crate::types::{License, MediaAsset, MediaType, RateLimitConfig, SearchQuery, SearchResult};
@@ -74,16 +73,14 @@ struct SearchResponse {
 
 impl ScryfallProvider {
     /// Create a new Scryfall provider.
-    #[must_use]
-    pub fn new(config: &Config) -> Self {
-        let client = HttpClient::with_config(
-            Self::RATE_LIMIT,
-            config.retry_attempts,
-            Duration::from_secs(config.timeout_secs),
-        )
-        .unwrap_or_default();
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP client cannot be created.
+    pub fn new(config: &Config) -> Result<Self> {
+        let client = HttpClient::from_config(Self::RATE_LIMIT, config)?;
 
-        Self { client }
+        Ok(Self { client })
     }
 
     /// Rate limit: 10 requests/second
@@ -228,6 +225,9 @@ impl Provider for ScryfallProvider {
                 providers_searched: vec![self.name().to_string()],
                 provider_errors: vec![],
                 duration_ms: 0,
+                cache_hits: Vec::new(),
+                cache_misses: Vec::new(),
+                filters: Vec::new(),
             });
         }
         
@@ -247,8 +247,29 @@ impl Provider for ScryfallProvider {
             providers_searched: vec![self.name().to_string()],
             provider_errors: vec![],
             duration_ms: 0,
+            cache_hits: Vec::new(),
+            cache_misses: Vec::new(),
+            filters: Vec::new(),
         })
     }
+
+    fn supports_get_asset(&self) -> bool {
+        true
+    }
+
+    async fn get_asset(&self, id: &str) -> Result<MediaAsset> {
+        let card_id = id.strip_prefix("scryfall_").unwrap_or(id);
+        let url = format!("{}/cards/{}", self.base_url(), card_id);
+
+        let response = self.client.get(&url).await?;
+        if response.status().as_u16() == 404 {
+            return Err(DxError::asset_not_found(self.name(), id));
+        }
+
+        let card: ScryfallCard = response.json_or_error().await?;
+        self.card_to_asset(card)
+            .ok_or_else(|| DxError::asset_not_found(self.name(), id))
+    }
 }
 
 #[cfg(test)]
@@ -258,7 +279,7 @@ mod tests {
     #[test]
     fn test_provider_info() {
         let config = Config::default();
-        let provider = ScryfallProvider::new(&config);
+        let provider = ScryfallProvider::new(&config).unwrap();
         assert_eq!(provider.name(), "scryfall");
         assert_eq!(provider.display_name(), "Scryfall");
         assert!(provider.is_available());
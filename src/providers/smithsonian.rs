@@ -6,7 +6,6 @@
 
 use async_trait::async_trait;
 use serde::Deserialize;
-use std::time::Duration;
 
 use crate::config::Config;
 use crate::error::Result;
@@ -24,19 +23,17 @@ pub struct SmithsonianProvider {
 
 impl SmithsonianProvider {
     /// Create a new Smithsonian provider.
-    #[must_use]
-    pub fn new(config: &Config) -> Self {
-        let client = HttpClient::with_config(
-            Self::RATE_LIMIT,
-            config.retry_attempts,
-            Duration::from_secs(config.timeout_secs),
-        )
-        .unwrap_or_default();
-
-        Self {
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP client cannot be created.
+    pub fn new(config: &Config) -> Result<Self> {
+        let client = HttpClient::from_config(Self::RATE_LIMIT, config)?;
+
+        Ok(Self {
             api_key: std::env::var("SMITHSONIAN_API_KEY").ok(),
             client,
-        }
+        })
     }
 
     /// Rate limit: Unlimited but be respectful
@@ -153,6 +150,9 @@ impl Provider for SmithsonianProvider {
             providers_searched: vec!["smithsonian".to_string()],
             provider_errors: vec![],
             duration_ms: 0,
+            cache_hits: Vec::new(),
+            cache_misses: Vec::new(),
+            filters: Vec::new(),
         })
     }
 }
@@ -240,7 +240,7 @@ mod tests {
     #[test]
     fn test_provider_metadata() {
         let config = Config::default_for_testing();
-        let provider = SmithsonianProvider::new(&config);
+        let provider = SmithsonianProvider::new(&config).unwrap();
 
         assert_eq!(provider.name(), "smithsonian");
         assert_eq!(provider.display_name(), "Smithsonian Open Access");
@@ -250,7 +250,7 @@ mod tests {
     #[test]
     fn test_supported_media_types() {
         let config = Config::default_for_testing();
-        let provider = SmithsonianProvider::new(&config);
+        let provider = SmithsonianProvider::new(&config).unwrap();
 
         let types = provider.supported_media_types();
         assert!(types.contains(&MediaType::Image));
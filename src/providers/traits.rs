@@ -4,8 +4,10 @@
 
 use async_trait::async_trait;
 
-use crate::error::Result;
-use crate::types::{MediaType, RateLimitConfig, SearchQuery, SearchResult};
+use crate::error::{DxError, Result};
+use crate::types::{
+    MediaAsset, MediaType, RateLimitConfig, SearchFilter, SearchQuery, SearchResult,
+};
 
 /// Trait for media asset providers.
 ///
@@ -38,6 +40,40 @@ pub trait Provider: Send + Sync {
     /// Returns an error if the search fails.
     async fn search(&self, query: &SearchQuery) -> Result<SearchResult>;
 
+    /// Query filters this provider applies server-side.
+    ///
+    /// Any other filter set on the query is enforced on the results client-side.
+    fn native_filters(&self) -> &[SearchFilter] {
+        &[]
+    }
+
+    /// Returns whether the provider fetches its whole catalog and pages client-side.
+    ///
+    /// Streaming searches query such providers once with an unbounded count
+    /// and page through the result locally instead of re-fetching per page.
+    fn pages_locally(&self) -> bool {
+        false
+    }
+
+    /// Returns whether [`Provider::get_asset`] can look up assets by ID.
+    fn supports_get_asset(&self) -> bool {
+        false
+    }
+
+    /// Fetch a single asset by its provider-specific ID (the `id` of a [`MediaAsset`]).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the provider has no lookup endpoint, the asset
+    /// doesn't exist, or the request fails.
+    async fn get_asset(&self, id: &str) -> Result<MediaAsset> {
+        Err(DxError::ProviderApi {
+            provider: self.name().to_string(),
+            message: format!("Looking up assets by ID is not supported (requested '{}')", id),
+            status_code: 501,
+        })
+    }
+
     /// Get the provider's base URL.
     fn base_url(&self) -> &'static str;
 }
@@ -4,13 +4,15 @@
 
 use async_trait::async_trait;
 use serde::Deserialize;
-use std::time::Duration;
 
 use crate::config::Config;
 use crate::error::Result;
 use crate::http::{HttpClient, ResponseExt};
 use crate::providers::traits::{Provider, ProviderInfo};
-use crate::types::{License, MediaAsset, MediaType, RateLimitConfig, SearchQuery, SearchResult};
+use crate::types::{
+    License, MediaAsset, MediaType, Orientation, RateLimitConfig, SearchFilter, SearchQuery,
+    SearchResult,
+};
 
 /// Unsplash provider for high-resolution photography.
 #[derive(Debug)]
@@ -21,31 +23,51 @@ pub struct UnsplashProvider {
 
 impl UnsplashProvider {
     /// Create a new Unsplash provider.
-    #[must_use]
-    pub fn new(config: &Config) -> Self {
-        let client = HttpClient::with_config(
-            Self::RATE_LIMIT,
-            config.retry_attempts,
-            Duration::from_secs(config.timeout_secs),
-        )
-        .unwrap_or_default();
-
-        Self {
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP client cannot be created.
+    pub fn new(config: &Config) -> Result<Self> {
+        let client = HttpClient::from_config(Self::RATE_LIMIT, config)?;
+
+        Ok(Self {
             api_key: config.unsplash_api_key.clone(),
             client,
-        }
+        })
     }
 
     /// Rate limit: 50 requests per hour
     const RATE_LIMIT: RateLimitConfig = RateLimitConfig::new(50, 3600);
 
     /// Build authorization header.
-    #[allow(dead_code)] // May be used in future methods
     fn auth_header(&self) -> Option<(&'static str, String)> {
         self.api_key
             .as_ref()
             .map(|key| ("Authorization", format!("Client-ID {key}")))
     }
+
+    /// Convert a photo record into a media asset.
+    fn photo_asset(photo: UnsplashPhoto) -> MediaAsset {
+        MediaAsset::builder()
+            .id(photo.id)
+            .provider("unsplash")
+            .media_type(MediaType::Image)
+            .title(
+                photo
+                    .description
+                    .or(photo.alt_description)
+                    .unwrap_or_else(|| "Unsplash Photo".to_string()),
+            )
+            .download_url(photo.urls.full)
+            .preview_url(photo.urls.small)
+            .source_url(photo.links.html)
+            .author(photo.user.name)
+            .author_url(photo.user.links.html)
+            .license(License::Unsplash)
+            .dimensions(photo.width, photo.height)
+            .tags(photo.tags.into_iter().map(|t| t.title).collect())
+            .build()
+    }
 }
 
 #[async_trait]
@@ -78,6 +100,10 @@ impl Provider for UnsplashProvider {
         "https://api.unsplash.com"
     }
 
+    fn native_filters(&self) -> &[SearchFilter] {
+        &[SearchFilter::Orientation, SearchFilter::Color]
+    }
+
     async fn search(&self, query: &SearchQuery) -> Result<SearchResult> {
         let Some(ref api_key) = self.api_key else {
             return Err(crate::error::DxError::MissingApiKey {
@@ -92,7 +118,11 @@ impl Provider for UnsplashProvider {
             query: &query.query,
             page: query.page,
             per_page: query.count.min(30), // Unsplash max is 30
-            orientation: query.orientation.as_ref().map(|o| o.to_string()),
+            // Unsplash calls square images "squarish"
+            orientation: query.orientation.map(|o| match o {
+                Orientation::Square => "squarish".to_string(),
+                _ => o.to_string(),
+            }),
             color: query.color.clone(),
         };
 
@@ -110,27 +140,7 @@ impl Provider for UnsplashProvider {
         let assets: Vec<MediaAsset> = api_response
             .results
             .into_iter()
-            .map(|photo| {
-                MediaAsset::builder()
-                    .id(photo.id)
-                    .provider("unsplash")
-                    .media_type(MediaType::Image)
-                    .title(
-                        photo
-                            .description
-                            .or(photo.alt_description)
-                            .unwrap_or_else(|| "Unsplash Photo".to_string()),
-                    )
-                    .download_url(photo.urls.full)
-                    .preview_url(photo.urls.small)
-                    .source_url(photo.links.html)
-                    .author(photo.user.name)
-                    .author_url(photo.user.links.html)
-                    .license(License::Unsplash)
-                    .dimensions(photo.width, photo.height)
-                    .tags(photo.tags.into_iter().map(|t| t.title).collect())
-                    .build()
-            })
+            .map(Self::photo_asset)
             .collect();
 
         Ok(SearchResult {
@@ -141,8 +151,33 @@ impl Provider for UnsplashProvider {
             providers_searched: vec!["unsplash".to_string()],
             provider_errors: vec![],
             duration_ms: 0,
+            cache_hits: Vec::new(),
+            cache_misses: Vec::new(),
+            filters: Vec::new(),
         })
     }
+
+    fn supports_get_asset(&self) -> bool {
+        true
+    }
+
+    async fn get_asset(&self, id: &str) -> Result<MediaAsset> {
+        let Some((name, value)) = self.auth_header() else {
+            return Err(crate::error::DxError::MissingApiKey {
+                provider: "unsplash".to_string(),
+                env_var: "UNSPLASH_ACCESS_KEY".to_string(),
+            });
+        };
+
+        let url = format!("{}/photos/{}", self.base_url(), id);
+        let response = self.client.get_with_headers(&url, &[(name, &value)]).await?;
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Err(crate::error::DxError::asset_not_found(self.name(), id));
+        }
+
+        let photo: UnsplashPhoto = response.json_or_error().await?;
+        Ok(Self::photo_asset(photo))
+    }
 }
 
 impl ProviderInfo for UnsplashProvider {
@@ -256,7 +291,7 @@ mod tests {
     #[test]
     fn test_provider_metadata() {
         let config = Config::default_for_testing();
-        let provider = UnsplashProvider::new(&config);
+        let provider = UnsplashProvider::new(&config).unwrap();
 
         assert_eq!(provider.name(), "unsplash");
         assert_eq!(provider.display_name(), "Unsplash");
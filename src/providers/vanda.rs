@@ -6,7 +6,6 @@
 
 use async_trait::async_trait;
 use serde::Deserialize;
-use std::time::Duration;
 
 use crate::config::Config;
 use crate::error::Result;
@@ -23,16 +22,14 @@ pub struct VandAMuseumProvider {
 
 impl VandAMuseumProvider {
     /// Create a new V&A Museum provider.
-    #[must_use]
-    pub fn new(config: &Config) -> Self {
-        let client = HttpClient::with_config(
-            Self::RATE_LIMIT,
-            config.retry_attempts,
-            Duration::from_secs(config.timeout_secs),
-        )
-        .unwrap_or_default();
-
-        Self { client }
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP client cannot be created.
+    pub fn new(config: &Config) -> Result<Self> {
+        let client = HttpClient::from_config(Self::RATE_LIMIT, config)?;
+
+        Ok(Self { client })
     }
 
     /// Rate limit
@@ -100,7 +97,7 @@ impl Provider for VandAMuseumProvider {
                         .id(format!("vanda_{}", record.system_number))
                         .provider("vanda")
                         .media_type(MediaType::Image)
-                        .title(record._primary_title.unwrap_or_else(|| "Untitled".to_string()))
+                        .title(record._primary_title.filter(|t| !t.is_empty()).unwrap_or_else(|| "Untitled".to_string()))
                         .download_url(image_url)
                         .preview_url(preview_url)
                         .source_url(format!("https://collections.vam.ac.uk/item/{}", record.system_number))
@@ -121,6 +118,9 @@ impl Provider for VandAMuseumProvider {
             providers_searched: vec!["vanda".to_string()],
             provider_errors: vec![],
             duration_ms: 0,
+            cache_hits: Vec::new(),
+            cache_misses: Vec::new(),
+            filters: Vec::new(),
         })
     }
 }
@@ -178,7 +178,7 @@ mod tests {
     #[test]
     fn test_provider_info() {
         let config = Config::default();
-        let provider = VandAMuseumProvider::new(&config);
+        let provider = VandAMuseumProvider::new(&config).unwrap();
         assert_eq!(provider.name(), "vanda");
         assert!(provider.is_available());
         assert!(!provider.requires_api_key());
@@ -11,7 +11,6 @@
 
 use async_trait::async_trait;
 use serde::Deserialize;
-use std::time::Duration;
 
 use crate::config::Config;
 use crate::error::Result;
@@ -41,16 +40,14 @@ const SFW_CATEGORIES: &[&str] = &[
 
 impl WaifuPicsProvider {
     /// Create a new Waifu.pics provider.
-    #[must_use]
-    pub fn new(config: &Config) -> Self {
-        let client = HttpClient::with_config(
-            Self::RATE_LIMIT,
-            config.retry_attempts,
-            Duration::from_secs(config.timeout_secs),
-        )
-        .unwrap_or_default();
-
-        Self { client }
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP client cannot be created.
+    pub fn new(config: &Config) -> Result<Self> {
+        let client = HttpClient::from_config(Self::RATE_LIMIT, config)?;
+
+        Ok(Self { client })
     }
 
     /// Rate limit: generous (no official limit)
@@ -105,7 +102,7 @@ impl Provider for WaifuPicsProvider {
     }
 
     fn supported_media_types(&self) -> &[MediaType] {
-        &[MediaType::Image]
+        &[MediaType::Image, MediaType::Gif]
     }
 
     fn requires_api_key(&self) -> bool {
@@ -168,6 +165,9 @@ impl Provider for WaifuPicsProvider {
             providers_searched: vec![self.name().to_string()],
             provider_errors: vec![],
             duration_ms: 0,
+            cache_hits: Vec::new(),
+            cache_misses: Vec::new(),
+            filters: Vec::new(),
         })
     }
 }
@@ -179,7 +179,7 @@ mod tests {
     #[test]
     fn test_provider_info() {
         let config = Config::default();
-        let provider = WaifuPicsProvider::new(&config);
+        let provider = WaifuPicsProvider::new(&config).unwrap();
         assert_eq!(provider.name(), "waifupics");
         assert_eq!(provider.display_name(), "Waifu.pics");
         assert!(provider.is_available());
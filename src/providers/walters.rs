@@ -6,7 +6,6 @@
 
 use async_trait::async_trait;
 use serde::Deserialize;
-use std::time::Duration;
 
 use crate::config::Config;
 use crate::error::Result;
@@ -23,16 +22,14 @@ pub struct WaltersArtMuseumProvider {
 
 impl WaltersArtMuseumProvider {
     /// Create a new Walters Art Museum provider.
-    #[must_use]
-    pub fn new(config: &Config) -> Self {
-        let client = HttpClient::with_config(
-            Self::RATE_LIMIT,
-            config.retry_attempts,
-            Duration::from_secs(config.timeout_secs),
-        )
-        .unwrap_or_default();
-
-        Self { client }
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP client cannot be created.
+    pub fn new(config: &Config) -> Result<Self> {
+        let client = HttpClient::from_config(Self::RATE_LIMIT, config)?;
+
+        Ok(Self { client })
     }
 
     /// Rate limit
@@ -126,6 +123,9 @@ impl Provider for WaltersArtMuseumProvider {
             providers_searched: vec!["walters".to_string()],
             provider_errors: vec![],
             duration_ms: 0,
+            cache_hits: Vec::new(),
+            cache_misses: Vec::new(),
+            filters: Vec::new(),
         })
     }
 }
@@ -161,6 +161,7 @@ struct WaltersStatus {
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "PascalCase")]
 struct WaltersItem {
+    #[serde(rename = "ObjectID")]
     object_id: i64,
     title: Option<String>,
     description: Option<String>,
@@ -184,7 +185,7 @@ mod tests {
     #[test]
     fn test_provider_info() {
         let config = Config::default();
-        let provider = WaltersArtMuseumProvider::new(&config);
+        let provider = WaltersArtMuseumProvider::new(&config).unwrap();
         assert_eq!(provider.name(), "walters");
         // Disabled due to Cloudflare protection
         assert!(!provider.is_available());
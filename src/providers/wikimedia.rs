@@ -7,10 +7,9 @@
 use async_trait::async_trait;
 use serde::Deserialize;
 use std::collections::HashMap;
-use std::time::Duration;
 
 use crate::config::Config;
-use crate::error::Result;
+use crate::error::{DxError, Result};
 use crate::http::{HttpClient, ResponseExt};
 use crate::providers::traits::{Provider, ProviderInfo};
 use crate::types::{License, MediaAsset, MediaType, RateLimitConfig, SearchQuery, SearchResult};
@@ -24,16 +23,14 @@ pub struct WikimediaCommonsProvider {
 
 impl WikimediaCommonsProvider {
     /// Create a new Wikimedia Commons provider.
-    #[must_use]
-    pub fn new(config: &Config) -> Self {
-        let client = HttpClient::with_config(
-            Self::RATE_LIMIT,
-            config.retry_attempts,
-            Duration::from_secs(config.timeout_secs),
-        )
-        .unwrap_or_default();
-
-        Self { client }
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP client cannot be created.
+    pub fn new(config: &Config) -> Result<Self> {
+        let client = HttpClient::from_config(Self::RATE_LIMIT, config)?;
+
+        Ok(Self { client })
     }
 
     /// Rate limit: Unlimited but be respectful
@@ -63,6 +60,47 @@ impl WikimediaCommonsProvider {
         }
     }
 
+    /// Convert a File page with image info into a media asset
+    fn page_asset(page: WikimediaPage) -> Option<MediaAsset> {
+        let info = page.imageinfo?.into_iter().next()?;
+        let media_type = Self::media_type_from_title(&page.title);
+
+        let license = info
+            .extmetadata
+            .as_ref()
+            .and_then(|m| m.license_short_name.as_ref())
+            .map(|l| {
+                let val = &l.value;
+                if val.contains("CC0") || val.contains("Public domain") {
+                    License::Cc0
+                } else if val.contains("CC BY-SA") {
+                    License::CcBySa
+                } else if val.contains("CC BY-NC") {
+                    License::CcByNc
+                } else if val.contains("CC BY") {
+                    License::CcBy
+                } else {
+                    License::Other(val.clone())
+                }
+            })
+            .unwrap_or(License::Other("Various".to_string()));
+
+        let asset = MediaAsset::builder()
+            .id(page.pageid.to_string())
+            .provider("wikimedia")
+            .media_type(media_type)
+            .title(Self::clean_title(&page.title))
+            .download_url(info.url.clone())
+            .preview_url(info.thumburl.unwrap_or_else(|| info.url.clone()))
+            .source_url(info.descriptionurl)
+            .author(info.user.unwrap_or_else(|| "Unknown".to_string()))
+            .license(license)
+            .dimensions(info.width.unwrap_or(0), info.height.unwrap_or(0))
+            .build();
+
+        Some(asset)
+    }
+
     /// Clean title for display (remove File: prefix and extension)
     fn clean_title(title: &str) -> String {
         title
@@ -132,52 +170,9 @@ impl Provider for WikimediaCommonsProvider {
 
         let assets: Vec<MediaAsset> = pages
             .into_values()
-            .filter_map(|page| {
-                let info = page.imageinfo?.into_iter().next()?;
-                let media_type = Self::media_type_from_title(&page.title);
-
-                // Filter by media type if specified
-                if let Some(requested_type) = query.media_type {
-                    if media_type != requested_type {
-                        return None;
-                    }
-                }
-
-                let license = info
-                    .extmetadata
-                    .as_ref()
-                    .and_then(|m| m.license_short_name.as_ref())
-                    .map(|l| {
-                        let val = &l.value;
-                        if val.contains("CC0") || val.contains("Public domain") {
-                            License::Cc0
-                        } else if val.contains("CC BY-SA") {
-                            License::CcBySa
-                        } else if val.contains("CC BY-NC") {
-                            License::CcByNc
-                        } else if val.contains("CC BY") {
-                            License::CcBy
-                        } else {
-                            License::Other(val.clone())
-                        }
-                    })
-                    .unwrap_or(License::Other("Various".to_string()));
-
-                let asset = MediaAsset::builder()
-                    .id(page.pageid.to_string())
-                    .provider("wikimedia")
-                    .media_type(media_type)
-                    .title(Self::clean_title(&page.title))
-                    .download_url(info.url.clone())
-                    .preview_url(info.thumburl.unwrap_or_else(|| info.url.clone()))
-                    .source_url(info.descriptionurl)
-                    .author(info.user.unwrap_or_else(|| "Unknown".to_string()))
-                    .license(license)
-                    .dimensions(info.width.unwrap_or(0), info.height.unwrap_or(0))
-                    .build();
-
-                Some(asset)
-            })
+            .filter_map(Self::page_asset)
+            // Filter by media type if specified
+            .filter(|asset| query.media_type.is_none_or(|t| asset.media_type == t))
             .collect();
 
         Ok(SearchResult {
@@ -188,8 +183,47 @@ impl Provider for WikimediaCommonsProvider {
             providers_searched: vec!["wikimedia".to_string()],
             provider_errors: vec![],
             duration_ms: 0,
+            cache_hits: Vec::new(),
+            cache_misses: Vec::new(),
+            filters: Vec::new(),
         })
     }
+
+    fn supports_get_asset(&self) -> bool {
+        true
+    }
+
+    async fn get_asset(&self, id: &str) -> Result<MediaAsset> {
+        // Asset IDs are page IDs, but a "File:..." title works too
+        let lookup = if id.chars().all(|c| c.is_ascii_digit()) {
+            ("pageids", id.to_string())
+        } else if id.starts_with("File:") {
+            ("titles", id.to_string())
+        } else {
+            ("titles", format!("File:{}", id))
+        };
+
+        let params = [
+            ("action", "query"),
+            ("format", "json"),
+            (lookup.0, lookup.1.as_str()),
+            ("prop", "imageinfo"),
+            ("iiprop", "url|size|mime|user|extmetadata"),
+            ("iiurlwidth", "640"),
+        ];
+
+        let response = self
+            .client
+            .get_with_query(self.base_url(), &params, &[])
+            .await?;
+
+        let api_response: WikimediaSearchResponse = response.json_or_error().await?;
+
+        api_response
+            .query
+            .and_then(|q| q.pages.into_values().find_map(Self::page_asset))
+            .ok_or_else(|| DxError::asset_not_found(self.name(), id))
+    }
 }
 
 impl ProviderInfo for WikimediaCommonsProvider {
@@ -225,6 +259,8 @@ struct WikimediaQuery {
 #[derive(Debug, Deserialize)]
 #[allow(dead_code)]
 struct WikimediaPage {
+    /// Absent for missing pages
+    #[serde(default)]
     pageid: u64,
     title: String,
     imageinfo: Option<Vec<WikimediaImageInfo>>,
@@ -267,7 +303,7 @@ mod tests {
     #[test]
     fn test_provider_metadata() {
         let config = Config::default_for_testing();
-        let provider = WikimediaCommonsProvider::new(&config);
+        let provider = WikimediaCommonsProvider::new(&config).unwrap();
 
         assert_eq!(provider.name(), "wikimedia");
         assert_eq!(provider.display_name(), "Wikimedia Commons");
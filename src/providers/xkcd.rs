@@ -10,10 +10,9 @@
 
 use async_trait::async_trait;
 use serde::Deserialize;
-use std::time::Duration;
 
 use crate::config::Config;
-use crate::error::Result;
+use crate::error::{DxError, Result};
 use crate::http::{HttpClient, ResponseExt};
 use crate::providers::traits::Provider;
 use crate::types::{License, MediaAsset, MediaType, RateLimitConfig, SearchQuery, SearchResult};
@@ -44,16 +43,14 @@ struct XkcdComic {
 
 impl XkcdProvider {
     /// Create a new xkcd provider.
-    #[must_use]
-    pub fn new(config: &Config) -> Self {
-        let client = HttpClient::with_config(
-            Self::RATE_LIMIT,
-            config.retry_attempts,
-            Duration::from_secs(config.timeout_secs),
-        )
-        .unwrap_or_default();
-
-        Self { client }
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP client cannot be created.
+    pub fn new(config: &Config) -> Result<Self> {
+        let client = HttpClient::from_config(Self::RATE_LIMIT, config)?;
+
+        Ok(Self { client })
     }
 
     /// Rate limit: Be respectful
@@ -169,8 +166,29 @@ impl Provider for XkcdProvider {
             providers_searched: vec![self.name().to_string()],
             provider_errors: vec![],
             duration_ms: 0,
+            cache_hits: Vec::new(),
+            cache_misses: Vec::new(),
+            filters: Vec::new(),
         })
     }
+
+    fn supports_get_asset(&self) -> bool {
+        true
+    }
+
+    async fn get_asset(&self, id: &str) -> Result<MediaAsset> {
+        let num: u32 = id
+            .strip_prefix("xkcd_")
+            .unwrap_or(id)
+            .parse()
+            .map_err(|_| DxError::asset_not_found(self.name(), id))?;
+
+        let comic = self.get_comic(num).await.map_err(|e| match e {
+            DxError::Http { status_code: Some(404), .. } => DxError::asset_not_found(self.name(), id),
+            e => e,
+        })?;
+        Ok(self.comic_to_asset(comic))
+    }
 }
 
 #[cfg(test)]
@@ -180,7 +198,7 @@ mod tests {
     #[test]
     fn test_provider_info() {
         let config = Config::default();
-        let provider = XkcdProvider::new(&config);
+        let provider = XkcdProvider::new(&config).unwrap();
         assert_eq!(provider.name(), "xkcd");
         assert_eq!(provider.display_name(), "xkcd Comics");
         assert!(provider.is_available());
@@ -0,0 +1,295 @@
+//! Ranking of results aggregated from several providers.
+//!
+//! Providers answer concurrently, so raw results arrive in whatever order their
+//! requests finish and the same file is often listed twice (e.g. a Wikimedia
+//! image that Openverse also indexes). [`rank`] removes duplicates by
+//! normalized download URL, scores each asset against the query terms, and orders the
+//! list according to the query's [`RankingStrategy`].
+
+use std::collections::{HashMap, HashSet};
+
+use tracing::debug;
+
+use crate::types::{MediaAsset, RankingStrategy, SearchQuery};
+
+/// Score for a query term found as a whole word in the title.
+const TITLE_WORD: f64 = 3.0;
+/// Score for a query term found inside a title word (e.g. "cat" in "cats").
+const TITLE_PARTIAL: f64 = 1.5;
+/// Score for a query term matching a tag.
+const TAG_MATCH: f64 = 1.0;
+/// Bonus when the whole query appears verbatim in the title.
+const PHRASE_BONUS: f64 = 2.0;
+
+/// Deduplicate and order assets for a query.
+///
+/// With [`RankingStrategy::Arrival`] the assets are returned unchanged.
+#[must_use]
+pub fn rank(assets: Vec<MediaAsset>, query: &SearchQuery) -> Vec<MediaAsset> {
+    if query.ranking == RankingStrategy::Arrival {
+        return assets;
+    }
+
+    let terms = query_terms(&query.query);
+    let phrase = terms.join(" ");
+
+    // Score first so that when two copies of an asset collide, the better match survives
+    let mut scored: Vec<(f64, MediaAsset)> = assets
+        .into_iter()
+        .map(|asset| (score(&asset, &terms, &phrase), asset))
+        .collect();
+    scored.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+    let before = scored.len();
+    let scored = dedupe(scored);
+    if scored.len() < before {
+        debug!("Removed {} duplicate results", before - scored.len());
+    }
+
+    match query.ranking {
+        RankingStrategy::Relevance => scored.into_iter().map(|(_, asset)| asset).collect(),
+        RankingStrategy::Interleave => interleave(scored),
+        RankingStrategy::Arrival => unreachable!("handled above"),
+    }
+}
+
+/// Relevance of an asset's title and tags to the query terms.
+#[must_use]
+pub fn relevance(asset: &MediaAsset, query: &str) -> f64 {
+    let terms = query_terms(query);
+    score(asset, &terms, &terms.join(" "))
+}
+
+fn score(asset: &MediaAsset, terms: &[String], phrase: &str) -> f64 {
+    if terms.is_empty() {
+        return 0.0;
+    }
+
+    let title_words = query_terms(&asset.title);
+    let tags: Vec<String> = asset.tags.iter().map(|t| t.to_lowercase()).collect();
+
+    let mut total = 0.0;
+    for term in terms {
+        if title_words.iter().any(|w| w == term) {
+            total += TITLE_WORD;
+        } else if title_words.iter().any(|w| w.contains(term.as_str())) {
+            total += TITLE_PARTIAL;
+        }
+
+        if tags.iter().any(|t| t == term || t.split_whitespace().any(|w| w == term)) {
+            total += TAG_MATCH;
+        }
+    }
+
+    if terms.len() > 1 && title_words.join(" ").contains(phrase) {
+        total += PHRASE_BONUS;
+    }
+
+    // Normalize so long queries don't dwarf short ones
+    #[allow(clippy::cast_precision_loss)]
+    let normalized = total / terms.len() as f64;
+    normalized
+}
+
+/// Lowercased alphanumeric words.
+fn query_terms(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// Drop assets whose download URL was already seen.
+fn dedupe(scored: Vec<(f64, MediaAsset)>) -> Vec<(f64, MediaAsset)> {
+    let mut seen = HashSet::new();
+
+    scored
+        .into_iter()
+        .filter(|(_, asset)| seen.insert(dedupe_key(asset)))
+        .collect()
+}
+
+/// Key identifying the file behind an asset, for duplicate detection.
+///
+/// Only the download URL is used: several distinct files often share one
+/// landing page (scraped assets, avatar styles, dataset resources), so the
+/// source URL alone says nothing about whether two assets are the same file.
+/// Assets without a download URL are keyed by `provider:id` instead.
+#[must_use]
+pub fn dedupe_key(asset: &MediaAsset) -> String {
+    let key =
+        normalize_url(&asset.download_url).unwrap_or_else(|| asset.download_url.trim().to_string());
+    if key.is_empty() {
+        format!("{}:{}", asset.provider, asset.id)
+    } else {
+        key
+    }
+}
+
+/// Canonical form of a URL for duplicate detection.
+///
+/// Ignores scheme, a leading `www.`, default ports, fragments, trailing
+/// slashes and `utm_*` tracking parameters.
+#[must_use]
+pub fn normalize_url(url: &str) -> Option<String> {
+    let parsed = url::Url::parse(url.trim()).ok()?;
+    let host = parsed.host_str()?.to_lowercase();
+    let host = host.strip_prefix("www.").unwrap_or(&host);
+
+    let mut key = String::from(host);
+    if let Some(port) = parsed.port() {
+        key.push(':');
+        key.push_str(&port.to_string());
+    }
+    key.push_str(parsed.path().trim_end_matches('/'));
+
+    let params: Vec<String> = parsed
+        .query_pairs()
+        .filter(|(k, _)| !k.starts_with("utm_"))
+        .map(|(k, v)| format!("{}={}", k, v))
+        .collect();
+    if !params.is_empty() {
+        key.push('?');
+        key.push_str(&params.join("&"));
+    }
+
+    Some(key)
+}
+
+/// Round-robin across providers, each in score order.
+///
+/// Providers take turns in order of their best-scoring asset.
+fn interleave(scored: Vec<(f64, MediaAsset)>) -> Vec<MediaAsset> {
+    let total = scored.len();
+    let mut order: Vec<String> = Vec::new();
+    let mut queues: HashMap<String, std::collections::VecDeque<MediaAsset>> = HashMap::new();
+
+    // `scored` is sorted by score, so first appearance gives each provider's best
+    for (_, asset) in scored {
+        if !queues.contains_key(&asset.provider) {
+            order.push(asset.provider.clone());
+        }
+        queues.entry(asset.provider.clone()).or_default().push_back(asset);
+    }
+
+    let mut ranked = Vec::with_capacity(total);
+    while ranked.len() < total {
+        for provider in &order {
+            if let Some(asset) = queues.get_mut(provider).and_then(|q| q.pop_front()) {
+                ranked.push(asset);
+            }
+        }
+    }
+
+    ranked
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::MediaType;
+
+    fn asset(provider: &str, id: &str, title: &str, url: &str) -> MediaAsset {
+        MediaAsset::builder()
+            .id(id)
+            .provider(provider)
+            .media_type(MediaType::Image)
+            .title(title)
+            .download_url(url)
+            .source_url(format!("https://{}.example/{}", provider, id))
+            .build()
+    }
+
+    #[test]
+    fn test_normalize_url() {
+        assert_eq!(
+            normalize_url("https://www.Example.com/a/b/?utm_source=x&w=640#top"),
+            normalize_url("http://example.com/a/b?w=640")
+        );
+        assert_ne!(
+            normalize_url("https://example.com/a?w=640"),
+            normalize_url("https://example.com/a?w=320")
+        );
+        assert_eq!(normalize_url(""), None);
+    }
+
+    #[test]
+    fn test_dedupe_keeps_best_match() {
+        let query = SearchQuery::new("red fox").ranking(RankingStrategy::Relevance);
+        let assets = vec![
+            asset("openverse", "1", "Animal", "https://upload.wikimedia.org/fox.jpg"),
+            asset("wikimedia", "2", "Red fox in snow", "http://upload.wikimedia.org/fox.jpg"),
+            asset("nasa", "3", "Nebula", "https://images.nasa.gov/n.jpg"),
+        ];
+
+        let ranked = rank(assets, &query);
+        let ids: Vec<&str> = ranked.iter().map(|a| a.id.as_str()).collect();
+        assert_eq!(ids, vec!["2", "3"]);
+    }
+
+    #[test]
+    fn test_dedupe_keeps_distinct_files_from_one_page() {
+        let query = SearchQuery::new("avatar");
+        let mut first = asset("dicebear", "1", "avatar", "https://api.example/bottts.svg");
+        let mut second = asset("dicebear", "2", "avatar", "https://api.example/pixel-art.svg");
+        first.source_url = "https://www.dicebear.com/styles/bottts".to_string();
+        second.source_url = "https://www.dicebear.com/styles/bottts".to_string();
+
+        let ranked = rank(vec![first, second], &query);
+        assert_eq!(ranked.len(), 2);
+    }
+
+    #[test]
+    fn test_dedupe_keeps_assets_without_download_url() {
+        let query = SearchQuery::new("fox");
+        let assets = vec![
+            asset("a", "1", "Fox", ""),
+            asset("a", "2", "Fox", "  "),
+            asset("b", "1", "Fox", ""),
+        ];
+
+        assert_eq!(dedupe_key(&assets[0]), "a:1");
+        assert_eq!(rank(assets, &query).len(), 3);
+    }
+
+    #[test]
+    fn test_relevance_scoring() {
+        let exact = asset("a", "1", "Red fox", "https://a.example/1");
+        let partial = asset("a", "2", "Foxes", "https://a.example/2");
+        let mut tagged = asset("a", "3", "Untitled", "https://a.example/3");
+        tagged.tags = vec!["Fox".to_string()];
+
+        assert!(relevance(&exact, "fox") > relevance(&partial, "fox"));
+        assert!(relevance(&partial, "fox") > relevance(&tagged, "fox"));
+        assert!(relevance(&exact, "whale").abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_interleave_round_robin() {
+        let query = SearchQuery::new("fox");
+        let assets = vec![
+            asset("a", "a1", "fox", "https://a.example/1"),
+            asset("a", "a2", "fox", "https://a.example/2"),
+            asset("a", "a3", "fox", "https://a.example/3"),
+            asset("b", "b1", "other", "https://b.example/1"),
+            asset("c", "c1", "fox den", "https://c.example/1"),
+        ];
+
+        let ranked = rank(assets, &query);
+        let ids: Vec<&str> = ranked.iter().map(|a| a.id.as_str()).collect();
+        assert_eq!(ids, vec!["a1", "c1", "b1", "a2", "a3"]);
+    }
+
+    #[test]
+    fn test_arrival_keeps_order() {
+        let query = SearchQuery::new("fox").ranking(RankingStrategy::Arrival);
+        let assets = vec![
+            asset("b", "1", "other", "https://x.example/1"),
+            asset("a", "2", "fox", "https://x.example/1"),
+        ];
+
+        let ranked = rank(assets, &query);
+        assert_eq!(ranked.len(), 2);
+        assert_eq!(ranked[0].id, "1");
+    }
+}
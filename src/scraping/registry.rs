@@ -6,6 +6,7 @@
 //! - License information
 
 use super::targets::{ScrapingCategory, ScrapingMethod, ScrapingTarget};
+use crate::error::{DxError, Result};
 
 // ═══════════════════════════════════════════════════════════════════════════════
 // BATCH 1: IMAGE SCRAPERS (1-50) - Stock Photos & Free Images
@@ -3505,6 +3506,46 @@ impl ScrapingRegistry {
             .collect()
     }
 
+    /// Get the searchable targets for a category (see [`ScrapingTarget::is_searchable`]).
+    pub fn searchable(category: ScrapingCategory) -> Vec<&'static ScrapingTarget> {
+        SCRAPING_TARGETS
+            .iter()
+            .filter(|t| t.category == category && t.is_searchable())
+            .collect()
+    }
+
+    /// Resolve target IDs and category names to targets.
+    ///
+    /// An ID selects that target; a category such as `textures` selects its
+    /// searchable targets. Each target is returned once, in the order given.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error for a name that is neither a target ID nor a category.
+    pub fn select<S: AsRef<str>>(names: &[S]) -> Result<Vec<&'static ScrapingTarget>> {
+        let mut selected: Vec<&'static ScrapingTarget> = Vec::new();
+        for name in names {
+            let name = name.as_ref().trim();
+            let targets = match Self::get(name) {
+                Some(target) => vec![target],
+                None => match name.parse::<ScrapingCategory>() {
+                    Ok(category) => Self::searchable(category),
+                    Err(_) => {
+                        return Err(DxError::InvalidQuery {
+                            message: format!("Unknown scraping target or category '{}'", name),
+                        });
+                    }
+                },
+            };
+            for target in targets {
+                if !selected.iter().any(|t| t.id == target.id) {
+                    selected.push(target);
+                }
+            }
+        }
+        Ok(selected)
+    }
+
     /// Get total count of all targets.
     pub fn total_count() -> usize {
         SCRAPING_TARGETS.len()
@@ -3533,3 +3574,35 @@ impl ScrapingRegistry {
             .collect()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_select_by_id_and_category() {
+        let targets = ScrapingRegistry::select(&["stocksnap", "textures", "stocksnap"]).unwrap();
+        assert_eq!(targets[0].id, "stocksnap");
+        assert_eq!(targets.iter().filter(|t| t.id == "stocksnap").count(), 1);
+        assert!(targets[1..].iter().all(|t| t.category == ScrapingCategory::Textures));
+        assert!(targets[1..].iter().all(|t| t.is_searchable()));
+
+        assert!(ScrapingRegistry::select(&["no-such-site"]).is_err());
+    }
+
+    #[test]
+    fn test_target_search_url_and_rate_limit() {
+        let target = ScrapingRegistry::get("stocksnap").unwrap();
+        assert_eq!(
+            target.search_url_for("red fox & hen"),
+            "https://stocksnap.io/search/red%20fox%20%26%20hen"
+        );
+        assert_eq!(target.rate_limit().delay_ms(), 1000);
+        assert_eq!(target.license().as_str(), "CC0");
+
+        let slow = STOCKSNAP.with_rate_limit(2500);
+        assert_eq!(slow.rate_limit().delay_ms(), 3000);
+        let fast = STOCKSNAP.with_rate_limit(250);
+        assert_eq!(fast.rate_limit().delay_ms(), 250);
+    }
+}
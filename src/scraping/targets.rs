@@ -1,7 +1,13 @@
 //! Scraping target definitions and types.
 
+use std::fmt;
+use std::str::FromStr;
+
 use serde::{Deserialize, Serialize};
 
+use crate::error::DxError;
+use crate::types::{License, MediaType, RateLimitConfig};
+
 /// Method for scraping a website.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ScrapingMethod {
@@ -44,6 +50,96 @@ pub enum ScrapingCategory {
     Maps,
 }
 
+impl ScrapingCategory {
+    /// Every category.
+    pub const ALL: [Self; 10] = [
+        Self::Images,
+        Self::Videos,
+        Self::Audio,
+        Self::Models3D,
+        Self::Textures,
+        Self::Vectors,
+        Self::Documents,
+        Self::GameAssets,
+        Self::Patterns,
+        Self::Maps,
+    ];
+
+    /// Name used on the command line, e.g. `"textures"`.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Images => "images",
+            Self::Videos => "videos",
+            Self::Audio => "audio",
+            Self::Models3D => "3d",
+            Self::Textures => "textures",
+            Self::Vectors => "vectors",
+            Self::Documents => "documents",
+            Self::GameAssets => "game",
+            Self::Patterns => "patterns",
+            Self::Maps => "maps",
+        }
+    }
+
+    /// Media type of assets from this category when the file doesn't say otherwise.
+    #[must_use]
+    pub const fn media_type(self) -> MediaType {
+        match self {
+            Self::Videos => MediaType::Video,
+            Self::Audio => MediaType::Audio,
+            Self::Models3D => MediaType::Model3D,
+            Self::Vectors => MediaType::Vector,
+            Self::Documents => MediaType::Document,
+            Self::Images | Self::Textures | Self::GameAssets | Self::Patterns | Self::Maps => {
+                MediaType::Image
+            }
+        }
+    }
+
+    /// The category searched by default for a media type.
+    #[must_use]
+    pub const fn for_media_type(media_type: MediaType) -> Option<Self> {
+        match media_type {
+            MediaType::Image | MediaType::Gif => Some(Self::Images),
+            MediaType::Video => Some(Self::Videos),
+            MediaType::Audio => Some(Self::Audio),
+            MediaType::Vector => Some(Self::Vectors),
+            MediaType::Model3D => Some(Self::Models3D),
+            MediaType::Document => Some(Self::Documents),
+            MediaType::Data | MediaType::Code | MediaType::Text => None,
+        }
+    }
+}
+
+impl fmt::Display for ScrapingCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for ScrapingCategory {
+    type Err = DxError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().replace('_', "-").as_str() {
+            "images" | "image" | "photos" => Ok(Self::Images),
+            "videos" | "video" => Ok(Self::Videos),
+            "audio" | "music" | "sounds" => Ok(Self::Audio),
+            "3d" | "models" | "models3d" => Ok(Self::Models3D),
+            "textures" | "texture" => Ok(Self::Textures),
+            "vectors" | "vector" | "icons" => Ok(Self::Vectors),
+            "documents" | "document" => Ok(Self::Documents),
+            "game" | "game-assets" | "gameassets" => Ok(Self::GameAssets),
+            "patterns" | "pattern" => Ok(Self::Patterns),
+            "maps" | "map" => Ok(Self::Maps),
+            _ => Err(DxError::InvalidQuery {
+                message: format!("Unknown scraping category '{}'", s),
+            }),
+        }
+    }
+}
+
 /// A pre-configured scraping target.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ScrapingTarget {
@@ -169,4 +265,38 @@ impl ScrapingTarget {
         self.notes = Some(notes);
         self
     }
+
+    /// Whether the target can be searched: it has a search URL and doesn't
+    /// need JavaScript to render results.
+    #[must_use]
+    pub const fn is_searchable(&self) -> bool {
+        self.search_url.is_some() && !self.requires_js
+    }
+
+    /// URL of the first result page for `query`.
+    ///
+    /// The query is percent-encoded into `{query}`; `{page}` is set to 1.
+    /// An empty query, or a target without a search URL, browses the base URL.
+    #[must_use]
+    pub fn search_url_for(&self, query: &str) -> String {
+        let Some(template) = self.search_url.filter(|_| !query.trim().is_empty()) else {
+            return self.base_url.to_string();
+        };
+        let encoded: String = url::form_urlencoded::byte_serialize(query.as_bytes())
+            .collect::<String>()
+            .replace('+', "%20");
+        template.replace("{query}", &encoded).replace("{page}", "1")
+    }
+
+    /// The site's request budget, derived from `rate_limit_ms`.
+    #[must_use]
+    pub const fn rate_limit(&self) -> RateLimitConfig {
+        RateLimitConfig::from_interval_ms(self.rate_limit_ms)
+    }
+
+    /// License of the target's assets.
+    #[must_use]
+    pub fn license(&self) -> License {
+        License::parse(self.license)
+    }
 }
@@ -41,6 +41,49 @@ impl SearchMode {
     pub fn is_quality(&self) -> bool {
         matches!(self, Self::Quality)
     }
+
+    /// How long a provider may take before it's timed out, until its own
+    /// latency is known: 5 seconds in quantity mode, 8 in quality mode.
+    #[must_use]
+    pub fn provider_timeout(&self) -> std::time::Duration {
+        match self {
+            Self::Quantity => std::time::Duration::from_secs(5),
+            Self::Quality => std::time::Duration::from_secs(8),
+        }
+    }
+}
+
+/// How a search uses the on-disk result cache.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, Display, EnumString)]
+#[strum(serialize_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum CachePolicy {
+    /// Serve fresh cached results and store new ones. DEFAULT policy.
+    #[default]
+    Use,
+    /// Ignore cached results but store the fresh ones.
+    Refresh,
+    /// Neither read nor write the cache.
+    Bypass,
+}
+
+/// How aggregated results from several providers are ordered.
+///
+/// - **Interleave**: Drop duplicates, rank each provider's results by relevance, then
+///   take one from each provider in turn so no single source dominates. DEFAULT.
+/// - **Relevance**: Drop duplicates and sort everything by relevance score.
+/// - **Arrival**: Keep results in the order providers responded, duplicates included.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, Display, EnumString)]
+#[strum(serialize_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum RankingStrategy {
+    /// Deduplicate and interleave providers round-robin by relevance. DEFAULT strategy.
+    #[default]
+    Interleave,
+    /// Deduplicate and sort by relevance score.
+    Relevance,
+    /// No ranking: provider response order, duplicates kept.
+    Arrival,
 }
 
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -194,6 +237,122 @@ impl License {
             Self::Other(s) => s.as_str(),
         }
     }
+
+    /// Parse a license name as produced by [`License::as_str`].
+    ///
+    /// Also accepts common spellings such as `cc-by-sa` or `CC BY 4.0`, and
+    /// license deed URLs (see [`License::from_url`]). Unrecognized names
+    /// become [`License::Other`].
+    #[must_use]
+    pub fn parse(name: &str) -> Self {
+        if let Some(license) = Self::from_url(name) {
+            return license;
+        }
+
+        let lowered = name.trim().to_lowercase().replace([' ', '_'], "-");
+        // Drop a trailing version such as "-4.0"
+        let normalized = match lowered.rsplit_once('-') {
+            Some((base, version)) if version.chars().all(|c| c.is_ascii_digit() || c == '.') => {
+                base.to_string()
+            }
+            _ => lowered,
+        };
+
+        match normalized.as_str() {
+            "cc0" | "cc-zero" => Self::Cc0,
+            "cc-by" | "by" => Self::CcBy,
+            "cc-by-sa" | "by-sa" => Self::CcBySa,
+            "cc-by-nc" | "by-nc" => Self::CcByNc,
+            "public-domain" | "pdm" | "pd" | "publicdomain" => Self::PublicDomain,
+            "unsplash-license" | "unsplash" => Self::Unsplash,
+            "pexels-license" | "pexels" => Self::Pexels,
+            "pixabay-license" | "pixabay" => Self::Pixabay,
+            _ => Self::Other(name.to_string()),
+        }
+    }
+
+    /// Identify a license from its deed URL, e.g.
+    /// `https://creativecommons.org/licenses/by-sa/4.0/`.
+    ///
+    /// Any version of the Creative Commons licenses is recognized; NonCommercial
+    /// variants map to [`License::CcByNc`].
+    #[must_use]
+    pub fn from_url(url: &str) -> Option<Self> {
+        let lowered = url.trim().to_lowercase();
+        if let Some((_, path)) = lowered.split_once("creativecommons.org/") {
+            let mut segments = path.split('/');
+            return match (segments.next(), segments.next()) {
+                (Some("publicdomain"), Some("zero")) => Some(Self::Cc0),
+                (Some("publicdomain"), Some("mark")) => Some(Self::PublicDomain),
+                (Some("licenses"), Some("by")) => Some(Self::CcBy),
+                (Some("licenses"), Some("by-sa")) => Some(Self::CcBySa),
+                (Some("licenses"), Some(kind)) if kind.starts_with("by-nc") => Some(Self::CcByNc),
+                _ => None,
+            };
+        }
+
+        let trimmed = lowered.trim_end_matches('/');
+        [Self::Unsplash, Self::Pexels, Self::Pixabay]
+            .into_iter()
+            .find(|license| license.url().is_some_and(|u| u.trim_end_matches('/') == trimmed))
+    }
+
+    /// URL of the license deed, if known.
+    ///
+    /// For [`License::Custom`] and [`License::Other`] this is the license
+    /// text itself when it is a URL.
+    #[must_use]
+    pub fn url(&self) -> Option<&str> {
+        match self {
+            Self::Cc0 => Some("https://creativecommons.org/publicdomain/zero/1.0/"),
+            Self::CcBy => Some("https://creativecommons.org/licenses/by/4.0/"),
+            Self::CcBySa => Some("https://creativecommons.org/licenses/by-sa/4.0/"),
+            Self::CcByNc => Some("https://creativecommons.org/licenses/by-nc/4.0/"),
+            Self::PublicDomain => Some("https://creativecommons.org/publicdomain/mark/1.0/"),
+            Self::Unsplash => Some("https://unsplash.com/license"),
+            Self::Pexels => Some("https://www.pexels.com/license/"),
+            Self::Pixabay => Some("https://pixabay.com/service/license-summary/"),
+            Self::Custom(s) | Self::Other(s) => {
+                (s.starts_with("http://") || s.starts_with("https://")).then_some(s.as_str())
+            }
+        }
+    }
+
+    /// Whether the license is in the public domain or waives all rights.
+    #[must_use]
+    pub fn is_public_domain(&self) -> bool {
+        matches!(self, Self::Cc0 | Self::PublicDomain)
+    }
+
+    /// Whether the license requires crediting the author.
+    ///
+    /// Unrecognized licenses are assumed to require attribution.
+    #[must_use]
+    pub fn requires_attribution(&self) -> bool {
+        !matches!(
+            self,
+            Self::Cc0 | Self::PublicDomain | Self::Unsplash | Self::Pexels | Self::Pixabay
+        )
+    }
+
+    /// Whether the license forbids commercial use.
+    #[must_use]
+    pub fn is_non_commercial(&self) -> bool {
+        match self {
+            Self::CcByNc => true,
+            Self::Custom(s) | Self::Other(s) => {
+                let s = s.to_lowercase();
+                s.contains("by-nc") || s.contains("noncommercial") || s.contains("non-commercial")
+            }
+            _ => false,
+        }
+    }
+
+    /// Whether this is a license DX Media can identify.
+    #[must_use]
+    pub fn is_known(&self) -> bool {
+        !matches!(self, Self::Custom(_) | Self::Other(_))
+    }
 }
 
 impl Default for License {
@@ -233,6 +392,9 @@ pub struct MediaAsset {
     pub width: Option<u32>,
     /// Height in pixels (for images/videos).
     pub height: Option<u32>,
+    /// Duration in seconds (for video/audio).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration_secs: Option<u32>,
     /// File size in bytes.
     pub file_size: Option<u64>,
     /// MIME type.
@@ -294,6 +456,7 @@ pub struct MediaAssetBuilder {
     license: Option<License>,
     width: Option<u32>,
     height: Option<u32>,
+    duration_secs: Option<u32>,
     file_size: Option<u64>,
     mime_type: Option<String>,
     tags: Vec<String>,
@@ -378,6 +541,13 @@ impl MediaAssetBuilder {
         self
     }
 
+    /// Set the duration in seconds.
+    #[must_use]
+    pub fn duration(mut self, secs: u32) -> Self {
+        self.duration_secs = Some(secs);
+        self
+    }
+
     /// Set the file size.
     #[must_use]
     pub fn file_size(mut self, size: u64) -> Self {
@@ -420,6 +590,7 @@ impl MediaAssetBuilder {
             license: self.license.unwrap_or_default(),
             width: self.width,
             height: self.height,
+            duration_secs: self.duration_secs,
             file_size: self.file_size,
             mime_type: self.mime_type,
             tags: self.tags,
@@ -456,6 +627,17 @@ pub struct SearchQuery {
     /// Search mode (Quantity=fast early-exit, Quality=wait for all).
     #[serde(default)]
     pub mode: SearchMode,
+    /// How the on-disk search cache is used for this query.
+    #[serde(default)]
+    pub cache_policy: CachePolicy,
+    /// How results aggregated across providers are deduplicated and ordered.
+    #[serde(default)]
+    pub ranking: RankingStrategy,
+    /// Scraping target IDs or categories searched by
+    /// [`DxMedia::search_all_query`](crate::DxMedia::search_all_query)
+    /// (empty = a short default list for the media type).
+    #[serde(default)]
+    pub scrape_targets: Vec<String>,
 }
 
 impl SearchQuery {
@@ -473,6 +655,9 @@ impl SearchQuery {
             orientation: None,
             color: None,
             mode: SearchMode::default(),
+            cache_policy: CachePolicy::default(),
+            ranking: RankingStrategy::default(),
+            scrape_targets: Vec::new(),
         }
     }
 
@@ -490,6 +675,9 @@ impl SearchQuery {
             orientation: None,
             color: None,
             mode: SearchMode::default(),
+            cache_policy: CachePolicy::default(),
+            ranking: RankingStrategy::default(),
+            scrape_targets: Vec::new(),
         }
     }
 
@@ -521,6 +709,27 @@ impl SearchQuery {
         self
     }
 
+    /// Set the ranking strategy.
+    #[must_use]
+    pub fn ranking(mut self, ranking: RankingStrategy) -> Self {
+        self.ranking = ranking;
+        self
+    }
+
+    /// Set the cache policy.
+    #[must_use]
+    pub fn cache_policy(mut self, cache_policy: CachePolicy) -> Self {
+        self.cache_policy = cache_policy;
+        self
+    }
+
+    /// Set the scraping targets or categories searched alongside providers.
+    #[must_use]
+    pub fn scrape_targets(mut self, targets: Vec<String>) -> Self {
+        self.scrape_targets = targets;
+        self
+    }
+
     /// Set specific providers to search.
     #[must_use]
     pub fn providers(mut self, providers: Vec<String>) -> Self {
@@ -542,6 +751,32 @@ impl SearchQuery {
         self.orientation = Some(orientation);
         self
     }
+
+    /// Set dominant color filter.
+    #[must_use]
+    pub fn color(mut self, color: impl Into<String>) -> Self {
+        self.color = Some(color.into());
+        self
+    }
+
+    /// Filters set on this query, in a fixed order.
+    #[must_use]
+    pub fn active_filters(&self) -> Vec<SearchFilter> {
+        let mut filters = Vec::new();
+        if self.min_width.is_some() {
+            filters.push(SearchFilter::MinWidth);
+        }
+        if self.min_height.is_some() {
+            filters.push(SearchFilter::MinHeight);
+        }
+        if self.orientation.is_some() {
+            filters.push(SearchFilter::Orientation);
+        }
+        if self.color.is_some() {
+            filters.push(SearchFilter::Color);
+        }
+        filters
+    }
 }
 
 /// Image orientation.
@@ -557,6 +792,61 @@ pub enum Orientation {
     Square,
 }
 
+impl Orientation {
+    /// Orientation of an image with the given dimensions.
+    #[must_use]
+    pub fn from_dimensions(width: u32, height: u32) -> Self {
+        match width.cmp(&height) {
+            std::cmp::Ordering::Greater => Self::Landscape,
+            std::cmp::Ordering::Less => Self::Portrait,
+            std::cmp::Ordering::Equal => Self::Square,
+        }
+    }
+}
+
+/// A result filter carried on [`SearchQuery`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Display, EnumString)]
+#[strum(serialize_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum SearchFilter {
+    /// `min_width`.
+    MinWidth,
+    /// `min_height`.
+    MinHeight,
+    /// `orientation`.
+    Orientation,
+    /// `color`.
+    Color,
+}
+
+/// How a filter was applied to a provider's results.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Display, EnumString)]
+#[strum(serialize_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum FilterStatus {
+    /// The provider applied the filter server-side.
+    Native,
+    /// The filter was enforced locally from asset metadata.
+    ClientSide,
+    /// The assets lacked the metadata needed to evaluate the filter.
+    Unevaluated,
+}
+
+/// Outcome of one filter for one provider.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FilterReport {
+    /// Provider the results came from.
+    pub provider: String,
+    /// The filter.
+    pub filter: SearchFilter,
+    /// How the filter was applied.
+    pub status: FilterStatus,
+    /// Assets removed by client-side filtering.
+    pub removed: usize,
+    /// Assets kept because they couldn't be evaluated (e.g. missing dimensions).
+    pub unknown: usize,
+}
+
 // ═══════════════════════════════════════════════════════════════════════════════
 // SEARCH RESULT
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -578,6 +868,15 @@ pub struct SearchResult {
     pub provider_errors: Vec<(String, String)>,
     /// Search duration in milliseconds.
     pub duration_ms: u64,
+    /// Providers whose results were served from the search cache.
+    #[serde(default)]
+    pub cache_hits: Vec<String>,
+    /// Providers that were queried because no fresh cache entry existed.
+    #[serde(default)]
+    pub cache_misses: Vec<String>,
+    /// How each query filter was applied, per provider.
+    #[serde(default)]
+    pub filters: Vec<FilterReport>,
 }
 
 impl SearchResult {
@@ -592,6 +891,9 @@ impl SearchResult {
             providers_searched: Vec::new(),
             provider_errors: Vec::new(),
             duration_ms: 0,
+            cache_hits: Vec::new(),
+            cache_misses: Vec::new(),
+            filters: Vec::new(),
         }
     }
 
@@ -606,6 +908,9 @@ impl SearchResult {
             providers_searched: Vec::new(),
             provider_errors: Vec::new(),
             duration_ms: 0,
+            cache_hits: Vec::new(),
+            cache_misses: Vec::new(),
+            filters: Vec::new(),
         }
     }
 
@@ -627,6 +932,9 @@ impl SearchResult {
         self.assets.extend(other.assets);
         self.providers_searched.extend(other.providers_searched);
         self.provider_errors.extend(other.provider_errors);
+        self.cache_hits.extend(other.cache_hits);
+        self.cache_misses.extend(other.cache_misses);
+        self.filters.extend(other.filters);
     }
 }
 
@@ -635,7 +943,7 @@ impl SearchResult {
 // ═══════════════════════════════════════════════════════════════════════════════
 
 /// Rate limit configuration for a provider.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct RateLimitConfig {
     /// Maximum requests allowed.
     pub requests: u32,
@@ -653,6 +961,17 @@ impl RateLimitConfig {
         }
     }
 
+    /// One request every `ms` milliseconds (0 = no rate limiting).
+    #[must_use]
+    pub const fn from_interval_ms(ms: u64) -> Self {
+        match ms {
+            0 => Self::unlimited(),
+            #[allow(clippy::cast_possible_truncation)]
+            1..1000 => Self::new((1000 / ms) as u32, 1),
+            ms => Self::new(1, ms.div_ceil(1000)),
+        }
+    }
+
     /// No rate limiting.
     #[must_use]
     pub const fn unlimited() -> Self {
@@ -0,0 +1,364 @@
+//! Integration tests for streaming, resumable and batch downloads and
+//! content type detection.
+
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use dx_media::types::RateLimitConfig;
+use dx_media::{Config, DownloadQueue, Downloader, MediaAsset, MediaType};
+use tempfile::TempDir;
+use wiremock::matchers::{header, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn test_asset(url: &str) -> MediaAsset {
+    MediaAsset::builder()
+        .id("42")
+        .provider("test")
+        .media_type(MediaType::Image)
+        .title("Test Asset")
+        .download_url(url)
+        .source_url("https://example.com/42")
+        .build()
+}
+
+#[tokio::test]
+async fn test_download_streams_with_progress() {
+    let server = MockServer::start().await;
+    let body = vec![7u8; 256 * 1024];
+    Mock::given(method("GET"))
+        .and(path("/file.jpg"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(body.clone()))
+        .mount(&server)
+        .await;
+
+    let dir = TempDir::new().unwrap();
+    let downloader = Downloader::new(&Config::default_for_testing()).unwrap();
+    let asset = test_asset(&format!("{}/file.jpg", server.uri()));
+
+    let updates = Arc::new(Mutex::new(Vec::new()));
+    let sink = Arc::clone(&updates);
+    let path = downloader
+        .download_to_with_progress(
+            dir.path(),
+            &asset,
+            Some(Arc::new(move |received, total| {
+                sink.lock().unwrap().push((received, total));
+            })),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(std::fs::read(&path).unwrap(), body);
+    assert!(!path.with_file_name("test-42.jpg.part").exists());
+
+    let updates = updates.lock().unwrap();
+    let (last_received, last_total) = *updates.last().unwrap();
+    assert_eq!(last_received, body.len() as u64);
+    assert_eq!(last_total, body.len() as u64);
+    assert!(updates.windows(2).all(|w| w[0].0 <= w[1].0));
+}
+
+#[tokio::test]
+async fn test_download_honors_site_limit() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(b"img".to_vec()))
+        .mount(&server)
+        .await;
+
+    let dir = TempDir::new().unwrap();
+    let site = url::Url::parse(&server.uri()).unwrap();
+    let limits = [(site.host_str().unwrap().to_string(), RateLimitConfig::new(2, 1))];
+    let downloader = Downloader::new(&Config::default_for_testing())
+        .unwrap()
+        .with_site_limits(limits.into());
+
+    let start = Instant::now();
+    for name in ["a", "b", "c"] {
+        let asset = test_asset(&format!("{}/{name}.jpg", server.uri()));
+        downloader.download_to(dir.path(), &asset).await.unwrap();
+    }
+    // Two tokens up front, the third refills after half a second
+    assert!(start.elapsed() >= Duration::from_millis(400));
+}
+
+#[tokio::test]
+async fn test_download_http_error_leaves_no_file() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/missing.jpg"))
+        .respond_with(ResponseTemplate::new(404))
+        .mount(&server)
+        .await;
+
+    let dir = TempDir::new().unwrap();
+    let downloader = Downloader::new(&Config::default_for_testing()).unwrap();
+    let asset = test_asset(&format!("{}/missing.jpg", server.uri()));
+
+    let result = downloader.download_to(dir.path(), &asset).await;
+
+    assert!(result.is_err());
+    assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 0);
+}
+
+#[tokio::test]
+async fn test_download_resumes_from_part_file() {
+    let server = MockServer::start().await;
+    let body = b"0123456789abcdefghij".to_vec();
+    Mock::given(method("GET"))
+        .and(path("/big.jpg"))
+        .and(header("range", "bytes=10-"))
+        .and(header("if-range", "\"v1\""))
+        .respond_with(
+            ResponseTemplate::new(206)
+                .insert_header("content-range", "bytes 10-19/20")
+                .set_body_bytes(body[10..].to_vec()),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let dir = TempDir::new().unwrap();
+    let url = format!("{}/big.jpg", server.uri());
+    std::fs::write(dir.path().join("test-42.jpg.part"), &body[..10]).unwrap();
+    std::fs::write(
+        dir.path().join("test-42.jpg.part.json"),
+        serde_json::json!({ "url": url, "etag": "\"v1\"", "last_modified": null }).to_string(),
+    )
+    .unwrap();
+
+    let downloader = Downloader::new(&Config::default_for_testing()).unwrap();
+    let path = downloader
+        .download_to(dir.path(), &test_asset(&url))
+        .await
+        .unwrap();
+
+    assert_eq!(std::fs::read(&path).unwrap(), body);
+    assert!(!dir.path().join("test-42.jpg.part").exists());
+    assert!(!dir.path().join("test-42.jpg.part.json").exists());
+}
+
+#[tokio::test]
+async fn test_download_restarts_when_range_ignored() {
+    let server = MockServer::start().await;
+    let body = b"fresh content from the start".to_vec();
+    Mock::given(method("GET"))
+        .and(path("/changed.jpg"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("etag", "\"v2\"")
+                .set_body_bytes(body.clone()),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let dir = TempDir::new().unwrap();
+    let url = format!("{}/changed.jpg", server.uri());
+    std::fs::write(dir.path().join("test-42.jpg.part"), b"stale bytes").unwrap();
+    std::fs::write(
+        dir.path().join("test-42.jpg.part.json"),
+        serde_json::json!({ "url": url, "etag": "\"v1\"", "last_modified": null }).to_string(),
+    )
+    .unwrap();
+
+    let downloader = Downloader::new(&Config::default_for_testing()).unwrap();
+    let path = downloader
+        .download_to(dir.path(), &test_asset(&url))
+        .await
+        .unwrap();
+
+    assert_eq!(std::fs::read(&path).unwrap(), body);
+}
+
+#[tokio::test]
+async fn test_download_queue_reports_each_asset() {
+    let server = MockServer::start().await;
+    for name in ["a.jpg", "b.jpg"] {
+        Mock::given(method("GET"))
+            .and(path(format!("/{name}")))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(name.as_bytes().to_vec()))
+            .mount(&server)
+            .await;
+    }
+    Mock::given(method("GET"))
+        .and(path("/gone.jpg"))
+        .respond_with(ResponseTemplate::new(404))
+        .mount(&server)
+        .await;
+
+    let asset = |id: &str, file: &str| {
+        MediaAsset::builder()
+            .id(id)
+            .provider("test")
+            .media_type(MediaType::Image)
+            .title(id)
+            .download_url(format!("{}/{}", server.uri(), file))
+            .source_url("https://example.com")
+            .build()
+    };
+    let assets = vec![
+        asset("a", "a.jpg"),
+        asset("b", "b.jpg"),
+        asset("a", "a.jpg"),
+        asset("gone", "gone.jpg"),
+        asset("existing", "a.jpg"),
+    ];
+
+    let dir = TempDir::new().unwrap();
+    std::fs::write(dir.path().join("test-existing.jpg"), b"already here").unwrap();
+
+    let downloader = Downloader::new(&Config::default_for_testing()).unwrap();
+    let report = DownloadQueue::new(downloader, 2)
+        .run(&assets, dir.path())
+        .await;
+
+    assert_eq!(report.outcomes.len(), 5);
+    assert_eq!(report.downloaded().len(), 2);
+    assert_eq!(report.skipped().len(), 2);
+    assert_eq!(report.failed().len(), 1);
+    assert_eq!(report.failed()[0].0.id, "gone");
+    let saved: Vec<_> = report.saved().iter().map(|a| a.id.as_str()).collect();
+    assert_eq!(saved, ["a", "b", "existing"]);
+    assert_eq!(std::fs::read(dir.path().join("test-b.jpg")).unwrap(), b"b.jpg");
+    assert_eq!(
+        std::fs::read(dir.path().join("test-existing.jpg")).unwrap(),
+        b"already here"
+    );
+}
+
+#[tokio::test]
+async fn test_download_queue_retries_at_one_layer() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/busy.jpg"))
+        .respond_with(ResponseTemplate::new(503))
+        .mount(&server)
+        .await;
+
+    let dir = TempDir::new().unwrap();
+    let downloader = Downloader::new(&Config::default_for_testing()).unwrap();
+    let report = DownloadQueue::new(downloader, 1)
+        .max_retries(1)
+        .run(&[test_asset(&format!("{}/busy.jpg", server.uri()))], dir.path())
+        .await;
+
+    assert_eq!(report.failed().len(), 1);
+    assert_eq!(report.outcomes[0].attempts, 2);
+    assert_eq!(server.received_requests().await.unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn test_download_detects_real_extension() {
+    let server = MockServer::start().await;
+    let png = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR".to_vec();
+    Mock::given(method("GET"))
+        .and(path("/api/asset/42"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("content-type", "image/jpeg")
+                .set_body_bytes(png.clone()),
+        )
+        .mount(&server)
+        .await;
+
+    let dir = TempDir::new().unwrap();
+    let downloader = Downloader::new(&Config::default_for_testing()).unwrap();
+    let mut asset = test_asset(&format!("{}/api/asset/42", server.uri()));
+
+    let file = downloader.fetch(dir.path(), &asset, None).await.unwrap();
+    file.apply_to(&mut asset);
+
+    assert_eq!(file.path, dir.path().join("test-42.png"));
+    assert_eq!(asset.mime_type.as_deref(), Some("image/png"));
+    assert_eq!(asset.file_size, Some(png.len() as u64));
+}
+
+#[tokio::test]
+async fn test_download_queue_skips_file_saved_with_detected_extension() {
+    let server = MockServer::start().await;
+    let png = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR".to_vec();
+    Mock::given(method("GET"))
+        .and(path("/api/asset/42"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(png))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let dir = TempDir::new().unwrap();
+    let assets = vec![test_asset(&format!("{}/api/asset/42", server.uri()))];
+    let queue = DownloadQueue::new(Downloader::new(&Config::default_for_testing()).unwrap(), 1);
+
+    let first = queue.run(&assets, dir.path()).await;
+    assert_eq!(first.downloaded().len(), 1);
+    assert!(dir.path().join("test-42.png").exists());
+
+    let second = queue.run(&assets, dir.path()).await;
+    assert_eq!(second.skipped().len(), 1);
+    assert!(second.downloaded().is_empty());
+}
+
+#[tokio::test]
+async fn test_download_rejects_html_error_page() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/image.jpg"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_raw("<!DOCTYPE html><html><body>Not found</body></html>", "text/html"),
+        )
+        .mount(&server)
+        .await;
+
+    let dir = TempDir::new().unwrap();
+    let downloader = Downloader::new(&Config::default_for_testing()).unwrap();
+    let asset = test_asset(&format!("{}/image.jpg", server.uri()));
+
+    let err = downloader.download_to(dir.path(), &asset).await.unwrap_err();
+
+    assert!(matches!(err, dx_media::DxError::Download { .. }));
+    assert!(err.to_string().contains("text/html"));
+    assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 0);
+}
+
+#[tokio::test]
+async fn test_download_rejects_html_instead_of_document() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/report.pdf"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_raw("<!DOCTYPE html><html><body>Please sign in</body></html>", "text/html"),
+        )
+        .mount(&server)
+        .await;
+
+    let dir = TempDir::new().unwrap();
+    let downloader = Downloader::new(&Config::default_for_testing()).unwrap();
+    let mut asset = test_asset(&format!("{}/report.pdf", server.uri()));
+    asset.media_type = MediaType::Document;
+
+    let err = downloader.download_to(dir.path(), &asset).await.unwrap_err();
+
+    assert!(matches!(err, dx_media::DxError::Download { .. }));
+    assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 0);
+}
+
+#[tokio::test]
+async fn test_download_rejects_sniffed_html_without_content_type() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/photo.jpg"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(b"<html><body>Blocked</body></html>".to_vec()))
+        .mount(&server)
+        .await;
+
+    let dir = TempDir::new().unwrap();
+    let downloader = Downloader::new(&Config::default_for_testing()).unwrap();
+    let asset = test_asset(&format!("{}/photo.jpg", server.uri()));
+
+    let err = downloader.download_to(dir.path(), &asset).await.unwrap_err();
+
+    assert!(matches!(err, dx_media::DxError::Download { .. }));
+    assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 0);
+}
@@ -0,0 +1,162 @@
+//! Integration tests for RSS and Atom feeds.
+
+use dx_media::config::ProviderSettings;
+use dx_media::engine::{ScrapeOptions, Scraper};
+use dx_media::scraping::{ScrapingCategory, ScrapingMethod, ScrapingTarget};
+use dx_media::types::{MediaType, SearchQuery};
+use dx_media::{Config, ProviderRegistry};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn feed(uri: &str) -> String {
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+        <rss version="2.0" xmlns:media="http://search.yahoo.com/mrss/">
+          <channel>
+            <title>Birds</title>
+            <link>{uri}/</link>
+            <item>
+              <title>Barn owl</title>
+              <link>{uri}/owl</link>
+              <media:content url="{uri}/media/owl.jpg" medium="image" width="1600" height="1200">
+                <media:thumbnail url="{uri}/media/owl-small.jpg"/>
+              </media:content>
+            </item>
+            <item>
+              <title>Owl calls at night</title>
+              <enclosure url="{uri}/media/owl-calls.mp3" type="audio/mpeg" length="52000"/>
+              <media:content url="{uri}/media/owl-calls.mp3" duration="95"/>
+            </item>
+            <item>
+              <title>Dawn chorus</title>
+              <enclosure url="{uri}/episodes/1/audio.mp3" type="audio/mpeg"/>
+            </item>
+            <item>
+              <title>Dusk chorus</title>
+              <enclosure url="{uri}/episodes/2/audio.mp3" type="audio/mpeg"/>
+            </item>
+            <item>
+              <title>Robin</title>
+              <media:content url="{uri}/media/robin.jpg" medium="image">
+                <media:license href="https://creativecommons.org/licenses/by/4.0/">CC BY 4.0</media:license>
+              </media:content>
+            </item>
+          </channel>
+        </rss>"#
+    )
+}
+
+async fn server() -> MockServer {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/rss"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("content-type", "application/rss+xml")
+                .set_body_string(feed(&server.uri())),
+        )
+        .mount(&server)
+        .await;
+    server
+}
+
+#[tokio::test]
+async fn test_feed_provider() {
+    let server = server().await;
+    let mut config = Config::default_for_testing();
+    config.providers.insert(
+        "birds".to_string(),
+        ProviderSettings {
+            feed: Some(format!("{}/rss", server.uri())),
+            license: Some("cc0".to_string()),
+            ..ProviderSettings::default()
+        },
+    );
+
+    let mut registry = ProviderRegistry::empty();
+    assert_eq!(registry.load_feed_providers(&config), 1);
+
+    let result = registry
+        .search_provider("birds", &SearchQuery::new("owl"))
+        .await
+        .unwrap();
+    let titles: Vec<_> = result.assets.iter().map(|a| a.title.as_str()).collect();
+    assert_eq!(titles, ["Barn owl", "Owl calls at night"]);
+    assert_eq!(result.assets[0].width, Some(1600));
+    assert_eq!(result.assets[0].license.as_str(), "CC0");
+    assert_eq!(result.assets[1].media_type, MediaType::Audio);
+    assert_eq!(result.assets[1].duration_secs, Some(95));
+    assert_eq!(result.assets[1].file_size, Some(52000));
+
+    let robin = registry
+        .search_provider("birds", &SearchQuery::new("robin"))
+        .await
+        .unwrap();
+    let robin = registry.get_asset("birds", &robin.assets[0].id).await.unwrap();
+    assert_eq!(robin.license.as_str(), "CC-BY");
+
+    // Enclosures sharing a file name still resolve to their own item
+    let dusk = registry
+        .search_provider("birds", &SearchQuery::new("dusk"))
+        .await
+        .unwrap();
+    let dusk_id = &dusk.assets[0].id;
+    assert!(dusk_id.starts_with("audio-"));
+    let dusk = registry.get_asset("birds", dusk_id).await.unwrap();
+    assert_eq!(dusk.title, "Dusk chorus");
+}
+
+#[tokio::test]
+async fn test_scrape_feed_url() {
+    let server = server().await;
+    let options = ScrapeOptions {
+        media_types: vec![MediaType::Image],
+        ..ScrapeOptions::default()
+    };
+    let result = Scraper::new()
+        .unwrap()
+        .scrape(&format!("{}/rss", server.uri()), &options)
+        .await
+        .unwrap();
+
+    assert_eq!(result.pages_scraped, 1);
+    let titles: Vec<_> = result.assets.iter().map(|a| a.title.as_str()).collect();
+    assert_eq!(titles, ["Barn owl", "Robin"]);
+    assert_eq!(
+        result.assets[0].preview_url.as_deref(),
+        Some(format!("{}/media/owl-small.jpg", server.uri()).as_str())
+    );
+    assert_eq!(result.assets[0].source_url, format!("{}/owl", server.uri()));
+}
+
+#[tokio::test]
+async fn test_feed_target_filters_before_limit() {
+    let server = server().await;
+    let feed_url: &'static str = Box::leak(format!("{}/rss", server.uri()).into_boxed_str());
+    let target = ScrapingTarget::new(
+        "birds",
+        "Birds",
+        feed_url,
+        "item",
+        "enclosure",
+        ScrapingCategory::Audio,
+        "CC0",
+        "5",
+    )
+    .with_method(ScrapingMethod::Feed)
+    .with_rate_limit(0);
+    let options = ScrapeOptions {
+        media_types: vec![MediaType::Image, MediaType::Audio],
+        max_assets: 1,
+        ..ScrapeOptions::default()
+    };
+
+    let result = Scraper::new()
+        .unwrap()
+        .read_target_feed(&target, "chorus", &options)
+        .await
+        .unwrap();
+
+    let titles: Vec<_> = result.assets.iter().map(|a| a.title.as_str()).collect();
+    assert_eq!(titles, ["Dawn chorus"]);
+}
@@ -0,0 +1,64 @@
+//! Integration tests for the HTTP client's rate limiting.
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use dx_media::DxError;
+use dx_media::http::{HostRateLimiter, HttpClient};
+use dx_media::types::RateLimitConfig;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn client(limiter: &Arc<HostRateLimiter>) -> HttpClient {
+    HttpClient::with_config(RateLimitConfig::unlimited(), 2, Duration::from_secs(10))
+        .unwrap()
+        .with_limiter(Arc::clone(limiter))
+}
+
+#[tokio::test]
+async fn test_retry_after_is_honored() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api"))
+        .respond_with(ResponseTemplate::new(429).insert_header("Retry-After", "1"))
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("ok"))
+        .mount(&server)
+        .await;
+
+    let limiter = Arc::new(HostRateLimiter::new());
+    let started = Instant::now();
+    let response = client(&limiter)
+        .get(&format!("{}/api", server.uri()))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), 200);
+    assert!(started.elapsed() >= Duration::from_millis(900));
+}
+
+#[tokio::test]
+async fn test_long_retry_after_gives_up() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(429).insert_header("Retry-After", "3600"))
+        .mount(&server)
+        .await;
+
+    let limiter = Arc::new(HostRateLimiter::new());
+    let err = client(&limiter)
+        .get(&format!("{}/api", server.uri()))
+        .await
+        .unwrap_err();
+
+    match err {
+        DxError::RateLimited {
+            retry_after_secs, ..
+        } => assert_eq!(retry_after_secs, 3600),
+        other => panic!("expected RateLimited, got {other:?}"),
+    }
+}
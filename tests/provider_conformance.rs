@@ -0,0 +1,126 @@
+//! Conformance tests running every registered provider against recorded
+//! API responses in `tests/fixtures/providers`, without network access.
+//!
+//! After an API change, re-record the fixtures with real keys configured:
+//!
+//! ```text
+//! DX_HTTP_RECORD=tests/fixtures/providers cargo test --test provider_conformance
+//! ```
+
+use dx_media::config::Config;
+use dx_media::http::{FixtureMode, Fixtures};
+use dx_media::providers::ProviderRegistry;
+use dx_media::providers::traits::Provider;
+use dx_media::types::{License, MediaAsset, SearchQuery};
+use url::Url;
+
+const FIXTURES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/providers");
+
+/// Query every provider is searched with.
+const QUERY: &str = "cat";
+
+/// Placeholder key for providers that need one; credential parameters are
+/// left out of fixture matching, so any value replays.
+const FIXTURE_KEY: &str = "fixture-key";
+
+/// Configuration with every provider usable. Replays use placeholder keys;
+/// recordings use the real ones.
+fn config(mode: FixtureMode) -> Config {
+    if mode == FixtureMode::Record {
+        return Config::load().expect("valid configuration");
+    }
+
+    for env in ["DPLA_API_KEY", "SMITHSONIAN_API_KEY"] {
+        if std::env::var_os(env).is_none() {
+            // SAFETY: this file has a single test, which sets these before
+            // building any provider and on a current-thread runtime.
+            unsafe { std::env::set_var(env, FIXTURE_KEY) };
+        }
+    }
+    let key = Some(FIXTURE_KEY.to_string());
+    Config {
+        unsplash_api_key: key.clone(),
+        pexels_api_key: key.clone(),
+        pixabay_api_key: key.clone(),
+        freesound_api_key: key.clone(),
+        giphy_api_key: key.clone(),
+        flickr_api_key: key,
+        ..Config::default_for_testing()
+    }
+}
+
+/// Problems with an asset, if any.
+fn violations(provider: &dyn Provider, asset: &MediaAsset) -> Vec<String> {
+    let mut problems = Vec::new();
+    let mut check = |ok: bool, problem: String| {
+        if !ok {
+            problems.push(format!("{}: {problem}", asset.id));
+        }
+    };
+
+    check(!asset.id.trim().is_empty(), "empty id".into());
+    check(
+        asset.provider == provider.name(),
+        format!("provider is {:?}", asset.provider),
+    );
+    check(!asset.title.trim().is_empty(), "empty title".into());
+    for (field, url) in [
+        ("download_url", Some(&asset.download_url)),
+        ("source_url", Some(&asset.source_url)),
+        ("preview_url", asset.preview_url.as_ref()),
+        ("author_url", asset.author_url.as_ref()),
+    ] {
+        let Some(url) = url else { continue };
+        check(
+            Url::parse(url).is_ok_and(|u| matches!(u.scheme(), "http" | "https")),
+            format!("{field} {url:?} is not an http(s) URL"),
+        );
+    }
+    check(
+        provider.supported_media_types().contains(&asset.media_type),
+        format!("unsupported media type {:?}", asset.media_type),
+    );
+
+    let license = asset.license.as_str();
+    check(!license.trim().is_empty(), "empty license".into());
+    if !matches!(asset.license, License::Custom(_) | License::Other(_)) {
+        check(
+            License::parse(license) == asset.license,
+            format!("license {license:?} doesn't parse back"),
+        );
+    }
+    problems
+}
+
+#[tokio::test]
+async fn test_providers_conform_to_fixtures() {
+    if Fixtures::global().is_none() {
+        Fixtures::replay(FIXTURES).install();
+    }
+    let mode = Fixtures::global().map_or(FixtureMode::Replay, |f| f.mode());
+    let registry = ProviderRegistry::new(&config(mode)).unwrap();
+
+    let mut providers = registry.all();
+    providers.sort_by_key(|p| p.name());
+    assert!(!providers.is_empty());
+
+    let mut failures = Vec::new();
+    for provider in providers {
+        let query = SearchQuery::new(QUERY).count(5);
+        match provider.search(&query).await {
+            Ok(result) if result.assets.is_empty() => {
+                failures.push(format!("{}: no assets", provider.name()));
+            }
+            Ok(result) => failures.extend(
+                result
+                    .assets
+                    .iter()
+                    .flat_map(|asset| violations(provider.as_ref(), asset))
+                    .map(|problem| format!("{}: {problem}", provider.name())),
+            ),
+            Err(e) => failures.push(format!("{}: {e}", provider.name())),
+        }
+    }
+
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
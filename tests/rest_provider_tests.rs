@@ -0,0 +1,132 @@
+//! Integration tests for declarative REST providers.
+
+use std::sync::Once;
+
+use dx_media::types::SearchQuery;
+use dx_media::{Config, ProviderRegistry};
+use serde_json::json;
+use tempfile::TempDir;
+use wiremock::matchers::{header, method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+/// Environment variable the test definition reads its API key from.
+const KEY_ENV: &str = "DX_TEST_ACME_API_KEY";
+
+fn definition(server: &MockServer) -> String {
+    format!(
+        r#"
+name = "acme"
+display_name = "ACME Assets"
+media_types = ["image"]
+
+[search]
+url = "{uri}/api/search"
+params = {{ q = "{{query}}" }}
+results = "results"
+total = "meta.total"
+
+[lookup]
+url = "{uri}/api/assets/{{id}}"
+
+[auth]
+env = "{key_env}"
+header = "X-Api-Key"
+format = "Key {{key}}"
+
+[pagination]
+style = "offset"
+param = "start"
+size_param = "limit"
+max_size = 50
+
+[mapping]
+id = "uid"
+title = "caption"
+download_url = "media.full"
+preview_url = "media.small"
+license = "rights"
+"#,
+        uri = server.uri(),
+        key_env = KEY_ENV
+    )
+}
+
+fn registry(server: &MockServer) -> (ProviderRegistry, TempDir) {
+    static SET_KEY: Once = Once::new();
+    // SAFETY: set once, before any provider in this file reads the environment
+    SET_KEY.call_once(|| unsafe { std::env::set_var(KEY_ENV, "acme-key") });
+
+    let dir = TempDir::new().unwrap();
+    std::fs::write(dir.path().join("acme.toml"), definition(server)).unwrap();
+    std::fs::write(dir.path().join("broken.json"), "{ not json").unwrap();
+
+    let mut registry = ProviderRegistry::empty();
+    let loaded = registry.load_rest_providers(dir.path(), &Config::default_for_testing());
+    assert_eq!(loaded, 1);
+    (registry, dir)
+}
+
+#[tokio::test]
+async fn test_search_and_lookup() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/search"))
+        .and(query_param("q", "red fox"))
+        .and(query_param("start", "20"))
+        .and(query_param("limit", "10"))
+        .and(header("X-Api-Key", "Key acme-key"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "meta": {"total": 31},
+            "results": [
+                {
+                    "uid": "a1",
+                    "caption": "Fox in snow",
+                    "media": {"full": "https://cdn.example.com/a1.jpg", "small": "https://cdn.example.com/a1_s.jpg"},
+                    "rights": "CC BY 4.0"
+                }
+            ]
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/assets/a1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "uid": "a1",
+            "caption": "Fox in snow",
+            "media": {"full": "https://cdn.example.com/a1.jpg"}
+        })))
+        .mount(&server)
+        .await;
+
+    let (registry, _dir) = registry(&server);
+    let provider = registry.get("acme").unwrap();
+    assert_eq!(provider.display_name(), "ACME Assets");
+    assert!(provider.is_available());
+
+    let query = SearchQuery::new("red fox").count(10).page(3);
+    let result = registry.search_provider("acme", &query).await.unwrap();
+    assert_eq!(result.total_count, 31);
+    assert_eq!(result.assets.len(), 1);
+    assert_eq!(result.assets[0].title, "Fox in snow");
+    assert_eq!(result.assets[0].license.as_str(), "CC-BY");
+
+    let asset = registry.get_asset("acme", "a1").await.unwrap();
+    assert_eq!(asset.download_url, "https://cdn.example.com/a1.jpg");
+}
+
+#[tokio::test]
+async fn test_offset_follows_capped_page_size() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/search"))
+        .and(query_param("start", "50"))
+        .and(query_param("limit", "50"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({"results": []})))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let (registry, _dir) = registry(&server);
+    let query = SearchQuery::new("fox").count(100).page(2);
+    registry.search_provider("acme", &query).await.unwrap();
+}
@@ -0,0 +1,157 @@
+//! Integration tests for robots.txt compliance when crawling.
+
+use dx_media::engine::{CrawlScope, SCRAPER_USER_AGENT, ScrapeOptions, ScrapeResult, Scraper};
+use dx_media::scraping::{ScrapingCategory, ScrapingTarget};
+use wiremock::matchers::{header, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn page(images: &[&str], links: &[&str]) -> String {
+    let images: String = images
+        .iter()
+        .map(|name| format!(r#"<img src="/img/{name}.jpg" alt="{name}" width="800" height="600">"#))
+        .collect();
+    let links: String = links
+        .iter()
+        .map(|href| format!(r#"<a href="{href}">link</a>"#))
+        .collect();
+    format!("<html><body>{images}{links}</body></html>")
+}
+
+/// Image names found, in crawl order.
+fn names(result: &ScrapeResult) -> Vec<&str> {
+    result
+        .assets
+        .iter()
+        .filter_map(|a| a.download_url.rsplit('/').next()?.strip_suffix(".jpg"))
+        .collect()
+}
+
+async fn mount(server: &MockServer, at: &str, body: String) {
+    Mock::given(method("GET"))
+        .and(path(at))
+        .respond_with(ResponseTemplate::new(200).set_body_string(body))
+        .mount(server)
+        .await;
+}
+
+/// A site linking to a page on `other`.
+async fn site(other: &MockServer) -> MockServer {
+    let server = MockServer::start().await;
+    let offsite = format!("{}/x", other.uri());
+    mount(
+        &server,
+        "/robots.txt",
+        "User-agent: *\nDisallow: /private/\nAllow: /private/ok\n\nUser-agent: dx-media\nDisallow: /private/\nAllow: /private/ok\nCrawl-delay: 0.1\n".to_string(),
+    )
+    .await;
+    mount(
+        &server,
+        "/",
+        page(
+            &["home"],
+            &["/private/secret", "/private/ok", &offsite, "/a", "/b#top"],
+        ),
+    )
+    .await;
+    mount(&server, "/private/secret", page(&["secret"], &[])).await;
+    mount(&server, "/private/ok", page(&["ok"], &[])).await;
+    mount(&server, "/a", page(&["a"], &[])).await;
+    mount(&server, "/b", page(&["b"], &[])).await;
+    server
+}
+
+#[tokio::test]
+async fn test_crawl_honours_robots_and_scope() {
+    let other = MockServer::start().await;
+    let server = site(&other).await;
+    let scraper = Scraper::new().unwrap().with_depth(1);
+    let options = ScrapeOptions {
+        max_depth: 1,
+        max_pages_per_host: Some(3),
+        ..ScrapeOptions::default()
+    };
+    let result = scraper
+        .scrape(&format!("{}/", server.uri()), &options)
+        .await
+        .unwrap();
+
+    assert_eq!(names(&result), ["home", "ok", "a"]);
+    assert_eq!(result.pages_scraped, 3);
+
+    let uri = server.uri();
+    let reason = |url: String| {
+        result
+            .errors
+            .iter()
+            .find(|e| e.starts_with(&format!("Skipped {url}:")))
+            .cloned()
+            .unwrap_or_default()
+    };
+    assert!(reason(format!("{uri}/private/secret")).ends_with("disallowed by robots.txt"));
+    assert!(reason(format!("{}/x", other.uri())).ends_with("outside the crawl scope"));
+    assert!(reason(format!("{uri}/b")).contains("page limit"));
+    assert_eq!(result.errors.len(), 3);
+}
+
+#[tokio::test]
+async fn test_crawl_ignoring_robots() {
+    let other = MockServer::start().await;
+    mount(&other, "/x", page(&["x"], &[])).await;
+    let server = site(&other).await;
+    let scraper = Scraper::new().unwrap().with_depth(1);
+    let options = ScrapeOptions {
+        max_depth: 1,
+        respect_robots: false,
+        scope: CrawlScope::Any,
+        ..ScrapeOptions::default()
+    };
+    let result = scraper
+        .scrape(&format!("{}/", server.uri()), &options)
+        .await
+        .unwrap();
+
+    assert_eq!(names(&result), ["home", "secret", "ok", "x", "a", "b"]);
+    assert!(result.errors.is_empty());
+}
+
+#[tokio::test]
+async fn test_target_search_honours_robots() {
+    let server = MockServer::start().await;
+    // Only answered for our own user agent, so a browser UA would see no rules
+    Mock::given(method("GET"))
+        .and(path("/robots.txt"))
+        .and(header("user-agent", SCRAPER_USER_AGENT))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_string("User-agent: dx-media\nDisallow: /search/\n"),
+        )
+        .mount(&server)
+        .await;
+    mount(&server, "/search/fox", page(&["fox"], &[])).await;
+
+    let base: &'static str = Box::leak(server.uri().into_boxed_str());
+    let search: &'static str = Box::leak(format!("{base}/search/{{query}}").into_boxed_str());
+    let target = ScrapingTarget::new(
+        "mocksite",
+        "Mock Site",
+        base,
+        "img",
+        "img",
+        ScrapingCategory::Images,
+        "CC0",
+        "10",
+    )
+    .with_search_url(search);
+
+    let result = Scraper::new()
+        .unwrap()
+        .search_target(&target, "fox", &ScrapeOptions::default())
+        .await
+        .unwrap();
+
+    assert!(result.assets.is_empty());
+    assert_eq!(result.pages_scraped, 0);
+    assert_eq!(
+        result.errors,
+        [format!("Skipped {base}/search/fox: disallowed by robots.txt")]
+    );
+}
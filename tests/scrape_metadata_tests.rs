@@ -0,0 +1,71 @@
+//! Integration tests for structured metadata on scraped assets.
+
+use dx_media::engine::{ScrapeOptions, Scraper};
+use dx_media::types::{License, MediaType};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const PAGE: &str = r#"<html><head>
+  <script type="application/ld+json">
+  {"@context": "https://schema.org", "@type": "VideoObject",
+   "contentUrl": "/media/otters.mp4", "name": "Otters at play",
+   "duration": "PT2M5S", "author": {"@type": "Person", "name": "Sam Reed"},
+   "license": "https://creativecommons.org/licenses/by-nc/4.0/"}
+  </script>
+</head><body>
+  <figure>
+    <img src="/img/kingfisher.jpg">
+    <figcaption>Kingfisher with a fish — <span class="credit">Ana Ruiz</span></figcaption>
+  </figure>
+  <img src="/img/pond.jpg" alt="pond">
+  <img src="https://ads.example.net/banner.jpg" alt="banner">
+  <video src="/media/otters.mp4"></video>
+  <footer><a rel="license" href="https://creativecommons.org/licenses/by/4.0/">CC BY 4.0</a></footer>
+</body></html>"#;
+
+#[tokio::test]
+async fn test_scrape_attaches_page_metadata() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(PAGE))
+        .mount(&server)
+        .await;
+
+    let options = ScrapeOptions {
+        media_types: vec![MediaType::Image, MediaType::Video],
+        ..ScrapeOptions::default()
+    };
+    let result = Scraper::new()
+        .unwrap()
+        .scrape(&format!("{}/", server.uri()), &options)
+        .await
+        .unwrap();
+    let asset = |name: &str| {
+        result
+            .assets
+            .iter()
+            .find(|a| a.download_url.ends_with(name))
+            .unwrap_or_else(|| panic!("no asset for {name}"))
+    };
+
+    let kingfisher = asset("/img/kingfisher.jpg");
+    assert_eq!(kingfisher.title, "Kingfisher with a fish");
+    assert_eq!(kingfisher.author.as_deref(), Some("Ana Ruiz"));
+    assert_eq!(kingfisher.license, License::CcBy);
+
+    // Without metadata of its own, the page's rel=license still applies
+    let pond = asset("/img/pond.jpg");
+    assert_eq!(pond.title, "IMG pond");
+    assert_eq!(pond.license, License::CcBy);
+
+    // Off-site images aren't covered by the page license
+    let banner = asset("/banner.jpg");
+    assert_ne!(banner.license, License::CcBy);
+
+    let otters = asset("/media/otters.mp4");
+    assert_eq!(otters.title, "Otters at play");
+    assert_eq!(otters.author.as_deref(), Some("Sam Reed"));
+    assert_eq!(otters.duration_secs, Some(125));
+    assert_eq!(otters.license, License::CcByNc);
+}
@@ -0,0 +1,87 @@
+//! Integration tests for searching pre-configured scraping targets.
+
+use dx_media::engine::{ScrapeOptions, Scraper};
+use dx_media::scraping::{ScrapingCategory, ScrapingTarget};
+use wiremock::matchers::{method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn target(server: &MockServer) -> ScrapingTarget {
+    let base: &'static str = Box::leak(server.uri().into_boxed_str());
+    let search: &'static str = Box::leak(format!("{base}/search/{{query}}").into_boxed_str());
+    ScrapingTarget::new(
+        "mocksite",
+        "Mock Site",
+        base,
+        ".tile",
+        "img",
+        ScrapingCategory::Images,
+        "CC0",
+        "10",
+    )
+    .with_search_url(search)
+    .with_pagination("a.next")
+    .with_rate_limit(100)
+}
+
+fn page(names: &[&str], next: Option<&str>) -> String {
+    let tiles: String = names
+        .iter()
+        .map(|name| format!(r#"<div class="tile"><img src="/img/{name}.jpg" alt="{name}"></div>"#))
+        .collect();
+    let next = next.map(|href| format!(r#"<a class="next" href="{href}">Next</a>"#));
+    format!("<html><body>{tiles}{}</body></html>", next.unwrap_or_default())
+}
+
+#[tokio::test]
+async fn test_search_target_follows_pagination() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/search/red%20fox"))
+        .and(query_param("page", "2"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(page(&["c", "d"], None)))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/search/red%20fox"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_string(page(&["a", "b"], Some("?page=2"))),
+        )
+        .mount(&server)
+        .await;
+
+    let target = target(&server);
+    let options = ScrapeOptions {
+        max_assets: 3,
+        ..ScrapeOptions::default()
+    };
+    let result = Scraper::new()
+        .unwrap()
+        .search_target(&target, "red fox", &options)
+        .await
+        .unwrap();
+
+    assert_eq!(result.source_url, format!("{}/search/red%20fox", server.uri()));
+    assert_eq!(result.pages_scraped, 2);
+    let titles: Vec<_> = result.assets.iter().map(|a| a.title.as_str()).collect();
+    assert_eq!(titles, ["a", "b", "c"]);
+    assert!(result.assets.iter().all(|a| a.provider == "mocksite"));
+    assert!(result.assets.iter().all(|a| a.license.as_str() == "CC0"));
+    assert!(result.errors.is_empty());
+}
+
+#[tokio::test]
+async fn test_search_target_error_page() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(403))
+        .mount(&server)
+        .await;
+
+    let target = target(&server);
+    let result = Scraper::new()
+        .unwrap()
+        .search_target(&target, "fox", &ScrapeOptions::default())
+        .await;
+
+    assert!(result.is_err());
+}
@@ -0,0 +1,174 @@
+//! Integration tests for sitemap harvesting.
+
+use std::io::Write;
+
+use chrono::{TimeZone, Utc};
+use dx_media::engine::{ScrapeOptions, Scraper, SitemapOptions};
+use flate2::Compression;
+use flate2::write::GzEncoder;
+use tempfile::TempDir;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn index(uri: &str, photos_lastmod: &str) -> String {
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+        <sitemapindex xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">
+          <sitemap><loc>{uri}/sitemap-photos.xml.gz</loc><lastmod>{photos_lastmod}</lastmod></sitemap>
+          <sitemap><loc>{uri}/sitemap-old.xml</loc><lastmod>2019-01-01</lastmod></sitemap>
+        </sitemapindex>"#
+    )
+}
+
+fn photos(uri: &str, extra: &str) -> Vec<u8> {
+    let xml = format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+        <urlset xmlns="http://www.sitemaps.org/schemas/sitemap/0.9"
+                xmlns:image="http://www.google.com/schemas/sitemap-image/1.1">
+          <url>
+            <loc>{uri}/photo/fox</loc>
+            <lastmod>2024-05-01</lastmod>
+            <image:image><image:loc>{uri}/img/fox.jpg</image:loc><image:title>Fox</image:title></image:image>
+          </url>
+          <url>
+            <loc>{uri}/photo/owl</loc>
+            <lastmod>2024-05-02</lastmod>
+            <image:image>
+              <image:loc>{uri}/img/owl.jpg</image:loc>
+              <image:license>https://creativecommons.org/licenses/by/4.0/</image:license>
+            </image:image>
+          </url>
+          {extra}
+        </urlset>"#
+    );
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(xml.as_bytes()).unwrap();
+    encoder.finish().unwrap()
+}
+
+async fn mount(server: &MockServer, index_body: String, photos_body: Vec<u8>) {
+    server.reset().await;
+    let uri = server.uri();
+    Mock::given(method("GET"))
+        .and(path("/robots.txt"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(format!("User-agent: *\nSitemap: {uri}/sitemap-index.xml\n")),
+        )
+        .mount(server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/sitemap-index.xml"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(index_body))
+        .mount(server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/sitemap-photos.xml.gz"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(photos_body))
+        .mount(server)
+        .await;
+}
+
+#[tokio::test]
+async fn test_incremental_harvest() {
+    let server = MockServer::start().await;
+    let uri = server.uri();
+    let dir = TempDir::new().unwrap();
+    let scraper = Scraper::new().unwrap();
+    let options = ScrapeOptions::default();
+    let sitemap = SitemapOptions {
+        since: Some(Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap()),
+        state_path: Some(dir.path().join("state.json")),
+    };
+
+    mount(&server, index(&uri, "2024-05-02"), photos(&uri, "")).await;
+    let first = scraper.harvest_sitemaps(&uri, &options, &sitemap).await.unwrap();
+    assert_eq!(first.pages_scraped, 2, "old sitemap is skipped by `since`");
+    let titles: Vec<_> = first.assets.iter().map(|a| a.title.as_str()).collect();
+    assert_eq!(titles, ["Fox", "owl"]);
+    assert_eq!(first.assets[1].license.as_str(), "CC-BY");
+    assert_eq!(first.assets[0].source_url, format!("{uri}/photo/fox"));
+
+    // Unchanged index: the photo sitemap isn't fetched again
+    let second = scraper.harvest_sitemaps(&uri, &options, &sitemap).await.unwrap();
+    assert_eq!(second.pages_scraped, 1);
+    assert!(second.assets.is_empty());
+
+    // Changed sitemap: only the new entry is harvested
+    let new_entry = format!(
+        "<url><loc>{uri}/photo/hen</loc><lastmod>2024-06-01</lastmod>\
+         <image:image><image:loc>{uri}/img/hen.jpg</image:loc></image:image></url>"
+    );
+    mount(&server, index(&uri, "2024-06-01"), photos(&uri, &new_entry)).await;
+    let third = scraper.harvest_sitemaps(&uri, &options, &sitemap).await.unwrap();
+    let ids: Vec<_> = third.assets.iter().map(|a| a.id.as_str()).collect();
+    assert_eq!(ids, ["hen"]);
+}
+
+#[tokio::test]
+async fn test_harvest_records_only_taken_entries() {
+    let server = MockServer::start().await;
+    let uri = server.uri();
+    let dir = TempDir::new().unwrap();
+    let scraper = Scraper::new().unwrap();
+    let sitemap = SitemapOptions {
+        since: None,
+        state_path: Some(dir.path().join("state.json")),
+    };
+    let pair = format!(
+        "<url><loc>{uri}/photo/pair</loc><lastmod>2024-05-03</lastmod>\
+         <image:image><image:loc>{uri}/img/hen.png</image:loc></image:image>\
+         <image:image><image:loc>{uri}/img/duck.png</image:loc></image:image></url>"
+    );
+    mount(&server, index(&uri, "2024-05-02"), photos(&uri, &pair)).await;
+
+    // Entries skipped by the pattern are left for a later run
+    let jpegs = ScrapeOptions {
+        pattern: Some("*.jpg".to_string()),
+        ..ScrapeOptions::default()
+    };
+    let first = scraper.harvest_sitemaps(&uri, &jpegs, &sitemap).await.unwrap();
+    let ids: Vec<_> = first.assets.iter().map(|a| a.id.as_str()).collect();
+    assert_eq!(ids, ["fox", "owl"]);
+
+    // An entry with more assets than there is room for isn't recorded either
+    let one = ScrapeOptions {
+        max_assets: 1,
+        ..ScrapeOptions::default()
+    };
+    let second = scraper.harvest_sitemaps(&uri, &one, &sitemap).await.unwrap();
+    let ids: Vec<_> = second.assets.iter().map(|a| a.id.as_str()).collect();
+    assert_eq!(ids, ["hen"]);
+
+    let third = scraper
+        .harvest_sitemaps(&uri, &ScrapeOptions::default(), &sitemap)
+        .await
+        .unwrap();
+    let ids: Vec<_> = third.assets.iter().map(|a| a.id.as_str()).collect();
+    assert_eq!(ids, ["hen", "duck"]);
+}
+
+#[tokio::test]
+async fn test_harvest_since_leaves_older_entries() {
+    let server = MockServer::start().await;
+    let uri = server.uri();
+    let dir = TempDir::new().unwrap();
+    let scraper = Scraper::new().unwrap();
+    let options = ScrapeOptions::default();
+    let state_path = Some(dir.path().join("state.json"));
+    mount(&server, index(&uri, "2024-05-02"), photos(&uri, "")).await;
+
+    let recent = SitemapOptions {
+        since: Some(Utc.with_ymd_and_hms(2024, 5, 2, 0, 0, 0).unwrap()),
+        state_path: state_path.clone(),
+    };
+    let first = scraper.harvest_sitemaps(&uri, &options, &recent).await.unwrap();
+    let ids: Vec<_> = first.assets.iter().map(|a| a.id.as_str()).collect();
+    assert_eq!(ids, ["owl"]);
+
+    // The photo sitemap isn't recorded as harvested, so a full run picks up the rest
+    let full = SitemapOptions { since: None, state_path };
+    let second = scraper.harvest_sitemaps(&uri, &options, &full).await.unwrap();
+    let ids: Vec<_> = second.assets.iter().map(|a| a.id.as_str()).collect();
+    assert_eq!(ids, ["fox"]);
+}